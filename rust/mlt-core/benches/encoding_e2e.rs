@@ -2,8 +2,8 @@ use std::hint::black_box;
 
 use criterion::{BatchSize, BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
 use mlt_core::__private::{dec, parser};
-use mlt_core::Layer;
-use mlt_core::encoder::{EncoderConfig, LogicalEncoder, stage_tile};
+use mlt_core::encoder::{EncoderConfig, LogicalEncoder, Sampling, stage_tile};
+use mlt_core::{Layer, TileLayer};
 use strum::IntoEnumIterator as _;
 use usize_cast::FromUsize as _;
 
@@ -47,6 +47,65 @@ fn decode_to_owned(tiles: &[(String, Vec<u8>)], tessellate: bool) -> Vec<StagedL
         .collect()
 }
 
+/// Decode tiles into the `TileLayer` values that `TileLayer::encode` optimizes.
+fn decode_to_tile_layers(tiles: &[(String, Vec<u8>)]) -> Vec<TileLayer> {
+    tiles
+        .iter()
+        .flat_map(|(_, data)| {
+            let mut d = dec();
+            let layers = parser().parse_layers(data).expect("mlt parse failed");
+            layers
+                .into_iter()
+                .filter_map(|layer| {
+                    let Layer::Tag01(layer01) = layer else {
+                        return None;
+                    };
+                    layer01.into_tile(&mut d).ok()
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+fn encode_all(layers: Vec<TileLayer>, cfg: &EncoderConfig) -> usize {
+    layers
+        .into_iter()
+        .map(|layer| layer.encode(cfg.clone()).expect("encode failed").len())
+        .sum()
+}
+
+/// Exhaustive sort trials and stream competitions against sampling-based selection
+/// of one or two finalists.
+///
+/// Criterion reports the speed; the encoded size of each mode is printed once per zoom level.
+fn bench_sampling(c: &mut Criterion) {
+    let mut group = c.benchmark_group("mlt sampling");
+    for zoom in BENCHMARKED_ZOOM_LEVELS {
+        let tiles = load_mlt_tiles(zoom);
+        let total_bytes: usize = tiles.iter().map(|(_, d)| d.len()).sum();
+        group.throughput(Throughput::Bytes(u64::from_usize(total_bytes)));
+        let layers = decode_to_tile_layers(&tiles);
+        for (mode, sampling) in [
+            ("exhaustive", Sampling::Off),
+            ("sampled-top1", Sampling::Finalists(1)),
+            ("sampled-top2", Sampling::Finalists(2)),
+        ] {
+            let cfg = EncoderConfig::default().with_sampling(sampling);
+            let size = encode_all(layers.clone(), &cfg);
+            println!("zoom {zoom} {mode}: {size} bytes");
+            group.bench_with_input(BenchmarkId::new(mode, zoom), &layers, |b, layers| {
+                b.iter_batched(
+                    || layers.clone(),
+                    |layers| black_box(encode_all(layers, &cfg)),
+                    BatchSize::SmallInput,
+                );
+            });
+        }
+    }
+
+    group.finish();
+}
+
 fn bench_encode(c: &mut Criterion) {
     let mut group = c.benchmark_group("mlt encode");
     for zoom in BENCHMARKED_ZOOM_LEVELS {
@@ -71,7 +130,7 @@ fn bench_encode(c: &mut Criterion) {
                                     let mut codecs = Codecs::default();
                                     for layer in layers {
                                         let enc = Encoder::with_explicit(
-                                            enc_config,
+                                            enc_config.clone(),
                                             ExplicitEncoder::all(int_enc),
                                         );
                                         black_box(
@@ -93,5 +152,5 @@ fn bench_encode(c: &mut Criterion) {
     group.finish();
 }
 
-criterion_group!(benches, bench_encode);
+criterion_group!(benches, bench_encode, bench_sampling);
 criterion_main!(benches);
@@ -52,7 +52,7 @@ fn bench_encode_from_mvt(c: &mut Criterion) {
                 |layers| {
                     let result: MltResult<Vec<Vec<u8>>> = layers
                         .into_iter()
-                        .map(|layer| layer.encode(black_box(cfg)))
+                        .map(|layer| layer.encode(black_box(cfg.clone())))
                         .collect();
                     black_box(result.expect("encode failed"));
                 },
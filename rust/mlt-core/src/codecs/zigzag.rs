@@ -1,7 +1,7 @@
 use num_traits::{AsPrimitive, WrappingAdd, WrappingSub};
 use zigzag::ZigZag;
 
-use crate::MltError::InvalidPairStreamSize;
+use crate::MltError::{InvalidPairStreamSize, InvalidTripleStreamSize};
 use crate::{Decoder, MltResult};
 
 /// ZigZag-encode `data` into `target`.
@@ -57,6 +57,32 @@ where
     target
 }
 
+/// Encode signed integer vec3 values using componentwise delta + zigzag into `target`.
+///
+/// Input: `[x0, y0, z0, x1, y1, z1, ...]`
+/// Output: `[zigzag(x0-0), zigzag(y0-0), zigzag(z0-0), zigzag(x1-x0), zigzag(y1-y0), zigzag(z1-z0), ...]`
+///
+/// `target` is treated as a scratch buffer: cleared before writing.
+/// This is the inverse of `decode_componentwise_delta_vec3s`.
+pub fn encode_componentwise_delta_vec3s<'a, T>(
+    data: &[T],
+    target: &'a mut Vec<T::UInt>,
+) -> &'a [T::UInt]
+where
+    T: ZigZag + WrappingSub,
+{
+    target.clear();
+    target.reserve(data.len());
+    let mut prev = [T::zero(); 3];
+    for vertex in data.as_chunks::<3>().0 {
+        for (&v, p) in vertex.iter().zip(&mut prev) {
+            target.push(T::encode(v.wrapping_sub(p)));
+            *p = v;
+        }
+    }
+    target
+}
+
 /// ZigZag-decode a slice, charging `dec` for the output allocation.
 pub fn decode_zigzag<T: ZigZag>(data: &[T::UInt], dec: &mut Decoder) -> MltResult<Vec<T>> {
     dec.consume_items::<T>(data.len())?;
@@ -104,6 +130,29 @@ pub fn decode_componentwise_delta_vec2s<T: ZigZag + WrappingAdd>(
     Ok(result)
 }
 
+/// Decode ([`ZigZag`] + delta) for Vec3s, charging `dec` for the output allocation.
+pub fn decode_componentwise_delta_vec3s<T: ZigZag + WrappingAdd>(
+    data: &[T::UInt],
+    dec: &mut Decoder,
+) -> MltResult<Vec<T>> {
+    if data.is_empty() || !data.len().is_multiple_of(3) {
+        return Err(InvalidTripleStreamSize(data.len()));
+    }
+
+    let alloc_size = data.len();
+    let mut result = dec.alloc(alloc_size)?;
+    let mut last = [T::zero(); 3];
+    for vertex in data.as_chunks::<3>().0 {
+        for (&v, l) in vertex.iter().zip(&mut last) {
+            *l = l.wrapping_add(&T::decode(v));
+            result.push(*l);
+        }
+    }
+
+    dec.adjust_alloc(&result, alloc_size)?;
+    Ok(result)
+}
+
 #[cfg(test)]
 mod tests {
     use proptest::prelude::*;
@@ -144,6 +193,18 @@ mod tests {
             let decoded = decode_componentwise_delta_vec2s::<i32>(data, &mut dec()).unwrap();
             prop_assert_eq!(data_slice, &decoded);
         }
+
+        #[test]
+        fn test_componentwise_delta_vec3s(data: Vec<[i32; 3]>) {
+            if data.is_empty() {
+                return Err(TestCaseError::reject("data not valid vertices"))
+            }
+            let data = data.as_flattened();
+            let mut encoded = Vec::new();
+            let encoded = encode_componentwise_delta_vec3s(data, &mut encoded);
+            let decoded = decode_componentwise_delta_vec3s::<i32>(encoded, &mut dec()).unwrap();
+            prop_assert_eq!(data, &decoded);
+        }
     }
 
     #[test]
@@ -197,4 +258,17 @@ mod tests {
         let decoded = decode_componentwise_delta_vec2s::<i32>(values, &mut dec()).unwrap();
         assert_eq!(&decoded, &[-1_i32, 1, -3, 3]);
     }
+
+    #[test]
+    fn test_decode_componentwise_delta_vec3s() {
+        let values = &[1_u32, 2, 4, 3, 4, 1];
+        let decoded = decode_componentwise_delta_vec3s::<i32>(values, &mut dec()).unwrap();
+        assert_eq!(&decoded, &[-1_i32, 1, 2, -3, 3, 1]);
+    }
+
+    #[test]
+    fn test_decode_componentwise_delta_vec3s_rejects_partial_vertex() {
+        let err = decode_componentwise_delta_vec3s::<i32>(&[1, 2, 3, 4], &mut dec()).unwrap_err();
+        assert!(matches!(err, InvalidTripleStreamSize(4)));
+    }
 }
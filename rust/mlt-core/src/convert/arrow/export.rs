@@ -0,0 +1,607 @@
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::{iter, slice, vec};
+
+use arrow_array::types::{
+    ArrowPrimitiveType, Float32Type, Float64Type, Int8Type, Int32Type, Int64Type, UInt8Type,
+    UInt32Type, UInt64Type,
+};
+use arrow_array::{
+    ArrayRef, BinaryArray, BooleanArray, DictionaryArray, FixedSizeListArray, Float64Array,
+    Int32Array, ListArray, PrimitiveArray, RecordBatch, StringArray,
+};
+use arrow_buffer::{NullBuffer, OffsetBuffer};
+use arrow_schema::{DataType, Field, Schema};
+use geo_types::{Coord, Geometry, LineString, Polygon};
+use serde_json::Value;
+
+use crate::convert::arrow::{
+    EXTENSION_METADATA_KEY, EXTENSION_NAME_KEY, EXTENT_KEY, GEOMETRY_COLUMN, ID_COLUMN,
+    LAYER_NAME_KEY,
+};
+use crate::decoder::{
+    ParsedProperty, ParsedSharedDict, ParsedSharedDictItem, ParsedStructChild, build_col_iters,
+};
+use crate::utils::Presence;
+use crate::{ColNames as _, GeometryType, GeometryValues, MltResult, ParsedLayer01, PropValueRef};
+
+/// Convert a decoded layer into an Arrow [`RecordBatch`] with one row per feature.
+///
+/// The columns are, in order:
+///
+/// - [`ID_COLUMN`] (`UInt64`), if the layer has feature IDs.
+/// - [`GEOMETRY_COLUMN`], a GeoArrow native array with interleaved `Float64` coordinates
+///   (`xyz` for 3D layers).  Its type is the narrowest one holding every feature, e.g.
+///   `geoarrow.linestring` for a layer of lines, or `geoarrow.multipolygon` for polygons
+///   mixed with multipolygons.  Layers mixing points, lines and polygons fall back to
+///   `geoarrow.wkb`.
+/// - One column per property, named and ordered like [`ParsedLayer01::iterate_prop_names`].
+///   Numbers and booleans map to the matching Arrow type.  Strings of a shared dictionary
+///   become `Dictionary(Int32, Utf8)` arrays keyed by dictionary entry, other strings `Utf8`.
+///   Nested and range map values are stored as `arrow.json` text.
+///
+/// Null bitmaps are taken from the presence streams.  Vertex-scoped properties are not
+/// exported.  The layer name and extent are kept in the schema metadata under
+/// [`LAYER_NAME_KEY`] and [`EXTENT_KEY`].
+pub fn layer_to_record_batch(layer: &ParsedLayer01<'_>) -> MltResult<RecordBatch> {
+    let mut columns = Columns::default();
+    if let Some(ids) = &layer.id {
+        columns.push(
+            ID_COLUMN.to_string(),
+            Arc::new(primitive::<UInt64Type>(ids)),
+        );
+    }
+    let (field, array) = geometry_column(&layer.geometry)?;
+    columns.fields.push(field);
+    columns.arrays.push(array);
+    for col in &layer.properties {
+        columns.push_property("", col)?;
+    }
+
+    let metadata = HashMap::from([
+        (LAYER_NAME_KEY.to_string(), layer.name().to_string()),
+        (EXTENT_KEY.to_string(), layer.extent().get().to_string()),
+    ]);
+    let schema = Schema::new(columns.fields).with_metadata(metadata);
+    Ok(RecordBatch::try_new(Arc::new(schema), columns.arrays)?)
+}
+
+/// Metadata marking a field as an Arrow extension type.
+fn extension(name: &str) -> HashMap<String, String> {
+    HashMap::from([
+        (EXTENSION_NAME_KEY.to_string(), name.to_string()),
+        (EXTENSION_METADATA_KEY.to_string(), "{}".to_string()),
+    ])
+}
+
+#[derive(Default)]
+struct Columns {
+    fields: Vec<Field>,
+    arrays: Vec<ArrayRef>,
+}
+
+impl Columns {
+    fn push(&mut self, name: String, array: ArrayRef) {
+        self.fields
+            .push(Field::new(name, array.data_type().clone(), true));
+        self.arrays.push(array);
+    }
+
+    fn push_json(&mut self, name: String, array: ArrayRef) {
+        self.fields.push(
+            Field::new(name, array.data_type().clone(), true)
+                .with_metadata(extension("arrow.json")),
+        );
+        self.arrays.push(array);
+    }
+
+    /// Append the columns of one property, named `{prefix}{name}`.
+    fn push_property(&mut self, prefix: &str, col: &ParsedProperty<'_>) -> MltResult<()> {
+        use ParsedProperty as PP;
+        let name = |sub: usize| format!("{prefix}{}", col.name_at(sub));
+        match col {
+            PP::Bool(s) => {
+                let values = s.iter_optional().map(Option::unwrap_or_default).collect();
+                self.push(
+                    name(0),
+                    Arc::new(BooleanArray::new(values, nulls(&s.presence))),
+                );
+            }
+            PP::I8(s) => self.push(name(0), Arc::new(primitive::<Int8Type>(s))),
+            PP::U8(s) => self.push(name(0), Arc::new(primitive::<UInt8Type>(s))),
+            PP::I32(s) => self.push(name(0), Arc::new(primitive::<Int32Type>(s))),
+            PP::U32(s) => self.push(name(0), Arc::new(primitive::<UInt32Type>(s))),
+            PP::I64(s) => self.push(name(0), Arc::new(primitive::<Int64Type>(s))),
+            PP::U64(s) => self.push(name(0), Arc::new(primitive::<UInt64Type>(s))),
+            PP::F32(s) => self.push(name(0), Arc::new(primitive::<Float32Type>(s))),
+            PP::F64(s) => self.push(name(0), Arc::new(primitive::<Float64Type>(s))),
+            PP::Str(_) | PP::Nested(_) | PP::RangeMap(_) => {
+                let slots = build_col_iters(slice::from_ref(col));
+                for (sub, values) in slots.into_iter().enumerate() {
+                    let array: StringArray = values.map(|v| v.map(text)).collect();
+                    if matches!(col, PP::Str(_)) {
+                        self.push(name(sub), Arc::new(array));
+                    } else {
+                        self.push_json(name(sub), Arc::new(array));
+                    }
+                }
+            }
+            PP::SharedDict(dict) => {
+                for (sub, item) in dict.items.iter().enumerate() {
+                    self.push(name(sub), Arc::new(dictionary_strings(dict, item)?));
+                }
+            }
+            PP::Struct(st) => {
+                for (sub, child) in st.children.iter().enumerate() {
+                    match child {
+                        ParsedStructChild::Shared(item) => {
+                            let dict = st
+                                .strings
+                                .as_ref()
+                                .expect("shared struct children imply a dictionary");
+                            let array = dictionary_strings(dict, &dict.items[*item])?;
+                            self.push(name(sub), Arc::new(array));
+                        }
+                        ParsedStructChild::Column(child) => {
+                            self.push_property(&format!("{prefix}{}", st.prefix), child)?;
+                        }
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Arrow null bitmap of a column, `None` if every value is present.
+fn nulls<T: Copy>(presence: &Presence<'_, T>) -> Option<NullBuffer> {
+    match presence {
+        Presence::AllPresent(_) => None,
+        Presence::Bits { bits, .. } => Some(NullBuffer::new(bits.iter().by_vals().collect())),
+    }
+}
+
+/// Spread the dense values of a column over all features, leaving defaults behind nulls.
+fn primitive<T: ArrowPrimitiveType>(presence: &Presence<'_, T::Native>) -> PrimitiveArray<T> {
+    let values = presence
+        .iter_optional()
+        .map(Option::unwrap_or_default)
+        .collect();
+    PrimitiveArray::new(values, nulls(presence))
+}
+
+/// String form of a text column value; nested and range map values are serialized to JSON.
+fn text(value: PropValueRef<'_>) -> Cow<'_, str> {
+    match value {
+        PropValueRef::Str(s) => Cow::Borrowed(s),
+        other => Cow::Owned(Value::from(other).to_string()),
+    }
+}
+
+/// Dictionary array of one shared dictionary item, with one key per distinct entry used.
+fn dictionary_strings(
+    dict: &ParsedSharedDict<'_>,
+    item: &ParsedSharedDictItem<'_>,
+) -> MltResult<DictionaryArray<Int32Type>> {
+    let mut keys_by_range = HashMap::new();
+    let mut values = Vec::new();
+    let keys = item
+        .ranges
+        .iter()
+        .enumerate()
+        .map(|(i, range)| -> MltResult<Option<i32>> {
+            let Some(value) = item.get(dict, i) else {
+                return Ok(None);
+            };
+            let next = i32::try_from(values.len())?;
+            Ok(Some(
+                *keys_by_range
+                    .entry((range.start, range.end))
+                    .or_insert_with(|| {
+                        values.push(value);
+                        next
+                    }),
+            ))
+        })
+        .collect::<MltResult<Int32Array>>()?;
+    Ok(DictionaryArray::try_new(
+        keys,
+        Arc::new(StringArray::from(values)),
+    )?)
+}
+
+// ── Geometry ──────────────────────────────────────────────────────────────────
+
+fn geometry_column(geometry: &GeometryValues) -> MltResult<(Field, ArrayRef)> {
+    let geom_type = if geometry.is_mesh_only() {
+        // Each feature is the union of its triangles.
+        Some(GeometryType::MultiPolygon)
+    } else {
+        native_type(geometry.vector_types())
+    };
+    let Some(geom_type) = geom_type else {
+        return wkb_column(geometry);
+    };
+
+    let mut builder = NativeBuilder::new(geom_type, if geometry.z.is_some() { 3 } else { 2 });
+    for index in 0..geometry.vector_types().len() {
+        let (geom, z, _) = geometry.feature_with_vertices(index)?;
+        builder.push(&geom, &mut z.unwrap_or_default().into_iter())?;
+    }
+    builder.finish()
+}
+
+/// The narrowest GeoArrow native type holding every feature, or `None` if points,
+/// lines and polygons are mixed.
+fn native_type(types: &[GeometryType]) -> Option<GeometryType> {
+    let mut types = types.iter().copied();
+    let first = types.next().unwrap_or(GeometryType::Point);
+    types.try_fold(first, |acc, geom_type| {
+        if acc == geom_type {
+            Some(acc)
+        } else if as_multi(acc) == as_multi(geom_type) {
+            Some(as_multi(acc))
+        } else {
+            None
+        }
+    })
+}
+
+fn as_multi(geom_type: GeometryType) -> GeometryType {
+    match geom_type {
+        GeometryType::Point | GeometryType::MultiPoint => GeometryType::MultiPoint,
+        GeometryType::LineString | GeometryType::MultiLineString => GeometryType::MultiLineString,
+        GeometryType::Polygon | GeometryType::MultiPolygon => GeometryType::MultiPolygon,
+    }
+}
+
+/// Builds a GeoArrow native array of one geometry type.
+struct NativeBuilder {
+    geom_type: GeometryType,
+    /// Values per coordinate: 2, or 3 with Z.
+    dims: usize,
+    coords: Vec<f64>,
+    /// List offsets from the outermost (one entry per feature) to the innermost
+    /// (into `coords`) nesting level, each starting with `0`.
+    offsets: Vec<Vec<i32>>,
+}
+
+impl NativeBuilder {
+    fn new(geom_type: GeometryType, dims: usize) -> Self {
+        Self {
+            geom_type,
+            dims,
+            coords: Vec::new(),
+            offsets: vec![vec![0]; Self::child_names(geom_type).len()],
+        }
+    }
+
+    /// Names of the list children, from the outermost nesting level inwards.
+    fn child_names(geom_type: GeometryType) -> &'static [&'static str] {
+        match geom_type {
+            GeometryType::Point => &[],
+            GeometryType::LineString => &["vertices"],
+            GeometryType::Polygon => &["rings", "vertices"],
+            GeometryType::MultiPoint => &["points"],
+            GeometryType::MultiLineString => &["linestrings", "vertices"],
+            GeometryType::MultiPolygon => &["polygons", "rings", "vertices"],
+        }
+    }
+
+    /// Append a feature, which must fit `self.geom_type`.
+    fn push(&mut self, geom: &Geometry<i32>, z: &mut vec::IntoIter<i32>) -> MltResult<()> {
+        let depth = self.offsets.len();
+        match geom {
+            Geometry::Point(point) => {
+                self.push_coords([point.0], z);
+                if depth == 1 {
+                    self.close(0)?;
+                }
+            }
+            Geometry::MultiPoint(points) => {
+                self.push_coords(points.iter().map(|p| p.0), z);
+                self.close(0)?;
+            }
+            Geometry::LineString(line) => {
+                self.push_coords(line.coords().copied(), z);
+                self.close(depth - 1)?;
+                if depth == 2 {
+                    self.close(0)?;
+                }
+            }
+            Geometry::MultiLineString(lines) => {
+                for line in lines {
+                    self.push_coords(line.coords().copied(), z);
+                    self.close(1)?;
+                }
+                self.close(0)?;
+            }
+            Geometry::Polygon(polygon) => {
+                self.push_polygon(polygon, z)?;
+                if depth == 3 {
+                    self.close(0)?;
+                }
+            }
+            Geometry::MultiPolygon(polygons) => {
+                for polygon in polygons {
+                    self.push_polygon(polygon, z)?;
+                }
+                self.close(0)?;
+            }
+            _ => unreachable!("MLT only decodes points, lines and polygons"),
+        }
+        Ok(())
+    }
+
+    fn push_polygon(
+        &mut self,
+        polygon: &Polygon<i32>,
+        z: &mut vec::IntoIter<i32>,
+    ) -> MltResult<()> {
+        let depth = self.offsets.len();
+        for ring in iter::once(polygon.exterior()).chain(polygon.interiors()) {
+            self.push_coords(ring.coords().copied(), z);
+            self.close(depth - 1)?;
+        }
+        self.close(depth - 2)
+    }
+
+    fn push_coords(
+        &mut self,
+        coords: impl IntoIterator<Item = Coord<i32>>,
+        z: &mut vec::IntoIter<i32>,
+    ) {
+        for coord in coords {
+            self.coords.push(coord.x.into());
+            self.coords.push(coord.y.into());
+            if self.dims == 3 {
+                self.coords.push(z.next().unwrap_or_default().into());
+            }
+        }
+    }
+
+    /// End the current list at nesting `level`.
+    fn close(&mut self, level: usize) -> MltResult<()> {
+        let len = match self.offsets.get(level + 1) {
+            Some(inner) => inner.len() - 1,
+            None => self.coords.len() / self.dims,
+        };
+        self.offsets[level].push(i32::try_from(len)?);
+        Ok(())
+    }
+
+    fn finish(self) -> MltResult<(Field, ArrayRef)> {
+        let coord_name = if self.dims == 3 { "xyz" } else { "xy" };
+        let mut array: ArrayRef = Arc::new(FixedSizeListArray::try_new(
+            Arc::new(Field::new(coord_name, DataType::Float64, false)),
+            i32::try_from(self.dims)?,
+            Arc::new(Float64Array::from(self.coords)),
+            None,
+        )?);
+        let names = Self::child_names(self.geom_type);
+        for (offsets, name) in self.offsets.into_iter().zip(names).rev() {
+            let child = Field::new(*name, array.data_type().clone(), false);
+            array = Arc::new(ListArray::try_new(
+                Arc::new(child),
+                OffsetBuffer::new(offsets.into()),
+                array,
+                None,
+            )?);
+        }
+        let extension_name = match self.geom_type {
+            GeometryType::Point => "geoarrow.point",
+            GeometryType::LineString => "geoarrow.linestring",
+            GeometryType::Polygon => "geoarrow.polygon",
+            GeometryType::MultiPoint => "geoarrow.multipoint",
+            GeometryType::MultiLineString => "geoarrow.multilinestring",
+            GeometryType::MultiPolygon => "geoarrow.multipolygon",
+        };
+        let field = Field::new(GEOMETRY_COLUMN, array.data_type().clone(), false)
+            .with_metadata(extension(extension_name));
+        Ok((field, array))
+    }
+}
+
+fn wkb_column(geometry: &GeometryValues) -> MltResult<(Field, ArrayRef)> {
+    let count = geometry.vector_types().len();
+    let mut values = Vec::with_capacity(count);
+    for index in 0..count {
+        let (geom, z, _) = geometry.feature_with_vertices(index)?;
+        let mut wkb = Wkb {
+            bytes: Vec::new(),
+            z: z.map(Vec::into_iter),
+        };
+        wkb.geometry(&geom)?;
+        values.push(wkb.bytes);
+    }
+    let field = Field::new(GEOMETRY_COLUMN, DataType::Binary, false)
+        .with_metadata(extension("geoarrow.wkb"));
+    Ok((field, Arc::new(BinaryArray::from_iter_values(values))))
+}
+
+/// Little-endian ISO WKB writer for one feature.
+struct Wkb {
+    bytes: Vec<u8>,
+    /// Z values in coordinate order, for 3D geometries.
+    z: Option<vec::IntoIter<i32>>,
+}
+
+impl Wkb {
+    fn header(&mut self, code: u32) {
+        let code = if self.z.is_some() { code + 1000 } else { code };
+        self.bytes.push(1);
+        self.bytes.extend_from_slice(&code.to_le_bytes());
+    }
+
+    fn count(&mut self, count: usize) -> MltResult<()> {
+        self.bytes
+            .extend_from_slice(&u32::try_from(count)?.to_le_bytes());
+        Ok(())
+    }
+
+    fn coord(&mut self, coord: Coord<i32>) {
+        self.bytes
+            .extend_from_slice(&f64::from(coord.x).to_le_bytes());
+        self.bytes
+            .extend_from_slice(&f64::from(coord.y).to_le_bytes());
+        if let Some(z) = &mut self.z {
+            let z = f64::from(z.next().unwrap_or_default());
+            self.bytes.extend_from_slice(&z.to_le_bytes());
+        }
+    }
+
+    fn line(&mut self, line: &LineString<i32>) -> MltResult<()> {
+        self.count(line.0.len())?;
+        for &coord in &line.0 {
+            self.coord(coord);
+        }
+        Ok(())
+    }
+
+    fn rings(&mut self, polygon: &Polygon<i32>) -> MltResult<()> {
+        self.count(1 + polygon.interiors().len())?;
+        for ring in iter::once(polygon.exterior()).chain(polygon.interiors()) {
+            self.line(ring)?;
+        }
+        Ok(())
+    }
+
+    fn geometry(&mut self, geom: &Geometry<i32>) -> MltResult<()> {
+        match geom {
+            Geometry::Point(point) => {
+                self.header(1);
+                self.coord(point.0);
+            }
+            Geometry::LineString(line) => {
+                self.header(2);
+                self.line(line)?;
+            }
+            Geometry::Polygon(polygon) => {
+                self.header(3);
+                self.rings(polygon)?;
+            }
+            Geometry::MultiPoint(points) => {
+                self.header(4);
+                self.count(points.0.len())?;
+                for point in points {
+                    self.header(1);
+                    self.coord(point.0);
+                }
+            }
+            Geometry::MultiLineString(lines) => {
+                self.header(5);
+                self.count(lines.0.len())?;
+                for line in lines {
+                    self.header(2);
+                    self.line(line)?;
+                }
+            }
+            Geometry::MultiPolygon(polygons) => {
+                self.header(6);
+                self.count(polygons.0.len())?;
+                for polygon in polygons {
+                    self.header(3);
+                    self.rings(polygon)?;
+                }
+            }
+            _ => unreachable!("MLT only decodes points, lines and polygons"),
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow_array::{Array as _, UInt32Array};
+    use bitvec::bitvec;
+    use bitvec::order::Lsb0;
+
+    use super::*;
+    use crate::decoder::DictRange;
+
+    fn range(start: i32, end: i32) -> DictRange {
+        DictRange { start, end }
+    }
+
+    /// A shared dictionary over the corpus `ParisLyon` with an `addr:city` and `addr:town` item.
+    fn cities() -> ParsedSharedDict<'static> {
+        let paris = range(0, 5);
+        let lyon = range(5, 9);
+        ParsedSharedDict {
+            prefix: "addr:",
+            data: Cow::Borrowed("ParisLyon"),
+            items: vec![
+                ParsedSharedDictItem {
+                    suffix: "city",
+                    ranges: vec![paris, DictRange::NULL, lyon, paris],
+                },
+                ParsedSharedDictItem {
+                    suffix: "town",
+                    ranges: vec![lyon, lyon, range(5, 5), DictRange::NULL],
+                },
+            ],
+        }
+    }
+
+    fn dictionary(keys: Vec<Option<i32>>, values: Vec<&str>) -> DictionaryArray<Int32Type> {
+        DictionaryArray::try_new(Int32Array::from(keys), Arc::new(StringArray::from(values)))
+            .unwrap()
+    }
+
+    #[test]
+    fn presence_bits_become_null_bitmap() {
+        let presence = Presence::Bits {
+            bits: Cow::Owned(bitvec![u8, Lsb0; 1, 0, 0, 1]),
+            values: vec![3_u32, 1],
+        };
+        let array = primitive::<UInt32Type>(&presence);
+        assert_eq!(array, UInt32Array::from(vec![Some(3), None, None, Some(1)]));
+        assert_eq!(
+            array.nulls(),
+            Some(&NullBuffer::from(vec![true, false, false, true]))
+        );
+    }
+
+    #[test]
+    fn all_present_has_no_null_bitmap() {
+        let array = primitive::<UInt32Type>(&Presence::AllPresent(vec![3_u32, 1]));
+        assert_eq!(array, UInt32Array::from(vec![3, 1]));
+        assert!(array.nulls().is_none());
+    }
+
+    #[test]
+    fn shared_dict_strings_are_dictionary_arrays() {
+        let dict = cities();
+        assert_eq!(
+            dictionary_strings(&dict, &dict.items[0]).unwrap(),
+            dictionary(vec![Some(0), None, Some(1), Some(0)], vec!["Paris", "Lyon"])
+        );
+        // Each item only holds the entries it uses, and empty strings are values, not nulls
+        assert_eq!(
+            dictionary_strings(&dict, &dict.items[1]).unwrap(),
+            dictionary(vec![Some(0), Some(0), Some(1), None], vec!["Lyon", ""])
+        );
+    }
+
+    #[test]
+    fn shared_dict_property_columns() {
+        let mut columns = Columns::default();
+        columns
+            .push_property("", &ParsedProperty::SharedDict(cities()))
+            .unwrap();
+
+        let names: Vec<_> = columns.fields.iter().map(Field::name).collect();
+        assert_eq!(names, ["addr:city", "addr:town"]);
+        let key_type = DataType::Dictionary(Box::new(DataType::Int32), Box::new(DataType::Utf8));
+        for (field, array) in columns.fields.iter().zip(&columns.arrays) {
+            assert_eq!(field.data_type(), &key_type);
+            assert!(field.is_nullable());
+            assert_eq!(array.len(), 4);
+        }
+        assert_eq!(
+            columns.arrays[0].nulls(),
+            Some(&NullBuffer::from(vec![true, false, true, true]))
+        );
+    }
+}
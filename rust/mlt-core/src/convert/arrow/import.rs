@@ -0,0 +1,715 @@
+use std::ops::Range;
+
+use arrow_array::cast::AsArray as _;
+use arrow_array::types::{
+    ArrowPrimitiveType, Date32Type, Float32Type, Float64Type, Int8Type, Int16Type, Int32Type,
+    Int64Type, TimestampMicrosecondType, TimestampMillisecondType, TimestampNanosecondType,
+    TimestampSecondType, UInt8Type, UInt16Type, UInt32Type, UInt64Type,
+};
+use arrow_array::{Array, ArrayRef, RecordBatch};
+use arrow_buffer::ArrowNativeType as _;
+use arrow_schema::{DataType, Field, Schema, TimeUnit};
+use geo_types::{
+    Coord, Geometry, LineString, MultiLineString, MultiPoint, MultiPolygon, Point, Polygon,
+};
+
+use crate::convert::arrow::{EXTENSION_NAME_KEY, GEOMETRY_COLUMN, ID_COLUMN};
+use crate::decoder::NestedValue;
+use crate::encoder::{Codecs, Encoder, EncoderConfig, StagedId, StagedLayer, StagedProperty};
+use crate::{GeometryType, GeometryValues, MltError, MltResult};
+
+/// Encode an Arrow [`RecordBatch`] as an MLT layer, without going through [`TileLayer`](crate::TileLayer).
+///
+/// The columns are read as described in [`StagedLayer::from_record_batch`].  Features are
+/// written in batch order and string columns are not grouped into shared dictionaries, so
+/// sort the batch beforehand if a spatial order is wanted.
+///
+/// Like [`TileLayer::encode`](crate::TileLayer::encode), returns a complete layer record,
+/// or nothing for an empty batch.
+pub fn encode_record_batch(
+    name: impl Into<String>,
+    extent: u32,
+    batch: &RecordBatch,
+    cfg: EncoderConfig,
+) -> MltResult<Vec<u8>> {
+    if batch.num_rows() == 0 {
+        return Ok(Vec::new());
+    }
+    StagedLayer::from_record_batch(name, extent, batch, cfg.tessellate())?
+        .encode_into(Encoder::new(cfg), &mut Codecs::default())?
+        .into_layer_bytes()
+}
+
+impl StagedLayer {
+    /// Build a layer from an Arrow [`RecordBatch`] with one row per feature.
+    ///
+    /// The geometry column is the one with a `geoarrow.*` extension type, or else the one
+    /// named [`GEOMETRY_COLUMN`], which then holds WKB.  Native GeoArrow arrays may use
+    /// interleaved or separated `Float64` coordinates; a Z dimension is kept and an M
+    /// dimension dropped.  WKB values may be ISO or extended WKB, but not geometry
+    /// collections.  Coordinates are rounded to the nearest integer, and null geometries
+    /// are rejected.
+    ///
+    /// An integer column named [`ID_COLUMN`] holds the feature IDs.  Every other column
+    /// becomes a property, with its null bitmap as presence stream:
+    ///
+    /// - booleans, integers and floats keep their type; 16-bit integers are widened to 32 bits
+    /// - `Utf8`, `LargeUtf8` and `Utf8View` arrays, and dictionary arrays of these, become
+    ///   strings
+    /// - `Binary`, `LargeBinary` and `BinaryView` arrays become nested lists of bytes
+    /// - `Date32` becomes an `Int32` day count, and timestamps of any unit become `Int64`
+    ///   millisecond counts
+    ///
+    /// Columns of other types fail with [`MltError::UnsupportedArrowType`].  The output of
+    /// [`layer_to_record_batch`](super::layer_to_record_batch) is accepted as input, its
+    /// shared dictionaries becoming plain string columns.
+    pub fn from_record_batch(
+        name: impl Into<String>,
+        extent: u32,
+        batch: &RecordBatch,
+        tessellate: bool,
+    ) -> MltResult<Self> {
+        let schema = batch.schema();
+        let geometry_idx = geometry_column(&schema)?;
+        let geometry = geometry_values(
+            schema.field(geometry_idx),
+            batch.column(geometry_idx),
+            tessellate,
+        )?;
+
+        let mut id = StagedId::None;
+        let mut properties = Vec::with_capacity(batch.num_columns());
+        for (idx, (field, array)) in schema.fields().iter().zip(batch.columns()).enumerate() {
+            if idx == geometry_idx {
+                continue;
+            }
+            if field.name() == ID_COLUMN
+                && let Some(ids) = ids(array)?
+            {
+                id = StagedId::from_optional(ids);
+                continue;
+            }
+            properties.push(property(field, array)?);
+        }
+        Self::new(name, extent, id, geometry, properties)
+    }
+}
+
+fn unsupported(field: &Field) -> MltError {
+    MltError::UnsupportedArrowType {
+        column: field.name().clone(),
+        data_type: field.data_type().clone(),
+    }
+}
+
+/// The `geoarrow.*` extension type of a field, without the prefix.
+fn geoarrow_extension(field: &Field) -> Option<&str> {
+    field
+        .metadata()
+        .get(EXTENSION_NAME_KEY)?
+        .strip_prefix("geoarrow.")
+}
+
+fn geometry_column(schema: &Schema) -> MltResult<usize> {
+    let mut found = None;
+    for (idx, field) in schema.fields().iter().enumerate() {
+        if geoarrow_extension(field).is_some() && found.replace(idx).is_some() {
+            return Err(MltError::MultipleGeometryColumns);
+        }
+    }
+    found
+        .or_else(|| schema.index_of(GEOMETRY_COLUMN).ok())
+        .ok_or(MltError::MissingGeometry)
+}
+
+fn ids(array: &ArrayRef) -> MltResult<Option<Vec<Option<u64>>>> {
+    Ok(Some(match array.data_type() {
+        DataType::UInt32 => array
+            .as_primitive::<UInt32Type>()
+            .iter()
+            .map(|id| id.map(u64::from))
+            .collect(),
+        DataType::UInt64 => array.as_primitive::<UInt64Type>().iter().collect(),
+        DataType::Int32 => array
+            .as_primitive::<Int32Type>()
+            .iter()
+            .map(|id| id.map(u64::try_from).transpose())
+            .collect::<Result<_, _>>()?,
+        DataType::Int64 => array
+            .as_primitive::<Int64Type>()
+            .iter()
+            .map(|id| id.map(u64::try_from).transpose())
+            .collect::<Result<_, _>>()?,
+        _ => return Ok(None),
+    }))
+}
+
+// ── Properties ────────────────────────────────────────────────────────────────
+
+fn property(field: &Field, array: &ArrayRef) -> MltResult<StagedProperty> {
+    use StagedProperty as SP;
+    let name = field.name().clone();
+    Ok(match array.data_type() {
+        DataType::Boolean => {
+            let array = array.as_boolean();
+            if array.null_count() == 0 {
+                SP::bool(name, array.values().iter().collect())
+            } else {
+                SP::opt_bool(name, array.iter())
+            }
+        }
+        DataType::Int8 => primitive::<Int8Type, _>(name, array, |v| v, SP::i8, SP::opt_i8),
+        DataType::UInt8 => primitive::<UInt8Type, _>(name, array, |v| v, SP::u8, SP::opt_u8),
+        DataType::Int16 => primitive::<Int16Type, _>(name, array, i32::from, SP::i32, SP::opt_i32),
+        DataType::UInt16 => {
+            primitive::<UInt16Type, _>(name, array, u32::from, SP::u32, SP::opt_u32)
+        }
+        DataType::Int32 => primitive::<Int32Type, _>(name, array, |v| v, SP::i32, SP::opt_i32),
+        DataType::UInt32 => primitive::<UInt32Type, _>(name, array, |v| v, SP::u32, SP::opt_u32),
+        DataType::Int64 => primitive::<Int64Type, _>(name, array, |v| v, SP::i64, SP::opt_i64),
+        DataType::UInt64 => primitive::<UInt64Type, _>(name, array, |v| v, SP::u64, SP::opt_u64),
+        DataType::Float32 => primitive::<Float32Type, _>(name, array, |v| v, SP::f32, SP::opt_f32),
+        DataType::Float64 => primitive::<Float64Type, _>(name, array, |v| v, SP::f64, SP::opt_f64),
+        DataType::Date32 => primitive::<Date32Type, _>(name, array, |v| v, SP::i32, SP::opt_i32),
+        DataType::Timestamp(TimeUnit::Second, _) => primitive::<TimestampSecondType, _>(
+            name,
+            array,
+            |v| v.saturating_mul(1000),
+            SP::i64,
+            SP::opt_i64,
+        ),
+        DataType::Timestamp(TimeUnit::Millisecond, _) => {
+            primitive::<TimestampMillisecondType, _>(name, array, |v| v, SP::i64, SP::opt_i64)
+        }
+        DataType::Timestamp(TimeUnit::Microsecond, _) => primitive::<TimestampMicrosecondType, _>(
+            name,
+            array,
+            |v| v.div_euclid(1000),
+            SP::i64,
+            SP::opt_i64,
+        ),
+        DataType::Timestamp(TimeUnit::Nanosecond, _) => primitive::<TimestampNanosecondType, _>(
+            name,
+            array,
+            |v| v.div_euclid(1_000_000),
+            SP::i64,
+            SP::opt_i64,
+        ),
+        DataType::Binary | DataType::LargeBinary | DataType::BinaryView => {
+            let values = binaries(array).ok_or_else(|| unsupported(field))?;
+            SP::nested(
+                name,
+                values.into_iter().map(|bytes| {
+                    let bytes = bytes?.iter().map(|&b| NestedValue::U64(b.into()));
+                    Some(NestedValue::List(bytes.collect()))
+                }),
+            )
+        }
+        _ => {
+            let values = strings(array).ok_or_else(|| unsupported(field))?;
+            if values.iter().all(Option::is_some) {
+                SP::str(name, values.into_iter().flatten())
+            } else {
+                SP::opt_str(name, values)
+            }
+        }
+    })
+}
+
+/// Convert a primitive array, using the non-optional column form if it has no nulls.
+fn primitive<T: ArrowPrimitiveType, U>(
+    name: String,
+    array: &ArrayRef,
+    convert: impl Fn(T::Native) -> U,
+    dense: impl FnOnce(String, Vec<U>) -> StagedProperty,
+    optional: impl FnOnce(String, Vec<Option<U>>) -> StagedProperty,
+) -> StagedProperty {
+    let array = array.as_primitive::<T>();
+    if array.null_count() == 0 {
+        dense(name, array.values().iter().map(|&v| convert(v)).collect())
+    } else {
+        optional(name, array.iter().map(|v| v.map(&convert)).collect())
+    }
+}
+
+/// The values of a string or string dictionary array, `None` for other types.
+fn strings(array: &dyn Array) -> Option<Vec<Option<&str>>> {
+    Some(match array.data_type() {
+        DataType::Utf8 => array.as_string::<i32>().iter().collect(),
+        DataType::LargeUtf8 => array.as_string::<i64>().iter().collect(),
+        DataType::Utf8View => array.as_string_view().iter().collect(),
+        DataType::Dictionary(..) => {
+            let dict = array.as_any_dictionary();
+            let values = strings(dict.values().as_ref())?;
+            let keys = dict.keys();
+            dict.normalized_keys()
+                .into_iter()
+                .enumerate()
+                .map(|(i, key)| {
+                    if keys.is_null(i) {
+                        None
+                    } else {
+                        values.get(key).copied().flatten()
+                    }
+                })
+                .collect()
+        }
+        _ => return None,
+    })
+}
+
+/// The values of a binary array, `None` for other types.
+fn binaries(array: &dyn Array) -> Option<Vec<Option<&[u8]>>> {
+    Some(match array.data_type() {
+        DataType::Binary => array.as_binary::<i32>().iter().collect(),
+        DataType::LargeBinary => array.as_binary::<i64>().iter().collect(),
+        DataType::BinaryView => array.as_binary_view().iter().collect(),
+        _ => return None,
+    })
+}
+
+// ── Geometry ──────────────────────────────────────────────────────────────────
+
+fn geometry_values(field: &Field, array: &ArrayRef, tessellate: bool) -> MltResult<GeometryValues> {
+    let mut values = if tessellate {
+        GeometryValues::new_tessellated()
+    } else {
+        GeometryValues::default()
+    };
+    let mut push = |geom: &Geometry<i32>, z: Option<&[i32]>| {
+        // The first 3D feature makes the whole geometry column 3D.
+        if z.is_some() || values.z.is_some() {
+            values.push_geom_z(geom, z);
+        } else {
+            values.push_geom(geom);
+        }
+    };
+    let invalid = |row: usize, reason: &'static str| MltError::InvalidArrowGeometry { row, reason };
+
+    match geoarrow_extension(field).unwrap_or("wkb") {
+        "wkb" => {
+            let wkb = binaries(array).ok_or_else(|| unsupported(field))?;
+            for (row, bytes) in wkb.into_iter().enumerate() {
+                let bytes = bytes.ok_or_else(|| invalid(row, "null geometry"))?;
+                let (geom, z) =
+                    read_wkb(bytes).ok_or_else(|| invalid(row, "invalid or unsupported WKB"))?;
+                push(&geom, z.as_deref());
+            }
+        }
+        name => {
+            let native = Native::new(name, array).ok_or_else(|| unsupported(field))?;
+            let mut z = Vec::new();
+            for row in 0..array.len() {
+                if array.is_null(row) {
+                    return Err(invalid(row, "null geometry"));
+                }
+                z.clear();
+                let geom = native
+                    .geometry(row, &mut z)
+                    .ok_or_else(|| invalid(row, "coordinate out of range"))?;
+                push(&geom, native.coords.has_z().then_some(z.as_slice()));
+            }
+        }
+    }
+    Ok(values)
+}
+
+/// Round a coordinate to the nearest integer, `None` if it is out of range or not finite.
+#[expect(
+    clippy::cast_possible_truncation,
+    reason = "the value is rounded and range checked"
+)]
+fn to_i32(value: f64) -> Option<i32> {
+    let value = value.round();
+    (f64::from(i32::MIN)..=f64::from(i32::MAX))
+        .contains(&value)
+        .then_some(value as i32)
+}
+
+/// Coordinates of a GeoArrow native array.
+enum Coords<'a> {
+    /// `FixedSizeList<Float64>` with `dims` values per coordinate.
+    Interleaved {
+        values: &'a [f64],
+        dims: usize,
+        has_z: bool,
+    },
+    /// `Struct` of `Float64` arrays.
+    Separated {
+        x: &'a [f64],
+        y: &'a [f64],
+        z: Option<&'a [f64]>,
+    },
+}
+
+impl<'a> Coords<'a> {
+    fn new(array: &'a dyn Array) -> Option<Self> {
+        let floats = |array: &'a ArrayRef| -> Option<&'a [f64]> {
+            Some(array.as_primitive_opt::<Float64Type>()?.values())
+        };
+        match array.data_type() {
+            DataType::FixedSizeList(child, size) => {
+                let dims = usize::try_from(*size).ok().filter(|&dims| dims >= 2)?;
+                Some(Self::Interleaved {
+                    values: floats(array.as_fixed_size_list().values())?,
+                    dims,
+                    has_z: dims == 4 || (dims == 3 && child.name() != "xym"),
+                })
+            }
+            DataType::Struct(_) => {
+                let array = array.as_struct();
+                Some(Self::Separated {
+                    x: floats(array.column_by_name("x")?)?,
+                    y: floats(array.column_by_name("y")?)?,
+                    z: array.column_by_name("z").and_then(floats),
+                })
+            }
+            _ => None,
+        }
+    }
+
+    fn has_z(&self) -> bool {
+        match self {
+            Self::Interleaved { has_z, .. } => *has_z,
+            Self::Separated { z, .. } => z.is_some(),
+        }
+    }
+
+    /// Coordinate `index`, appending its Z value to `z` if there is one.
+    fn get(&self, index: usize, z: &mut Vec<i32>) -> Option<Coord<i32>> {
+        let (x, y, z_value) = match self {
+            Self::Interleaved {
+                values,
+                dims,
+                has_z,
+            } => {
+                let coord = &values[index * dims..(index + 1) * dims];
+                (coord[0], coord[1], has_z.then(|| coord[2]))
+            }
+            Self::Separated { x, y, z } => (x[index], y[index], z.map(|z| z[index])),
+        };
+        if let Some(value) = z_value {
+            z.push(to_i32(value)?);
+        }
+        Some(Coord {
+            x: to_i32(x)?,
+            y: to_i32(y)?,
+        })
+    }
+}
+
+/// A GeoArrow native array of one geometry type.
+struct Native<'a> {
+    geom_type: GeometryType,
+    /// List offsets from the outermost (one entry per feature) to the innermost
+    /// (into `coords`) nesting level.
+    offsets: Vec<Vec<usize>>,
+    coords: Coords<'a>,
+}
+
+impl<'a> Native<'a> {
+    /// Read an array of the `geoarrow.{name}` extension type, `None` if not supported.
+    fn new(name: &str, array: &'a ArrayRef) -> Option<Self> {
+        let (geom_type, depth) = match name {
+            "point" => (GeometryType::Point, 0),
+            "linestring" => (GeometryType::LineString, 1),
+            "polygon" => (GeometryType::Polygon, 2),
+            "multipoint" => (GeometryType::MultiPoint, 1),
+            "multilinestring" => (GeometryType::MultiLineString, 2),
+            "multipolygon" => (GeometryType::MultiPolygon, 3),
+            _ => return None,
+        };
+        let mut offsets = Vec::with_capacity(depth);
+        let mut array: &ArrayRef = array;
+        for _ in 0..depth {
+            let (level, values): (Vec<usize>, &ArrayRef) = match array.data_type() {
+                DataType::List(_) => {
+                    let list = array.as_list::<i32>();
+                    (
+                        list.value_offsets().iter().map(|o| o.as_usize()).collect(),
+                        list.values(),
+                    )
+                }
+                DataType::LargeList(_) => {
+                    let list = array.as_list::<i64>();
+                    (
+                        list.value_offsets().iter().map(|o| o.as_usize()).collect(),
+                        list.values(),
+                    )
+                }
+                _ => return None,
+            };
+            offsets.push(level);
+            array = values;
+        }
+        Some(Self {
+            geom_type,
+            offsets,
+            coords: Coords::new(array.as_ref())?,
+        })
+    }
+
+    fn range(&self, level: usize, index: usize) -> Range<usize> {
+        self.offsets[level][index]..self.offsets[level][index + 1]
+    }
+
+    /// Feature `row`, appending its Z values to `z`; `None` if a coordinate is out of range.
+    fn geometry(&self, row: usize, z: &mut Vec<i32>) -> Option<Geometry<i32>> {
+        Some(match self.geom_type {
+            GeometryType::Point => Point(self.coords.get(row, z)?).into(),
+            GeometryType::LineString => self.line(0, row, z)?.into(),
+            GeometryType::Polygon => self.polygon(0, row, z)?.into(),
+            GeometryType::MultiPoint => MultiPoint(
+                self.range(0, row)
+                    .map(|i| self.coords.get(i, z).map(Point))
+                    .collect::<Option<_>>()?,
+            )
+            .into(),
+            GeometryType::MultiLineString => MultiLineString(
+                self.range(0, row)
+                    .map(|i| self.line(1, i, z))
+                    .collect::<Option<_>>()?,
+            )
+            .into(),
+            GeometryType::MultiPolygon => MultiPolygon(
+                self.range(0, row)
+                    .map(|i| self.polygon(1, i, z))
+                    .collect::<Option<_>>()?,
+            )
+            .into(),
+        })
+    }
+
+    /// List `index` of nesting `level`, whose items are coordinates.
+    fn line(&self, level: usize, index: usize, z: &mut Vec<i32>) -> Option<LineString<i32>> {
+        self.range(level, index)
+            .map(|i| self.coords.get(i, z))
+            .collect::<Option<_>>()
+            .map(LineString)
+    }
+
+    /// List `index` of nesting `level`, whose items are rings.
+    fn polygon(&self, level: usize, index: usize, z: &mut Vec<i32>) -> Option<Polygon<i32>> {
+        let mut rings = self.range(level, index).map(|i| self.line(level + 1, i, z));
+        let exterior = rings
+            .next()
+            .unwrap_or_else(|| Some(LineString::new(vec![])))?;
+        let interiors = rings.collect::<Option<_>>()?;
+        Some(Polygon::new(exterior, interiors))
+    }
+}
+
+/// Parse a WKB geometry and its Z values, if it has any.
+fn read_wkb(bytes: &[u8]) -> Option<(Geometry<i32>, Option<Vec<i32>>)> {
+    let mut reader = WkbReader {
+        bytes,
+        z: Vec::new(),
+        has_z: false,
+    };
+    let geom = reader.geometry(None)?;
+    Some((geom, reader.has_z.then_some(reader.z)))
+}
+
+/// Reads ISO and extended (`PostGIS`) WKB.
+struct WkbReader<'a> {
+    bytes: &'a [u8],
+    z: Vec<i32>,
+    has_z: bool,
+}
+
+/// Byte order and dimensions of a WKB geometry.
+#[derive(Clone, Copy)]
+struct WkbDims {
+    little_endian: bool,
+    has_z: bool,
+    has_m: bool,
+}
+
+impl WkbReader<'_> {
+    fn take<const N: usize>(&mut self) -> Option<[u8; N]> {
+        let (head, tail) = self.bytes.split_first_chunk::<N>()?;
+        self.bytes = tail;
+        Some(*head)
+    }
+
+    fn u32(&mut self, little_endian: bool) -> Option<u32> {
+        let bytes = self.take()?;
+        Some(if little_endian {
+            u32::from_le_bytes(bytes)
+        } else {
+            u32::from_be_bytes(bytes)
+        })
+    }
+
+    fn f64(&mut self, little_endian: bool) -> Option<f64> {
+        let bytes = self.take()?;
+        Some(if little_endian {
+            f64::from_le_bytes(bytes)
+        } else {
+            f64::from_be_bytes(bytes)
+        })
+    }
+
+    /// Read a geometry, which must be of WKB type `expected` if given.
+    fn geometry(&mut self, expected: Option<u32>) -> Option<Geometry<i32>> {
+        let little_endian = match self.take::<1>()? {
+            [0] => false,
+            [1] => true,
+            _ => return None,
+        };
+        let code = self.u32(little_endian)?;
+        if code & 0x2000_0000 != 0 {
+            // Extended WKB SRID
+            self.u32(little_endian)?;
+        }
+        let iso_dims = (code & 0x0FFF_FFFF) / 1000;
+        let dims = WkbDims {
+            little_endian,
+            has_z: code & 0x8000_0000 != 0 || iso_dims == 1 || iso_dims == 3,
+            has_m: code & 0x4000_0000 != 0 || iso_dims == 2 || iso_dims == 3,
+        };
+        let geom_type = (code & 0x0FFF_FFFF) % 1000;
+        if expected.is_some_and(|expected| expected != geom_type) {
+            return None;
+        }
+        Some(match geom_type {
+            1 => Point(self.coord(dims)?).into(),
+            2 => self.line(dims)?.into(),
+            3 => self.polygon(dims)?.into(),
+            4 => MultiPoint(self.parts(dims, |reader| match reader.geometry(Some(1))? {
+                Geometry::Point(point) => Some(point),
+                _ => None,
+            })?)
+            .into(),
+            5 => MultiLineString(self.parts(dims, |reader| match reader.geometry(Some(2))? {
+                Geometry::LineString(line) => Some(line),
+                _ => None,
+            })?)
+            .into(),
+            6 => MultiPolygon(self.parts(dims, |reader| match reader.geometry(Some(3))? {
+                Geometry::Polygon(polygon) => Some(polygon),
+                _ => None,
+            })?)
+            .into(),
+            _ => return None,
+        })
+    }
+
+    fn parts<T>(
+        &mut self,
+        dims: WkbDims,
+        mut part: impl FnMut(&mut Self) -> Option<T>,
+    ) -> Option<Vec<T>> {
+        let count = self.u32(dims.little_endian)?;
+        (0..count).map(|_| part(self)).collect()
+    }
+
+    fn coord(&mut self, dims: WkbDims) -> Option<Coord<i32>> {
+        let x = self.f64(dims.little_endian)?;
+        let y = self.f64(dims.little_endian)?;
+        if dims.has_z {
+            let z = self.f64(dims.little_endian)?;
+            self.z.push(to_i32(z)?);
+            self.has_z = true;
+        }
+        if dims.has_m {
+            self.f64(dims.little_endian)?;
+        }
+        Some(Coord {
+            x: to_i32(x)?,
+            y: to_i32(y)?,
+        })
+    }
+
+    fn line(&mut self, dims: WkbDims) -> Option<LineString<i32>> {
+        self.parts(dims, |reader| reader.coord(dims))
+            .map(LineString)
+    }
+
+    fn polygon(&mut self, dims: WkbDims) -> Option<Polygon<i32>> {
+        let mut rings = self.parts(dims, |reader| reader.line(dims))?.into_iter();
+        let exterior = rings.next().unwrap_or_else(|| LineString::new(vec![]));
+        Some(Polygon::new(exterior, rings.collect()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use arrow_array::{
+        BooleanArray, Decimal128Array, DictionaryArray, Int8Array, Int16Array, StringArray,
+        UInt32Array,
+    };
+
+    use super::*;
+
+    fn field(name: &str, array: &ArrayRef) -> Field {
+        Field::new(name, array.data_type().clone(), true)
+    }
+
+    fn convert(array: impl Array + 'static) -> StagedProperty {
+        let array: ArrayRef = Arc::new(array);
+        property(&field("col", &array), &array).unwrap()
+    }
+
+    #[test]
+    fn null_bitmap_becomes_presence() {
+        assert_eq!(
+            convert(UInt32Array::from(vec![Some(3), None, Some(1)])),
+            StagedProperty::opt_u32("col", [Some(3), None, Some(1)])
+        );
+        assert_eq!(
+            convert(BooleanArray::from(vec![None, Some(true), Some(false)])),
+            StagedProperty::opt_bool("col", [None, Some(true), Some(false)])
+        );
+        // 16-bit integers are widened and keep their nulls
+        assert_eq!(
+            convert(Int16Array::from(vec![Some(-2), None])),
+            StagedProperty::opt_i32("col", [Some(-2), None])
+        );
+    }
+
+    #[test]
+    fn no_nulls_stays_dense() {
+        assert_eq!(
+            convert(UInt32Array::from(vec![3, 1])),
+            StagedProperty::u32("col", vec![3, 1])
+        );
+        assert_eq!(
+            convert(StringArray::from(vec!["a", "b"])),
+            StagedProperty::str("col", ["a", "b"])
+        );
+    }
+
+    #[test]
+    fn dictionary_strings_are_resolved() {
+        let keys = Int8Array::from(vec![Some(1), None, Some(0), Some(1)]);
+        let values = StringArray::from(vec!["Paris", "Lyon"]);
+        let dict = DictionaryArray::<Int8Type>::try_new(keys, Arc::new(values)).unwrap();
+        assert_eq!(
+            convert(dict),
+            StagedProperty::opt_str("col", [Some("Lyon"), None, Some("Paris"), Some("Lyon")])
+        );
+    }
+
+    #[test]
+    fn null_dictionary_value_is_null() {
+        let keys = Int8Array::from(vec![0, 1]);
+        let values = StringArray::from(vec![Some("Paris"), None]);
+        let dict = DictionaryArray::<Int8Type>::try_new(keys, Arc::new(values)).unwrap();
+        assert_eq!(
+            convert(dict),
+            StagedProperty::opt_str("col", [Some("Paris"), None])
+        );
+    }
+
+    #[test]
+    fn unsupported_type_is_rejected() {
+        let array: ArrayRef = Arc::new(Decimal128Array::from(vec![1_i128]));
+        assert!(matches!(
+            property(&field("col", &array), &array),
+            Err(MltError::UnsupportedArrowType { column, .. }) if column == "col"
+        ));
+    }
+}
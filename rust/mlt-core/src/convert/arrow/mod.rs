@@ -0,0 +1,26 @@
+//! Convert between MLT layers and Apache Arrow [`RecordBatch`](arrow_array::RecordBatch)es
+//! with a [GeoArrow](https://geoarrow.org) geometry column.
+//!
+//! Requires the `arrow` feature.
+
+mod export;
+mod import;
+#[cfg(test)]
+mod tests;
+
+pub use export::layer_to_record_batch;
+pub use import::encode_record_batch;
+
+/// Name of the feature ID column, present only if the layer has IDs.
+pub const ID_COLUMN: &str = "id";
+/// Name of the GeoArrow geometry column.
+pub const GEOMETRY_COLUMN: &str = "geometry";
+/// Schema metadata key holding the layer name.
+pub const LAYER_NAME_KEY: &str = "mlt:layer";
+/// Schema metadata key holding the layer extent.
+pub const EXTENT_KEY: &str = "mlt:extent";
+
+/// Field metadata key naming the Arrow extension type of a column.
+const EXTENSION_NAME_KEY: &str = "ARROW:extension:name";
+/// Field metadata key holding the parameters of the extension type.
+const EXTENSION_METADATA_KEY: &str = "ARROW:extension:metadata";
@@ -0,0 +1,322 @@
+use std::sync::Arc;
+
+use arrow_array::cast::AsArray as _;
+use arrow_array::types::{Float64Type, Int8Type, Int32Type, Int64Type, UInt32Type, UInt64Type};
+use arrow_array::{
+    Array as _, ArrayRef, BinaryArray, DictionaryArray, Int16Array, Int64Array, ListArray,
+    RecordBatch, TimestampSecondArray, UInt32Array,
+};
+use arrow_schema::DataType;
+use geo_types::{Geometry, LineString, MultiPolygon, Point, Polygon};
+
+use crate::Layer;
+use crate::MltError;
+use crate::convert::arrow::{
+    EXTENSION_NAME_KEY, EXTENT_KEY, GEOMETRY_COLUMN, LAYER_NAME_KEY, encode_record_batch,
+    layer_to_record_batch,
+};
+use crate::decoder::GeometryValues;
+use crate::encoder::{
+    Codecs, Encoder, EncoderConfig, Presence, StagedId, StagedLayer, StagedProperty,
+    StagedSharedDict,
+};
+use crate::test_helpers::{dec, parser};
+
+fn export(
+    id: StagedId,
+    geometries: &[Geometry<i32>],
+    properties: Vec<StagedProperty>,
+) -> RecordBatch {
+    let mut geometry = GeometryValues::default();
+    for geom in geometries {
+        geometry.push_geom(geom);
+    }
+    let buf = StagedLayer::new("layer", 4096, id, geometry, properties)
+        .unwrap()
+        .encode_into(Encoder::default(), &mut Codecs::default())
+        .unwrap()
+        .into_layer_bytes()
+        .unwrap();
+    decode(&buf)
+}
+
+fn decode(buf: &[u8]) -> RecordBatch {
+    let (_, layer) = Layer::from_bytes(buf, &mut parser()).unwrap();
+    let Layer::Tag01(lazy) = layer else { panic!() };
+    layer_to_record_batch(&lazy.decode_all(&mut dec()).unwrap()).unwrap()
+}
+
+fn geometry_extension(batch: &RecordBatch) -> String {
+    let schema = batch.schema();
+    let field = schema.field_with_name(GEOMETRY_COLUMN).unwrap();
+    field.metadata()[EXTENSION_NAME_KEY].clone()
+}
+
+fn square(x: i32) -> Polygon<i32> {
+    Polygon::new(
+        LineString::from(vec![(x, 0), (x + 2, 0), (x + 2, 2), (x, 0)]),
+        vec![],
+    )
+}
+
+#[test]
+fn points_layer_exports_typed_columns() {
+    let city = StagedSharedDict::new(
+        "addr:",
+        [(
+            "city",
+            vec![Some("Paris"), None, Some("Paris")],
+            Presence::Mixed,
+        )],
+    )
+    .unwrap();
+    let batch = export(
+        StagedId::from_optional(vec![Some(7), None, Some(9)]),
+        &[
+            Point::new(1, 2).into(),
+            Point::new(3, 4).into(),
+            Point::new(5, 6).into(),
+        ],
+        vec![
+            StagedProperty::opt_u32("rank", [Some(3), None, Some(1)]),
+            StagedProperty::opt_str("name", [Some("a"), Some("b"), None]),
+            StagedProperty::SharedDict(city),
+            StagedProperty::bool("open", vec![true, false, true]),
+        ],
+    );
+
+    let schema = batch.schema();
+    let names: Vec<_> = schema.fields().iter().map(|f| f.name().as_str()).collect();
+    assert_eq!(
+        names,
+        ["id", "geometry", "rank", "name", "addr:city", "open"]
+    );
+    assert_eq!(schema.metadata()[LAYER_NAME_KEY], "layer");
+    assert_eq!(schema.metadata()[EXTENT_KEY], "4096");
+    assert_eq!(batch.num_rows(), 3);
+
+    let ids = batch.column(0).as_primitive::<UInt64Type>();
+    assert_eq!(ids.iter().collect::<Vec<_>>(), [Some(7), None, Some(9)]);
+
+    assert_eq!(geometry_extension(&batch), "geoarrow.point");
+    let points = batch.column(1).as_fixed_size_list();
+    assert_eq!(points.value_length(), 2);
+    let coords = points.values().as_primitive::<Float64Type>();
+    assert_eq!(coords.values(), &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+
+    let rank = batch.column(2).as_primitive::<UInt32Type>();
+    assert_eq!(rank.iter().collect::<Vec<_>>(), [Some(3), None, Some(1)]);
+    let name = batch.column(3).as_string::<i32>();
+    assert_eq!(
+        name.iter().collect::<Vec<_>>(),
+        [Some("a"), Some("b"), None]
+    );
+
+    let city = batch.column(4).as_dictionary::<Int32Type>();
+    assert_eq!(city.values().len(), 1);
+    assert_eq!(
+        city.keys().iter().collect::<Vec<_>>(),
+        [Some(0), None, Some(0)]
+    );
+
+    let open = batch.column(5).as_boolean();
+    assert_eq!(open.null_count(), 0);
+    assert_eq!(
+        open.iter().collect::<Vec<_>>(),
+        [Some(true), Some(false), Some(true)]
+    );
+}
+
+#[test]
+fn polygons_are_promoted_to_multipolygons() {
+    let batch = export(
+        StagedId::None,
+        &[
+            square(0).into(),
+            MultiPolygon::new(vec![square(4), square(8)]).into(),
+        ],
+        vec![],
+    );
+    assert_eq!(batch.schema().fields().len(), 1);
+    assert_eq!(geometry_extension(&batch), "geoarrow.multipolygon");
+
+    let polygons = batch.column(0).as_list::<i32>();
+    assert_eq!(polygons.value_offsets(), &[0, 1, 3]);
+    let rings = polygons.values().as_list::<i32>();
+    assert_eq!(rings.value_offsets(), &[0, 1, 2, 3]);
+    // Rings are closed, as GeoArrow requires
+    let vertices = rings.values().as_list::<i32>();
+    assert_eq!(vertices.value_offsets(), &[0, 4, 8, 12]);
+    let coords = vertices.values().as_fixed_size_list();
+    let coords = coords.values().as_primitive::<Float64Type>();
+    assert_eq!(
+        &coords.values()[..8],
+        &[0.0, 0.0, 2.0, 0.0, 2.0, 2.0, 0.0, 0.0]
+    );
+}
+
+#[test]
+fn mixed_geometry_families_fall_back_to_wkb() {
+    let batch = export(
+        StagedId::None,
+        &[
+            Point::new(1, 2).into(),
+            LineString::from(vec![(0, 0), (3, 4)]).into(),
+        ],
+        vec![],
+    );
+    assert_eq!(geometry_extension(&batch), "geoarrow.wkb");
+    assert_eq!(batch.column(0).data_type(), &DataType::Binary);
+
+    let wkb = batch.column(0).as_binary::<i32>();
+    assert_eq!(wkb.value(0), wkb_point(1.0, 2.0));
+    // Byte order, type 2 (LineString), two vertices
+    assert_eq!(&wkb.value(1)[..9], &[1, 2, 0, 0, 0, 2, 0, 0, 0]);
+}
+
+/// Little-endian WKB of a 2D point.
+fn wkb_point(x: f64, y: f64) -> Vec<u8> {
+    let mut wkb = vec![1, 1, 0, 0, 0];
+    wkb.extend_from_slice(&x.to_le_bytes());
+    wkb.extend_from_slice(&y.to_le_bytes());
+    wkb
+}
+
+#[test]
+fn record_batch_round_trip() {
+    let batch = export(
+        StagedId::from_optional(vec![Some(1), Some(u64::MAX), None]),
+        &[
+            LineString::from(vec![(0, 0), (3, 4)]).into(),
+            LineString::from(vec![(-5, 8), (1, 1), (2, 7)]).into(),
+            LineString::from(vec![(9, 9), (0, 0)]).into(),
+        ],
+        vec![
+            StagedProperty::opt_u32("rank", [Some(3), None, Some(1)]),
+            StagedProperty::opt_str("name", [Some("a"), None, Some("c")]),
+            StagedProperty::bool("open", vec![true, false, true]),
+            StagedProperty::opt_f64("speed", [None, Some(2.5), Some(-1.0)]),
+            StagedProperty::i32("since", vec![0, 19_000, -1]),
+            StagedProperty::opt_i64("seen", [Some(1_700_000_000_000), None, Some(0)]),
+        ],
+    );
+    let schema = batch.schema();
+    let name = &schema.metadata()[LAYER_NAME_KEY];
+    let extent = schema.metadata()[EXTENT_KEY].parse().unwrap();
+    let buf = encode_record_batch(name, extent, &batch, EncoderConfig::default()).unwrap();
+    assert_eq!(decode(&buf), batch);
+}
+
+#[test]
+fn record_batch_types_are_mapped() {
+    // ISO WKB point Z, little-endian
+    let mut point_z = vec![1, 0xE9, 0x03, 0, 0];
+    for v in [1.0_f64, 2.0, 3.0] {
+        point_z.extend_from_slice(&v.to_le_bytes());
+    }
+    // Extended WKB point with SRID 4326, big-endian
+    let mut point_srid = vec![0, 0x20, 0, 0, 1, 0, 0, 0x10, 0xE6];
+    for v in [4.4_f64, 4.6] {
+        point_srid.extend_from_slice(&v.to_be_bytes());
+    }
+    let batch = RecordBatch::try_from_iter([
+        (
+            "id",
+            Arc::new(Int64Array::from(vec![Some(5), None])) as ArrayRef,
+        ),
+        (
+            GEOMETRY_COLUMN,
+            Arc::new(BinaryArray::from_vec(vec![&point_z[..], &point_srid[..]])) as ArrayRef,
+        ),
+        (
+            "kind",
+            Arc::new(DictionaryArray::<Int8Type>::from_iter([Some("a"), None])) as ArrayRef,
+        ),
+        ("level", Arc::new(Int16Array::from(vec![1, -2])) as ArrayRef),
+        (
+            "at",
+            Arc::new(TimestampSecondArray::from(vec![Some(1), None])) as ArrayRef,
+        ),
+    ])
+    .unwrap();
+    let buf = encode_record_batch("mapped", 512, &batch, EncoderConfig::default()).unwrap();
+    let decoded = decode(&buf);
+
+    let schema = decoded.schema();
+    let names: Vec<_> = schema.fields().iter().map(|f| f.name().as_str()).collect();
+    assert_eq!(names, ["id", "geometry", "kind", "level", "at"]);
+    assert_eq!(schema.metadata()[LAYER_NAME_KEY], "mapped");
+
+    let ids = decoded.column(0).as_primitive::<UInt64Type>();
+    assert_eq!(ids.iter().collect::<Vec<_>>(), [Some(5), None]);
+    // The 2D point gets a zero Z in the now 3D layer
+    let points = decoded.column(1).as_fixed_size_list();
+    assert_eq!(points.value_length(), 3);
+    let coords = points.values().as_primitive::<Float64Type>();
+    assert_eq!(coords.values(), &[1.0, 2.0, 3.0, 4.0, 5.0, 0.0]);
+    let kind = decoded.column(2).as_string::<i32>();
+    assert_eq!(kind.iter().collect::<Vec<_>>(), [Some("a"), None]);
+    let level = decoded.column(3).as_primitive::<Int32Type>();
+    assert_eq!(level.iter().collect::<Vec<_>>(), [Some(1), Some(-2)]);
+    let at = decoded.column(4).as_primitive::<Int64Type>();
+    assert_eq!(at.iter().collect::<Vec<_>>(), [Some(1000), None]);
+}
+
+#[test]
+fn record_batch_errors() {
+    let wkb = |geometry: BinaryArray, column: ArrayRef| {
+        let batch = RecordBatch::try_from_iter([
+            (GEOMETRY_COLUMN, Arc::new(geometry) as ArrayRef),
+            ("value", column),
+        ])
+        .unwrap();
+        StagedLayer::from_record_batch("layer", 4096, &batch, false)
+    };
+    let point = wkb_point(0.0, 0.0);
+    let point = point.as_slice();
+
+    let list: ArrayRef = Arc::new(ListArray::from_iter_primitive::<Int32Type, _, _>([Some([
+        Some(1),
+    ])]));
+    assert!(matches!(
+        wkb(BinaryArray::from_vec(vec![point]), list),
+        Err(MltError::UnsupportedArrowType { column, data_type: DataType::List(_) })
+            if column == "value"
+    ));
+
+    let int: ArrayRef = Arc::new(Int16Array::from(vec![1, 2]));
+    let geometry = BinaryArray::from_opt_vec(vec![Some(point), None]);
+    assert!(matches!(
+        wkb(geometry, int.clone()),
+        Err(MltError::InvalidArrowGeometry { row: 1, .. })
+    ));
+
+    // Geometry collections are not supported
+    let collection: &[u8] = &[1, 7, 0, 0, 0, 0, 0, 0, 0];
+    let geometry = BinaryArray::from_vec(vec![point, collection]);
+    assert!(matches!(
+        wkb(geometry, int),
+        Err(MltError::InvalidArrowGeometry { row: 1, .. })
+    ));
+}
+
+#[test]
+fn record_batch_keeps_values_of_staged_layer() {
+    let batch = RecordBatch::try_from_iter([
+        (
+            GEOMETRY_COLUMN,
+            Arc::new(BinaryArray::from_vec(vec![
+                wkb_point(10.0, 20.0).as_slice(),
+            ])) as ArrayRef,
+        ),
+        ("rank", Arc::new(UInt32Array::from(vec![7])) as ArrayRef),
+    ])
+    .unwrap();
+    let layer = StagedLayer::from_record_batch("layer", 4096, &batch, false).unwrap();
+    assert_eq!(layer.id(), &StagedId::None);
+    assert_eq!(
+        layer.geometry(),
+        &GeometryValues::default().with_geom(&Point::new(10, 20).into())
+    );
+    assert_eq!(layer.properties(), &[StagedProperty::u32("rank", vec![7])]);
+}
@@ -8,7 +8,7 @@ use serde::ser::SerializeMap as _;
 use serde::{Deserialize, Serialize};
 use serde_json::{Number, Value};
 
-use crate::decoder::{Layer, PropValueRef};
+use crate::decoder::{Layer, NestedValue, PropValueRef};
 use crate::{LendingIterator, MltResult, ParsedLayer};
 
 /// `GeoJSON` [`FeatureCollection`]
@@ -105,7 +105,7 @@ impl Serialize for Feature {
 
 /// Serialize/deserialize [`Geometry<i32>`](geo_types::Geometry) in `GeoJSON` wire format:
 /// `{"type":"…","coordinates":…}` with `[x, y]` integer arrays.
-mod geom_serde {
+pub(crate) mod geom_serde {
     use geo_types::{
         Geometry, LineString, MultiLineString, MultiPoint, MultiPolygon, Point, Polygon,
     };
@@ -250,12 +250,84 @@ impl From<PropValueRef<'_>> for Value {
             PropValueRef::F32(v) => f32_to_json(v),
             PropValueRef::F64(v) => f64_to_json(v),
             PropValueRef::Str(s) => Self::String(s.to_string()),
+            PropValueRef::Nested(v) => Self::from(v),
+            PropValueRef::Date(days) => Self::String(format_date(days)),
+            PropValueRef::Timestamp(ms) => Self::String(format_timestamp(ms)),
+            // Malformed documents are kept verbatim rather than dropped
+            PropValueRef::Json(s) => {
+                serde_json::from_str(s).unwrap_or_else(|_| Self::String(s.to_string()))
+            }
+            PropValueRef::Binary(b) => Self::Array(b.iter().map(|&v| Self::from(v)).collect()),
+            PropValueRef::RangeMap(entries) => Self::Array(
+                entries
+                    .iter()
+                    .map(|(start, end, value)| {
+                        serde_json::json!({
+                            "between": [f64_to_json(*start), f64_to_json(*end)],
+                            "value": value.as_value_ref().map_or(Self::Null, Self::from),
+                        })
+                    })
+                    .collect(),
+            ),
+        }
+    }
+}
+
+/// Format days since the Unix epoch as an ISO 8601 date (`YYYY-MM-DD`).
+pub(crate) fn format_date(days: i32) -> String {
+    let (y, m, d) = civil_from_days(i64::from(days));
+    format!("{y:04}-{m:02}-{d:02}")
+}
+
+/// Format milliseconds since the Unix epoch as an ISO 8601 UTC timestamp
+/// (`YYYY-MM-DDTHH:MM:SS.sssZ`).
+pub(crate) fn format_timestamp(ms: i64) -> String {
+    let days = ms.div_euclid(86_400_000);
+    let ms_of_day = ms.rem_euclid(86_400_000);
+    let (y, m, d) = civil_from_days(days);
+    let (hh, mm) = (ms_of_day / 3_600_000, ms_of_day / 60_000 % 60);
+    let (ss, frac) = (ms_of_day / 1000 % 60, ms_of_day % 1000);
+    format!("{y:04}-{m:02}-{d:02}T{hh:02}:{mm:02}:{ss:02}.{frac:03}Z")
+}
+
+/// Proleptic Gregorian `(year, month, day)` of a day count relative to 1970-01-01.
+///
+/// See <https://howardhinnant.github.io/date_algorithms.html#civil_from_days>.
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let d = doy - (153 * mp + 2) / 5 + 1;
+    let m = if mp < 10 { mp + 3 } else { mp - 9 };
+    let y = yoe + era * 400 + i64::from(m <= 2);
+    (y, m, d)
+}
+
+impl From<&NestedValue> for Value {
+    fn from(v: &NestedValue) -> Self {
+        match v {
+            NestedValue::Bool(v) => Self::Bool(*v),
+            NestedValue::I64(v) => Self::from(*v),
+            NestedValue::U64(v) => Self::from(*v),
+            NestedValue::F32(v) => f32_to_json(*v),
+            NestedValue::F64(v) => f64_to_json(*v),
+            NestedValue::Str(s) => Self::String(s.clone()),
+            NestedValue::List(items) => Self::Array(items.iter().map(Self::from).collect()),
+            NestedValue::Map(entries) => Self::Object(
+                entries
+                    .iter()
+                    .map(|(k, v)| (k.clone(), Self::from(v)))
+                    .collect(),
+            ),
         }
     }
 }
 
 /// Replace tiny float values (e.g. `1e-40`) with `0.0` to handle codec precision issues.
-fn normalize_tiny_floats(value: Value) -> Value {
+pub(crate) fn normalize_tiny_floats(value: Value) -> Value {
     match value {
         Value::Number(ref n) => {
             let eps = f64::from(f32::EPSILON);
@@ -281,7 +353,7 @@ fn normalize_tiny_floats(value: Value) -> Value {
 /// Compare two JSON values for equality. Numbers are compared with float tolerance so that
 /// f32 round-trip (e.g. 3.14 vs 3.140000104904175) and Java minimal decimal (e.g. 3.4028235e+38)
 /// match the Rust decoder output.
-fn json_values_equal(a: &Value, b: &Value) -> bool {
+pub(crate) fn json_values_equal(a: &Value, b: &Value) -> bool {
     match (a, b) {
         (Value::Number(na), Value::Number(nb)) if na.is_f64() && nb.is_f64() => {
             let na = na.as_f64().expect("f64");
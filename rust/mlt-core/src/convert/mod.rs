@@ -1,2 +1,4 @@
+#[cfg(feature = "arrow")]
+pub mod arrow;
 pub mod geojson;
 pub mod mvt;
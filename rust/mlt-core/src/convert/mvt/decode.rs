@@ -116,7 +116,9 @@ fn tile_layer_from_ref(layer: MvtLayerRef<'_>) -> MltResult<TileLayer> {
         tile_features.push(TileFeature {
             id: feat.id(),
             geometry: feat.geometry()?,
+            z: None,
             properties,
+            vertex_properties: Vec::new(),
         });
     }
 
@@ -171,7 +173,9 @@ impl TryFrom<MvtLayer> for TileLayer {
             tile_features.push(TileFeature {
                 id: feat.id,
                 geometry: feat.geometry,
+                z: None,
                 properties,
+                vertex_properties: Vec::new(),
             });
         }
 
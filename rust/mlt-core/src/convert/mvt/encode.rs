@@ -3,10 +3,14 @@
 
 use fast_mvt::{MvtTileBuilder, MvtValue};
 
-use crate::decoder::{PropValue, TileLayer};
+use crate::convert::geojson::{format_date, format_timestamp};
+use crate::decoder::{PropValue, PropValueRef, TileFeature, TileLayer};
 use crate::{MltError, MltResult};
 
 /// Encode row-oriented [`TileLayer`]s as MVT (Mapbox Vector Tile) bytes.
+///
+/// MVT geometry is 2D only and has no per-vertex attributes, so Z values of 3D features
+/// and vertex-scoped properties are dropped.
 pub fn tile_layers_to_mvt(layers: Vec<TileLayer>) -> MltResult<Vec<u8>> {
     let mut tile = MvtTileBuilder::with_capacity(layers.len());
     for layer in layers {
@@ -16,9 +20,16 @@ pub fn tile_layers_to_mvt(layers: Vec<TileLayer>) -> MltResult<Vec<u8>> {
         let mut mvt_layer = tile.layer_with_capacity(layer.name, layer.features.len())?;
         mvt_layer.extent(layer.extent.into());
         for feat in layer.features {
-            let mut feature = mvt_layer.feature(&feat.geometry)?;
-            feature.id(feat.id);
-            for (col_idx, prop) in feat.properties.into_iter().enumerate() {
+            let TileFeature {
+                id,
+                geometry,
+                z: _,
+                properties,
+                vertex_properties: _,
+            } = feat;
+            let mut feature = mvt_layer.feature(&geometry)?;
+            feature.id(id);
+            for (col_idx, prop) in properties.into_iter().enumerate() {
                 if let Some(name) = layer.property_names.get(col_idx)
                     && let Ok(value) = MvtValue::try_from(prop)
                 {
@@ -46,7 +57,17 @@ impl TryFrom<PropValue> for MvtValue {
             PropValue::U64(Some(u)) => Self::UInt(u),
             PropValue::F32(Some(f)) => Self::Float(f),
             PropValue::F64(Some(f)) => Self::Double(f),
-            PropValue::Str(Some(s)) => Self::String(s),
+            PropValue::Str(Some(s)) | PropValue::Json(Some(s)) => Self::String(s),
+            // MVT has no map/list values; store them as JSON text like other MVT tools do
+            PropValue::Nested(Some(v)) => Self::String(serde_json::Value::from(&v).to_string()),
+            // MVT has no logical types; dates and timestamps become ISO 8601 text
+            PropValue::Date(Some(d)) => Self::String(format_date(d)),
+            PropValue::Timestamp(Some(t)) => Self::String(format_timestamp(t)),
+            PropValue::Binary(Some(b)) => Self::String(hex::encode(b)),
+            // Range maps are stored as the JSON text of their GeoJSON form
+            PropValue::RangeMap(Some(v)) => {
+                Self::String(serde_json::Value::from(PropValueRef::RangeMap(&v)).to_string())
+            }
             _ => Err(())?,
         })
     }
@@ -55,7 +76,7 @@ impl TryFrom<PropValue> for MvtValue {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::decoder::{Extent, TileFeature};
+    use crate::decoder::Extent;
     use crate::mvt::mvt_to_tile_layers;
 
     #[test]
@@ -72,6 +93,8 @@ mod tests {
             extent: Extent::new(4096).unwrap(),
             property_names: vec![],
             property_kinds: vec![],
+            vertex_property_names: vec![],
+            vertex_property_kinds: vec![],
             features: vec![],
         };
 
@@ -100,7 +123,9 @@ mod tests {
             vec![TileFeature {
                 id: Some(1),
                 geometry: Geometry::Polygon(Polygon::new(LineString(ring), vec![])),
+                z: None,
                 properties: vec![],
+                vertex_properties: Vec::new(),
             }],
         )
         .unwrap();
@@ -115,4 +140,19 @@ mod tests {
         assert_eq!(p.exterior().0.len(), 5);
         assert_eq!(p.exterior().0.first(), p.exterior().0.last());
     }
+
+    #[test]
+    fn z_values_are_dropped() {
+        use geo_types::{Geometry, LineString};
+        let line = Geometry::LineString(LineString::from(vec![(0, 0), (10, 5)]));
+        let mut builder = TileLayer::builder("terrain", 4096).unwrap();
+        let mut feature = builder.feature(line.clone());
+        feature.z(vec![100, 250]).unwrap();
+        feature.finish().unwrap();
+
+        let decoded = mvt_to_tile_layers(tile_layers_to_mvt(vec![builder.finish()]).unwrap());
+        let feature = &decoded.unwrap()[0].features[0];
+        assert_eq!(feature.geometry, line);
+        assert_eq!(feature.z, None);
+    }
 }
@@ -1,7 +1,8 @@
 use crate::decoder::{
-    Geometry, GeometryType, GeometryValues, Id, Layer01, Property, RawFsstData, RawGeometry, RawId,
-    RawIdValue, RawPlainData, RawPresence, RawProperty, RawScalar, RawSharedDict,
-    RawSharedDictEncoding, RawSharedDictItem, RawStrings, RawStringsEncoding, StreamMeta,
+    Geometry, GeometryType, GeometryValues, Id, Layer01, Property, RawFloats, RawFloatsEncoding,
+    RawFsstData, RawGeometry, RawId, RawIdValue, RawNested, RawNestedInts, RawPlainData,
+    RawPresence, RawProperty, RawRangeMap, RawScalar, RawSharedDict, RawSharedDictEncoding,
+    RawSharedDictItem, RawStrings, RawStringsEncoding, RawStruct, RawStructChild, StreamMeta,
 };
 use crate::{Analyze, DecodeState, StatType};
 
@@ -17,6 +18,7 @@ where
             StatType::DecodedDataSize => {
                 self.id.collect_statistic(stat)
                     + self.geometry.collect_statistic(stat)
+                    + self.vertex_properties.collect_statistic(stat)
                     + self.properties.collect_statistic(stat)
             }
             StatType::FeatureCount => self.geometry.collect_statistic(stat),
@@ -26,6 +28,7 @@ where
     fn for_each_stream(&self, cb: &mut dyn FnMut(StreamMeta)) {
         self.id.for_each_stream(cb);
         self.geometry.for_each_stream(cb);
+        self.vertex_properties.for_each_stream(cb);
         self.properties.for_each_stream(cb);
     }
 }
@@ -48,6 +51,7 @@ impl Analyze for GeometryValues {
                     + self.index_buffer.collect_statistic(stat)
                     + self.triangles.collect_statistic(stat)
                     + self.vertices.collect_statistic(stat)
+                    + self.z.collect_statistic(stat)
             }
             StatType::DecodedMetaSize => 0,
             StatType::FeatureCount => self.vector_types.len(),
@@ -128,6 +132,22 @@ impl Analyze for RawScalar<'_> {
     }
 }
 
+impl Analyze for RawFloats<'_> {
+    fn for_each_stream(&self, cb: &mut dyn FnMut(StreamMeta)) {
+        self.presence.for_each_stream(cb);
+        match &self.encoding {
+            RawFloatsEncoding::Plain(data) => data.for_each_stream(cb),
+            RawFloatsEncoding::Dictionary {
+                offsets,
+                dictionary,
+            } => {
+                offsets.for_each_stream(cb);
+                dictionary.for_each_stream(cb);
+            }
+        }
+    }
+}
+
 impl Analyze for RawStrings<'_> {
     fn for_each_stream(&self, cb: &mut dyn FnMut(StreamMeta)) {
         self.presence.for_each_stream(cb);
@@ -151,6 +171,15 @@ impl Analyze for RawSharedDictEncoding<'_> {
     }
 }
 
+impl Analyze for RawRangeMap<'_> {
+    fn for_each_stream(&self, cb: &mut dyn FnMut(StreamMeta)) {
+        self.presence.for_each_stream(cb);
+        self.lengths.for_each_stream(cb);
+        self.ranges.for_each_stream(cb);
+        self.values.for_each_stream(cb);
+    }
+}
+
 impl Analyze for RawSharedDict<'_> {
     fn for_each_stream(&self, cb: &mut dyn FnMut(StreamMeta)) {
         self.encoding.for_each_stream(cb);
@@ -158,6 +187,41 @@ impl Analyze for RawSharedDict<'_> {
     }
 }
 
+impl Analyze for RawStruct<'_> {
+    fn for_each_stream(&self, cb: &mut dyn FnMut(StreamMeta)) {
+        self.dictionary.for_each_stream(cb);
+        for child in &self.children {
+            match child {
+                RawStructChild::Shared(item) => item.for_each_stream(cb),
+                RawStructChild::Column(column) => column.for_each_stream(cb),
+            }
+        }
+    }
+}
+
+impl Analyze for RawNestedInts<'_> {
+    fn for_each_stream(&self, cb: &mut dyn FnMut(StreamMeta)) {
+        match self {
+            Self::Int32(s) | Self::Int64(s) => s.for_each_stream(cb),
+        }
+    }
+}
+
+impl Analyze for RawNested<'_> {
+    fn for_each_stream(&self, cb: &mut dyn FnMut(StreamMeta)) {
+        if let Some(body) = &self.body {
+            body.lengths.for_each_stream(cb);
+            body.strings.for_each_stream(cb);
+            body.ints.for_each_stream(cb);
+            body.uints.for_each_stream(cb);
+            body.floats.for_each_stream(cb);
+            body.doubles.for_each_stream(cb);
+            body.presence.for_each_stream(cb);
+            body.values.for_each_stream(cb);
+        }
+    }
+}
+
 impl Analyze for RawProperty<'_> {
     fn for_each_stream(&self, cb: &mut dyn FnMut(StreamMeta)) {
         match self {
@@ -167,11 +231,13 @@ impl Analyze for RawProperty<'_> {
             | Self::I32(s)
             | Self::U32(s)
             | Self::I64(s)
-            | Self::U64(s)
-            | Self::F32(s)
-            | Self::F64(s) => s.for_each_stream(cb),
+            | Self::U64(s) => s.for_each_stream(cb),
+            Self::F32(s) | Self::F64(s) => s.for_each_stream(cb),
             Self::Str(s) => s.for_each_stream(cb),
             Self::SharedDict(s) => s.for_each_stream(cb),
+            Self::Nested(s) => s.for_each_stream(cb),
+            Self::RangeMap(s) => s.for_each_stream(cb),
+            Self::Struct(s) => s.for_each_stream(cb),
         }
     }
 }
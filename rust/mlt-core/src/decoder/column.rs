@@ -46,18 +46,58 @@ impl ColumnType {
     }
 
     /// Returns true if the column definition includes a name field in the serialized format.
-    /// Note: ID and Geometry columns use implicit naming and do not include a name field.
+    /// Note: ID, Geometry and vertex-scope group columns use implicit naming and do not
+    /// include a name field.
     #[must_use]
     pub(crate) fn has_name(self) -> bool {
         !matches!(
             self,
-            Self::Id | Self::OptId | Self::LongId | Self::OptLongId | Self::Geometry
+            Self::Id
+                | Self::OptId
+                | Self::LongId
+                | Self::OptLongId
+                | Self::Geometry
+                | Self::GeometryZ
+                | Self::VertexScope
         )
     }
 
+    /// Whether values of this column type can be vertex-scoped, see [`Self::VertexScope`].
+    #[must_use]
+    pub(crate) fn is_vertex_scopable(self) -> bool {
+        matches!(
+            self,
+            Self::Bool
+                | Self::I8
+                | Self::U8
+                | Self::I32
+                | Self::U32
+                | Self::I64
+                | Self::U64
+                | Self::F32
+                | Self::F64
+        )
+    }
+
+    /// Whether this column type can describe the values of a [`Self::RangeMap`].
+    #[must_use]
+    pub(crate) fn is_range_map_value(self) -> bool {
+        (Self::Bool as u8..=Self::OptStr as u8).contains(&(self as u8))
+    }
+
+    /// Whether this column type can be a child of a [`Self::Struct`].
+    #[must_use]
+    pub(crate) fn is_struct_child(self) -> bool {
+        (Self::Bool as u8..=Self::OptStr as u8).contains(&(self as u8))
+    }
+
     /// Check if the column type has a presence stream
+    ///
+    /// Nested columns carry their own (optional) presence stream inside the
+    /// column body, and geometry columns never have one, so neither is considered
+    /// optional here despite the odd value.
     #[must_use]
     pub(crate) fn is_optional(self) -> bool {
-        (self as u8) & 1 != 0
+        !matches!(self, Self::Nested | Self::GeometryZ) && (self as u8) & 1 != 0
     }
 }
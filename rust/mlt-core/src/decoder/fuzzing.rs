@@ -19,6 +19,7 @@ use crate::*;
 pub enum LayerOrdering {
     Id,
     Geometry,
+    VertexProperties,
     Property,
 }
 
@@ -29,10 +30,10 @@ impl From<ColumnType> for LayerOrdering {
         match typ {
             OptId | Id | LongId | OptLongId => Self::Id,
             Bool | OptBool | I8 | OptI8 | U8 | OptU8 | I32 | OptI32 | U32 | OptU32 | I64
-            | OptI64 | U64 | OptU64 | F32 | OptF32 | F64 | OptF64 | Str | OptStr | SharedDict => {
-                Self::Property
-            }
-            Geometry => Self::Geometry,
+            | OptI64 | U64 | OptU64 | F32 | OptF32 | F64 | OptF64 | Str | OptStr | SharedDict
+            | Nested | RangeMap | OptRangeMap | Struct => Self::Property,
+            Geometry | GeometryZ => Self::Geometry,
+            VertexScope => Self::VertexProperties,
         }
     }
 }
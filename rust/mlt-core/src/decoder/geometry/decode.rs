@@ -1,10 +1,11 @@
 use usize_cast::IntoUsize as _;
 
 use crate::codecs::varint::parse_varint;
+use crate::codecs::zigzag::decode_componentwise_delta_vec3s;
 use crate::decoder::stream::header01;
 use crate::decoder::{
-    DictionaryType, GeometryType, GeometryValues, IntEncoding, LengthType, OffsetType, RawGeometry,
-    RawStream, StreamMeta, StreamType,
+    DictionaryType, GeometryType, GeometryValues, IntEncoding, LengthType, LogicalEncoding,
+    OffsetType, RawGeometry, RawStream, StreamMeta, StreamType,
 };
 use crate::errors::AsMltError as _;
 use crate::utils::SetOptionOnce as _;
@@ -217,6 +218,30 @@ pub fn decode_level2_length_stream(
     Ok(level2_buffer_offsets)
 }
 
+/// Decode a `GeometryZ` vertex stream into flat `[x0, y0, z0, x1, y1, z1, …]` triples.
+///
+/// Componentwise delta runs over all three components; Morton codes only describe
+/// `(x, y)` and are rejected.
+pub(crate) fn decode_vec3_vertices(
+    mut stream: RawStream<'_>,
+    dec: &mut Decoder,
+) -> MltResult<Vec<i32>> {
+    match stream.meta.encoding.logical {
+        LogicalEncoding::ComponentwiseDelta => {
+            stream.meta.encoding.logical = LogicalEncoding::None;
+            let words = stream.decode_ints::<u32>(dec)?;
+            decode_componentwise_delta_vec3s(&words, dec)
+        }
+        logical @ (LogicalEncoding::Morton(_)
+        | LogicalEncoding::MortonDelta(_)
+        | LogicalEncoding::MortonRle(_)) => Err(MltError::UnsupportedLogicalEncoding(
+            logical,
+            "vec3 vertices",
+        )),
+        _ => stream.decode_ints::<i32>(dec),
+    }
+}
+
 impl<'a> RawGeometry<'a> {
     /// Parse encoded geometry from bytes (expects varint stream count + streams).
     /// Reserves decoded memory against the parser's budget.
@@ -236,6 +261,7 @@ impl<'a> RawGeometry<'a> {
                         &[],
                     ),
                     items: Vec::new(),
+                    has_z: false,
                 },
             ));
         }
@@ -244,7 +270,14 @@ impl<'a> RawGeometry<'a> {
         // Safety: stream_count is validated != 0
         let (input, items) = header01::parse_multiple_streams(input, stream_count - 1, parser)?;
 
-        Ok((input, Self { meta, items }))
+        Ok((
+            input,
+            Self {
+                meta,
+                items,
+                has_z: false,
+            },
+        ))
     }
 }
 
@@ -253,7 +286,7 @@ impl Decode<GeometryValues> for RawGeometry<'_> {
     /// allocation.  All streams carry `num_values` in their metadata so every
     /// charge is pre-hoc.
     fn decode(self, dec: &mut Decoder) -> MltResult<GeometryValues> {
-        let RawGeometry { meta, items } = self;
+        let RawGeometry { meta, items, has_z } = self;
         let vector_types = decode_geometry_types(meta, dec)?;
         let mut geometry_offsets: Option<Vec<u32>> = None;
         let mut part_offsets: Option<Vec<u32>> = None;
@@ -268,7 +301,11 @@ impl Decode<GeometryValues> for RawGeometry<'_> {
                 StreamType::Present => {}
                 StreamType::Data(v) => match v {
                     DictionaryType::Vertex | DictionaryType::Morton => {
-                        vertices.set_once(stream.decode_ints::<i32>(dec)?)?;
+                        vertices.set_once(if has_z {
+                            decode_vec3_vertices(stream, dec)?
+                        } else {
+                            stream.decode_ints::<i32>(dec)?
+                        })?;
                     }
                     _ => Err(MltError::UnexpectedStreamType(stream.meta.stream_type))?,
                 },
@@ -295,16 +332,22 @@ impl Decode<GeometryValues> for RawGeometry<'_> {
 
         if index_buffer.is_some() && part_offsets.is_none() {
             // Case when the indices of a Polygon outline are not encoded in the data so no
-            // topology data are present in the tile
-            //
-            // return FlatGpuVector::new(vector_types, triangles, index_buffer, vertices);
-            return Err(MltError::NotImplemented(
-                "index_buffer.is_some() && part_offsets.is_none() case",
-            ));
-        }
-
-        // Use decode_root_length_stream if geometry_offsets is present
-        if let Some(offsets) = geometry_offsets.take() {
+            // topology data are present in the tile: every feature is a polygon mesh, with
+            // one triangle count per feature and indices into the whole vertex buffer.
+            // Any leftover topology stream cannot be resolved without parts, so drop it.
+            geometry_offsets = None;
+            ring_offsets = None;
+            if let Some(index) = vector_types.iter().position(|t| !t.is_polygon()) {
+                return Err(MltError::NonPolygonMesh(index, vector_types[index]));
+            }
+            let actual = triangles.as_ref().map_or(0, Vec::len);
+            if actual != vector_types.len() {
+                return Err(MltError::MeshTriangleCountMismatch {
+                    expected: vector_types.len(),
+                    actual,
+                });
+            }
+        } else if let Some(offsets) = geometry_offsets.take() {
             geometry_offsets = Some(decode_root_length_stream(
                 &vector_types,
                 &offsets,
@@ -370,33 +413,42 @@ impl Decode<GeometryValues> for RawGeometry<'_> {
         // - `vertices` holds only the unique dictionary entries and
         // - `vertex_offsets` holds per-vertex indices into it.
         //
-        // Expand them into a single flat (x, y) sequence so that `GeometryValues` always
-        // represents fully decoded data, regardless of the encoding that was used.
+        // Expand them into a single flat (x, y) or (x, y, z) sequence so that `GeometryValues`
+        // always represents fully decoded data, regardless of the encoding that was used.
+        let dim = if has_z { 3 } else { 2 };
         if let Some(offsets) = vertex_offsets.take()
             && let Some(dict) = vertices.as_deref()
         {
-            dec.consume_items::<[i32; 2]>(offsets.len())?;
+            dec.consume_items::<i32>(offsets.len().checked_mul(dim).or_overflow()?)?;
             // SAFETY:
             // Check before multiplying: i < dict_vertex_count guarantees
-            // i * 2 + 1 < dict.len() with no risk of overflow, because
+            // i * dim + dim - 1 < dict.len() with no risk of overflow, because
             // Rust limits Vec::len() to isize::MAX, so
-            // dict_vertex_count <= isize::MAX / 2, meaning
-            // i * 2 + 1 <= isize::MAX < usize::MAX.
-            let dict_vertex_count = dict.len() / 2;
+            // dict_vertex_count <= isize::MAX / dim, meaning
+            // i * dim + dim - 1 <= isize::MAX < usize::MAX.
+            let dict_vertex_count = dict.len() / dim;
             vertices = Some(offsets.iter().try_fold(
-                Vec::with_capacity(offsets.len() * 2),
+                Vec::with_capacity(offsets.len() * dim),
                 |mut acc, &idx| -> MltResult<_> {
                     let i = idx.into_usize();
                     if i >= dict_vertex_count {
                         return Err(MltError::DictIndexOutOfBounds(idx, dict_vertex_count));
                     }
-                    acc.push(dict[i * 2]);
-                    acc.push(dict[i * 2 + 1]);
+                    acc.extend_from_slice(&dict[i * dim..(i + 1) * dim]);
                     Ok(acc)
                 },
             )?);
         }
 
+        // Keep `vertices` as (x, y) pairs for 2D consumers and move Z into its own buffer.
+        // The split buffers together hold exactly the already-charged triples.
+        let mut z = None;
+        if has_z && let Some(flat) = vertices.take() {
+            let (xyz, _) = flat.as_chunks::<3>();
+            vertices = Some(xyz.iter().flat_map(|&[x, y, _]| [x, y]).collect());
+            z = Some(xyz.iter().map(|&[_, _, z]| z).collect());
+        }
+
         Ok(GeometryValues {
             vector_types,
             geometry_offsets,
@@ -405,6 +457,7 @@ impl Decode<GeometryValues> for RawGeometry<'_> {
             index_buffer,
             triangles,
             vertices,
+            z,
         })
     }
 }
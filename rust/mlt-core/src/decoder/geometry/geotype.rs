@@ -1,3 +1,4 @@
+use std::cell::{Cell, RefCell};
 use std::ops::Range;
 
 use geo_types::{
@@ -6,12 +7,15 @@ use geo_types::{
 use usize_cast::IntoUsize as _;
 
 use crate::MltError::{
-    GeometryIndexOutOfBounds, GeometryOutOfBounds, GeometryVertexOutOfBounds, NoGeometryOffsets,
-    NoPartOffsets, NoRingOffsets,
+    GeometryIndexOutOfBounds, GeometryOutOfBounds, GeometryVertexOutOfBounds, IntegerOverflow,
+    NoGeometryOffsets, NoPartOffsets, NoRingOffsets, NonPolygonMesh,
 };
 use crate::MltResult;
 use crate::decoder::{GeometryType, GeometryValues};
 
+/// A feature's geometry, its Z values, and the range of vertex buffer entries it uses.
+type FeatureVertices = (Geometry<i32>, Option<Vec<i32>>, Range<usize>);
+
 impl GeometryType {
     #[must_use]
     pub fn is_polygon(self) -> bool {
@@ -84,10 +88,69 @@ impl GeometryValues {
         self.vertices.as_deref()
     }
 
+    /// Per-vertex Z values, parallel to [`Self::vertices`] (one value per `(x, y)` pair).
+    /// `None` for 2D geometry.
+    #[must_use]
+    pub fn z(&self) -> Option<&[i32]> {
+        self.z.as_deref()
+    }
+
+    /// Whether this geometry column is a bare triangle mesh without polygon outlines.
+    ///
+    /// Such columns hold only polygons; each feature is described by its triangle count
+    /// and its slice of [`Self::index_buffer`], whose indices address the whole vertex buffer.
+    #[must_use]
+    pub fn is_mesh_only(&self) -> bool {
+        self.index_buffer.is_some() && self.part_offsets.is_none()
+    }
+
+    /// Whether this geometry column carries per-vertex Z values.
+    #[must_use]
+    pub fn has_z(&self) -> bool {
+        self.z.is_some()
+    }
+
     /// Build a `GeoJSON` geometry for a single feature at index `i`.
     /// Polygon and `MultiPolygon` rings are closed per `GeoJSON` spec
     /// (MLT omits the closing vertex).
+    ///
+    /// Features of a [mesh-only](Self::is_mesh_only) column have no outlines and are
+    /// returned as a `MultiPolygon` with one triangle per polygon.
     pub fn to_geojson(&self, index: usize) -> MltResult<Geometry<i32>> {
+        self.feature_geometry(index, None).map(|(geom, _)| geom)
+    }
+
+    /// Like [`Self::to_geojson`], but also returns the feature's Z values for 3D geometry.
+    ///
+    /// Z values follow the coordinate order of the returned geometry, including the
+    /// repeated closing vertex of polygon rings.
+    pub fn to_geojson_with_z(&self, index: usize) -> MltResult<(Geometry<i32>, Option<Vec<i32>>)> {
+        self.feature_with_vertices(index)
+            .map(|(geom, z, _)| (geom, z))
+    }
+
+    /// Like [`Self::to_geojson_with_z`], but also returns the range of vertex buffer
+    /// entries used by the feature, which vertex-scoped properties are indexed by.
+    pub(crate) fn feature_with_vertices(&self, index: usize) -> MltResult<FeatureVertices> {
+        let Some(z) = self.z.as_deref() else {
+            let (geom, vertices) = self.feature_geometry(index, None)?;
+            return Ok((geom, None, vertices));
+        };
+        let out = RefCell::new(Vec::new());
+        let (geom, vertices) = self.feature_geometry(index, Some((z, &out)))?;
+        Ok((geom, Some(out.into_inner()), vertices))
+    }
+
+    /// Shared body of [`Self::to_geojson`] and [`Self::feature_with_vertices`]: every vertex
+    /// read also appends its Z value to `z_out` when given.
+    ///
+    /// Also returns the smallest vertex buffer range covering every vertex read; features
+    /// always use a contiguous run of vertices.
+    fn feature_geometry(
+        &self,
+        index: usize,
+        z_out: Option<(&[i32], &RefCell<Vec<i32>>)>,
+    ) -> MltResult<(Geometry<i32>, Range<usize>)> {
         let verts = self.vertices.as_deref().unwrap_or(&[]);
         let geoms = self.geometry_offsets.as_deref();
         let parts = self.part_offsets.as_deref();
@@ -114,15 +177,29 @@ impl GeometryValues {
         let part_range = |s: &[u32], i: usize| off_pair(s, i, "part_offsets");
         let ring_range = |s: &[u32], i: usize| off_pair(s, i, "ring_offsets");
 
+        let span: Cell<Option<(usize, usize)>> = Cell::new(None);
         let vert = |idx: usize| -> MltResult<Coord<i32>> {
-            verts
+            let coord = verts
                 .get(idx * 2..idx * 2 + 2)
                 .map(|s| Coord { x: s[0], y: s[1] })
                 .ok_or(GeometryVertexOutOfBounds {
                     index,
                     vertex: idx,
                     count: verts.len() / 2,
-                })
+                })?;
+            if let Some((z, out)) = z_out {
+                let value = z.get(idx).ok_or(GeometryVertexOutOfBounds {
+                    index,
+                    vertex: idx,
+                    count: z.len(),
+                })?;
+                out.borrow_mut().push(*value);
+            }
+            span.set(Some(match span.get() {
+                Some((lo, hi)) => (lo.min(idx), hi.max(idx)),
+                None => (idx, idx),
+            }));
+            Ok(coord)
         };
         let line = |r: Range<usize>| -> MltResult<LineString<i32>> { r.map(&vert).collect() };
         let closed_ring = |r: Range<usize>| -> MltResult<LineString<i32>> {
@@ -147,7 +224,35 @@ impl GeometryValues {
             .get(index)
             .ok_or(GeometryIndexOutOfBounds(index))?;
 
-        match geom_type {
+        if self.is_mesh_only() {
+            // Without outlines, a feature is the union of its triangles.
+            if !geom_type.is_polygon() {
+                return Err(NonPolygonMesh(index, geom_type));
+            }
+            let tris = self.triangles.as_deref().unwrap_or(&[]);
+            let first = tris
+                .get(..index)
+                .ok_or(GeometryIndexOutOfBounds(index))?
+                .iter()
+                .try_fold(0_usize, |acc, &n| acc.checked_add(n.into_usize()))
+                .ok_or(IntegerOverflow)?;
+            let count = off(tris, index, "triangles")?;
+            let indices = self.index_buffer.as_deref().unwrap_or(&[]);
+            let corner = |i: usize| vert(off(indices, i, "index_buffer")?);
+            let polys = (first..first + count)
+                .map(|tri| -> MltResult<Polygon<i32>> {
+                    let ring = [0, 1, 2, 0]
+                        .into_iter()
+                        .map(|k| corner(tri * 3 + k))
+                        .collect::<MltResult<_>>()?;
+                    Ok(Polygon::new(LineString(ring), vec![]))
+                })
+                .collect::<MltResult<_>>()?;
+            let vertices = span.get().map_or(0..0, |(lo, hi)| lo..hi + 1);
+            return Ok((Geometry::MultiPolygon(MultiPolygon(polys)), vertices));
+        }
+
+        let geom = match geom_type {
             GeometryType::Point => {
                 // Resolve through hierarchy: geoms? -> parts? -> rings? -> vertex
                 let idx = geoms.map_or(Ok(index), |g| geom_off(g, index))?;
@@ -221,6 +326,8 @@ impl GeometryValues {
                     .collect::<Result<_, _>>()?;
                 Ok(Geometry::<i32>::MultiPolygon(MultiPolygon(polys)))
             }
-        }
+        }?;
+        let vertices = span.get().map_or(0..0, |(lo, hi)| lo..hi + 1);
+        Ok((geom, vertices))
     }
 }
@@ -17,6 +17,8 @@ pub type Geometry<'a, S = Lazy> = <S as DecodeState>::LazyOrParsed<RawGeometry<'
 pub struct RawGeometry<'a> {
     pub(crate) meta: RawStream<'a>,
     pub(crate) items: Vec<RawStream<'a>>,
+    /// Stored as a `GeometryZ` column, so vertices are `(x, y, z)` triples.
+    pub(crate) has_z: bool,
 }
 
 /// Parsed (decoded) geometry data
@@ -36,6 +38,9 @@ pub struct GeometryValues {
     pub(crate) triangles: Option<Vec<u32>>,
     #[dbg(formatter = "opt_vec_seq")]
     pub(crate) vertices: Option<Vec<i32>>,
+    /// One Z value per vertex, parallel to `vertices`; `None` for 2D geometry.
+    #[dbg(formatter = "opt_vec_seq")]
+    pub(crate) z: Option<Vec<i32>>,
 }
 
 /// Types of geometries supported in MLT
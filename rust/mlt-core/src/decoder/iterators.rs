@@ -23,8 +23,15 @@ use std::ops::Range;
 use geo_types::Geometry;
 use usize_cast::IntoUsize as _;
 
-use crate::decoder::{Layer01, ParsedLayer01, ParsedProperty, ParsedScalar, Property, RawProperty};
-use crate::{Lazy, LazyParsed, MltResult, Parsed};
+use crate::decoder::{
+    GeometryType, GeometryValues, Layer01, NestedValue, ParsedLayer01, ParsedProperty,
+    ParsedScalar, ParsedSharedDict, ParsedSharedDictItem, ParsedStrings, ParsedStructChild,
+    PropValue, Property, RawProperty, RawStructChild, VertexValues,
+};
+use crate::errors::AsMltError as _;
+use crate::filter::Selection;
+use crate::utils::Presence;
+use crate::{Lazy, LazyParsed, MltError, MltResult, Parsed};
 
 /// A minimal lending (streaming) iterator trait.
 ///
@@ -84,7 +91,16 @@ impl<'a> ParsedLayer01<'a> {
     /// standard [`std::iter::Iterator`] trait and compose normally.
     #[must_use]
     pub fn iter_features(&self) -> Layer01FeatureIter<'_, 'a> {
-        Layer01FeatureIter::new(self)
+        Layer01FeatureIter::new(self, None)
+    }
+
+    /// Iterate over the features in `selection` only, e.g. the result of
+    /// [`Filter::evaluate`](crate::filter::Filter::evaluate).
+    ///
+    /// Features that are not selected are skipped without decoding their geometry.
+    #[must_use]
+    pub fn iter_selected<'s>(&'s self, selection: &'s Selection) -> Layer01FeatureIter<'s, 'a> {
+        Layer01FeatureIter::new(self, Some(selection))
     }
 
     /// Iterate over the property column names of this layer, in order.
@@ -92,13 +108,20 @@ impl<'a> ParsedLayer01<'a> {
     pub fn iterate_prop_names(&self) -> PropNamesIter<'_, ParsedProperty<'a>> {
         PropNamesIter::new(&self.properties)
     }
+
+    /// Tessellation triangles of all features, or `None` if the layer was not tessellated.
+    /// See [`LayerTriangles::from_geometry`] for details.
+    pub fn triangles(&self) -> MltResult<Option<LayerTriangles>> {
+        LayerTriangles::from_geometry(&self.geometry)
+    }
 }
 
 /// A zero-allocation two-part property name yielded by [`FeatureRef::iter_properties`].
 ///
 /// The two parts concatenate on [`Display`](fmt::Display) as `"{}{}"`:
 /// - For regular columns: `(column_name, "")` - zero allocation, second part always empty.
-/// - For `SharedDict` sub-items: `(prefix, suffix)` - both borrow directly from layer data.
+/// - For `SharedDict` and `Struct` sub-items: `(prefix, suffix)` - both borrow directly from
+///   layer data.
 ///
 /// Structural [`PartialEq`] compares both parts independently.  Use [`PartialEq<str>`] or
 /// [`PartialEq<&str>`] (also implemented) to compare against a plain `&str` as if the two
@@ -166,6 +189,15 @@ pub enum PropValueRef<'a> {
     F32(f32),
     F64(f64),
     Str(&'a str),
+    Nested(&'a NestedValue),
+    /// Days since the Unix epoch.
+    Date(i32),
+    /// Milliseconds since the Unix epoch.
+    Timestamp(i64),
+    Json(&'a str),
+    Binary(&'a [u8]),
+    /// `(start, end, value)` entries of a range map, see [`PropValue::RangeMap`].
+    RangeMap(&'a [(f64, f64, PropValue)]),
 }
 
 macro_rules! impl_from_for_prop_value_ref {
@@ -182,6 +214,119 @@ impl_from_for_prop_value_ref!(
     f32 => F32, f64 => F64,
 );
 
+/// Borrowed values of one vertex-scoped property, one per vertex.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum VertexValuesRef<'a> {
+    Bool(&'a [bool]),
+    I8(&'a [i8]),
+    U8(&'a [u8]),
+    I32(&'a [i32]),
+    U32(&'a [u32]),
+    I64(&'a [i64]),
+    U64(&'a [u64]),
+    F32(&'a [f32]),
+    F64(&'a [f64]),
+}
+
+impl VertexValuesRef<'_> {
+    #[must_use]
+    pub fn len(&self) -> usize {
+        match self {
+            Self::Bool(v) => v.len(),
+            Self::I8(v) => v.len(),
+            Self::U8(v) => v.len(),
+            Self::I32(v) => v.len(),
+            Self::U32(v) => v.len(),
+            Self::I64(v) => v.len(),
+            Self::U64(v) => v.len(),
+            Self::F32(v) => v.len(),
+            Self::F64(v) => v.len(),
+        }
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Sub-slice of the values, or `None` if `range` is out of bounds.
+    #[must_use]
+    pub fn get(self, range: Range<usize>) -> Option<Self> {
+        Some(match self {
+            Self::Bool(v) => Self::Bool(v.get(range)?),
+            Self::I8(v) => Self::I8(v.get(range)?),
+            Self::U8(v) => Self::U8(v.get(range)?),
+            Self::I32(v) => Self::I32(v.get(range)?),
+            Self::U32(v) => Self::U32(v.get(range)?),
+            Self::I64(v) => Self::I64(v.get(range)?),
+            Self::U64(v) => Self::U64(v.get(range)?),
+            Self::F32(v) => Self::F32(v.get(range)?),
+            Self::F64(v) => Self::F64(v.get(range)?),
+        })
+    }
+}
+
+impl From<VertexValuesRef<'_>> for VertexValues {
+    fn from(values: VertexValuesRef<'_>) -> Self {
+        use VertexValuesRef as R;
+        match values {
+            R::Bool(v) => Self::Bool(v.to_vec()),
+            R::I8(v) => Self::I8(v.to_vec()),
+            R::U8(v) => Self::U8(v.to_vec()),
+            R::I32(v) => Self::I32(v.to_vec()),
+            R::U32(v) => Self::U32(v.to_vec()),
+            R::I64(v) => Self::I64(v.to_vec()),
+            R::U64(v) => Self::U64(v.to_vec()),
+            R::F32(v) => Self::F32(v.to_vec()),
+            R::F64(v) => Self::F64(v.to_vec()),
+        }
+    }
+}
+
+impl ParsedProperty<'_> {
+    /// Values of a vertex-scoped column: any non-optional bool, integer or float column.
+    pub(crate) fn vertex_values(&self) -> Option<VertexValuesRef<'_>> {
+        fn dense<'a, T: Copy + PartialEq>(s: &'a ParsedScalar<'_, T>) -> Option<&'a [T]> {
+            match &s.presence {
+                Presence::AllPresent(values) => Some(values.as_slice()),
+                Presence::Bits { .. } => None,
+            }
+        }
+        Some(match self {
+            Self::Bool(s) => VertexValuesRef::Bool(dense(s)?),
+            Self::I8(s) => VertexValuesRef::I8(dense(s)?),
+            Self::U8(s) => VertexValuesRef::U8(dense(s)?),
+            Self::I32(s) => VertexValuesRef::I32(dense(s)?),
+            Self::U32(s) => VertexValuesRef::U32(dense(s)?),
+            Self::I64(s) => VertexValuesRef::I64(dense(s)?),
+            Self::U64(s) => VertexValuesRef::U64(dense(s)?),
+            Self::F32(s) => VertexValuesRef::F32(dense(s)?),
+            Self::F64(s) => VertexValuesRef::F64(dense(s)?),
+            _ => return None,
+        })
+    }
+}
+
+/// One vertex-scoped property of a feature, yielded by [`FeatureRef::iter_vertex_properties`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VertexColumnRef<'a> {
+    name: &'a str,
+    values: VertexValuesRef<'a>,
+}
+
+impl<'a> VertexColumnRef<'a> {
+    #[must_use]
+    pub fn name(&self) -> &'a str {
+        self.name
+    }
+
+    /// One value per vertex of the feature, in vertex buffer order.
+    #[must_use]
+    pub fn values(&self) -> VertexValuesRef<'a> {
+        self.values
+    }
+}
+
 /// A single non-null property value for one feature, yielded by [`FeatureRef::iter_properties`].
 ///
 /// `name` is a [`PropName`] that displays as `"{prefix}{suffix}"`.
@@ -214,6 +359,16 @@ pub struct FeatureRef<'feat, 'layer: 'feat> {
     id: Option<u64>,
     /// Geometry in [`Geometry<i32>`] form (owned, decoded on demand by the iterator).
     geometry: Geometry<i32>,
+    /// Whether `geometry` is a triangle mesh from a layer stored without outlines.
+    mesh: bool,
+    /// Tessellation of a polygon feature; `None` for other features or untessellated layers.
+    triangles: Option<TrianglesRef<'layer>>,
+    /// Per-vertex Z values in `geometry` coordinate order; `None` for 2D layers.
+    z: Option<Vec<i32>>,
+    /// Range of vertex buffer entries used by this feature.
+    vertices: Range<usize>,
+    /// Vertex-scoped columns of the layer, sliced by `vertices` on access.
+    vertex_columns: &'layer [ParsedProperty<'layer>],
     /// Borrowed slice of column descriptors from the layer; used to yield column names.
     columns: &'layer [ParsedProperty<'layer>],
     /// Per-feature values in column order, one per slot (scalar, string, or `SharedDict`
@@ -232,6 +387,57 @@ impl<'feat, 'layer: 'feat> FeatureRef<'feat, 'layer> {
         &self.geometry
     }
 
+    /// Whether [`Self::geometry`] is a triangle mesh rather than the original outline.
+    ///
+    /// Layers encoded without polygon outlines only keep their tessellation, so every
+    /// feature is a `MultiPolygon` holding one polygon per triangle.
+    #[must_use]
+    pub fn is_mesh(&self) -> bool {
+        self.mesh
+    }
+
+    /// Pre-computed tessellation of this feature, if the layer was encoded with triangles.
+    ///
+    /// `None` for point and line features, and for every feature of an untessellated layer.
+    /// Renderers can upload these instead of running Earcut on [`Self::geometry`].
+    #[must_use]
+    pub fn triangles(&self) -> Option<TrianglesRef<'layer>> {
+        self.triangles
+    }
+
+    /// Per-vertex Z values of a 3D geometry, one per coordinate of [`Self::geometry`]
+    /// in iteration order (closing ring vertices included). `None` for 2D layers.
+    #[must_use]
+    pub fn z(&self) -> Option<&[i32]> {
+        self.z.as_deref()
+    }
+
+    /// Iterate over the vertex-scoped properties of this feature.
+    ///
+    /// Each yields one value per vertex of [`Self::geometry`], in vertex buffer order:
+    /// points and line vertices in coordinate order, polygon rings without their
+    /// closing vertex.
+    #[must_use]
+    pub fn iter_vertex_properties(
+        &self,
+    ) -> impl ExactSizeIterator<Item = VertexColumnRef<'layer>> + '_ {
+        self.vertex_columns.iter().map(|col| VertexColumnRef {
+            name: col.name_at(0).0,
+            values: col
+                .vertex_values()
+                .and_then(|v| v.get(self.vertices.clone()))
+                .expect("vertex-scoped columns are validated against the vertex count"),
+        })
+    }
+
+    /// Look up a vertex-scoped property by name.
+    #[must_use]
+    pub fn get_vertex_property(&self, name: &str) -> Option<VertexValuesRef<'layer>> {
+        self.iter_vertex_properties()
+            .find(|col| col.name() == name)
+            .map(|col| col.values())
+    }
+
     /// Iterate over every property slot for this feature, **values only**, in column order.
     ///
     /// Yields `Option<PropValueRef>`:
@@ -274,12 +480,192 @@ impl<'feat, 'layer: 'feat> FeatureRef<'feat, 'layer> {
     }
 }
 
+// ── Triangles ─────────────────────────────────────────────────────────────────
+
+/// Tessellation triangles of one polygon feature, returned by [`FeatureRef::triangles`].
+///
+/// Triangles are stored as three indices each into the layer's vertex buffer
+/// ([`GeometryValues::vertices`]), offset by [`Self::base_vertex`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TrianglesRef<'a> {
+    indices: &'a [u32],
+    base: usize,
+}
+
+impl<'a> TrianglesRef<'a> {
+    /// Number of triangles.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.indices.len() / 3
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.indices.is_empty()
+    }
+
+    /// Triangle corners as stored in the tile, three per triangle, relative to
+    /// [`Self::base_vertex`].
+    #[must_use]
+    pub fn raw_indices(&self) -> &'a [u32] {
+        self.indices
+    }
+
+    /// The vertex that [`Self::raw_indices`] count from: the feature's first vertex,
+    /// or `0` in mesh-only layers whose indices address the whole vertex buffer.
+    #[must_use]
+    pub fn base_vertex(&self) -> usize {
+        self.base
+    }
+
+    /// Iterate over the triangles as absolute vertex buffer indices, so vertex `n`
+    /// lives at `vertices[n * 2]`, `vertices[n * 2 + 1]`.
+    #[must_use]
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = [usize; 3]> + 'a {
+        let base = self.base;
+        self.indices
+            .chunks_exact(3)
+            .map(move |t| [0, 1, 2].map(|i| base + t[i].into_usize()))
+    }
+}
+
+/// Tessellation triangles of a whole layer, returned by [`ParsedLayer01::triangles`].
+///
+/// Laid out for direct upload into GPU buffers: [`Self::indices`] holds three absolute
+/// vertex buffer indices per triangle, and [`Self::offsets`] the cumulative triangle count
+/// per feature, so feature `i` owns triangles `offsets[i]..offsets[i + 1]`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LayerTriangles {
+    offsets: Vec<u32>,
+    indices: Vec<u32>,
+}
+
+impl LayerTriangles {
+    /// Collect the triangles of every feature of `geometry`, or `None` if it was not
+    /// tessellated.
+    ///
+    /// Indices stored relative to each feature are rebased onto the whole vertex buffer.
+    /// Features other than polygons own no triangles.
+    pub fn from_geometry(geometry: &GeometryValues) -> MltResult<Option<Self>> {
+        let Some(mut cursor) = TriangleCursor::new(geometry) else {
+            return Ok(None);
+        };
+        let types = geometry.vector_types();
+        let mut offsets = Vec::with_capacity(types.len() + 1);
+        let mut indices = Vec::with_capacity(cursor.indices.len());
+        offsets.push(0);
+        for (index, &geom_type) in types.iter().enumerate() {
+            if let Some(feature) = cursor.next(index, geom_type)? {
+                let base = if geometry.is_mesh_only() {
+                    0
+                } else {
+                    u32::try_from(geometry.feature_with_vertices(index)?.2.start)?
+                };
+                for &idx in feature {
+                    indices.push(base.checked_add(idx).or_overflow()?);
+                }
+            }
+            offsets.push(u32::try_from(indices.len() / 3)?);
+        }
+        Ok(Some(Self { offsets, indices }))
+    }
+
+    /// Cumulative triangle counts, one more than the number of features.
+    #[must_use]
+    pub fn offsets(&self) -> &[u32] {
+        &self.offsets
+    }
+
+    /// Absolute vertex buffer indices, three per triangle.
+    #[must_use]
+    pub fn indices(&self) -> &[u32] {
+        &self.indices
+    }
+
+    /// Total number of triangles.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.indices.len() / 3
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.indices.is_empty()
+    }
+
+    /// Absolute vertex buffer indices of the triangles of feature `index`,
+    /// or `None` if `index` is out of range.
+    #[must_use]
+    pub fn feature(&self, index: usize) -> Option<&[u32]> {
+        let start = self.offsets.get(index)?.into_usize() * 3;
+        let end = self.offsets.get(index + 1)?.into_usize() * 3;
+        self.indices.get(start..end)
+    }
+}
+
+/// Walks the triangle counts and index buffer of a tessellated layer in feature order.
+///
+/// Only polygon features have a triangle count.
+struct TriangleCursor<'a> {
+    counts: &'a [u32],
+    indices: &'a [u32],
+    /// Next entry of `counts`.
+    count_pos: usize,
+    /// Start of the next feature's triangles in `indices`.
+    index_pos: usize,
+}
+
+impl<'a> TriangleCursor<'a> {
+    fn new(geometry: &'a GeometryValues) -> Option<Self> {
+        Some(Self {
+            counts: geometry.triangles()?,
+            indices: geometry.index_buffer()?,
+            count_pos: 0,
+            index_pos: 0,
+        })
+    }
+
+    /// The index buffer slice of feature `index`, or `None` if it is not a polygon.
+    fn next(&mut self, index: usize, geom_type: GeometryType) -> MltResult<Option<&'a [u32]>> {
+        if !geom_type.is_polygon() {
+            return Ok(None);
+        }
+        let count = *self
+            .counts
+            .get(self.count_pos)
+            .ok_or(MltError::GeometryOutOfBounds {
+                index,
+                field: "triangles",
+                idx: self.count_pos,
+                len: self.counts.len(),
+            })?;
+        self.count_pos += 1;
+        let start = self.index_pos;
+        let end = count
+            .into_usize()
+            .checked_mul(3)
+            .and_then(|n| start.checked_add(n))
+            .or_overflow()?;
+        let feature = self
+            .indices
+            .get(start..end)
+            .ok_or(MltError::GeometryOutOfBounds {
+                index,
+                field: "index_buffer",
+                idx: end,
+                len: self.indices.len(),
+            })?;
+        self.index_pos = end;
+        Ok(Some(feature))
+    }
+}
+
 // ── Column name helpers ───────────────────────────────────────────────────────
 
 /// A property column that contributes one or more [`PropName`]s.
 ///
-/// Scalar and string columns contribute exactly one name; `SharedDict` columns
-/// contribute one per sub-item.
+/// Scalar and string columns contribute exactly one name; `SharedDict` and `Struct`
+/// columns contribute one per sub-item.
 pub trait ColNames {
     /// Always `PropName<'tile>`.  It cannot be written as `PropName<'_>` directly:
     /// that would tie names to the `&self` borrow rather than to the tile buffer,
@@ -299,6 +685,8 @@ impl<'p> ColNames for ParsedProperty<'p> {
     fn name_count(&self) -> usize {
         match self {
             Self::SharedDict(sd) => sd.items.len(),
+            Self::Nested(n) => n.items.len(),
+            Self::Struct(s) => s.children.len(),
             _ => 1,
         }
     }
@@ -316,7 +704,22 @@ impl<'p> ColNames for ParsedProperty<'p> {
             P::F32(s) => PropName(s.name, ""),
             P::F64(s) => PropName(s.name, ""),
             P::Str(s) => PropName(s.name, ""),
+            P::RangeMap(s) => PropName(s.name, ""),
             P::SharedDict(sd) => PropName(sd.prefix, sd.items[idx].suffix),
+            P::Nested(n) => PropName(n.prefix, n.items[idx].suffix),
+            P::Struct(s) => PropName(
+                s.prefix,
+                match &s.children[idx] {
+                    ParsedStructChild::Shared(item) => {
+                        s.strings
+                            .as_ref()
+                            .expect("shared struct children imply a dictionary")
+                            .items[*item]
+                            .suffix
+                    }
+                    ParsedStructChild::Column(child) => child.name_at(0).0,
+                },
+            ),
         }
     }
 }
@@ -327,6 +730,9 @@ impl<'p> ColNames for RawProperty<'p> {
     fn name_count(&self) -> usize {
         match self {
             Self::SharedDict(sd) => sd.children.len(),
+            // A nested column without children holds its values directly
+            Self::Nested(n) => n.children.len().max(1),
+            Self::Struct(s) => s.children.len(),
             _ => 1,
         }
     }
@@ -334,17 +740,21 @@ impl<'p> ColNames for RawProperty<'p> {
     fn name_at(&self, idx: usize) -> PropName<'p> {
         use RawProperty as P;
         match self {
-            P::Bool(s)
-            | P::I8(s)
-            | P::U8(s)
-            | P::I32(s)
-            | P::U32(s)
-            | P::I64(s)
-            | P::U64(s)
-            | P::F32(s)
-            | P::F64(s) => PropName(s.name, ""),
+            P::Bool(s) | P::I8(s) | P::U8(s) | P::I32(s) | P::U32(s) | P::I64(s) | P::U64(s) => {
+                PropName(s.name, "")
+            }
+            P::F32(s) | P::F64(s) => PropName(s.name, ""),
             P::Str(s) => PropName(s.name, ""),
+            P::RangeMap(s) => PropName(s.name, ""),
             P::SharedDict(sd) => PropName(sd.name, sd.children[idx].name),
+            P::Nested(n) => PropName(n.name, n.children.get(idx).copied().unwrap_or("")),
+            P::Struct(s) => PropName(
+                s.name,
+                match &s.children[idx] {
+                    RawStructChild::Shared(item) => item.name,
+                    RawStructChild::Column(child) => child.name_at(0).0,
+                },
+            ),
         }
     }
 }
@@ -453,13 +863,13 @@ impl<C: ColNames> ExactSizeIterator for PropNamesIter<'_, C> {
 impl<C: ColNames> FusedIterator for PropNamesIter<'_, C> {}
 
 /// A boxed per-column-slot value iterator yielding one `Option<`[`PropValueRef`]`>` per feature.
-type ColValIter<'l> = Box<dyn Iterator<Item = Option<PropValueRef<'l>>> + 'l>;
+pub(crate) type ColValIter<'l> = Box<dyn Iterator<Item = Option<PropValueRef<'l>>> + 'l>;
 
 /// Build one [`ColValIter`] per property column "slot" from a decoded column slice.
 ///
 /// - Scalar and string columns contribute one slot each.
-/// - `SharedDict` columns contribute one slot per sub-item.
-fn build_col_iters<'p>(columns: &'p [ParsedProperty<'p>]) -> Vec<ColValIter<'p>> {
+/// - `SharedDict`, `Nested` and `Struct` columns contribute one slot per sub-item.
+pub(crate) fn build_col_iters<'p>(columns: &'p [ParsedProperty<'p>]) -> Vec<ColValIter<'p>> {
     use ParsedProperty as PP;
     let mut iters: Vec<ColValIter<'p>> = Vec::new();
     for col in columns {
@@ -473,37 +883,45 @@ fn build_col_iters<'p>(columns: &'p [ParsedProperty<'p>]) -> Vec<ColValIter<'p>>
             PP::U64(s) => iters.push(scalar_col_iter(s)),
             PP::F32(s) => iters.push(scalar_col_iter(s)),
             PP::F64(s) => iters.push(scalar_col_iter(s)),
-            PP::Str(strings) => {
-                let data: &'p str = strings.data.as_ref();
-                let lengths: &'p [i32] = &strings.lengths;
-                let mut curr_end: usize = 0;
-                let mut feat_idx = 0usize;
-                iters.push(Box::new(std::iter::from_fn(move || {
-                    let &end_i32 = lengths.get(feat_idx)?;
-                    feat_idx += 1;
-                    if end_i32 >= 0 {
-                        let start = curr_end;
-                        curr_end = end_i32.cast_unsigned().into_usize();
-                        Some(data.get(start..curr_end).map(PropValueRef::Str))
-                    } else {
-                        // Null slot: curr_end unchanged (null encodes the current byte offset).
-                        Some(None)
-                    }
-                })));
+            PP::Str(strings) => iters.push(strings_col_iter(strings)),
+            PP::RangeMap(range_map) => {
+                let entries: &'p [_] = &range_map.entries;
+                iters.push(Box::new(end_offsets_iter(&range_map.lengths).map(
+                    move |span| {
+                        span.and_then(|(start, end)| {
+                            entries.get(start..end).map(PropValueRef::RangeMap)
+                        })
+                    },
+                )));
             }
             PP::SharedDict(dict) => {
                 for item in &dict.items {
-                    let dict_ref: &'p _ = dict;
-                    let item_ref: &'p _ = item;
-                    let mut feat_idx = 0usize;
-                    iters.push(Box::new(std::iter::from_fn(move || {
-                        if feat_idx >= item_ref.ranges.len() {
-                            return None;
+                    iters.push(shared_dict_item_iter(dict, item));
+                }
+            }
+            PP::Nested(nested) => {
+                for item in &nested.items {
+                    iters.push(Box::new(
+                        item.values
+                            .iter()
+                            .map(|v| v.as_ref().map(PropValueRef::Nested)),
+                    ));
+                }
+            }
+            PP::Struct(st) => {
+                for child in &st.children {
+                    match child {
+                        ParsedStructChild::Shared(item) => {
+                            let dict = st
+                                .strings
+                                .as_ref()
+                                .expect("shared struct children imply a dictionary");
+                            iters.push(shared_dict_item_iter(dict, &dict.items[*item]));
+                        }
+                        ParsedStructChild::Column(col) => {
+                            iters.extend(build_col_iters(std::slice::from_ref(col)));
                         }
-                        let idx = feat_idx;
-                        feat_idx += 1;
-                        Some(item_ref.get(dict_ref, idx).map(PropValueRef::Str))
-                    })));
+                    }
                 }
             }
         }
@@ -511,6 +929,48 @@ fn build_col_iters<'p>(columns: &'p [ParsedProperty<'p>]) -> Vec<ColValIter<'p>>
     iters
 }
 
+/// Build a boxed value iterator for one sub-item of a shared dictionary.
+fn shared_dict_item_iter<'p>(
+    dict: &'p ParsedSharedDict<'p>,
+    item: &'p ParsedSharedDictItem<'p>,
+) -> ColValIter<'p> {
+    let mut feat_idx = 0usize;
+    Box::new(std::iter::from_fn(move || {
+        if feat_idx >= item.ranges.len() {
+            return None;
+        }
+        let idx = feat_idx;
+        feat_idx += 1;
+        Some(item.get(dict, idx).map(PropValueRef::Str))
+    }))
+}
+
+/// Build a boxed value iterator for a single string property column.
+fn strings_col_iter<'p>(strings: &'p ParsedStrings<'p>) -> ColValIter<'p> {
+    let data: &'p str = strings.data.as_ref();
+    Box::new(
+        end_offsets_iter(&strings.lengths).map(move |span| {
+            span.and_then(|(start, end)| data.get(start..end).map(PropValueRef::Str))
+        }),
+    )
+}
+
+/// Turn cumulative end offsets (see [`ParsedStrings`]) into per-feature byte spans,
+/// yielding `None` for null slots.
+fn end_offsets_iter(lengths: &[i32]) -> impl Iterator<Item = Option<(usize, usize)>> + '_ {
+    let mut curr_end: usize = 0;
+    lengths.iter().map(move |&end_i32| {
+        if end_i32 >= 0 {
+            let start = curr_end;
+            curr_end = end_i32.cast_unsigned().into_usize();
+            Some((start, curr_end))
+        } else {
+            // Null slot: curr_end unchanged (null encodes the current byte offset).
+            None
+        }
+    })
+}
+
 /// Build a boxed value iterator for a single scalar property column.
 fn scalar_col_iter<'p, T>(scalar: &'p ParsedScalar<'p, T>) -> ColValIter<'p>
 where
@@ -539,10 +999,14 @@ pub struct Layer01FeatureIter<'layer, 'data: 'layer> {
     col_iters: Vec<ColValIter<'layer>>,
     /// Reused buffer: filled on each `next()` call, borrowed by the yielded [`FeatureRef`].
     values_buf: Vec<Option<PropValueRef<'layer>>>,
+    /// Triangle cursor, `None` when the layer was not tessellated.
+    triangles: Option<TriangleCursor<'layer>>,
+    /// Features to yield, `None` to yield all of them.
+    selection: Option<&'layer Selection>,
 }
 
 impl<'layer, 'data: 'layer> Layer01FeatureIter<'layer, 'data> {
-    fn new(layer: &'layer Layer01<'data, Parsed>) -> Self {
+    fn new(layer: &'layer Layer01<'data, Parsed>, selection: Option<&'layer Selection>) -> Self {
         let col_iters = build_col_iters(&layer.properties);
         let cap = col_iters.len();
         Self {
@@ -552,19 +1016,46 @@ impl<'layer, 'data: 'layer> Layer01FeatureIter<'layer, 'data> {
             id_iter: layer.id.as_ref().map(|id| id.iter_optional()),
             col_iters,
             values_buf: Vec::with_capacity(cap),
+            triangles: TriangleCursor::new(&layer.geometry),
+            selection,
         }
     }
 
     /// Number of features not yet yielded.
     #[must_use]
     pub fn len(&self) -> usize {
-        self.feature_count - self.index
+        match self.selection {
+            Some(selection) => selection.count_in(self.index..self.feature_count),
+            None => self.feature_count - self.index,
+        }
     }
 
     /// Returns `true` if all features have been yielded.
     #[must_use]
     pub fn is_empty(&self) -> bool {
-        self.index >= self.feature_count
+        self.len() == 0
+    }
+
+    fn is_selected(&self, index: usize) -> bool {
+        self.selection
+            .is_none_or(|selection| selection.contains(index))
+    }
+
+    /// Advance all per-feature cursors past feature `index` without decoding it.
+    fn skip(&mut self, index: usize) -> MltResult<()> {
+        if let Some(ids) = &mut self.id_iter {
+            ids.next();
+        }
+        for values in &mut self.col_iters {
+            values.next();
+        }
+        if let (Some(cursor), Some(&geom_type)) = (
+            &mut self.triangles,
+            self.layer.geometry.vector_types().get(index),
+        ) {
+            cursor.next(index, geom_type)?;
+        }
+        Ok(())
     }
 }
 
@@ -575,6 +1066,13 @@ impl<'layer> LendingIterator for Layer01FeatureIter<'layer, '_> {
         Self: 'this;
 
     fn next(&mut self) -> Option<Self::Item<'_>> {
+        while self.index < self.feature_count && !self.is_selected(self.index) {
+            let index = self.index;
+            self.index += 1;
+            if let Err(err) = self.skip(index) {
+                return Some(Err(err));
+            }
+        }
         let index = self.index;
         if index >= self.feature_count {
             return None;
@@ -587,32 +1085,49 @@ impl<'layer> LendingIterator for Layer01FeatureIter<'layer, '_> {
         self.values_buf.clear();
         self.values_buf
             .extend(self.col_iters.iter_mut().map(|it| it.next().flatten()));
+        let layer = self.layer;
+        let geometry = &layer.geometry;
+        let triangles = match (&mut self.triangles, geometry.vector_types().get(index)) {
+            (Some(cursor), Some(&geom_type)) => cursor.next(index, geom_type),
+            _ => Ok(None),
+        };
 
-        Some(
-            self.layer
-                .geometry
-                .to_geojson(index)
-                .map(|geometry| FeatureRef {
-                    id,
-                    geometry,
-                    columns: &self.layer.properties,
-                    values: &self.values_buf,
+        Some(triangles.and_then(|triangles| {
+            let mesh = geometry.is_mesh_only();
+            let (geometry, z, vertices) = geometry.feature_with_vertices(index)?;
+            Ok(FeatureRef {
+                id,
+                geometry,
+                mesh,
+                triangles: triangles.map(|indices| TrianglesRef {
+                    indices,
+                    base: if mesh { 0 } else { vertices.start },
                 }),
-        )
+                z,
+                vertices,
+                vertex_columns: &layer.vertex_properties,
+                columns: &layer.properties,
+                values: &self.values_buf,
+            })
+        }))
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use geo_types::Point;
+    use geo_types::{LineString, Point, Polygon};
+    use rstest::rstest;
     use serde_json::Value;
 
     use super::*;
-    use crate::Layer;
     use crate::decoder::GeometryValues;
     use crate::encoder::model::StagedLayer;
-    use crate::encoder::{Codecs, Encoder, Presence, StagedId, StagedProperty, StagedSharedDict};
+    use crate::encoder::{
+        Codecs, Encoder, EncoderConfig, Presence, StagedId, StagedProperty, StagedSharedDict,
+        StagedStruct,
+    };
     use crate::test_helpers::{assert_size_hint_exact, dec, parser};
+    use crate::{Layer, MltError, TileFeature};
 
     fn layer_buf(staged: StagedLayer) -> Vec<u8> {
         staged
@@ -702,6 +1217,51 @@ mod tests {
         );
     }
 
+    #[rstest]
+    #[case::epoch(PropValueRef::Date(0), "1970-01-01")]
+    #[case::leap_day(PropValueRef::Date(19_782), "2024-02-29")]
+    #[case::before_epoch(PropValueRef::Date(-1), "1969-12-31")]
+    #[case::ts_epoch(PropValueRef::Timestamp(0), "1970-01-01T00:00:00.000Z")]
+    #[case::ts_millis(PropValueRef::Timestamp(1_709_210_096_789), "2024-02-29T12:34:56.789Z")]
+    #[case::ts_before_epoch(PropValueRef::Timestamp(-1), "1969-12-31T23:59:59.999Z")]
+    fn prop_value_ref_temporal_converts_to_iso(
+        #[case] v: PropValueRef<'_>,
+        #[case] expected: &str,
+    ) {
+        assert_eq!(Value::from(v), Value::String(expected.into()));
+    }
+
+    #[test]
+    fn prop_value_ref_json_and_binary_convert_to_json() {
+        assert_eq!(
+            Value::from(PropValueRef::Json(r#"{"a":[1,true]}"#)),
+            serde_json::json!({"a": [1, true]})
+        );
+        assert_eq!(
+            Value::from(PropValueRef::Json("not json")),
+            Value::String("not json".into())
+        );
+        assert_eq!(
+            Value::from(PropValueRef::Binary(&[0, 255])),
+            serde_json::json!([0, 255])
+        );
+    }
+
+    #[test]
+    fn prop_value_ref_range_map_converts_to_between_objects() {
+        let entries = [
+            (0.2, 0.6, PropValue::U32(Some(50))),
+            (0.6, 1.0, PropValue::U32(None)),
+        ];
+        assert_eq!(
+            Value::from(PropValueRef::RangeMap(&entries)),
+            serde_json::json!([
+                {"between": [0.2, 0.6], "value": 50},
+                {"between": [0.6, 1.0], "value": null},
+            ])
+        );
+    }
+
     #[test]
     fn prop_value_ref_float_finite_is_number() {
         assert!(matches!(
@@ -972,6 +1532,72 @@ mod tests {
         assert_eq!(feat.get_property("no_such_column"), None);
     }
 
+    fn poi_layer() -> Vec<u8> {
+        let addr = StagedSharedDict::new(
+            "addr:",
+            [
+                ("city", vec![Some("Paris"), None, None], Presence::Mixed),
+                ("zip", vec![None, Some("00100"), None], Presence::Mixed),
+            ],
+        )
+        .unwrap();
+        layer_buf(staged_layer(
+            "poi",
+            StagedId::from_optional(vec![Some(1), Some(2), Some(3)]),
+            three_points(),
+            vec![
+                StagedProperty::u32("rank", vec![3, 1, 2]),
+                StagedProperty::str("description", vec!["a long text"; 3]),
+                StagedProperty::str("name", vec!["cafe", "bar", "shop"]),
+                StagedProperty::SharedDict(addr),
+            ],
+        ))
+    }
+
+    #[test]
+    fn decode_columns_keeps_only_selected_properties() {
+        let buf = poi_layer();
+        let (_, layer) = Layer::from_bytes(&buf, &mut parser()).unwrap();
+        let Layer::Tag01(lazy) = layer else { panic!() };
+
+        let mut all_dec = dec();
+        lazy.clone().decode_all(&mut all_dec).unwrap();
+        let mut projected_dec = dec();
+        let parsed = lazy
+            .decode_columns(&mut projected_dec, &["name", "addr:zip", "rank", "missing"])
+            .unwrap();
+        assert!(projected_dec.consumed() < all_dec.consumed());
+
+        // Original column order is kept, and a shared dictionary is selected as a whole
+        let names: Vec<_> = parsed.iterate_prop_names().map(|n| n.to_string()).collect();
+        assert_eq!(names, ["rank", "name", "addr:city", "addr:zip"]);
+
+        let mut iter = parsed.iter_features();
+        let feat = iter.next().unwrap().unwrap();
+        assert_eq!(feat.id(), Some(1));
+        assert_eq!(feat.geometry(), &Geometry::<i32>::Point(Point::new(1, 2)));
+        assert_eq!(feat.get_property("name"), Some(PropValueRef::Str("cafe")));
+        assert_eq!(feat.get_property("rank"), Some(PropValueRef::U32(3)));
+        assert_eq!(feat.get_property("description"), None);
+        assert_eq!(feat.iter_all_properties().len(), 4);
+    }
+
+    #[test]
+    fn decode_columns_without_names_keeps_geometry_and_ids() {
+        let buf = poi_layer();
+        let (_, layer) = Layer::from_bytes(&buf, &mut parser()).unwrap();
+        let Layer::Tag01(lazy) = layer else { panic!() };
+        let tile = lazy
+            .decode_columns::<&str>(&mut dec(), &[])
+            .unwrap()
+            .into_tile(&mut dec())
+            .unwrap();
+
+        assert!(tile.property_names().is_empty());
+        let ids: Vec<_> = tile.features().iter().map(TileFeature::id).collect();
+        assert_eq!(ids, [Some(1), Some(2), Some(3)]);
+    }
+
     #[test]
     fn shared_dict_columns_are_expanded() {
         let shared_dict = StagedSharedDict::new(
@@ -1043,6 +1669,76 @@ mod tests {
         assert_eq!(names, ["addr:city", "addr:zip"]);
     }
 
+    #[test]
+    fn struct_columns_are_expanded() {
+        let place = StagedStruct::new(
+            "place:",
+            [
+                StagedProperty::str("name", ["Paris", "Rome", "Paris"]),
+                StagedProperty::u32("rank", vec![1, 2, 3]),
+                StagedProperty::opt_str("alt", [None, Some("Roma"), Some("Paris")]),
+                StagedProperty::opt_i32("pop", [Some(-5), None, Some(7)]),
+            ],
+        )
+        .unwrap();
+
+        let buf = layer_buf(staged_layer(
+            "test",
+            StagedId::None,
+            three_points(),
+            vec![
+                StagedProperty::Struct(place),
+                StagedProperty::bool("after", vec![true, false, true]),
+            ],
+        ));
+        let (_, layer) = Layer::from_bytes(&buf, &mut parser()).unwrap();
+        let Layer::Tag01(lazy) = layer else { panic!() };
+        let expected_names = [
+            "place:name",
+            "place:rank",
+            "place:alt",
+            "place:pop",
+            "after",
+        ];
+        assert_eq!(strs(lazy.iterate_prop_names()), expected_names);
+
+        let parsed = lazy.decode_all(&mut dec()).unwrap();
+        assert_eq!(strs(parsed.iterate_prop_names()), expected_names);
+        let ParsedProperty::Struct(place) = &parsed.properties[0] else {
+            panic!("expected a struct column");
+        };
+        assert_eq!(place.prefix, "place:");
+        assert_eq!(place.strings.as_ref().map(|s| s.items.len()), Some(2));
+
+        let mut iter = parsed.iter_features();
+        let feat = iter.next().unwrap().unwrap();
+        let all: Vec<_> = feat.iter_all_properties().collect();
+        assert_eq!(
+            all,
+            [
+                Some(PropValueRef::Str("Paris")),
+                Some(PropValueRef::U32(1)),
+                None,
+                Some(PropValueRef::I32(-5)),
+                Some(PropValueRef::Bool(true)),
+            ]
+        );
+        let feat = iter.next().unwrap().unwrap();
+        assert_eq!(
+            feat.get_property("place:alt"),
+            Some(PropValueRef::Str("Roma"))
+        );
+        assert_eq!(feat.get_property("place:pop"), None);
+        assert_eq!(feat.get_property("place:rank"), Some(PropValueRef::U32(2)));
+        let feat = iter.next().unwrap().unwrap();
+        assert_eq!(
+            feat.get_property("place:name"),
+            Some(PropValueRef::Str("Paris"))
+        );
+        assert_eq!(feat.get_property("place:pop"), Some(PropValueRef::I32(7)));
+        assert!(iter.next().is_none());
+    }
+
     fn strs<'p>(iter: impl Iterator<Item = PropName<'p>>) -> Vec<String> {
         iter.map(|n| n.to_string()).collect()
     }
@@ -1189,4 +1885,221 @@ mod tests {
         props_back.reverse();
         assert_eq!(props_back, BOTH_ENDS_NAMES);
     }
+
+    fn vertex_layer() -> StagedLayer {
+        let mut g = GeometryValues::default();
+        g.push_geom(&Geometry::<i32>::LineString(LineString::from(vec![
+            (0, 0),
+            (10, 0),
+            (10, 10),
+        ])));
+        // The closing vertex of the ring is not stored, so it carries no value.
+        g.push_geom(&Geometry::<i32>::Polygon(Polygon::new(
+            LineString::from(vec![(0, 0), (5, 0), (5, 5), (0, 0)]),
+            vec![],
+        )));
+        staged_layer(
+            "test",
+            StagedId::None,
+            g,
+            vec![StagedProperty::u32("n", vec![1, 2])],
+        )
+        .with_vertex_properties(vec![
+            StagedProperty::f32("speed", vec![1.5, 2.5, 3.5, 4.5, 5.5, 6.5]),
+            StagedProperty::i32("elevation", vec![-1, 0, 1, 2, 3, 4]),
+        ])
+        .unwrap()
+    }
+
+    #[test]
+    fn vertex_properties_are_sliced_per_feature() {
+        let buf = layer_buf(vertex_layer());
+        let (_, layer) = Layer::from_bytes(&buf, &mut parser()).unwrap();
+        let Layer::Tag01(lazy) = layer else { panic!() };
+        let parsed = lazy.decode_all(&mut dec()).unwrap();
+
+        let mut iter = parsed.iter_features();
+        let line = iter.next().unwrap().unwrap();
+        assert_eq!(
+            line.get_vertex_property("speed"),
+            Some(VertexValuesRef::F32(&[1.5, 2.5, 3.5]))
+        );
+        assert_eq!(
+            line.get_vertex_property("elevation"),
+            Some(VertexValuesRef::I32(&[-1, 0, 1]))
+        );
+        assert_eq!(line.get_vertex_property("n"), None);
+        let names: Vec<_> = line.iter_vertex_properties().map(|c| c.name()).collect();
+        assert_eq!(names, ["speed", "elevation"]);
+
+        let poly = iter.next().unwrap().unwrap();
+        assert_eq!(
+            poly.get_vertex_property("speed"),
+            Some(VertexValuesRef::F32(&[4.5, 5.5, 6.5]))
+        );
+        assert_eq!(poly.get_property("n"), Some(PropValueRef::U32(2)));
+        assert!(iter.next().is_none());
+    }
+
+    #[test]
+    fn vertex_properties_roundtrip_through_tile() {
+        let buf = layer_buf(vertex_layer());
+        let (_, layer) = Layer::from_bytes(&buf, &mut parser()).unwrap();
+        let Layer::Tag01(lazy) = layer else { panic!() };
+        let tile = lazy.into_tile(&mut dec()).unwrap();
+
+        assert_eq!(tile.vertex_property_names(), ["speed", "elevation"]);
+        assert_eq!(
+            tile.features[1].vertex_properties(),
+            [
+                VertexValues::F32(vec![4.5, 5.5, 6.5]),
+                VertexValues::I32(vec![2, 3, 4]),
+            ]
+        );
+
+        let buf = tile.clone().encode(EncoderConfig::default()).unwrap();
+        let (_, layer) = Layer::from_bytes(&buf, &mut parser()).unwrap();
+        let Layer::Tag01(lazy) = layer else { panic!() };
+        let reencoded = lazy.into_tile(&mut dec()).unwrap();
+        assert_eq!(
+            reencoded.vertex_property_names(),
+            tile.vertex_property_names()
+        );
+        for feat in &reencoded.features {
+            assert!(
+                tile.features.contains(feat),
+                "{feat:?} was not in the source"
+            );
+        }
+    }
+
+    #[test]
+    fn vertex_property_length_must_match_vertex_count() {
+        let err = staged_layer("test", StagedId::None, three_points(), vec![])
+            .with_vertex_properties(vec![StagedProperty::u8("v", vec![1, 2])])
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            MltError::VertexPropertyLengthMismatch {
+                index: 0,
+                expected: 3,
+                actual: 2
+            }
+        ));
+    }
+
+    fn tessellated_layer(geoms: &[Geometry<i32>], cfg: EncoderConfig) -> Vec<u8> {
+        let mut g = GeometryValues::new_tessellated();
+        for geom in geoms {
+            g.push_geom(geom);
+        }
+        staged_layer("test", StagedId::None, g, vec![])
+            .encode_into(Encoder::new(cfg), &mut Codecs::default())
+            .unwrap()
+            .into_layer_bytes()
+            .unwrap()
+    }
+
+    fn square_and_triangle() -> [Geometry<i32>; 2] {
+        [
+            Polygon::new(
+                LineString::from(vec![(0, 0), (10, 0), (10, 10), (0, 10), (0, 0)]),
+                vec![],
+            )
+            .into(),
+            Polygon::new(
+                LineString::from(vec![(20, 0), (30, 0), (30, 10), (20, 0)]),
+                vec![],
+            )
+            .into(),
+        ]
+    }
+
+    fn sorted_corners(tris: impl Iterator<Item = [usize; 3]>) -> Vec<usize> {
+        let mut corners: Vec<_> = tris.flatten().collect();
+        corners.sort_unstable();
+        corners
+    }
+
+    #[test]
+    fn feature_triangles_address_layer_vertices() {
+        let [square, triangle] = square_and_triangle();
+        let line = Geometry::<i32>::LineString(LineString::from(vec![(0, 0), (10, 0)]));
+        let buf = tessellated_layer(&[line, square, triangle], EncoderConfig::default());
+        let (_, layer) = Layer::from_bytes(&buf, &mut parser()).unwrap();
+        let Layer::Tag01(lazy) = layer else { panic!() };
+        let parsed = lazy.decode_all(&mut dec()).unwrap();
+
+        let mut iter = parsed.iter_features();
+        assert_eq!(iter.next().unwrap().unwrap().triangles(), None);
+
+        let square = iter.next().unwrap().unwrap().triangles().unwrap();
+        assert_eq!(square.len(), 2);
+        assert_eq!(square.base_vertex(), 2);
+        let mut corners = sorted_corners(square.iter());
+        corners.dedup();
+        assert_eq!(corners, [2, 3, 4, 5]);
+
+        let triangle = iter.next().unwrap().unwrap().triangles().unwrap();
+        assert_eq!(triangle.base_vertex(), 6);
+        assert_eq!(triangle.raw_indices().len(), 3);
+        assert_eq!(sorted_corners(triangle.iter()), [6, 7, 8]);
+        assert!(iter.next().is_none());
+
+        let layer_tris = parsed.triangles().unwrap().unwrap();
+        assert_eq!(layer_tris.offsets(), [0, 0, 2, 3]);
+        assert_eq!(layer_tris.len(), 3);
+        assert_eq!(layer_tris.feature(0), Some([].as_slice()));
+        let flat: Vec<_> = square.iter().flatten().collect();
+        let from_layer: Vec<_> = layer_tris
+            .feature(1)
+            .unwrap()
+            .iter()
+            .map(|&i| i.into_usize())
+            .collect();
+        assert_eq!(from_layer, flat);
+        assert_eq!(layer_tris.feature(3), None);
+    }
+
+    #[test]
+    fn mesh_only_triangles_match_topology_layout() {
+        let geoms = square_and_triangle();
+        let decode = |cfg| {
+            let buf = tessellated_layer(&geoms, cfg);
+            let (_, layer) = Layer::from_bytes(&buf, &mut parser()).unwrap();
+            let Layer::Tag01(lazy) = layer else { panic!() };
+            let parsed = lazy.decode_all(&mut dec()).unwrap();
+            let mut per_feature = Vec::new();
+            let mut iter = parsed.iter_features();
+            while let Some(feat) = iter.next() {
+                per_feature.push(
+                    feat.unwrap()
+                        .triangles()
+                        .unwrap()
+                        .iter()
+                        .collect::<Vec<_>>(),
+                );
+            }
+            (parsed.triangles().unwrap().unwrap(), per_feature)
+        };
+
+        let (topology, topology_features) = decode(EncoderConfig::default());
+        let (mesh, mesh_features) = decode(EncoderConfig::default().with_mesh_only(true));
+        assert_eq!(mesh, topology);
+        assert_eq!(mesh_features, topology_features);
+    }
+
+    #[test]
+    fn untessellated_layer_has_no_triangles() {
+        let buf = layer_buf(vertex_layer());
+        let (_, layer) = Layer::from_bytes(&buf, &mut parser()).unwrap();
+        let Layer::Tag01(lazy) = layer else { panic!() };
+        let parsed = lazy.decode_all(&mut dec()).unwrap();
+
+        assert_eq!(parsed.triangles().unwrap(), None);
+        let mut iter = parsed.iter_features();
+        while let Some(feat) = iter.next() {
+            assert_eq!(feat.unwrap().triangles(), None);
+        }
+    }
 }
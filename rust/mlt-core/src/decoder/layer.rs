@@ -37,7 +37,8 @@ impl<'a> Layer<'a> {
         let (input, value) = take(input, size)?;
 
         let layer = match tag {
-            1 => Layer::Tag01(Layer01::from_bytes(value, parser)?),
+            // Tag 0x02 shares the 0x01 layout, but may contain nested columns
+            1 | 2 => Layer::Tag01(Layer01::from_bytes(tag, value, parser)?),
             tag => Layer::Unknown(Unknown { tag, value }),
         };
 
@@ -17,26 +17,33 @@ mod tile;
 // ── Crate-internal re-exports ─────────────────────────────────────────────────
 // Allow internal modules to keep using `crate::decoder::*` paths without
 // reaching into sub-module paths explicitly.
+pub(crate) use geometry::decode::decode_vec3_vertices;
 pub(crate) use geometry::{Geometry, RawGeometry};
 pub use geometry::{GeometryType, GeometryValues};
 pub use id::ParsedId;
 // pub (not pub(crate)) so __private module can re-export it
 pub(crate) use id::{Id, RawId, RawIdValue};
 pub use iterators::{
-    ColNames, ColumnRef, FeatureRef, Layer01FeatureIter, LendingIterator, PropName, PropNamesIter,
-    PropValueRef,
+    ColNames, ColumnRef, FeatureRef, Layer01FeatureIter, LayerTriangles, LendingIterator, PropName,
+    PropNamesIter, PropValueRef, TrianglesRef, VertexColumnRef, VertexValuesRef,
 };
+pub(crate) use iterators::{ColValIter, build_col_iters};
 pub(crate) use model::Column;
 pub use model::{
-    ColumnType, Extent, Layer, Layer01, ParsedLayer, ParsedLayer01, PropKind, PropValue,
-    PropertyKey, TileFeature, TileFeatureBuilder, TileLayer, TileLayerBuilder, Unknown,
+    ColumnType, Extent, Layer, Layer01, NestedValue, ParsedLayer, ParsedLayer01, PropKind,
+    PropValue, PropertyKey, TileFeature, TileFeatureBuilder, TileLayer, TileLayerBuilder, Unknown,
+    VertexPropertyKey, VertexValues,
 };
 // Re-export strings sub-module so encoder can use `crate::decoder::strings::*`
+pub(crate) use property::nested::{MAX_NESTED_DEPTH, NestedMask, NestedToken};
 pub(crate) use property::strings;
 pub(crate) use property::{
-    DictRange, ParsedProperty, ParsedScalar, ParsedSharedDict, ParsedSharedDictItem, ParsedStrings,
-    Property, RawFsstData, RawPlainData, RawPresence, RawProperty, RawScalar, RawSharedDict,
-    RawSharedDictEncoding, RawSharedDictItem, RawStrings, RawStringsEncoding,
+    DictRange, ParsedNested, ParsedNestedItem, ParsedProperty, ParsedRangeMap, ParsedScalar,
+    ParsedSharedDict, ParsedSharedDictItem, ParsedStrings, ParsedStruct, ParsedStructChild,
+    Property, RawFloats, RawFloatsEncoding, RawFsstData, RawNested, RawNestedBody, RawNestedInts,
+    RawPlainData, RawPresence, RawProperty, RawRangeMap, RawScalar, RawSharedDict,
+    RawSharedDictEncoding, RawSharedDictItem, RawStrings, RawStringsEncoding, RawStruct,
+    RawStructChild,
 };
 pub use root::{Decoder, Parser};
 pub(crate) use stream::model::{
@@ -1,9 +1,11 @@
 use std::fmt;
 use std::num::NonZeroU32;
 
+use geo::CoordsIter as _;
 use num_enum::TryFromPrimitive;
 
-use crate::decoder::{Geometry, Id, Property};
+use crate::decoder::{Geometry, Id, PropValueRef, Property};
+use crate::encoder::stored_vertex_count;
 use crate::{DecodeState, Lazy, MltError, MltResult, Parsed};
 
 /// Non-zero tile extent.
@@ -39,7 +41,7 @@ impl From<Extent> for NonZeroU32 {
 /// - `Layer<'a, Parsed>` - returned by [`Layer::decode_all`]; all columns are decoded. Use `ParsedLayer` alias.
 #[non_exhaustive]
 pub enum Layer<'a, S: DecodeState = Lazy> {
-    /// MVT-compatible layer (tag = 1)
+    /// MVT-compatible layer (tag = 1, or tag = 2 if it uses nested columns)
     Tag01(Layer01<'a, S>),
     /// Unknown layer with tag, size, and value
     Unknown(Unknown<'a>),
@@ -100,6 +102,13 @@ pub enum ColumnType {
     LongId = 2,
     OptLongId = 3,
     Geometry = 4,
+    /// Geometry with a per-vertex Z value (`Vec3` vertices).
+    GeometryZ = 5,
+    /// Group of vertex-scoped columns holding one value per vertex instead of per feature.
+    ///
+    /// Followed by a child count and the child column definitions, like [`Self::SharedDict`].
+    /// Children must be non-optional `Bool`, integer or float columns.
+    VertexScope = 6,
     Bool = 10,
     OptBool = 11,
     I8 = 12,
@@ -121,6 +130,22 @@ pub enum ColumnType {
     Str = 28,
     OptStr = 29,
     SharedDict = 30,
+    /// Experimental nested-field (JSON-like map / list) column.
+    Nested = 31,
+    /// Logical `RangeMap` (`Map<vec2<Double>, T>`) for linear referencing.
+    ///
+    /// Followed by a single child column definition describing the value type `T`, which
+    /// must be a bool, integer, float or string column. The column body holds the number
+    /// of ranges per feature, the interleaved `[start, end]` range bounds as doubles,
+    /// and the child column with one value per range.
+    RangeMap = 40,
+    OptRangeMap = 41,
+    /// Struct of named child columns of mixed scalar types (e.g. `name:*` strings plus `rank`).
+    ///
+    /// Followed by a child count and the child column definitions, like [`Self::SharedDict`].
+    /// The column body starts with an optional dictionary shared by all string children,
+    /// followed by each child in order. Structs themselves are never optional.
+    Struct = 42,
 }
 
 /// Representation of an MLT feature table layer with tag `0x01` during decoding.
@@ -134,10 +159,17 @@ pub enum ColumnType {
 ///   `properties` hold the parsed types directly, allowing infallible readonly access.
 ///   There is a `ParsedLayer01<'a>` type alias for this.
 pub struct Layer01<'a, S: DecodeState = Lazy> {
+    /// Layer tag this layer was parsed from: 1, or 2 if it may contain nested columns.
+    pub(crate) tag: u8,
     pub(crate) name: &'a str,
+    /// Encoded bytes following the layer name, copied verbatim by
+    /// [`TileAssembler`](crate::encoder::TileAssembler).
+    pub(crate) body: &'a [u8],
     pub(crate) extent: Extent,
     pub(crate) id: Option<Id<'a, S>>,
     pub(crate) geometry: Geometry<'a, S>,
+    /// Vertex-scoped columns, each holding one value per vertex of `geometry`.
+    pub(crate) vertex_properties: Vec<Property<'a, S>>,
     pub(crate) properties: Vec<Property<'a, S>>,
     #[cfg(fuzzing)]
     pub(crate) layer_order: Vec<crate::decoder::fuzzing::LayerOrdering>,
@@ -170,6 +202,7 @@ where
             .field("extent", &self.extent)
             .field("id", &self.id)
             .field("geometry", &self.geometry)
+            .field("vertex_properties", &self.vertex_properties)
             .field("properties", &self.properties);
         #[cfg(fuzzing)]
         s.field("layer_order", &self.layer_order);
@@ -186,10 +219,13 @@ where
 {
     fn clone(&self) -> Self {
         Self {
+            tag: self.tag,
             name: self.name,
+            body: self.body,
             extent: self.extent,
             id: self.id.clone(),
             geometry: self.geometry.clone(),
+            vertex_properties: self.vertex_properties.clone(),
             properties: self.properties.clone(),
             #[cfg(fuzzing)]
             layer_order: self.layer_order.clone(),
@@ -210,6 +246,10 @@ pub struct TileLayer {
     pub(crate) property_names: Vec<String>,
     /// Column types, parallel to `TileFeature::properties`.
     pub(crate) property_kinds: Vec<PropKind>,
+    /// Vertex-scoped column names, parallel to `TileFeature::vertex_properties`.
+    pub(crate) vertex_property_names: Vec<String>,
+    /// Vertex-scoped column types, parallel to `TileFeature::vertex_properties`.
+    pub(crate) vertex_property_kinds: Vec<PropKind>,
     pub(crate) features: Vec<TileFeature>,
 }
 
@@ -219,9 +259,14 @@ pub struct TileFeature {
     pub(crate) id: Option<u64>,
     /// Geometry as a [`geo_types`] form
     pub(crate) geometry: geo_types::Geometry<i32>,
+    /// Per-vertex Z values, one per coordinate of `geometry` in `coords_iter` order.
+    pub(crate) z: Option<Vec<i32>>,
     /// One value per property column, in the same order as
     /// [`TileLayer::property_names`].
     pub(crate) properties: Vec<PropValue>,
+    /// One entry per vertex-scoped column, in the same order as
+    /// [`TileLayer::vertex_property_names`], each with [`TileFeature::vertex_count`] values.
+    pub(crate) vertex_properties: Vec<VertexValues>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -234,6 +279,16 @@ impl PropertyKey {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct VertexPropertyKey(usize);
+
+impl VertexPropertyKey {
+    #[must_use]
+    pub fn index(self) -> usize {
+        self.0
+    }
+}
+
 impl TileLayer {
     pub fn new(name: impl Into<String>, extent: u32) -> MltResult<Self> {
         Self::with_capacity(name, extent, 0)
@@ -248,6 +303,8 @@ impl TileLayer {
             extent,
             property_names: Vec::new(),
             property_kinds: Vec::new(),
+            vertex_property_names: Vec::new(),
+            vertex_property_kinds: Vec::new(),
             features: Vec::with_capacity(features),
         })
     }
@@ -268,11 +325,41 @@ impl TileLayer {
             extent,
             property_names,
             property_kinds,
+            vertex_property_names: Vec::new(),
+            vertex_property_kinds: Vec::new(),
             features,
         };
         Ok(layer)
     }
 
+    /// Name the vertex-scoped columns already present on every feature, inferring their kinds.
+    pub(crate) fn with_vertex_property_names(mut self, names: Vec<String>) -> MltResult<Self> {
+        for name in &names {
+            self.check_unique_name(name)?;
+        }
+        validate_property_names(&names)?;
+        let kinds = match self.features.first() {
+            Some(feature) => feature
+                .vertex_properties
+                .iter()
+                .map(VertexValues::kind)
+                .collect(),
+            None => Vec::new(),
+        };
+        if kinds.len() != names.len() && !self.features.is_empty() {
+            return Err(MltError::PropertyLengthMismatch {
+                expected: names.len(),
+                actual: kinds.len(),
+            });
+        }
+        self.vertex_property_names = names;
+        self.vertex_property_kinds = kinds;
+        for feature in &self.features {
+            self.validate_vertex_properties(feature)?;
+        }
+        Ok(self)
+    }
+
     #[must_use]
     pub fn name(&self) -> &str {
         &self.name
@@ -288,14 +375,15 @@ impl TileLayer {
         &self.property_names
     }
 
+    /// Names of the vertex-scoped columns, parallel to [`TileFeature::vertex_properties`].
     #[must_use]
-    pub fn features(&self) -> &[TileFeature] {
-        &self.features
+    pub fn vertex_property_names(&self) -> &[String] {
+        &self.vertex_property_names
     }
 
     #[must_use]
-    pub(crate) fn features_mut(&mut self) -> &mut [TileFeature] {
-        &mut self.features
+    pub fn features(&self) -> &[TileFeature] {
+        &self.features
     }
 
     #[must_use]
@@ -309,9 +397,7 @@ impl TileLayer {
         kind: PropKind,
     ) -> MltResult<PropertyKey> {
         let name = name.into();
-        if self.property_names.contains(&name) {
-            return Err(MltError::DuplicatePropertyName(name));
-        }
+        self.check_unique_name(&name)?;
         for feature in &mut self.features {
             feature.properties.push(PropValue::null(kind));
         }
@@ -320,6 +406,103 @@ impl TileLayer {
         Ok(PropertyKey(self.property_names.len() - 1))
     }
 
+    /// Key of the property column called `name`, if any.
+    #[must_use]
+    pub fn property_key(&self, name: &str) -> Option<PropertyKey> {
+        self.property_names
+            .iter()
+            .position(|n| n == name)
+            .map(PropertyKey)
+    }
+
+    /// Reinterpret a property column as the logical type `kind`.
+    ///
+    /// Tiles only store physical column types: `Date` is written as `I32`, `Timestamp` as
+    /// `I64`, `Json` as `Str` and `Binary` as a nested list of bytes, so a decoded layer
+    /// reports those columns by their physical kind. Use this to restore the logical type;
+    /// [`TileSetMetadata::restore_logical_kinds`](crate::metadata::TileSetMetadata::restore_logical_kinds)
+    /// does so for every column of the tileset schema. Setting the kind a column already has
+    /// is a no-op.
+    pub fn set_logical_kind(&mut self, key: PropertyKey, kind: PropKind) -> MltResult<()> {
+        let index = key.0;
+        let actual = self.property_kinds[index];
+        if actual == kind {
+            return Ok(());
+        }
+        let expected = match kind {
+            PropKind::Date => PropKind::I32,
+            PropKind::Timestamp => PropKind::I64,
+            PropKind::Json => PropKind::Str,
+            PropKind::Binary => PropKind::Nested,
+            _ => kind,
+        };
+        let mismatch = || MltError::PropertyKindMismatch {
+            index,
+            expected,
+            actual,
+        };
+        if actual != expected || expected == kind {
+            return Err(mismatch());
+        }
+        let mut values = Vec::with_capacity(self.features.len());
+        for feature in &self.features {
+            values.push(match &feature.properties[index] {
+                PropValue::I32(v) => PropValue::Date(*v),
+                PropValue::I64(v) => PropValue::Timestamp(*v),
+                PropValue::Str(v) => PropValue::Json(v.clone()),
+                PropValue::Nested(None) => PropValue::Binary(None),
+                PropValue::Nested(Some(NestedValue::List(items))) => PropValue::Binary(Some(
+                    items
+                        .iter()
+                        .map(|item| match item {
+                            NestedValue::U64(b) => u8::try_from(*b).ok(),
+                            NestedValue::I64(b) => u8::try_from(*b).ok(),
+                            _ => None,
+                        })
+                        .collect::<Option<_>>()
+                        .ok_or_else(mismatch)?,
+                )),
+                _ => return Err(mismatch()),
+            });
+        }
+        for (feature, value) in self.features.iter_mut().zip(values) {
+            feature.properties[index] = value;
+        }
+        self.property_kinds[index] = kind;
+        Ok(())
+    }
+
+    /// Add a vertex-scoped column holding one value per vertex of each feature.
+    ///
+    /// Only `Bool`, integer and float kinds can be vertex-scoped. Existing features
+    /// get zero (or `false`) for every vertex.
+    pub fn add_vertex_property(
+        &mut self,
+        name: impl Into<String>,
+        kind: PropKind,
+    ) -> MltResult<VertexPropertyKey> {
+        let name = name.into();
+        VertexValues::zeroed(kind, 0)?;
+        self.check_unique_name(&name)?;
+        for feature in &mut self.features {
+            let values = VertexValues::zeroed(kind, feature.vertex_count())?;
+            feature.vertex_properties.push(values);
+        }
+        self.vertex_property_names.push(name);
+        self.vertex_property_kinds.push(kind);
+        Ok(VertexPropertyKey(self.vertex_property_names.len() - 1))
+    }
+
+    /// Feature- and vertex-scoped columns share one namespace.
+    fn check_unique_name(&self, name: &str) -> MltResult<()> {
+        if self.property_names.iter().any(|n| n == name)
+            || self.vertex_property_names.iter().any(|n| n == name)
+        {
+            return Err(MltError::DuplicatePropertyName(name.to_string()));
+        }
+        Ok(())
+    }
+
     pub fn push_feature(&mut self, feature: TileFeature) -> MltResult<()> {
         self.validate_feature(&feature)?;
         self.features.push(feature);
@@ -348,6 +531,42 @@ impl TileLayer {
                     actual,
                 });
             }
+            if let PropValue::RangeMap(Some(entries)) = prop
+                && let Some(kind) = entries
+                    .iter()
+                    .map(|(_, _, value)| value.kind())
+                    .find(|kind| !kind.is_range_map_value())
+            {
+                return Err(MltError::UnsupportedRangeMapValueKind(kind));
+            }
+        }
+        self.validate_vertex_properties(feature)
+    }
+
+    fn validate_vertex_properties(&self, feature: &TileFeature) -> MltResult<()> {
+        let expected = self.vertex_property_names.len();
+        let actual = feature.vertex_properties.len();
+        if actual != expected {
+            return Err(MltError::PropertyLengthMismatch { expected, actual });
+        }
+        let vertex_count = feature.vertex_count();
+        for (idx, values) in feature.vertex_properties.iter().enumerate() {
+            let expected = self.vertex_property_kinds[idx];
+            let actual = values.kind();
+            if actual != expected {
+                return Err(MltError::PropertyKindMismatch {
+                    index: idx,
+                    expected,
+                    actual,
+                });
+            }
+            if values.len() != vertex_count {
+                return Err(MltError::VertexPropertyLengthMismatch {
+                    index: idx,
+                    expected: vertex_count,
+                    actual: values.len(),
+                });
+            }
         }
         Ok(())
     }
@@ -359,7 +578,9 @@ impl TileFeature {
         Self {
             id: None,
             geometry,
+            z: None,
             properties: Vec::new(),
+            vertex_properties: Vec::new(),
         }
     }
 
@@ -368,7 +589,9 @@ impl TileFeature {
         Self {
             id: Some(id),
             geometry,
+            z: None,
             properties: Vec::new(),
+            vertex_properties: Vec::new(),
         }
     }
 
@@ -382,6 +605,77 @@ impl TileFeature {
         &self.geometry
     }
 
+    /// Per-vertex Z values, one per coordinate of [`Self::geometry`] in
+    /// [`coords_iter`](geo::CoordsIter::coords_iter) order. `None` for 2D features.
+    #[must_use]
+    pub fn z(&self) -> Option<&[i32]> {
+        self.z.as_deref()
+    }
+
+    /// Attach (or with `None`, remove) per-vertex Z values.
+    ///
+    /// Closing vertices of polygon rings need a value too, although only the one on the
+    /// first vertex of each ring is stored.
+    pub fn set_z(&mut self, z: Option<Vec<i32>>) -> MltResult<()> {
+        if let Some(z) = &z {
+            let expected = self.geometry.coords_count();
+            if z.len() != expected {
+                return Err(MltError::VertexZLengthMismatch {
+                    expected,
+                    actual: z.len(),
+                });
+            }
+        }
+        self.z = z;
+        Ok(())
+    }
+
+    /// Number of vertices stored for [`Self::geometry`]: every coordinate except the
+    /// closing coordinate of each polygon ring.
+    ///
+    /// Each vertex-scoped property holds exactly this many values.
+    #[must_use]
+    pub fn vertex_count(&self) -> usize {
+        stored_vertex_count(&self.geometry)
+    }
+
+    /// Per-vertex values, one entry per column of [`TileLayer::vertex_property_names`].
+    #[must_use]
+    pub fn vertex_properties(&self) -> &[VertexValues] {
+        &self.vertex_properties
+    }
+
+    /// Replace the values of one vertex-scoped column.
+    pub fn set_vertex_property(
+        &mut self,
+        key: VertexPropertyKey,
+        values: VertexValues,
+    ) -> MltResult<()> {
+        let vertex_count = self.vertex_count();
+        let Some(slot) = self.vertex_properties.get_mut(key.index()) else {
+            return Err(MltError::PropertyLengthMismatch {
+                expected: key.index() + 1,
+                actual: self.vertex_properties.len(),
+            });
+        };
+        if values.kind() != slot.kind() {
+            return Err(MltError::PropertyKindMismatch {
+                index: key.index(),
+                expected: slot.kind(),
+                actual: values.kind(),
+            });
+        }
+        if values.len() != vertex_count {
+            return Err(MltError::VertexPropertyLengthMismatch {
+                index: key.index(),
+                expected: vertex_count,
+                actual: values.len(),
+            });
+        }
+        *slot = values;
+        Ok(())
+    }
+
     #[must_use]
     pub fn properties(&self) -> &[PropValue] {
         &self.properties
@@ -434,16 +728,35 @@ impl TileLayerBuilder {
             .copied()
             .map(PropValue::null)
             .collect();
+        let vertex_count = stored_vertex_count(&geometry);
+        let vertex_properties = self
+            .layer
+            .vertex_property_kinds
+            .iter()
+            .map(|&kind| {
+                VertexValues::zeroed(kind, vertex_count).expect("validated by add_vertex_property")
+            })
+            .collect();
         TileFeatureBuilder {
             layer: self,
             feature: TileFeature {
                 id: None,
                 geometry,
+                z: None,
                 properties,
+                vertex_properties,
             },
         }
     }
 
+    pub fn add_vertex_property(
+        &mut self,
+        name: impl Into<String>,
+        kind: PropKind,
+    ) -> MltResult<VertexPropertyKey> {
+        self.layer.add_vertex_property(name, kind)
+    }
+
     pub fn push_feature(&mut self, feature: TileFeature) -> MltResult<()> {
         self.layer.push_feature(feature)
     }
@@ -470,6 +783,22 @@ impl TileFeatureBuilder<'_> {
         Ok(self)
     }
 
+    /// Attach per-vertex Z values, see [`TileFeature::set_z`].
+    pub fn z(&mut self, z: Vec<i32>) -> MltResult<&mut Self> {
+        self.feature.set_z(Some(z))?;
+        Ok(self)
+    }
+
+    /// Set the values of a vertex-scoped column, see [`TileFeature::set_vertex_property`].
+    pub fn vertex_property(
+        &mut self,
+        key: VertexPropertyKey,
+        values: VertexValues,
+    ) -> MltResult<&mut Self> {
+        self.feature.set_vertex_property(key, values)?;
+        Ok(self)
+    }
+
     pub fn finish(self) -> MltResult<()> {
         self.layer.push_feature(self.feature)
     }
@@ -494,6 +823,36 @@ pub enum PropValue {
     F32(Option<f32>),
     F64(Option<f64>),
     Str(Option<String>),
+    Nested(Option<NestedValue>),
+    /// Days since the Unix epoch.
+    Date(Option<i32>),
+    /// Milliseconds since the Unix epoch.
+    Timestamp(Option<i64>),
+    /// A JSON document, kept as its serialized text.
+    Json(Option<String>),
+    Binary(Option<Vec<u8>>),
+    /// Linear referencing: `(start, end, value)` entries, with `start` and `end` given as
+    /// fractions of the feature geometry length.
+    ///
+    /// Values must be non-nested bool, integer, float or string values of a single kind
+    /// per column; they may be null.
+    RangeMap(Option<Vec<(f64, f64, Self)>>),
+}
+
+/// A JSON-like tree value stored in a [`ColumnType::Nested`] column.
+///
+/// Map entries keep their encoded order. Null map values and null list items
+/// are not representable on the wire and are dropped by the encoder.
+#[derive(Debug, Clone, PartialEq)]
+pub enum NestedValue {
+    Bool(bool),
+    I64(i64),
+    U64(u64),
+    F32(f32),
+    F64(f64),
+    Str(String),
+    List(Vec<Self>),
+    Map(Vec<(String, Self)>),
 }
 
 impl PropValue {
@@ -508,13 +867,16 @@ impl PropValue {
             Self::Bool(v) => v.is_none(),
             Self::I8(v) => v.is_none(),
             Self::U8(v) => v.is_none(),
-            Self::I32(v) => v.is_none(),
+            Self::I32(v) | Self::Date(v) => v.is_none(),
             Self::U32(v) => v.is_none(),
-            Self::I64(v) => v.is_none(),
+            Self::I64(v) | Self::Timestamp(v) => v.is_none(),
             Self::U64(v) => v.is_none(),
             Self::F32(v) => v.is_none(),
             Self::F64(v) => v.is_none(),
-            Self::Str(v) => v.is_none(),
+            Self::Str(v) | Self::Json(v) => v.is_none(),
+            Self::Nested(v) => v.is_none(),
+            Self::Binary(v) => v.is_none(),
+            Self::RangeMap(v) => v.is_none(),
         }
     }
 
@@ -531,8 +893,114 @@ impl PropValue {
             PropKind::F32 => Self::F32(None),
             PropKind::F64 => Self::F64(None),
             PropKind::Str => Self::Str(None),
+            PropKind::Nested => Self::Nested(None),
+            PropKind::Date => Self::Date(None),
+            PropKind::Timestamp => Self::Timestamp(None),
+            PropKind::Json => Self::Json(None),
+            PropKind::Binary => Self::Binary(None),
+            PropKind::RangeMap => Self::RangeMap(None),
+        }
+    }
+
+    /// Borrow this value as a [`PropValueRef`], or `None` if it is null.
+    #[must_use]
+    pub fn as_value_ref(&self) -> Option<PropValueRef<'_>> {
+        Some(match self {
+            Self::Bool(v) => PropValueRef::Bool((*v)?),
+            Self::I8(v) => PropValueRef::I8((*v)?),
+            Self::U8(v) => PropValueRef::U8((*v)?),
+            Self::I32(v) => PropValueRef::I32((*v)?),
+            Self::U32(v) => PropValueRef::U32((*v)?),
+            Self::I64(v) => PropValueRef::I64((*v)?),
+            Self::U64(v) => PropValueRef::U64((*v)?),
+            Self::F32(v) => PropValueRef::F32((*v)?),
+            Self::F64(v) => PropValueRef::F64((*v)?),
+            Self::Str(v) => PropValueRef::Str(v.as_deref()?),
+            Self::Nested(v) => PropValueRef::Nested(v.as_ref()?),
+            Self::Date(v) => PropValueRef::Date((*v)?),
+            Self::Timestamp(v) => PropValueRef::Timestamp((*v)?),
+            Self::Json(v) => PropValueRef::Json(v.as_deref()?),
+            Self::Binary(v) => PropValueRef::Binary(v.as_deref()?),
+            Self::RangeMap(v) => PropValueRef::RangeMap(v.as_deref()?),
+        })
+    }
+}
+
+/// Values of one vertex-scoped property for a single feature, one per stored vertex.
+///
+/// See [`TileFeature::vertex_count`] for which vertices are stored.
+#[derive(Debug, Clone, PartialEq)]
+pub enum VertexValues {
+    Bool(Vec<bool>),
+    I8(Vec<i8>),
+    U8(Vec<u8>),
+    I32(Vec<i32>),
+    U32(Vec<u32>),
+    I64(Vec<i64>),
+    U64(Vec<u64>),
+    F32(Vec<f32>),
+    F64(Vec<f64>),
+}
+
+impl VertexValues {
+    /// `len` zeros (or `false`) of the given kind.
+    ///
+    /// Fails for kinds that cannot be vertex-scoped.
+    pub fn zeroed(kind: PropKind, len: usize) -> MltResult<Self> {
+        Ok(match kind {
+            PropKind::Bool => Self::Bool(vec![false; len]),
+            PropKind::I8 => Self::I8(vec![0; len]),
+            PropKind::U8 => Self::U8(vec![0; len]),
+            PropKind::I32 => Self::I32(vec![0; len]),
+            PropKind::U32 => Self::U32(vec![0; len]),
+            PropKind::I64 => Self::I64(vec![0; len]),
+            PropKind::U64 => Self::U64(vec![0; len]),
+            PropKind::F32 => Self::F32(vec![0.0; len]),
+            PropKind::F64 => Self::F64(vec![0.0; len]),
+            PropKind::Str
+            | PropKind::Nested
+            | PropKind::Date
+            | PropKind::Timestamp
+            | PropKind::Json
+            | PropKind::Binary
+            | PropKind::RangeMap => return Err(MltError::UnsupportedVertexPropertyKind(kind)),
+        })
+    }
+
+    #[must_use]
+    pub fn kind(&self) -> PropKind {
+        match self {
+            Self::Bool(_) => PropKind::Bool,
+            Self::I8(_) => PropKind::I8,
+            Self::U8(_) => PropKind::U8,
+            Self::I32(_) => PropKind::I32,
+            Self::U32(_) => PropKind::U32,
+            Self::I64(_) => PropKind::I64,
+            Self::U64(_) => PropKind::U64,
+            Self::F32(_) => PropKind::F32,
+            Self::F64(_) => PropKind::F64,
         }
     }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        match self {
+            Self::Bool(v) => v.len(),
+            Self::I8(v) => v.len(),
+            Self::U8(v) => v.len(),
+            Self::I32(v) => v.len(),
+            Self::U32(v) => v.len(),
+            Self::I64(v) => v.len(),
+            Self::U64(v) => v.len(),
+            Self::F32(v) => v.len(),
+            Self::F64(v) => v.len(),
+        }
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, strum::IntoStaticStr)]
@@ -548,6 +1016,32 @@ pub enum PropKind {
     F32,
     F64,
     Str,
+    Nested,
+    Date,
+    Timestamp,
+    Json,
+    Binary,
+    RangeMap,
+}
+
+impl PropKind {
+    /// Whether values of this kind can be stored in a [`PropValue::RangeMap`].
+    #[must_use]
+    pub fn is_range_map_value(self) -> bool {
+        matches!(
+            self,
+            Self::Bool
+                | Self::I8
+                | Self::U8
+                | Self::I32
+                | Self::U32
+                | Self::I64
+                | Self::U64
+                | Self::F32
+                | Self::F64
+                | Self::Str
+        )
+    }
 }
 
 fn validate_layer_name(name: &str) -> MltResult<()> {
@@ -610,6 +1104,12 @@ impl From<&PropValue> for PropKind {
             PropValue::F32(_) => Self::F32,
             PropValue::F64(_) => Self::F64,
             PropValue::Str(_) => Self::Str,
+            PropValue::Nested(_) => Self::Nested,
+            PropValue::Date(_) => Self::Date,
+            PropValue::Timestamp(_) => Self::Timestamp,
+            PropValue::Json(_) => Self::Json,
+            PropValue::Binary(_) => Self::Binary,
+            PropValue::RangeMap(_) => Self::RangeMap,
         }
     }
 }
@@ -624,10 +1124,28 @@ mod tests {
         TileFeature {
             id: None,
             geometry: Geometry::Point(Point::new(0, 0)),
+            z: None,
             properties,
+            vertex_properties: Vec::new(),
         }
     }
 
+    #[test]
+    fn set_z_requires_one_value_per_coordinate() {
+        let mut feature = point_feature(vec![]);
+        assert!(matches!(
+            feature.set_z(Some(vec![1, 2])),
+            Err(MltError::VertexZLengthMismatch {
+                expected: 1,
+                actual: 2
+            })
+        ));
+        feature.set_z(Some(vec![5])).unwrap();
+        assert_eq!(feature.z(), Some([5].as_slice()));
+        feature.set_z(None).unwrap();
+        assert_eq!(feature.z(), None);
+    }
+
     #[test]
     fn tile_layer_constructor_rejects_empty_name() {
         assert!(matches!(
@@ -726,4 +1244,45 @@ mod tests {
             PropValue::Bool(Some(true))
         );
     }
+
+    #[test]
+    fn vertex_property_shares_namespace_with_properties() {
+        let mut layer = TileLayer::new("layer", 4096).unwrap();
+        layer.add_property("speed", PropKind::F32).unwrap();
+        assert!(matches!(
+            layer.add_vertex_property("speed", PropKind::F32),
+            Err(MltError::DuplicatePropertyName(name)) if name == "speed"
+        ));
+        assert!(matches!(
+            layer.add_vertex_property("label", PropKind::Str),
+            Err(MltError::UnsupportedVertexPropertyKind(PropKind::Str))
+        ));
+    }
+
+    #[test]
+    fn builder_vertex_property_requires_one_value_per_stored_vertex() {
+        let mut builder = TileLayer::builder("layer", 4096).unwrap();
+        let speed = builder.add_vertex_property("speed", PropKind::F32).unwrap();
+        let ring = geo_types::LineString::from(vec![(0, 0), (4, 0), (4, 4), (0, 0)]);
+        let mut feature = builder.feature(Geometry::Polygon(geo_types::Polygon::new(ring, vec![])));
+        assert!(matches!(
+            feature.vertex_property(speed, VertexValues::F32(vec![1.0; 4])),
+            Err(MltError::VertexPropertyLengthMismatch {
+                index: 0,
+                expected: 3,
+                actual: 4
+            })
+        ));
+        feature
+            .vertex_property(speed, VertexValues::F32(vec![1.0, 2.0, 3.0]))
+            .unwrap();
+        feature.finish().unwrap();
+        let layer = builder.finish();
+
+        assert_eq!(layer.vertex_property_names(), ["speed"]);
+        assert_eq!(
+            layer.features()[0].vertex_properties(),
+            [VertexValues::F32(vec![1.0, 2.0, 3.0])]
+        );
+    }
 }
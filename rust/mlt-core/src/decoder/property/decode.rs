@@ -84,15 +84,18 @@ impl<'a> Decode<ParsedProperty<'a>> for RawProperty<'a> {
                 P::U64(S::from_parts(v.name, v.presence, vals, dec)?)
             }
             Self::F32(v) => {
-                let vals = v.data.decode_floats::<f32>(dec)?;
+                let vals = v.encoding.decode_values::<f32>(dec)?;
                 P::F32(S::from_parts(v.name, v.presence, vals, dec)?)
             }
             Self::F64(v) => {
-                let vals = v.data.decode_floats::<f64>(dec)?;
+                let vals = v.encoding.decode_values::<f64>(dec)?;
                 P::F64(S::from_parts(v.name, v.presence, vals, dec)?)
             }
             Self::Str(v) => P::Str(v.decode(dec)?),
             Self::SharedDict(v) => P::SharedDict(v.decode(dec)?),
+            Self::Nested(v) => P::Nested(v.decode(dec)?),
+            Self::RangeMap(v) => P::RangeMap(v.decode(dec)?),
+            Self::Struct(v) => P::Struct(v.decode(dec)?),
         })
     }
 }
@@ -0,0 +1,45 @@
+use num_traits::AsPrimitive;
+use usize_cast::IntoUsize as _;
+
+use crate::MltError::DictIndexOutOfBounds;
+use crate::decoder::stream::DecodeFloat;
+use crate::decoder::{RawFloats, RawFloatsEncoding, RawPresence};
+use crate::{Decoder, MltResult};
+
+impl<'a> RawFloats<'a> {
+    #[must_use]
+    pub fn new(name: &'a str, presence: RawPresence<'a>, encoding: RawFloatsEncoding<'a>) -> Self {
+        Self {
+            name,
+            presence,
+            encoding,
+        }
+    }
+}
+
+impl RawFloatsEncoding<'_> {
+    /// Decode one value per present feature, resolving dictionary offsets if needed.
+    pub fn decode_values<T: DecodeFloat>(self, dec: &mut Decoder) -> MltResult<Vec<T>>
+    where
+        f64: AsPrimitive<T>,
+    {
+        match self {
+            Self::Plain(data) => data.decode_floats(dec),
+            Self::Dictionary {
+                offsets,
+                dictionary,
+            } => {
+                let dictionary: Vec<T> = dictionary.decode_floats(dec)?;
+                let offsets: Vec<u32> = offsets.decode_ints(dec)?;
+                let mut values = dec.alloc::<T>(offsets.len())?;
+                for idx in offsets {
+                    let value = dictionary
+                        .get(idx.into_usize())
+                        .ok_or(DictIndexOutOfBounds(idx, dictionary.len()))?;
+                    values.push(*value);
+                }
+                Ok(values)
+            }
+        }
+    }
+}
@@ -1,5 +1,9 @@
 mod decode;
+mod floats;
 mod model;
+pub(crate) mod nested;
+mod range_map;
 pub(crate) mod strings;
+mod structs;
 
 pub use model::*;
@@ -3,7 +3,7 @@ use std::ops::Deref;
 
 use enum_dispatch::enum_dispatch;
 
-use crate::decoder::RawStream;
+use crate::decoder::{NestedValue, PropValue, RawStream};
 use crate::utils::Presence;
 use crate::{DecodeState, Lazy};
 
@@ -22,6 +22,26 @@ pub struct RawScalar<'a> {
     pub(crate) data: RawStream<'a>,
 }
 
+/// Raw float column (`f32` or `f64`) as read directly from the tile.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RawFloats<'a> {
+    pub(crate) name: &'a str,
+    pub(crate) presence: RawPresence<'a>,
+    pub(crate) encoding: RawFloatsEncoding<'a>,
+}
+
+/// Raw encoding payload for a float column.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RawFloatsEncoding<'a> {
+    /// One data stream holding every present value (raw, RLE or pseudo-decimal)
+    Plain(RawStream<'a>),
+    /// Dictionary: per-value offsets + distinct values
+    Dictionary {
+        offsets: RawStream<'a>,
+        dictionary: RawStream<'a>,
+    },
+}
+
 /// Raw string column as read directly from the tile.
 #[derive(Debug, Clone, PartialEq)]
 pub struct RawStrings<'a> {
@@ -82,10 +102,13 @@ pub enum RawProperty<'a> {
     U32(RawScalar<'a>),
     I64(RawScalar<'a>),
     U64(RawScalar<'a>),
-    F32(RawScalar<'a>),
-    F64(RawScalar<'a>),
+    F32(RawFloats<'a>),
+    F64(RawFloats<'a>),
     Str(RawStrings<'a>),
     SharedDict(RawSharedDict<'a>),
+    Nested(RawNested<'a>),
+    RangeMap(RawRangeMap<'a>),
+    Struct(RawStruct<'a>),
 }
 
 /// Parsed property values in a typed enum form.
@@ -104,6 +127,9 @@ pub enum ParsedProperty<'a> {
     F64(ParsedScalar<'a, f64>),
     Str(ParsedStrings<'a>),
     SharedDict(ParsedSharedDict<'a>),
+    Nested(ParsedNested<'a>),
+    RangeMap(ParsedRangeMap<'a>),
+    Struct(ParsedStruct<'a>),
 }
 
 /// Decoded scalar property column (bool, integer, or float).
@@ -177,6 +203,30 @@ pub struct ParsedStrings<'a> {
     pub(crate) data: Cow<'a, str>,
 }
 
+/// Raw `RangeMap` column: range counts, interleaved range bounds and a value column.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RawRangeMap<'a> {
+    pub name: &'a str,
+    pub presence: RawPresence<'a>,
+    /// Number of ranges per present feature.
+    pub lengths: RawStream<'a>,
+    /// `[start, end]` pairs of doubles, one pair per range.
+    pub ranges: RawStream<'a>,
+    /// One value per range; a bool, integer, float or string column.
+    pub values: Box<RawProperty<'a>>,
+}
+
+/// Parsed `RangeMap` values for a single property.
+///
+/// `lengths` uses the same cumulative end-offset encoding as [`ParsedStrings::lengths`],
+/// counting entries instead of bytes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParsedRangeMap<'a> {
+    pub(crate) name: &'a str,
+    pub(crate) lengths: Vec<i32>,
+    pub(crate) entries: Vec<(f64, f64, PropValue)>,
+}
+
 /// Parsed shared dictionary payload shared by one or more child string properties.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ParsedSharedDict<'a> {
@@ -193,6 +243,100 @@ pub struct RawSharedDictItem<'a> {
     pub data: RawStream<'a>,
 }
 
+/// Raw struct column: named children of mixed scalar types under a common prefix.
+///
+/// String children share a single dictionary when `dictionary` is present, exactly like
+/// the items of a [`RawSharedDict`]; all other children are regular columns named by
+/// their suffix.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RawStruct<'a> {
+    pub name: &'a str,
+    pub dictionary: Option<RawSharedDictEncoding<'a>>,
+    pub children: Vec<RawStructChild<'a>>,
+}
+
+/// A single child of a [`RawStruct`], in column order.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RawStructChild<'a> {
+    /// String child referencing the struct's shared dictionary.
+    Shared(RawSharedDictItem<'a>),
+    /// Any other child, stored as a regular column.
+    Column(RawProperty<'a>),
+}
+
+/// Parsed struct column, see [`RawStruct`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParsedStruct<'a> {
+    pub(crate) prefix: &'a str,
+    /// Shared dictionary of the string children, `None` if there are none.
+    pub(crate) strings: Option<ParsedSharedDict<'a>>,
+    pub(crate) children: Vec<ParsedStructChild<'a>>,
+}
+
+/// A single child of a [`ParsedStruct`], in column order.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParsedStructChild<'a> {
+    /// Index into the items of [`ParsedStruct::strings`].
+    Shared(usize),
+    /// Any other child; its name is the suffix.
+    Column(ParsedProperty<'a>),
+}
+
+/// Raw nested-field (JSON-like map / list) column as read directly from the tile.
+///
+/// A nested column either holds its values directly (no `children`), or
+/// shares its dictionaries between several child columns named
+/// `"{name}{child}"`, just like a `SharedDict`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RawNested<'a> {
+    pub name: &'a str,
+    pub children: Vec<&'a str>,
+    /// `None` if the column was written with zero streams, i.e. every value is null.
+    pub body: Option<Box<RawNestedBody<'a>>>,
+}
+
+/// Streams of a non-empty nested column, in wire order.
+///
+/// Every dictionary is optional and announced by the leading mask byte.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RawNestedBody<'a> {
+    /// Number of control/dictionary values per present feature.
+    pub lengths: RawStream<'a>,
+    pub strings: Option<RawStringsEncoding<'a>>,
+    pub ints: Option<RawNestedInts<'a>>,
+    pub uints: Option<RawNestedInts<'a>>,
+    pub floats: Option<RawStream<'a>>,
+    pub doubles: Option<RawStream<'a>>,
+    /// One bit per (child, feature) pair, grouped by child.
+    pub presence: Option<RawStream<'a>>,
+    /// Flattened control values and dictionary indices.
+    pub values: Option<RawStream<'a>>,
+}
+
+/// Integer dictionary stream of a nested column, with its physical width.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RawNestedInts<'a> {
+    Int32(RawStream<'a>),
+    Int64(RawStream<'a>),
+}
+
+/// Decoded nested-field column: one value list per child column.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParsedNested<'a> {
+    pub(crate) prefix: &'a str,
+    pub(crate) items: Vec<ParsedNestedItem<'a>>,
+}
+
+/// A single child of a parsed nested column.
+///
+/// A nested column without children is represented by one item with an empty suffix.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParsedNestedItem<'a> {
+    pub(crate) suffix: &'a str,
+    /// Per-feature values; may be shorter than the feature count for an all-null column.
+    pub(crate) values: Vec<Option<NestedValue>>,
+}
+
 /// Raw plain data (length stream + data stream) borrowed from input bytes.
 #[derive(Debug, Clone, PartialEq)]
 pub struct RawPlainData<'a> {
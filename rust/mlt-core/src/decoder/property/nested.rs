@@ -0,0 +1,304 @@
+use usize_cast::IntoUsize as _;
+
+use crate::MltError::{DictIndexOutOfBounds, InvalidNestedPayload};
+use crate::decoder::{
+    NestedValue, ParsedNested, ParsedNestedItem, RawNested, RawNestedBody, RawNestedInts,
+    RawPresence, RawStream, RawStrings, RawStringsEncoding,
+};
+use crate::errors::AsMltError as _;
+use crate::{Decoder, MltResult};
+
+/// Bits of the mask byte that announces which optional streams a nested column carries.
+pub(crate) struct NestedMask;
+
+impl NestedMask {
+    pub const STRING: u8 = 1;
+    pub const INT32: u8 = 2;
+    pub const UINT32: u8 = 4;
+    pub const INT64: u8 = 8;
+    pub const UINT64: u8 = 16;
+    pub const FLOAT: u8 = 32;
+    pub const DOUBLE: u8 = 64;
+    pub const PRESENCE: u8 = 128;
+}
+
+/// Control values of the flattened nested value stream.
+/// Any larger value is an index into the concatenated dictionaries, offset by [`Self::COUNT`].
+pub(crate) struct NestedToken;
+
+impl NestedToken {
+    pub const FALSE: u32 = 0;
+    pub const TRUE: u32 = 1;
+    pub const START_MAP: u32 = 2;
+    pub const START_LIST: u32 = 3;
+    pub const COUNT: u32 = 4;
+}
+
+/// Maximum map/list nesting accepted by the decoder, to bound recursion on malformed input.
+pub(crate) const MAX_NESTED_DEPTH: usize = 64;
+
+impl<'a> RawNested<'a> {
+    /// Decode all child columns, charging `dec` for every value allocation.
+    pub fn decode(self, dec: &mut Decoder) -> MltResult<ParsedNested<'a>> {
+        let suffixes = if self.children.is_empty() {
+            vec![""]
+        } else {
+            self.children
+        };
+        let Some(body) = self.body else {
+            let items = suffixes
+                .into_iter()
+                .map(|suffix| ParsedNestedItem {
+                    suffix,
+                    values: Vec::new(),
+                })
+                .collect();
+            return Ok(ParsedNested {
+                prefix: self.name,
+                items,
+            });
+        };
+
+        let RawNestedBody {
+            lengths,
+            strings,
+            ints,
+            uints,
+            floats,
+            doubles,
+            presence,
+            values,
+        } = *body;
+        let lengths = lengths.decode_ints::<u32>(dec)?;
+        let dict = decode_dictionaries(strings, ints, uints, floats, doubles, dec)?;
+        let presence = presence.map(|s| s.decode_bools(dec)).transpose()?;
+        let tokens = values
+            .map(|s| s.decode_ints::<u32>(dec))
+            .transpose()?
+            .unwrap_or_default();
+
+        let total = presence.as_ref().map_or(lengths.len(), Vec::len);
+        let feature_count = total / suffixes.len();
+        let reader = TokenReader {
+            tokens: &tokens,
+            dict: &dict,
+        };
+
+        let mut lengths = lengths.iter();
+        let mut pos = 0_usize;
+        let mut items = Vec::with_capacity(suffixes.len());
+        for (child_idx, suffix) in suffixes.into_iter().enumerate() {
+            let mut values = dec.alloc(feature_count)?;
+            for feat_idx in 0..feature_count {
+                if let Some(bits) = &presence
+                    && !bits[child_idx * feature_count + feat_idx]
+                {
+                    values.push(None);
+                    continue;
+                }
+                let count = lengths
+                    .next()
+                    .ok_or(InvalidNestedPayload("length stream underflow"))?
+                    .into_usize();
+                let end = pos
+                    .checked_add(count)
+                    .filter(|&end| end <= tokens.len())
+                    .ok_or(InvalidNestedPayload("value stream underflow"))?;
+                values.push(Some(reader.root(pos, end, dec)?));
+                pos = end;
+            }
+            items.push(ParsedNestedItem { suffix, values });
+        }
+        if lengths.next().is_some() || pos != tokens.len() {
+            return Err(InvalidNestedPayload("unused values remain after decode"));
+        }
+
+        Ok(ParsedNested {
+            prefix: self.name,
+            items,
+        })
+    }
+}
+
+/// Decode the per-type dictionaries into a single scalar lookup table,
+/// in the order the value stream indexes them.
+fn decode_dictionaries(
+    strings: Option<RawStringsEncoding<'_>>,
+    ints: Option<RawNestedInts<'_>>,
+    uints: Option<RawNestedInts<'_>>,
+    floats: Option<RawStream<'_>>,
+    doubles: Option<RawStream<'_>>,
+    dec: &mut Decoder,
+) -> MltResult<Vec<NestedValue>> {
+    let mut dict = Vec::new();
+    if let Some(encoding) = strings {
+        let strings = RawStrings::new("", RawPresence::AllPresent, encoding).decode(dec)?;
+        dict.extend(strings.dense_values().into_iter().map(NestedValue::Str));
+    }
+    match ints {
+        Some(RawNestedInts::Int32(s)) => dict.extend(
+            s.decode_ints::<i32>(dec)?
+                .into_iter()
+                .map(|v| NestedValue::I64(i64::from(v))),
+        ),
+        Some(RawNestedInts::Int64(s)) => {
+            dict.extend(s.decode_ints::<i64>(dec)?.into_iter().map(NestedValue::I64));
+        }
+        None => {}
+    }
+    match uints {
+        Some(RawNestedInts::Int32(s)) => dict.extend(
+            s.decode_ints::<u32>(dec)?
+                .into_iter()
+                .map(|v| NestedValue::U64(u64::from(v))),
+        ),
+        Some(RawNestedInts::Int64(s)) => {
+            dict.extend(s.decode_ints::<u64>(dec)?.into_iter().map(NestedValue::U64));
+        }
+        None => {}
+    }
+    if let Some(s) = floats {
+        dict.extend(
+            s.decode_floats::<f32>(dec)?
+                .into_iter()
+                .map(NestedValue::F32),
+        );
+    }
+    if let Some(s) = doubles {
+        dict.extend(
+            s.decode_floats::<f64>(dec)?
+                .into_iter()
+                .map(NestedValue::F64),
+        );
+    }
+    dec.consume_items::<NestedValue>(dict.len())?;
+    Ok(dict)
+}
+
+/// Rebuilds [`NestedValue`] trees from a slice of the flattened value stream.
+struct TokenReader<'v> {
+    tokens: &'v [u32],
+    dict: &'v [NestedValue],
+}
+
+impl TokenReader<'_> {
+    /// Decode the value of one feature stored in `tokens[start..end]`.
+    ///
+    /// A single token is a root scalar, a leading `START_LIST` is a root list,
+    /// and anything else is the entry list of a root map.
+    fn root(&self, start: usize, end: usize, dec: &mut Decoder) -> MltResult<NestedValue> {
+        if end - start == 1 || self.tokens[start..end].first() == Some(&NestedToken::START_LIST) {
+            let (value, next) = self.value(start, end, 0, dec)?;
+            if next != end {
+                return Err(InvalidNestedPayload(
+                    "root value did not consume its payload",
+                ));
+            }
+            Ok(value)
+        } else {
+            Ok(NestedValue::Map(self.map_entries(start, end, 0, dec)?))
+        }
+    }
+
+    fn value(
+        &self,
+        start: usize,
+        end: usize,
+        depth: usize,
+        dec: &mut Decoder,
+    ) -> MltResult<(NestedValue, usize)> {
+        let &token = self.tokens[..end]
+            .get(start)
+            .ok_or(InvalidNestedPayload("unexpected end of value stream"))?;
+        match token {
+            NestedToken::FALSE => Ok((NestedValue::Bool(false), start + 1)),
+            NestedToken::TRUE => Ok((NestedValue::Bool(true), start + 1)),
+            NestedToken::START_MAP | NestedToken::START_LIST => {
+                if depth >= MAX_NESTED_DEPTH {
+                    return Err(InvalidNestedPayload("nesting too deep"));
+                }
+                let &len = self.tokens[..end]
+                    .get(start + 1)
+                    .ok_or(InvalidNestedPayload("missing nested payload length"))?;
+                let value_end = start
+                    .checked_add(len.into_usize())
+                    .filter(|&e| len >= 2 && e <= end)
+                    .ok_or(InvalidNestedPayload("invalid nested payload length"))?;
+                let value = if token == NestedToken::START_MAP {
+                    NestedValue::Map(self.map_entries(start + 2, value_end, depth + 1, dec)?)
+                } else {
+                    let mut items = Vec::new();
+                    let mut pos = start + 2;
+                    while pos < value_end {
+                        let item;
+                        (item, pos) = self.value(pos, value_end, depth + 1, dec)?;
+                        items.push(item);
+                    }
+                    dec.consume_items::<NestedValue>(items.len())?;
+                    NestedValue::List(items)
+                };
+                Ok((value, value_end))
+            }
+            _ => Ok((self.scalar(token, dec)?, start + 1)),
+        }
+    }
+
+    fn map_entries(
+        &self,
+        start: usize,
+        end: usize,
+        depth: usize,
+        dec: &mut Decoder,
+    ) -> MltResult<Vec<(String, NestedValue)>> {
+        let mut entries = Vec::new();
+        let mut pos = start;
+        while pos < end {
+            let NestedValue::Str(key) = self.scalar(self.tokens[pos], dec)? else {
+                return Err(InvalidNestedPayload("map key is not a string"));
+            };
+            let value;
+            (value, pos) = self.value(pos + 1, end, depth, dec)?;
+            entries.push((key, value));
+        }
+        dec.consume_items::<(String, NestedValue)>(entries.len())?;
+        Ok(entries)
+    }
+
+    fn scalar(&self, token: u32, dec: &mut Decoder) -> MltResult<NestedValue> {
+        let value = token
+            .checked_sub(NestedToken::COUNT)
+            .and_then(|idx| self.dict.get(idx.into_usize()))
+            .ok_or(DictIndexOutOfBounds(token, self.dict.len()))?;
+        if let NestedValue::Str(s) = value {
+            dec.consume(u32::try_from(s.len()).or_overflow()?)?;
+        }
+        Ok(value.clone())
+    }
+}
+
+impl<'a> ParsedNested<'a> {
+    /// The column name, or the common prefix of all child column names.
+    #[must_use]
+    pub fn prefix(&self) -> &'a str {
+        self.prefix
+    }
+
+    #[must_use]
+    pub fn items(&self) -> &[ParsedNestedItem<'a>] {
+        &self.items
+    }
+}
+
+impl<'a> ParsedNestedItem<'a> {
+    /// The child name appended to the parent prefix; empty for a column without children.
+    #[must_use]
+    pub fn suffix(&self) -> &'a str {
+        self.suffix
+    }
+
+    /// The value of feature `i`, or `None` if it is null.
+    #[must_use]
+    pub fn get(&self, i: usize) -> Option<&NestedValue> {
+        self.values.get(i).and_then(Option::as_ref)
+    }
+}
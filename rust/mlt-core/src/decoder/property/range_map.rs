@@ -0,0 +1,71 @@
+use usize_cast::IntoUsize as _;
+
+use crate::MltError::RangeMapLengthMismatch;
+use crate::decoder::strings::to_absolute_lengths;
+use crate::decoder::{ParsedProperty, ParsedRangeMap, PropValue, RawRangeMap};
+use crate::{Decode as _, Decoder, MltResult};
+
+impl<'a> RawRangeMap<'a> {
+    /// Decode a range map property from its encoded column.
+    pub fn decode(self, dec: &mut Decoder) -> MltResult<ParsedRangeMap<'a>> {
+        let presence = self.presence.decode_bools(dec)?;
+        let counts: Vec<u32> = self.lengths.decode_ints(dec)?;
+        let lengths = to_absolute_lengths(&counts, presence.as_deref(), dec)?;
+        let ranges: Vec<f64> = self.ranges.decode_floats(dec)?;
+        let values = range_values((*self.values).decode(dec)?, dec)?;
+
+        let range_count = counts.iter().map(|&c| c.into_usize()).sum::<usize>();
+        if ranges.len() != range_count * 2 || values.len() != range_count {
+            return Err(RangeMapLengthMismatch {
+                ranges: range_count,
+                bounds: ranges.len(),
+                values: values.len(),
+            });
+        }
+        let mut entries = dec.alloc(range_count)?;
+        entries.extend(
+            ranges
+                .chunks_exact(2)
+                .zip(values)
+                .map(|(bounds, value)| (bounds[0], bounds[1], value)),
+        );
+        Ok(ParsedRangeMap {
+            name: self.name,
+            lengths,
+            entries,
+        })
+    }
+}
+
+/// Flatten the decoded value column of a range map, one [`PropValue`] per range.
+fn range_values(values: ParsedProperty<'_>, dec: &mut Decoder) -> MltResult<Vec<PropValue>> {
+    use ParsedProperty as P;
+
+    macro_rules! scalars {
+        ($values:expr, $variant:ident) => {{
+            let mut out = dec.alloc($values.feature_count())?;
+            out.extend($values.iter_optional().map(PropValue::$variant));
+            out
+        }};
+    }
+
+    Ok(match values {
+        P::Bool(v) => scalars!(v, Bool),
+        P::I8(v) => scalars!(v, I8),
+        P::U8(v) => scalars!(v, U8),
+        P::I32(v) => scalars!(v, I32),
+        P::U32(v) => scalars!(v, U32),
+        P::I64(v) => scalars!(v, I64),
+        P::U64(v) => scalars!(v, U64),
+        P::F32(v) => scalars!(v, F32),
+        P::F64(v) => scalars!(v, F64),
+        P::Str(v) => {
+            let mut out = dec.alloc(v.feature_count())?;
+            out.extend(v.materialize().into_iter().map(PropValue::Str));
+            out
+        }
+        P::SharedDict(_) | P::Nested(_) | P::RangeMap(_) | P::Struct(_) => {
+            unreachable!("range map value columns are validated when parsing")
+        }
+    })
+}
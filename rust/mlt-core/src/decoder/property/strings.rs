@@ -324,7 +324,7 @@ impl<'a> RawStrings<'a> {
     }
 }
 
-fn to_absolute_lengths(
+pub(crate) fn to_absolute_lengths(
     lengths: &[u32],
     presence: Option<&[bool]>,
     dec: &mut Decoder,
@@ -395,7 +395,7 @@ pub(crate) fn encode_null_end(end: i32) -> i32 {
     -end - 1
 }
 
-fn decode_end(end: i32) -> u32 {
+pub(crate) fn decode_end(end: i32) -> u32 {
     if end >= 0 {
         u32::try_from(end).expect("non-negative decoded string end must fit in u32")
     } else {
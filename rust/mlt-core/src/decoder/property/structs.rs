@@ -0,0 +1,29 @@
+use crate::decoder::{ParsedStruct, ParsedStructChild, RawStruct, RawStructChild};
+use crate::{Decode as _, Decoder, MltResult, RawSharedDict};
+
+impl<'a> RawStruct<'a> {
+    /// Decode a struct column: the shared string dictionary and every child column.
+    pub fn decode(self, dec: &mut Decoder) -> MltResult<ParsedStruct<'a>> {
+        let mut shared = Vec::new();
+        let mut children = Vec::with_capacity(self.children.len());
+        for child in self.children {
+            children.push(match child {
+                RawStructChild::Shared(item) => {
+                    shared.push(item);
+                    ParsedStructChild::Shared(shared.len() - 1)
+                }
+                RawStructChild::Column(column) => ParsedStructChild::Column(column.decode(dec)?),
+            });
+        }
+        // Shared children are only parsed when the dictionary is present
+        let strings = match self.dictionary {
+            Some(encoding) => Some(RawSharedDict::new(self.name, encoding, shared).decode(dec)?),
+            None => None,
+        };
+        Ok(ParsedStruct {
+            prefix: self.name,
+            strings,
+            children,
+        })
+    }
+}
@@ -2,20 +2,23 @@ use usize_cast::IntoUsize as _;
 
 use crate::LazyParsed::Raw;
 use crate::MltError::{
-    BufferUnderflow, GeometryWithoutStreams, InvalidSharedDictStreamCount, MissingGeometry,
-    MissingLayerName, MultipleGeometryColumns, MultipleIdColumns, SharedDictRequiresStreams,
-    TrailingLayerData, UnexpectedStructChildCount, UnsupportedStringStreamCount,
+    BufferUnderflow, GeometryWithoutStreams, InvalidNestedStreamCount, InvalidRangeMapValueColumn,
+    InvalidSharedDictStreamCount, InvalidStructChildColumn, InvalidVertexScopedColumn,
+    MissingGeometry, MissingLayerName, MultipleGeometryColumns, MultipleIdColumns,
+    RangeMapValueColumnCount, SharedDictRequiresStreams, TrailingLayerData, UnexpectedStreamType,
+    UnexpectedStructChildCount, UnsupportedStringStreamCount, VertexPropertyLengthMismatch,
 };
 use crate::codecs::varint::parse_varint;
 use crate::decoder::stream::header01;
 use crate::decoder::{
-    Column, ColumnType, DictionaryType, Extent, Geometry, Id, Layer01, ParsedLayer01, RawFsstData,
-    RawGeometry, RawId, RawIdValue, RawPlainData, RawPresence, RawProperty, RawScalar,
-    RawSharedDict, RawSharedDictEncoding, RawSharedDictItem, RawStream, RawStrings,
-    RawStringsEncoding, StreamType,
+    ColNames as _, Column, ColumnType, DictionaryType, Extent, Geometry, Id, Layer01, NestedMask,
+    OffsetType, ParsedLayer01, Property, RawFloats, RawFloatsEncoding, RawFsstData, RawGeometry,
+    RawId, RawIdValue, RawNested, RawNestedBody, RawNestedInts, RawPlainData, RawPresence,
+    RawProperty, RawRangeMap, RawScalar, RawSharedDict, RawSharedDictEncoding, RawSharedDictItem,
+    RawStream, RawStrings, RawStringsEncoding, RawStruct, RawStructChild, StreamType,
 };
 use crate::errors::AsMltError as _;
-use crate::utils::{SetOptionOnce as _, parse_string};
+use crate::utils::{SetOptionOnce as _, parse_string, parse_u8};
 use crate::{Layer, Lazy, MltError, MltRefResult, MltResult, ParsedLayer};
 
 /// Default memory budget: 20 MiB.
@@ -261,11 +264,12 @@ impl MemBudget {
 
 impl<'a> Layer01<'a, Lazy> {
     /// Parse `v01::Layer` metadata, reserving decoded memory against the parser's budget.
-    pub(crate) fn from_bytes(input: &'a [u8], parser: &mut Parser) -> MltResult<Self> {
+    pub(crate) fn from_bytes(tag: u8, input: &'a [u8], parser: &mut Parser) -> MltResult<Self> {
         let (input, layer_name) = parse_string(input)?;
         if layer_name.is_empty() {
             return Err(MissingLayerName);
         }
+        let body = input;
         let (input, extent) = parse_varint::<u32>(input)?;
         let extent = Extent::new(extent)?;
         let (input, column_count) = parse_varint::<u32>(input)?;
@@ -286,15 +290,13 @@ impl<'a> Layer01<'a, Lazy> {
             .collect();
 
         let mut properties = Vec::with_capacity(prop_count.into_usize());
+        let mut vertex_properties = Vec::new();
         let mut id_column: Option<Id> = None;
         let mut geometry: Option<Geometry> = None;
 
         for column in col_info {
-            use crate::decoder::RawProperty as RP;
-
             let presence;
             let value;
-            let name = column.name.unwrap_or("");
 
             match column.typ {
                 ColumnType::Id | ColumnType::OptId => {
@@ -313,72 +315,33 @@ impl<'a> Layer01<'a, Lazy> {
                         value: RawIdValue::Id64(value),
                     }))?;
                 }
-                ColumnType::Geometry => {
-                    input = parse_geometry_column(input, &mut geometry, parser)?;
-                }
-                ColumnType::Bool | ColumnType::OptBool => {
-                    (input, presence) = parse_optional(column.typ, input, parser)?;
-                    (input, value) = header01::parse_bool_stream(input, parser)?;
-                    properties.push(Raw(RP::Bool(RawScalar::new(name, presence, value))));
-                }
-                ColumnType::I8 | ColumnType::OptI8 => {
-                    (input, presence) = parse_optional(column.typ, input, parser)?;
-                    (input, value) = header01::parse_stream(input, parser)?;
-                    properties.push(Raw(RP::I8(RawScalar::new(name, presence, value))));
-                }
-                ColumnType::U8 | ColumnType::OptU8 => {
-                    (input, presence) = parse_optional(column.typ, input, parser)?;
-                    (input, value) = header01::parse_stream(input, parser)?;
-                    properties.push(Raw(RP::U8(RawScalar::new(name, presence, value))));
-                }
-                ColumnType::I32 | ColumnType::OptI32 => {
-                    (input, presence) = parse_optional(column.typ, input, parser)?;
-                    (input, value) = header01::parse_stream(input, parser)?;
-                    properties.push(Raw(RP::I32(RawScalar::new(name, presence, value))));
-                }
-                ColumnType::U32 | ColumnType::OptU32 => {
-                    (input, presence) = parse_optional(column.typ, input, parser)?;
-                    (input, value) = header01::parse_stream(input, parser)?;
-                    properties.push(Raw(RP::U32(RawScalar::new(name, presence, value))));
-                }
-                ColumnType::I64 | ColumnType::OptI64 => {
-                    (input, presence) = parse_optional(column.typ, input, parser)?;
-                    (input, value) = header01::parse_stream(input, parser)?;
-                    properties.push(Raw(RP::I64(RawScalar::new(name, presence, value))));
-                }
-                ColumnType::U64 | ColumnType::OptU64 => {
-                    (input, presence) = parse_optional(column.typ, input, parser)?;
-                    (input, value) = header01::parse_stream(input, parser)?;
-                    properties.push(Raw(RP::U64(RawScalar::new(name, presence, value))));
-                }
-                ColumnType::F32 | ColumnType::OptF32 => {
-                    (input, presence) = parse_optional(column.typ, input, parser)?;
-                    (input, value) = header01::parse_stream(input, parser)?;
-                    properties.push(Raw(RP::F32(RawScalar::new(name, presence, value))));
+                ColumnType::Geometry | ColumnType::GeometryZ => {
+                    let has_z = column.typ == ColumnType::GeometryZ;
+                    input = parse_geometry_column(input, has_z, &mut geometry, parser)?;
                 }
-                ColumnType::F64 | ColumnType::OptF64 => {
-                    (input, presence) = parse_optional(column.typ, input, parser)?;
-                    (input, value) = header01::parse_stream(input, parser)?;
-                    properties.push(Raw(RP::F64(RawScalar::new(name, presence, value))));
+                ColumnType::VertexScope => {
+                    for child in &column.children {
+                        let prop;
+                        (input, prop) = parse_property_column(input, child, parser)?;
+                        vertex_properties.push(Raw(prop));
+                    }
                 }
-                ColumnType::Str | ColumnType::OptStr => {
+                _ => {
                     let prop;
-                    (input, prop) = parse_str_column(input, name, column.typ, parser)?;
-                    properties.push(Raw(prop));
-                }
-                ColumnType::SharedDict => {
-                    let prop;
-                    (input, prop) = parse_shared_dict_column(input, &column, parser)?;
+                    (input, prop) = parse_property_column(input, &column, parser)?;
                     properties.push(Raw(prop));
                 }
             }
         }
         if input.is_empty() {
             Ok(Layer01 {
+                tag,
                 name: layer_name,
+                body,
                 extent,
                 id: id_column,
                 geometry: geometry.ok_or(MissingGeometry)?,
+                vertex_properties,
                 properties,
                 #[cfg(fuzzing)]
                 layer_order,
@@ -393,14 +356,71 @@ impl<'a> Layer01<'a, Lazy> {
     /// Consumes `self` (a `Layer01<Lazy>`) and returns a `Layer01<Parsed>` where every
     /// column field holds its parsed value directly, enabling infallible readonly access.
     pub fn decode_all(self, dec: &mut Decoder) -> MltResult<ParsedLayer01<'a>> {
+        self.decode_selected(dec, |_| true)
+    }
+
+    /// Decode the ID, the geometry and only the property columns named in `columns`.
+    ///
+    /// All other property columns are dropped without being decoded or charged against
+    /// `dec`, so the returned layer and its [`iter_features`](ParsedLayer01::iter_features)
+    /// only contain the selected columns, in their original order.
+    ///
+    /// Names are matched like [`Self::iterate_prop_names`] yields them, and vertex-scoped
+    /// columns are selected the same way.  Columns holding several names (`SharedDict`,
+    /// `Nested` and `Struct`) are decoded as a whole when any of their names is selected.
+    /// Names that match no column are ignored.
+    pub fn decode_columns<S: AsRef<str>>(
+        self,
+        dec: &mut Decoder,
+        columns: &[S],
+    ) -> MltResult<ParsedLayer01<'a>> {
+        self.decode_selected(dec, |col| {
+            (0..col.name_count()).any(|idx| {
+                let name = col.name_at(idx);
+                columns.iter().any(|c| name == c.as_ref())
+            })
+        })
+    }
+
+    /// Shared body of [`Self::decode_all`] and [`Self::decode_columns`]: decodes the
+    /// property columns for which `keep` returns `true` and drops the rest.
+    fn decode_selected(
+        self,
+        dec: &mut Decoder,
+        keep: impl Fn(&Property<'a, Lazy>) -> bool,
+    ) -> MltResult<ParsedLayer01<'a>> {
+        let geometry = self.geometry.into_parsed(dec)?;
+        let vertex_count = geometry.vertex_count();
+        let vertex_properties = self
+            .vertex_properties
+            .into_iter()
+            .filter(&keep)
+            .enumerate()
+            .map(|(index, p)| {
+                let p = p.into_parsed(dec)?;
+                let actual = p.vertex_values().map_or(0, |v| v.len());
+                if actual != vertex_count {
+                    return Err(VertexPropertyLengthMismatch {
+                        index,
+                        expected: vertex_count,
+                        actual,
+                    });
+                }
+                Ok(p)
+            })
+            .collect::<MltResult<Vec<_>>>()?;
         Ok(Layer01 {
+            tag: self.tag,
             name: self.name,
+            body: self.body,
             extent: self.extent,
             id: self.id.map(|id| id.into_parsed(dec)).transpose()?,
-            geometry: self.geometry.into_parsed(dec)?,
+            geometry,
+            vertex_properties,
             properties: self
                 .properties
                 .into_iter()
+                .filter(&keep)
                 .map(|p| p.into_parsed(dec))
                 .collect::<MltResult<Vec<_>>>()?,
             #[cfg(fuzzing)]
@@ -409,6 +429,66 @@ impl<'a> Layer01<'a, Lazy> {
     }
 }
 
+/// Parse the streams of a single property column, including vertex-scoped ones.
+fn parse_property_column<'a>(
+    input: &'a [u8],
+    column: &Column<'a>,
+    parser: &mut Parser,
+) -> MltRefResult<'a, RawProperty<'a>> {
+    use crate::decoder::RawProperty as RP;
+
+    let typ = column.typ;
+    let name = column.name.unwrap_or("");
+    let scalar = |input: &'a [u8], parser: &mut Parser| -> MltRefResult<'a, RawScalar<'a>> {
+        let (input, presence) = parse_optional(typ, input, parser)?;
+        let (input, value) = header01::parse_stream(input, parser)?;
+        Ok((input, RawScalar::new(name, presence, value)))
+    };
+
+    Ok(match typ {
+        ColumnType::Bool | ColumnType::OptBool => {
+            let (input, presence) = parse_optional(typ, input, parser)?;
+            let (input, value) = header01::parse_bool_stream(input, parser)?;
+            (input, RP::Bool(RawScalar::new(name, presence, value)))
+        }
+        ColumnType::I8 | ColumnType::OptI8 => map_prop(scalar(input, parser)?, RP::I8),
+        ColumnType::U8 | ColumnType::OptU8 => map_prop(scalar(input, parser)?, RP::U8),
+        ColumnType::I32 | ColumnType::OptI32 => map_prop(scalar(input, parser)?, RP::I32),
+        ColumnType::U32 | ColumnType::OptU32 => map_prop(scalar(input, parser)?, RP::U32),
+        ColumnType::I64 | ColumnType::OptI64 => map_prop(scalar(input, parser)?, RP::I64),
+        ColumnType::U64 | ColumnType::OptU64 => map_prop(scalar(input, parser)?, RP::U64),
+        ColumnType::F32 | ColumnType::OptF32 => {
+            map_prop(parse_float_column(input, name, typ, parser)?, RP::F32)
+        }
+        ColumnType::F64 | ColumnType::OptF64 => {
+            map_prop(parse_float_column(input, name, typ, parser)?, RP::F64)
+        }
+        ColumnType::Str | ColumnType::OptStr => {
+            map_prop(parse_str_column(input, name, typ, parser)?, RP::Str)
+        }
+        ColumnType::RangeMap | ColumnType::OptRangeMap => {
+            map_prop(parse_range_map_column(input, column, parser)?, RP::RangeMap)
+        }
+        ColumnType::Struct => map_prop(parse_struct_column(input, column, parser)?, RP::Struct),
+        ColumnType::SharedDict => parse_shared_dict_column(input, column, parser)?,
+        ColumnType::Nested => parse_nested_column(input, column, parser)?,
+        ColumnType::Id
+        | ColumnType::OptId
+        | ColumnType::LongId
+        | ColumnType::OptLongId
+        | ColumnType::Geometry
+        | ColumnType::GeometryZ
+        | ColumnType::VertexScope => unreachable!("{typ:?} is not a property column"),
+    })
+}
+
+fn map_prop<'a, T>(
+    (input, value): (&'a [u8], T),
+    wrap: impl FnOnce(T) -> RawProperty<'a>,
+) -> (&'a [u8], RawProperty<'a>) {
+    (input, wrap(value))
+}
+
 fn parse_shared_dict_children<'a>(
     mut input: &'a [u8],
     column: &Column<'a>,
@@ -450,6 +530,7 @@ fn parse_optional<'a>(
 
 fn parse_geometry_column<'a>(
     input: &'a [u8],
+    has_z: bool,
     geometry: &mut Option<Geometry<'a>>,
     parser: &mut Parser,
 ) -> MltResult<&'a [u8]> {
@@ -466,7 +547,7 @@ fn parse_geometry_column<'a>(
     let (input, meta) = header01::parse_stream(input, parser)?;
     // geometry items
     let (input, items) = header01::parse_multiple_streams(input, stream_count_capa - 1, parser)?;
-    geometry.set_once(Raw(RawGeometry { meta, items }))?;
+    geometry.set_once(Raw(RawGeometry { meta, items, has_z }))?;
     Ok(input)
 }
 
@@ -475,7 +556,7 @@ fn parse_str_column<'a>(
     name: &'a str,
     typ: ColumnType,
     parser: &mut Parser,
-) -> MltRefResult<'a, RawProperty<'a>> {
+) -> MltRefResult<'a, RawStrings<'a>> {
     let mut stream_count = {
         let stream_count_u32;
         (input, stream_count_u32) = parse_varint::<u32>(input)?;
@@ -489,6 +570,81 @@ fn parse_str_column<'a>(
         }
         stream_count -= 1;
     }
+    let encoding;
+    (input, encoding) = parse_str_streams(input, stream_count, parser)?;
+    Ok((
+        input,
+        RawStrings {
+            name,
+            presence,
+            encoding,
+        },
+    ))
+}
+
+/// Parse a float column: optional presence, then either a single data stream or
+/// a `Key` offset stream followed by a `Single` dictionary data stream.
+fn parse_float_column<'a>(
+    input: &'a [u8],
+    name: &'a str,
+    typ: ColumnType,
+    parser: &mut Parser,
+) -> MltRefResult<'a, RawFloats<'a>> {
+    let (input, presence) = parse_optional(typ, input, parser)?;
+    let (input, first) = header01::parse_stream(input, parser)?;
+    if first.meta.stream_type != StreamType::Offset(OffsetType::Key) {
+        let encoding = RawFloatsEncoding::Plain(first);
+        return Ok((input, RawFloats::new(name, presence, encoding)));
+    }
+    let (input, dictionary) = header01::parse_stream(input, parser)?;
+    let dict_type = dictionary.meta.stream_type;
+    if dict_type != StreamType::Data(DictionaryType::Single) {
+        return Err(UnexpectedStreamType(dict_type));
+    }
+    let encoding = RawFloatsEncoding::Dictionary {
+        offsets: first,
+        dictionary,
+    };
+    Ok((input, RawFloats::new(name, presence, encoding)))
+}
+
+/// Parse a range map column: optional presence, range counts, range bounds, then the value column.
+fn parse_range_map_column<'a>(
+    mut input: &'a [u8],
+    column: &Column<'a>,
+    parser: &mut Parser,
+) -> MltRefResult<'a, RawRangeMap<'a>> {
+    let name = column.name.unwrap_or("");
+    let (presence, lengths, ranges);
+    (input, presence) = parse_optional(column.typ, input, parser)?;
+    (input, lengths) = header01::parse_stream(input, parser)?;
+    (input, ranges) = header01::parse_stream(input, parser)?;
+    // Validated in `parse_columns_meta`: exactly one value child of a scalar or string type
+    let child = Column {
+        typ: column.children[0].typ,
+        name: Some(name),
+        children: Vec::new(),
+    };
+    let values;
+    (input, values) = parse_property_column(input, &child, parser)?;
+    Ok((
+        input,
+        RawRangeMap {
+            name,
+            presence,
+            lengths,
+            ranges,
+            values: Box::new(values),
+        },
+    ))
+}
+
+/// Parse `stream_count` string streams (plain, dictionary or FSST variants).
+fn parse_str_streams<'a>(
+    mut input: &'a [u8],
+    stream_count: usize,
+    parser: &mut Parser,
+) -> MltRefResult<'a, RawStringsEncoding<'a>> {
     let mut str_streams = [None, None, None, None, None];
     if stream_count > str_streams.len() {
         return Err(UnsupportedStringStreamCount(stream_count));
@@ -513,14 +669,7 @@ fn parse_str_column<'a>(
         }
         _ => Err(UnsupportedStringStreamCount(stream_count))?,
     };
-    Ok((
-        input,
-        RawProperty::Str(RawStrings {
-            name,
-            presence,
-            encoding,
-        }),
-    ))
+    Ok((input, encoding))
 }
 
 fn parse_shared_dict_column<'a>(
@@ -591,12 +740,203 @@ fn parse_shared_dict_column<'a>(
     ))
 }
 
+/// Parse a struct column: the optional shared string dictionary, then each child in order.
+///
+/// String children reference the dictionary through a single offset stream when it is
+/// present; every other child is stored as a regular column body named by its suffix.
+fn parse_struct_column<'a>(
+    mut input: &'a [u8],
+    column: &Column<'a>,
+    parser: &mut Parser,
+) -> MltRefResult<'a, RawStruct<'a>> {
+    let stream_count;
+    (input, stream_count) = parse_varint::<u32>(input)?;
+    let dictionary = match stream_count {
+        0 => None,
+        2 => {
+            let (s1, s2);
+            (input, s1) = header01::parse_stream(input, parser)?;
+            (input, s2) = header01::parse_stream(input, parser)?;
+            Some(RawSharedDictEncoding::plain(RawPlainData::new(s1, s2)?))
+        }
+        4 => {
+            let (s1, s2, s3, s4);
+            (input, s1) = header01::parse_stream(input, parser)?;
+            (input, s2) = header01::parse_stream(input, parser)?;
+            (input, s3) = header01::parse_stream(input, parser)?;
+            (input, s4) = header01::parse_stream(input, parser)?;
+            Some(RawSharedDictEncoding::fsst_plain(RawFsstData::new(
+                s1, s2, s3, s4,
+            )?))
+        }
+        n => return Err(SharedDictRequiresStreams(n.into_usize())),
+    };
+
+    let mut children = Vec::with_capacity(column.children.len());
+    for child in &column.children {
+        let is_shared = matches!(child.typ, ColumnType::Str | ColumnType::OptStr);
+        if dictionary.is_some() && is_shared {
+            let (presence, data);
+            (input, presence) = parse_optional(child.typ, input, parser)?;
+            (input, data) = header01::parse_stream(input, parser)?;
+            children.push(RawStructChild::Shared(RawSharedDictItem {
+                name: child.name.unwrap_or(""),
+                presence,
+                data,
+            }));
+        } else {
+            let prop;
+            (input, prop) = parse_property_column(input, child, parser)?;
+            children.push(RawStructChild::Column(prop));
+        }
+    }
+    Ok((
+        input,
+        RawStruct {
+            name: column.name.unwrap_or(""),
+            dictionary,
+            children,
+        },
+    ))
+}
+
+fn parse_nested_column<'a>(
+    mut input: &'a [u8],
+    column: &Column<'a>,
+    parser: &mut Parser,
+) -> MltRefResult<'a, RawProperty<'a>> {
+    let name = column.name.unwrap_or("");
+    let children = column
+        .children
+        .iter()
+        .map(|child| child.name.unwrap_or(""))
+        .collect();
+
+    let stream_count;
+    (input, stream_count) = parse_varint::<u32>(input)?;
+    if stream_count == 0 {
+        // Every value of every child is null
+        let prop = RawNested {
+            name,
+            children,
+            body: None,
+        };
+        return Ok((input, RawProperty::Nested(prop)));
+    }
+
+    let mask;
+    (input, mask) = parse_u8(input)?;
+    let lengths;
+    (input, lengths) = header01::parse_stream(input, parser)?;
+    let mut expected = 1_u32;
+
+    let mut strings = None;
+    if mask & NestedMask::STRING != 0 {
+        let str_stream_count;
+        (input, str_stream_count) = parse_u8(input)?;
+        let encoding;
+        (input, encoding) = parse_str_streams(input, usize::from(str_stream_count), parser)?;
+        strings = Some(encoding);
+        expected += u32::from(str_stream_count);
+    }
+
+    let (ints, uints, floats, doubles, presence);
+    (input, ints) =
+        parse_nested_stream(input, mask, NestedMask::INT32 | NestedMask::INT64, parser)?;
+    let ints = ints.map(|s| {
+        if mask & NestedMask::INT32 == 0 {
+            RawNestedInts::Int64(s)
+        } else {
+            RawNestedInts::Int32(s)
+        }
+    });
+    (input, uints) =
+        parse_nested_stream(input, mask, NestedMask::UINT32 | NestedMask::UINT64, parser)?;
+    let uints = uints.map(|s| {
+        if mask & NestedMask::UINT32 == 0 {
+            RawNestedInts::Int64(s)
+        } else {
+            RawNestedInts::Int32(s)
+        }
+    });
+    (input, floats) = parse_nested_stream(input, mask, NestedMask::FLOAT, parser)?;
+    (input, doubles) = parse_nested_stream(input, mask, NestedMask::DOUBLE, parser)?;
+    if mask & NestedMask::PRESENCE == 0 {
+        presence = None;
+    } else {
+        let stream;
+        (input, stream) = header01::parse_bool_stream(input, parser)?;
+        presence = Some(stream);
+    }
+    let optional_count = [
+        ints.is_some(),
+        uints.is_some(),
+        floats.is_some(),
+        doubles.is_some(),
+        presence.is_some(),
+    ]
+    .into_iter()
+    .map(u32::from)
+    .sum::<u32>();
+    expected = expected.checked_add(optional_count).or_overflow()?;
+
+    // The flattened value stream is omitted when it would be empty
+    let mut values = None;
+    if stream_count > expected {
+        let stream;
+        (input, stream) = header01::parse_stream(input, parser)?;
+        values = Some(stream);
+        expected += 1;
+    }
+    if stream_count != expected {
+        return Err(InvalidNestedStreamCount {
+            actual: stream_count,
+            expected,
+        });
+    }
+
+    let body = RawNestedBody {
+        lengths,
+        strings,
+        ints,
+        uints,
+        floats,
+        doubles,
+        presence,
+        values,
+    };
+    let prop = RawNested {
+        name,
+        children,
+        body: Some(Box::new(body)),
+    };
+    Ok((input, RawProperty::Nested(prop)))
+}
+
+/// Parse one optional dictionary stream of a nested column if any of `bits` is set in `mask`.
+fn parse_nested_stream<'a>(
+    input: &'a [u8],
+    mask: u8,
+    bits: u8,
+    parser: &mut Parser,
+) -> MltRefResult<'a, Option<RawStream<'a>>> {
+    if mask & bits == 0 {
+        Ok((input, None))
+    } else {
+        let (input, stream) = header01::parse_stream(input, parser)?;
+        Ok((input, Some(stream)))
+    }
+}
+
 fn parse_columns_meta<'a>(
     mut input: &'a [u8],
     column_count: u32,
     parser: &mut Parser,
 ) -> MltRefResult<'a, (Vec<Column<'a>>, u32)> {
-    use crate::decoder::ColumnType::{Geometry, Id, LongId, OptId, OptLongId, SharedDict};
+    use crate::decoder::ColumnType::{
+        Geometry, GeometryZ, Id, LongId, Nested, OptId, OptLongId, OptRangeMap, RangeMap,
+        SharedDict, Struct, VertexScope,
+    };
 
     let mut col_info = Vec::with_capacity(column_count.into_usize());
     let mut geometries = 0;
@@ -605,25 +945,31 @@ fn parse_columns_meta<'a>(
         let mut typ;
         (input, typ) = Column::from_bytes(input, parser)?;
         match typ.typ {
-            Geometry => geometries += 1,
+            Geometry | GeometryZ => geometries += 1,
             Id | OptId | LongId | OptLongId => ids += 1,
-            SharedDict => {
+            SharedDict | Nested => {
                 // Yes, we need to parse children right here; otherwise this messes up the next column
-                let child_column_count;
-                (input, child_column_count) = parse_varint::<u32>(input)?;
-
-                // Each column requires at least 1 byte (ColumnType without a name)
-                let child_col_capacity = child_column_count.into_usize();
-                if input.len() < child_col_capacity {
-                    return Err(BufferUnderflow(child_column_count, input.len()));
+                (input, typ.children) = parse_column_children(input, parser)?;
+            }
+            VertexScope => {
+                (input, typ.children) = parse_column_children(input, parser)?;
+                if let Some(child) = typ.children.iter().find(|c| !c.typ.is_vertex_scopable()) {
+                    return Err(InvalidVertexScopedColumn(child.typ as u8));
+                }
+            }
+            RangeMap | OptRangeMap => {
+                (input, typ.children) = parse_column_children(input, parser)?;
+                match typ.children.as_slice() {
+                    [child] if child.typ.is_range_map_value() => {}
+                    [child] => return Err(InvalidRangeMapValueColumn(child.typ as u8)),
+                    children => return Err(RangeMapValueColumnCount(children.len())),
                 }
-                let mut children = Vec::with_capacity(child_col_capacity);
-                for _ in 0..child_column_count {
-                    let child;
-                    (input, child) = Column::from_bytes(input, parser)?;
-                    children.push(child);
+            }
+            Struct => {
+                (input, typ.children) = parse_column_children(input, parser)?;
+                if let Some(child) = typ.children.iter().find(|c| !c.typ.is_struct_child()) {
+                    return Err(InvalidStructChildColumn(child.typ as u8));
                 }
-                typ.children = children;
             }
             _ => {}
         }
@@ -639,6 +985,37 @@ fn parse_columns_meta<'a>(
     Ok((input, (col_info, column_count - geometries - ids)))
 }
 
+fn parse_column_children<'a>(
+    mut input: &'a [u8],
+    parser: &mut Parser,
+) -> MltRefResult<'a, Vec<Column<'a>>> {
+    let child_column_count;
+    (input, child_column_count) = parse_varint::<u32>(input)?;
+
+    // Each column requires at least 1 byte (ColumnType without a name)
+    let child_col_capacity = child_column_count.into_usize();
+    if input.len() < child_col_capacity {
+        return Err(BufferUnderflow(child_column_count, input.len()));
+    }
+    let mut children = Vec::with_capacity(child_col_capacity);
+    for _ in 0..child_column_count {
+        let child;
+        (input, child) = Column::from_bytes(input, parser)?;
+        if child.typ == ColumnType::Nested {
+            // Nested children carry their own child list, which must be empty
+            let grandchildren;
+            (input, grandchildren) = parse_varint::<u32>(input)?;
+            if grandchildren != 0 {
+                return Err(MltError::NotImplemented(
+                    "nested column with nested children",
+                ));
+            }
+        }
+        children.push(child);
+    }
+    Ok((input, children))
+}
+
 impl<'a> RawScalar<'a> {
     fn new(name: &'a str, presence: RawPresence<'a>, data: RawStream<'a>) -> Self {
         Self {
@@ -1,8 +1,10 @@
 use std::borrow::Cow;
+use std::fmt::Debug;
 use std::mem;
 
 use bitvec::prelude::{BitSlice, BitVec, Lsb0};
 use bitvec::view::BitView as _;
+use num_traits::{AsPrimitive, PrimInt};
 use usize_cast::IntoUsize as _;
 
 use crate::codecs::bytes::{PhysicalWord, decode_bytes_to_bools, decode_bytes_to_words};
@@ -18,11 +20,11 @@ impl<'a> RawStream<'a> {
     /// Borrows directly from tile bytes (zero-copy) when both logical and physical
     /// encodings are `None`; otherwise decompresses byte-RLE into an owned `BitVec`.
     /// The result is always truncated to exactly `num_values` bits.
+    ///
+    /// Like the Java and TypeScript decoders, a `VarInt` physical encoding does not change the
+    /// payload: it is always byte-RLE.
     pub(crate) fn decode_bitvec(self, dec: &mut Decoder) -> MltResult<Cow<'a, BitSlice<u8, Lsb0>>> {
         let num_values = self.meta.num_values.into_usize();
-        if self.meta.encoding.physical == PhysicalEncoding::VarInt {
-            return Err(MltError::NotImplemented("varint presence decoding"));
-        }
         if self.meta.encoding.logical == LogicalEncoding::None
             && self.meta.encoding.physical == PhysicalEncoding::None
         {
@@ -40,10 +42,9 @@ impl<'a> RawStream<'a> {
     }
 
     /// Decode a boolean data stream: byte-RLE -> packed bitmap -> `Vec<bool>`, charging `dec`.
+    ///
+    /// The physical encoding is ignored, as in the Java and TypeScript decoders.
     pub fn decode_bools(self, dec: &mut Decoder) -> MltResult<Vec<bool>> {
-        if self.meta.encoding.physical == PhysicalEncoding::VarInt {
-            return Err(MltError::NotImplemented("varint bool decoding"));
-        }
         let num_values = self.meta.num_values.into_usize();
         let num_bytes = num_values.div_ceil(8);
         let decoded = decode_byte_rle(self.data, num_bytes, dec)?;
@@ -86,14 +87,27 @@ impl<'a> RawStream<'a> {
 
     /// Decode a stream of `f32`/`f64` from raw little-endian bytes, charging `dec`.
     ///
-    /// Floats do not support varint physical encoding.
-    pub fn decode_floats<T>(self, dec: &mut Decoder) -> MltResult<Vec<T>>
+    /// `Rle` streams hold run lengths and values as same-width words of the float bits.
+    /// `PseudoDecimal` streams hold per-value exponents followed by significands as same-width words
+    /// (see [`LogicalValue::decode_pseudo_decimal`]). Other streams are plain little-endian floats
+    /// whatever their physical encoding, as in the Java and TypeScript decoders.
+    pub fn decode_floats<T: DecodeFloat>(self, dec: &mut Decoder) -> MltResult<Vec<T>>
     where
-        T: num_traits::FromBytes,
-        for<'b> <T as num_traits::FromBytes>::Bytes: TryFrom<&'b [u8]>,
+        f64: AsPrimitive<T>,
     {
-        if self.meta.encoding.physical == PhysicalEncoding::VarInt {
-            return Err(MltError::NotImplemented("varint float decoding"));
+        match self.meta.encoding.logical {
+            LogicalEncoding::PseudoDecimal => {
+                let mut words = Vec::new();
+                self.decode_bits::<T::Physical>(&mut words, dec)?;
+                return LogicalValue::new(self.meta).decode_pseudo_decimal(&words, dec);
+            }
+            LogicalEncoding::Rle(rle) => {
+                let mut words = Vec::new();
+                self.decode_bits::<T::Physical>(&mut words, dec)?;
+                let expanded = rle.decode(&words, dec)?;
+                return Ok(expanded.into_iter().map(T::from_physical).collect());
+            }
+            _ => {}
         }
         let num = self.meta.num_values.into_usize();
         let width = size_of::<T>();
@@ -103,14 +117,7 @@ impl<'a> RawStream<'a> {
         Ok(self
             .data
             .chunks_exact(width)
-            .map(|chunk| {
-                T::from_le_bytes(
-                    &chunk
-                        .try_into()
-                        .ok()
-                        .expect("infallible: chunks_exact(width)"),
-                )
-            })
+            .map(|chunk| T::from_physical(T::Physical::from_le_word(chunk)))
             .collect())
     }
 
@@ -141,6 +148,31 @@ impl<'a> RawStream<'a> {
     }
 }
 
+/// Logical output float type of a decoded stream (`f32` / `f64`).
+pub trait DecodeFloat: Copy + 'static {
+    /// Same-width unsigned word holding the float's bit pattern.
+    type Physical: PhysicalWord + PrimInt + Debug + Into<u64>;
+
+    /// Reinterpret the bit pattern as a float.
+    fn from_physical(bits: Self::Physical) -> Self;
+}
+
+impl DecodeFloat for f32 {
+    type Physical = u32;
+
+    fn from_physical(bits: u32) -> Self {
+        Self::from_bits(bits)
+    }
+}
+
+impl DecodeFloat for f64 {
+    type Physical = u64;
+
+    fn from_physical(bits: u64) -> Self {
+        Self::from_bits(bits)
+    }
+}
+
 /// Logical output integer type of a decoded stream (`i32` / `u32` / `i64` / `u64`).
 ///
 /// Decoder-side mirror of the encoder's `LogicalIntStreamKind`.
@@ -1,15 +1,45 @@
 use std::fmt::Debug;
 use std::iter::repeat_n;
 
-use num_traits::{PrimInt, ToPrimitive as _};
+use num_traits::{AsPrimitive, PrimInt, ToPrimitive as _};
 use usize_cast::IntoUsize as _;
+use zigzag::ZigZag as _;
 
-use crate::MltError::{ParsingLogicalTechnique, RleRunLenInvalid, UnsupportedLogicalEncoding};
+use crate::MltError::{
+    InvalidPseudoDecimalExponent, InvalidPseudoDecimalWordCount, ParsingLogicalTechnique,
+    RleRunLenInvalid, UnsupportedLogicalEncoding,
+};
 use crate::codecs::zigzag::{decode_componentwise_delta_vec2s, decode_zigzag, decode_zigzag_delta};
 use crate::decoder::{LogicalEncoding, LogicalTechnique, LogicalValue, RleMeta, StreamMeta};
 use crate::errors::{AsMltError as _, fail_if_invalid_stream_size};
 use crate::{Decoder, MltResult};
 
+/// Largest absolute `PseudoDecimal` exponent.
+///
+/// `10^22` is the largest power of ten that is exactly representable in `f64`.
+pub(crate) const MAX_PSEUDO_DECIMAL_EXPONENT: u32 = 22;
+
+pub(crate) const POW10: [f64; MAX_PSEUDO_DECIMAL_EXPONENT as usize + 1] = [
+    1e0, 1e1, 1e2, 1e3, 1e4, 1e5, 1e6, 1e7, 1e8, 1e9, 1e10, 1e11, 1e12, 1e13, 1e14, 1e15, 1e16,
+    1e17, 1e18, 1e19, 1e20, 1e21, 1e22,
+];
+
+/// Compute `significand × 10^exponent` in `f64` with a single rounding step.
+///
+/// The exponent must be within [`MAX_PSEUDO_DECIMAL_EXPONENT`].
+#[expect(
+    clippy::cast_precision_loss,
+    reason = "the encoder only writes significands below 10^15"
+)]
+pub(crate) fn pseudo_decimal_value(significand: i64, exponent: i32) -> f64 {
+    let pow = POW10[exponent.unsigned_abs().into_usize()];
+    if exponent < 0 {
+        significand as f64 / pow
+    } else {
+        significand as f64 * pow
+    }
+}
+
 impl RleMeta {
     /// Decode RLE (Run-Length Encoding) data.
     /// Charges the decoder for the expanded output allocation.
@@ -83,6 +113,37 @@ impl LogicalValue {
         }
     }
 
+    /// Logically decode `PseudoDecimal` words (physically decoded `u32` or `u64`) into floats.
+    ///
+    /// The first half of `data` holds the zigzag-encoded exponents and the second half the
+    /// zigzag-encoded significands, one of each per value. Every value is computed as
+    /// `significand × 10^exponent` in `f64` and then narrowed to `T`.
+    pub fn decode_pseudo_decimal<W, T>(self, data: &[W], dec: &mut Decoder) -> MltResult<Vec<T>>
+    where
+        W: Copy + Into<u64>,
+        T: Copy + 'static,
+        f64: AsPrimitive<T>,
+    {
+        let num = self.meta.num_values.into_usize();
+        fail_if_invalid_stream_size(data.len(), num)?;
+        if !num.is_multiple_of(2) {
+            return Err(InvalidPseudoDecimalWordCount(num));
+        }
+        let (exponents, significands) = data.split_at(data.len() / 2);
+        let mut values = dec.alloc::<T>(exponents.len())?;
+        for (&exponent, &significand) in exponents.iter().zip(significands) {
+            // A zigzag u32 widened to u64 decodes to the same value as a zigzag u64.
+            let exponent = i64::decode(exponent.into());
+            let exponent = i32::try_from(exponent)
+                .ok()
+                .filter(|e| e.unsigned_abs() <= MAX_PSEUDO_DECIMAL_EXPONENT)
+                .ok_or(InvalidPseudoDecimalExponent(exponent))?;
+            let significand = i64::decode(significand.into());
+            values.push(pseudo_decimal_value(significand, exponent).as_());
+        }
+        Ok(values)
+    }
+
     /// Logically decode `data` (physically decoded u32 words) into `Vec<u32>`.
     ///
     /// Not called for `LogicalEncoding::None` - that case is handled entirely
@@ -161,6 +222,7 @@ impl LogicalValue {
 mod tests {
     use super::*;
     use crate::MltError::InvalidDecodingStreamSize;
+    use crate::decoder::{DictionaryType, IntEncoding, PhysicalEncoding, StreamType};
     use crate::test_helpers::dec;
 
     #[test]
@@ -172,6 +234,37 @@ mod tests {
         assert_eq!(rle.decode::<u32>(&[], &mut dec()).unwrap(), [] as [u32; 0]);
     }
 
+    fn pseudo_decimal(num_values: u32) -> LogicalValue {
+        let encoding = IntEncoding::new(LogicalEncoding::PseudoDecimal, PhysicalEncoding::None);
+        LogicalValue::new(StreamMeta::new(
+            StreamType::Data(DictionaryType::None),
+            encoding,
+            num_values,
+        ))
+    }
+
+    #[test]
+    fn decode_pseudo_decimal_words() {
+        // exponents -2 and 0 (zigzag 3, 0), significands 199 and -5 (zigzag 398, 9)
+        let decoded: Vec<f64> = pseudo_decimal(4)
+            .decode_pseudo_decimal(&[3_u32, 0, 398, 9], &mut dec())
+            .unwrap();
+        assert_eq!(decoded, [1.99, -5.0]);
+    }
+
+    #[test]
+    fn decode_pseudo_decimal_rejects_large_exponent() {
+        // zigzag(-23) = 45
+        let err = pseudo_decimal(2)
+            .decode_pseudo_decimal::<u64, f64>(&[45, 2], &mut dec())
+            .unwrap_err();
+        assert!(matches!(err, InvalidPseudoDecimalExponent(-23)));
+        let err = pseudo_decimal(3)
+            .decode_pseudo_decimal::<u64, f64>(&[0, 2, 4], &mut dec())
+            .unwrap_err();
+        assert!(matches!(err, InvalidPseudoDecimalWordCount(3)));
+    }
+
     #[test]
     fn test_decode_rle_invalid_stream_size() {
         // Valid RLE for runs=2 needs 4 elements (2 run lengths + 2 values). Only 3 provided.
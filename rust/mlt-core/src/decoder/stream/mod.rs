@@ -3,3 +3,5 @@ mod decode;
 pub(crate) mod header01;
 pub(crate) mod logical;
 pub(crate) mod model;
+
+pub(crate) use decode::DecodeFloat;
@@ -7,11 +7,12 @@
 //! and free from any encoded/decoded duality.
 
 use crate::decoder::{
-    GeometryValues, Layer01, ParsedLayer01, ParsedProperty, PropValue, PropValueRef, TileFeature,
-    TileLayer,
+    ColNames as _, GeometryValues, Layer01, ParsedLayer01, ParsedProperty, ParsedStructChild,
+    PropValue, PropValueRef, TileFeature, TileLayer, VertexValues,
 };
 use crate::errors::AsMltError as _;
-use crate::{Decoder, LendingIterator, MltResult};
+use crate::filter::Selection;
+use crate::{Analyze as _, Decoder, LendingIterator, MltResult, StatType};
 
 impl ParsedLayer01<'_> {
     /// Returns the decoded geometry buffer for this layer.
@@ -28,13 +29,41 @@ impl ParsedLayer01<'_> {
     /// Decode and convert into a row-oriented [`TileLayer`], charging every
     /// heap allocation against `dec`.
     pub fn into_tile(self, dec: &mut Decoder) -> MltResult<TileLayer> {
+        self.into_tile_with(None, dec)
+    }
+
+    /// Like [`Self::into_tile`], but keep only the features in `selection`, e.g. to
+    /// re-encode the result of a [`Filter`](crate::filter::Filter).
+    ///
+    /// All property columns are kept, even if no selected feature has a value in them.
+    pub fn into_tile_selected(
+        self,
+        selection: &Selection,
+        dec: &mut Decoder,
+    ) -> MltResult<TileLayer> {
+        self.into_tile_with(Some(selection), dec)
+    }
+
+    fn into_tile_with(
+        self,
+        selection: Option<&Selection>,
+        dec: &mut Decoder,
+    ) -> MltResult<TileLayer> {
         // Extract owned/copied fields before borrowing self for the feature iterator.
         let name = self.name().to_string();
         let extent = self.extent().get();
         let names: Vec<String> = self.iterate_prop_names().map(|n| n.to_string()).collect();
         let col_nulls = typed_nulls(&self.properties);
-        let mut features = dec.alloc::<TileFeature>(self.feature_count())?;
-        let mut feat_iter = self.iter_features();
+        let vertex_names: Vec<String> = self
+            .vertex_properties
+            .iter()
+            .map(|p| p.name_at(0).to_string())
+            .collect();
+        let mut feat_iter = match selection {
+            Some(selection) => self.iter_selected(selection),
+            None => self.iter_features(),
+        };
+        let mut features = dec.alloc::<TileFeature>(feat_iter.len())?;
         while let Some(feat) = feat_iter.next() {
             let feat = feat?;
             let mut values = dec.alloc::<PropValue>(names.len())?;
@@ -47,14 +76,23 @@ impl ParsedLayer01<'_> {
 
             charge_str_props(dec, &values)?;
 
+            let mut vertex_values = dec.alloc::<VertexValues>(vertex_names.len())?;
+            vertex_values.extend(
+                feat.iter_vertex_properties()
+                    .map(|c| VertexValues::from(c.values())),
+            );
+
             features.push(TileFeature {
                 id: feat.id(),
                 geometry: feat.geometry().clone(),
+                z: feat.z().map(<[i32]>::to_vec),
                 properties: values,
+                vertex_properties: vertex_values,
             });
         }
 
-        TileLayer::from_parts(name, extent, names, features)
+        TileLayer::from_parts(name, extent, names, features)?
+            .with_vertex_property_names(vertex_names)
     }
 
     #[must_use]
@@ -84,6 +122,12 @@ fn prop_value_from_ref(value: PropValueRef<'_>) -> PropValue {
         PropValueRef::F32(v) => PropValue::F32(Some(v)),
         PropValueRef::F64(v) => PropValue::F64(Some(v)),
         PropValueRef::Str(s) => PropValue::Str(Some(s.to_string())),
+        PropValueRef::Nested(v) => PropValue::Nested(Some(v.clone())),
+        PropValueRef::Date(v) => PropValue::Date(Some(v)),
+        PropValueRef::Timestamp(v) => PropValue::Timestamp(Some(v)),
+        PropValueRef::Json(s) => PropValue::Json(Some(s.to_string())),
+        PropValueRef::Binary(b) => PropValue::Binary(Some(b.to_vec())),
+        PropValueRef::RangeMap(entries) => PropValue::RangeMap(Some(entries.to_vec())),
     }
 }
 
@@ -92,7 +136,8 @@ fn prop_value_from_ref(value: PropValueRef<'_>) -> PropValue {
 ///
 /// Each scalar column contributes one entry with its specific null variant (e.g.
 /// `PropValue::Bool(None)`).  A `SharedDict` column expands to one `PropValue::Str(None)`
-/// entry per sub-item.
+/// entry per sub-item, a `Nested` column to one `PropValue::Nested(None)` per child, and a
+/// `Struct` column to the nulls of each of its children.
 fn typed_nulls(properties: &[ParsedProperty<'_>]) -> Vec<PropValue> {
     use ParsedProperty as PP;
     use PropValue as PV;
@@ -109,26 +154,42 @@ fn typed_nulls(properties: &[ParsedProperty<'_>]) -> Vec<PropValue> {
             PP::F32(_) => nulls.push(PV::F32(None)),
             PP::F64(_) => nulls.push(PV::F64(None)),
             PP::Str(_) => nulls.push(PV::Str(None)),
+            PP::RangeMap(_) => nulls.push(PV::RangeMap(None)),
             PP::SharedDict(d) => {
                 for _ in &d.items {
                     nulls.push(PV::Str(None));
                 }
             }
+            PP::Nested(n) => {
+                for _ in &n.items {
+                    nulls.push(PV::Nested(None));
+                }
+            }
+            PP::Struct(s) => {
+                for child in &s.children {
+                    match child {
+                        ParsedStructChild::Shared(_) => nulls.push(PV::Str(None)),
+                        ParsedStructChild::Column(col) => {
+                            nulls.extend(typed_nulls(std::slice::from_ref(col)));
+                        }
+                    }
+                }
+            }
         }
     }
     nulls
 }
 
-/// Charge `dec` for the heap bytes of owned `String` values inside `PropValue::Str`.
+/// Charge `dec` for the heap bytes of owned values inside string, nested and range map
+/// `PropValue`s.
 fn charge_str_props(dec: &mut Decoder, props: &[PropValue]) -> MltResult<()> {
     let str_bytes = props
         .iter()
-        .filter_map(|p| {
-            if let PropValue::Str(Some(s)) = p {
-                Some(s.len())
-            } else {
-                None
-            }
+        .filter_map(|p| match p {
+            PropValue::Str(Some(s)) => Some(s.len()),
+            PropValue::Nested(Some(v)) => Some(v.collect_statistic(StatType::DecodedDataSize)),
+            PropValue::RangeMap(Some(_)) => Some(p.collect_statistic(StatType::DecodedDataSize)),
+            _ => None,
         })
         .try_fold(0u32, |acc, n| {
             acc.checked_add(u32::try_from(n).or_overflow()?)
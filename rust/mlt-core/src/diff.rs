@@ -0,0 +1,505 @@
+//! Semantic comparison of decoded tiles.
+//!
+//! [`diff_layers`] compares two tiles given as [`TileLayer`]s, so an MLT tile can be compared
+//! with another MLT tile or with the MVT tile it was converted from. Layers are matched by name,
+//! features by ID if every feature in both layers has a distinct ID, and by position otherwise.
+//! Property values are compared as `GeoJSON` values, so a value stored as `I64` on one side
+//! and `U32` on the other is equal, and an absent property equals a null one.
+//!
+//! ```
+//! use mlt_core::TileLayer;
+//! use mlt_core::diff::diff_layers;
+//!
+//! # fn compare(before: &[TileLayer], after: &[TileLayer]) {
+//! let diff = diff_layers(before, after);
+//! if !diff.is_empty() {
+//!     println!("{diff}");
+//! }
+//! # }
+//! ```
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use geo::CoordsIter as _;
+use geo_types::Geometry;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::convert::geojson::{geom_serde, json_values_equal, normalize_tiny_floats};
+use crate::{TileFeature, TileLayer, VertexValues};
+
+/// Differences between two tiles, from the left one to the right one.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[expect(
+    clippy::struct_field_names,
+    reason = "field names are the keys of the JSON diff output"
+)]
+pub struct TileDiff {
+    /// Layers only present in the right tile.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub added_layers: Vec<String>,
+    /// Layers only present in the left tile.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub removed_layers: Vec<String>,
+    /// Layers present in both tiles with different content.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub changed_layers: Vec<LayerDiff>,
+}
+
+impl TileDiff {
+    /// `true` if both tiles are semantically equal.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.added_layers.is_empty()
+            && self.removed_layers.is_empty()
+            && self.changed_layers.is_empty()
+    }
+}
+
+/// Differences between two layers of the same name.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LayerDiff {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extent: Option<Change<u32>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub feature_count: Option<Change<usize>>,
+    /// Features only present in the right layer.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub added_features: Vec<FeatureKey>,
+    /// Features only present in the left layer.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub removed_features: Vec<FeatureKey>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub changed_features: Vec<FeatureDiff>,
+}
+
+impl LayerDiff {
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.extent.is_none()
+            && self.feature_count.is_none()
+            && self.added_features.is_empty()
+            && self.removed_features.is_empty()
+            && self.changed_features.is_empty()
+    }
+}
+
+/// A value that differs between the left and the right tile.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Change<T> {
+    pub left: T,
+    pub right: T,
+}
+
+/// How a feature was matched between the two layers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FeatureKey {
+    /// Matched by feature ID.
+    Id(u64),
+    /// Matched by position in the layer, because not all features have distinct IDs.
+    Index(usize),
+}
+
+impl fmt::Display for FeatureKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Id(id) => write!(f, "id {id}"),
+            Self::Index(index) => write!(f, "#{index}"),
+        }
+    }
+}
+
+/// Differences between two matched features.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FeatureDiff {
+    pub key: FeatureKey,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub geometry: Option<GeometryChange>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub z: Option<Change<Option<Vec<i32>>>>,
+    /// Changed properties in column order; `null` stands for an absent or null value.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub properties: Vec<PropertyChange>,
+    /// Names of the vertex properties with different values.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub vertex_properties: Vec<String>,
+}
+
+/// A geometry that differs between the two tiles, serialized as `GeoJSON` geometries.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct GeometryChange {
+    #[serde(serialize_with = "geom_serde::serialize")]
+    pub left: Geometry<i32>,
+    #[serde(serialize_with = "geom_serde::serialize")]
+    pub right: Geometry<i32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PropertyChange {
+    pub name: String,
+    pub left: Value,
+    pub right: Value,
+}
+
+/// Compare two tiles layer by layer.
+///
+/// Layers sharing a name are paired in order of appearance.
+#[must_use]
+pub fn diff_layers(left: &[TileLayer], right: &[TileLayer]) -> TileDiff {
+    let mut diff = TileDiff::default();
+    let mut matched = vec![false; right.len()];
+    for layer in left {
+        let other = (0..right.len()).find(|&idx| !matched[idx] && right[idx].name == layer.name);
+        match other {
+            Some(idx) => {
+                matched[idx] = true;
+                let layer_diff = diff_layer(layer, &right[idx]);
+                if !layer_diff.is_empty() {
+                    diff.changed_layers.push(layer_diff);
+                }
+            }
+            None => diff.removed_layers.push(layer.name.clone()),
+        }
+    }
+    diff.added_layers = right
+        .iter()
+        .zip(matched)
+        .filter(|(_, matched)| !matched)
+        .map(|(layer, _)| layer.name.clone())
+        .collect();
+    diff
+}
+
+fn diff_layer(left: &TileLayer, right: &TileLayer) -> LayerDiff {
+    let (lc, rc) = (left.feature_count(), right.feature_count());
+    let mut diff = LayerDiff {
+        name: left.name.clone(),
+        extent: change(left.extent.get(), right.extent.get()),
+        feature_count: change(lc, rc),
+        added_features: Vec::new(),
+        removed_features: Vec::new(),
+        changed_features: Vec::new(),
+    };
+    let columns = Columns::new(left, right);
+    let mut compare = |key, l, r| {
+        if let Some(feature_diff) = diff_feature(key, l, r, &columns) {
+            diff.changed_features.push(feature_diff);
+        }
+    };
+
+    if let (Some(left_ids), Some(right_ids)) = (distinct_ids(left), distinct_ids(right)) {
+        let right_idx: HashMap<u64, usize> = right_ids
+            .iter()
+            .enumerate()
+            .map(|(idx, &id)| (id, idx))
+            .collect();
+        for (feature, &id) in left.features.iter().zip(&left_ids) {
+            match right_idx.get(&id) {
+                Some(&idx) => compare(FeatureKey::Id(id), feature, &right.features[idx]),
+                None => diff.removed_features.push(FeatureKey::Id(id)),
+            }
+        }
+        let left_ids: HashSet<_> = left_ids.into_iter().collect();
+        diff.added_features = right_ids
+            .into_iter()
+            .filter(|id| !left_ids.contains(id))
+            .map(FeatureKey::Id)
+            .collect();
+    } else {
+        for (idx, (l, r)) in left.features.iter().zip(&right.features).enumerate() {
+            compare(FeatureKey::Index(idx), l, r);
+        }
+        diff.removed_features = (rc..lc).map(FeatureKey::Index).collect();
+        diff.added_features = (lc..rc).map(FeatureKey::Index).collect();
+    }
+    diff
+}
+
+/// IDs of all features, or `None` if any feature has no ID or an ID is repeated.
+fn distinct_ids(layer: &TileLayer) -> Option<Vec<u64>> {
+    let ids: Vec<u64> = layer
+        .features
+        .iter()
+        .map(TileFeature::id)
+        .collect::<Option<_>>()?;
+    let distinct: HashSet<_> = ids.iter().collect();
+    (distinct.len() == ids.len()).then_some(ids)
+}
+
+/// Union of the column names of both layers, with their index on each side.
+struct Columns {
+    properties: Vec<(String, Option<usize>, Option<usize>)>,
+    vertex_properties: Vec<(String, Option<usize>, Option<usize>)>,
+}
+
+impl Columns {
+    fn new(left: &TileLayer, right: &TileLayer) -> Self {
+        Self {
+            properties: union(&left.property_names, &right.property_names),
+            vertex_properties: union(&left.vertex_property_names, &right.vertex_property_names),
+        }
+    }
+}
+
+fn union(left: &[String], right: &[String]) -> Vec<(String, Option<usize>, Option<usize>)> {
+    let right_idx: HashMap<&str, usize> = right
+        .iter()
+        .enumerate()
+        .map(|(idx, name)| (name.as_str(), idx))
+        .collect();
+    let left_names: HashSet<&str> = left.iter().map(String::as_str).collect();
+    let shared = left.iter().enumerate().map(|(idx, name)| {
+        let other = right_idx.get(name.as_str()).copied();
+        (name.clone(), Some(idx), other)
+    });
+    let added = right
+        .iter()
+        .enumerate()
+        .filter(|(_, name)| !left_names.contains(name.as_str()))
+        .map(|(idx, name)| (name.clone(), None, Some(idx)));
+    shared.chain(added).collect()
+}
+
+fn diff_feature(
+    key: FeatureKey,
+    left: &TileFeature,
+    right: &TileFeature,
+    columns: &Columns,
+) -> Option<FeatureDiff> {
+    let properties: Vec<_> = columns
+        .properties
+        .iter()
+        .filter_map(|(name, l, r)| {
+            let lv = property_value(left, *l);
+            let rv = property_value(right, *r);
+            (!json_values_equal(&lv, &rv)).then(|| PropertyChange {
+                name: name.clone(),
+                left: lv,
+                right: rv,
+            })
+        })
+        .collect();
+    let vertex_properties: Vec<_> = columns
+        .vertex_properties
+        .iter()
+        .filter(|(_, l, r)| vertex_values(left, *l) != vertex_values(right, *r))
+        .map(|(name, _, _)| name.clone())
+        .collect();
+    let diff = FeatureDiff {
+        key,
+        geometry: (left.geometry != right.geometry).then(|| GeometryChange {
+            left: left.geometry.clone(),
+            right: right.geometry.clone(),
+        }),
+        z: change(left.z.clone(), right.z.clone()),
+        properties,
+        vertex_properties,
+    };
+    let unchanged = diff.geometry.is_none()
+        && diff.z.is_none()
+        && diff.properties.is_empty()
+        && diff.vertex_properties.is_empty();
+    (!unchanged).then_some(diff)
+}
+
+fn property_value(feature: &TileFeature, column: Option<usize>) -> Value {
+    column
+        .and_then(|idx| feature.properties[idx].as_value_ref())
+        .map_or(Value::Null, |v| normalize_tiny_floats(v.into()))
+}
+
+fn vertex_values(feature: &TileFeature, column: Option<usize>) -> Option<&VertexValues> {
+    column.map(|idx| &feature.vertex_properties[idx])
+}
+
+fn change<T: PartialEq>(left: T, right: T) -> Option<Change<T>> {
+    (left != right).then_some(Change { left, right })
+}
+
+impl fmt::Display for TileDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for name in &self.removed_layers {
+            writeln!(f, "- layer {name}")?;
+        }
+        for name in &self.added_layers {
+            writeln!(f, "+ layer {name}")?;
+        }
+        for layer in &self.changed_layers {
+            write!(f, "{layer}")?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for LayerDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "~ layer {}", self.name)?;
+        if let Some(Change { left, right }) = &self.extent {
+            writeln!(f, "    extent: {left} -> {right}")?;
+        }
+        if let Some(Change { left, right }) = &self.feature_count {
+            writeln!(f, "    features: {left} -> {right}")?;
+        }
+        for key in &self.removed_features {
+            writeln!(f, "  - feature {key}")?;
+        }
+        for key in &self.added_features {
+            writeln!(f, "  + feature {key}")?;
+        }
+        for feature in &self.changed_features {
+            write!(f, "{feature}")?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for FeatureDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "  ~ feature {}", self.key)?;
+        if let Some(GeometryChange { left, right }) = &self.geometry {
+            writeln!(
+                f,
+                "      geometry: {} with {} vertices -> {} with {} vertices",
+                geometry_type(left),
+                left.coords_count(),
+                geometry_type(right),
+                right.coords_count(),
+            )?;
+        }
+        if self.z.is_some() {
+            writeln!(f, "      z values changed")?;
+        }
+        for PropertyChange { name, left, right } in &self.properties {
+            writeln!(f, "      {name}: {left} -> {right}")?;
+        }
+        for name in &self.vertex_properties {
+            writeln!(f, "      vertex property {name} changed")?;
+        }
+        Ok(())
+    }
+}
+
+fn geometry_type(geometry: &Geometry<i32>) -> &'static str {
+    match geometry {
+        Geometry::Point(_) => "Point",
+        Geometry::Line(_) => "Line",
+        Geometry::LineString(_) => "LineString",
+        Geometry::Polygon(_) => "Polygon",
+        Geometry::MultiPoint(_) => "MultiPoint",
+        Geometry::MultiLineString(_) => "MultiLineString",
+        Geometry::MultiPolygon(_) => "MultiPolygon",
+        Geometry::GeometryCollection(_) => "GeometryCollection",
+        Geometry::Rect(_) => "Rect",
+        Geometry::Triangle(_) => "Triangle",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use geo_types::{line_string, point};
+    use insta::assert_snapshot;
+
+    use super::*;
+    use crate::{PropKind, PropValue};
+
+    fn roads(features: &[(Option<u64>, i32, &str)]) -> TileLayer {
+        let mut layer = TileLayer::builder("roads", 4096).unwrap();
+        let class = layer.add_property("class", PropKind::Str).unwrap();
+        for &(id, x, value) in features {
+            let mut feature = layer.feature(point!(x: x, y: 0).into());
+            feature
+                .id(id)
+                .property(class, PropValue::Str(Some(value.into())))
+                .unwrap();
+            feature.finish().unwrap();
+        }
+        layer.finish()
+    }
+
+    #[test]
+    fn equal_tiles_have_no_diff() {
+        let tile = [roads(&[(Some(1), 0, "a")])];
+        assert!(diff_layers(&tile, &tile).is_empty());
+    }
+
+    #[test]
+    fn layers_are_added_and_removed() {
+        let mut water = TileLayer::builder("water", 4096).unwrap();
+        water
+            .feature(line_string![(x: 0, y: 0), (x: 1, y: 1)].into())
+            .finish()
+            .unwrap();
+        let diff = diff_layers(&[roads(&[])], &[water.finish()]);
+        assert_eq!(diff.removed_layers, ["roads"]);
+        assert_eq!(diff.added_layers, ["water"]);
+        assert!(diff.changed_layers.is_empty());
+    }
+
+    #[test]
+    fn features_are_matched_by_id() {
+        let left = roads(&[(Some(1), 0, "a"), (Some(2), 5, "b"), (Some(3), 9, "c")]);
+        let right = roads(&[(Some(3), 9, "c"), (Some(2), 6, "x"), (Some(4), 1, "d")]);
+        let diff = diff_layers(&[left], &[right]);
+
+        let [layer] = diff.changed_layers.as_slice() else {
+            panic!("expected one changed layer: {diff:?}");
+        };
+        assert_eq!(layer.feature_count, None);
+        assert_eq!(layer.removed_features, [FeatureKey::Id(1)]);
+        assert_eq!(layer.added_features, [FeatureKey::Id(4)]);
+        let [feature] = layer.changed_features.as_slice() else {
+            panic!("expected one changed feature: {layer:?}");
+        };
+        assert_eq!(feature.key, FeatureKey::Id(2));
+        assert!(feature.geometry.is_some());
+        assert_eq!(
+            feature.properties,
+            [PropertyChange {
+                name: "class".into(),
+                left: "b".into(),
+                right: "x".into(),
+            }]
+        );
+        assert_snapshot!(diff.to_string(), @r#"
+        ~ layer roads
+          - feature id 1
+          + feature id 4
+          ~ feature id 2
+              geometry: Point with 1 vertices -> Point with 1 vertices
+              class: "b" -> "x"
+        "#);
+    }
+
+    #[test]
+    fn features_without_ids_are_matched_by_position() {
+        let left = roads(&[(None, 0, "a"), (None, 1, "b")]);
+        let right = roads(&[(None, 0, "a")]);
+        let diff = diff_layers(&[left], &[right]);
+        let layer = &diff.changed_layers[0];
+        assert_eq!(layer.feature_count, Some(Change { left: 2, right: 1 }));
+        assert_eq!(layer.removed_features, [FeatureKey::Index(1)]);
+        assert!(layer.changed_features.is_empty());
+    }
+
+    #[test]
+    fn property_kinds_and_nulls_compare_by_value() {
+        let mut left = TileLayer::builder("roads", 4096).unwrap();
+        let rank = left.add_property("rank", PropKind::I64).unwrap();
+        let empty = left.add_property("empty", PropKind::Str).unwrap();
+        let mut feature = left.feature(point!(x: 0, y: 0).into());
+        feature.property(rank, PropValue::I64(Some(3))).unwrap();
+        feature.property(empty, PropValue::Str(None)).unwrap();
+        feature.finish().unwrap();
+
+        let mut right = TileLayer::builder("roads", 4096).unwrap();
+        let rank = right.add_property("rank", PropKind::U32).unwrap();
+        let mut feature = right.feature(point!(x: 0, y: 0).into());
+        feature.property(rank, PropValue::U32(Some(3))).unwrap();
+        feature.finish().unwrap();
+
+        assert!(diff_layers(&[left.finish()], &[right.finish()]).is_empty());
+    }
+}
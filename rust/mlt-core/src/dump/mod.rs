@@ -2,12 +2,15 @@
 //!
 //! [`annotate_tile`] walks a tile buffer into a [`DumpTree`] of [`Region`]s.
 //! [`render()`] formats that tree as an annotated hexdump.
+//! [`validate_tile`] checks a tile against the specification, one [`Finding`] per problem.
 
 mod model;
 mod render;
+mod validate;
 mod walker;
 mod walker01;
 
 pub use model::{BitField, BlobInfo, DecodeHint, DumpTree, Region, RegionKind};
 pub use render::{DataMode, RenderOpts, render};
+pub use validate::{Finding, Severity, validate_tile};
 pub use walker::annotate_tile;
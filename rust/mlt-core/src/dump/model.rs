@@ -22,6 +22,8 @@ pub enum DecodeHint {
     Bool,
     /// Signed 32-bit integers (`i8`/`i32` columns).
     I32,
+    /// Signed 32-bit `(x, y, z)` vertices of a `GeometryZ` column.
+    Vec3,
     /// Unsigned 32-bit integers (`u8`/`u32` columns, offsets, lengths).
     U32,
     /// Signed 64-bit integers (`i64` columns).
@@ -4,7 +4,7 @@ use std::io::{self, Write};
 
 use super::model::{BlobInfo, DecodeHint, DumpTree, Region, RegionKind};
 use crate::Decoder;
-use crate::decoder::RawStream;
+use crate::decoder::{RawStream, decode_vec3_vertices};
 
 /// How data-payload blobs are shown.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -238,6 +238,7 @@ fn decode_blob(info: BlobInfo, data: &[u8], dec: &mut Decoder) -> String {
         },
         DecodeHint::Bool => fmt_res(RawStream::new(meta, data).decode_bools(dec)),
         DecodeHint::I32 => fmt_res(RawStream::new(meta, data).decode_ints::<i32>(dec)),
+        DecodeHint::Vec3 => fmt_res(decode_vec3_vertices(RawStream::new(meta, data), dec)),
         DecodeHint::U32 => fmt_res(RawStream::new(meta, data).decode_ints::<u32>(dec)),
         DecodeHint::I64 => fmt_res(RawStream::new(meta, data).decode_ints::<i64>(dec)),
         DecodeHint::U64 => fmt_res(RawStream::new(meta, data).decode_ints::<u64>(dec)),
@@ -0,0 +1,559 @@
+//! Strict conformance checks on top of the annotating walker.
+//!
+//! [`validate_tile`] walks a tile like [`annotate_tile`](super::annotate_tile) and
+//! inspects the resulting regions, so every [`Finding`] points at the exact bytes it
+//! concerns and can be matched against `mlt hexdump` output.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use geo_types::{Geometry, LineString, Polygon};
+use usize_cast::IntoUsize as _;
+
+use super::model::{BlobInfo, DecodeHint, Region, RegionKind};
+use super::walker::{WalkError, annotate_partial};
+use crate::decoder::{
+    DictionaryType, LengthType, LogicalEncoding, OffsetType, PhysicalEncoding, RawStream,
+    StreamType, decode_vec3_vertices,
+};
+use crate::{Decoder, Layer, MltResult, Parser};
+
+/// How serious a [`Finding`] is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    /// Readable, but wasteful or inconsistent with common conventions.
+    Warning,
+    /// Not allowed by the specification; decoders may reject or misread the tile.
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Warning => "warning",
+            Self::Error => "error",
+        })
+    }
+}
+
+/// One problem reported by [`validate_tile`].
+#[derive(Debug, Clone)]
+pub struct Finding {
+    pub severity: Severity,
+    pub message: String,
+    /// The offending bytes. For parse errors, everything from where parsing stopped
+    /// to the end of the buffer.
+    pub region: Region,
+    /// Labels of the containers enclosing `region`, outermost first.
+    pub path: Vec<String>,
+}
+
+/// Check a tile against the MLT specification, ordered by byte offset.
+///
+/// Beyond what [`Parser::parse_layers`] rejects, this reports unused trailing bytes,
+/// stream type / encoding combinations the spec does not allow, undecodable payloads,
+/// duplicate column names, out-of-bounds dictionary and vertex offsets, dictionary
+/// entries that are never referenced, and polygon rings that do not follow the MVT
+/// winding order. A parse error ends the walk and is reported as the last finding.
+#[must_use]
+pub fn validate_tile(buf: &[u8]) -> Vec<Finding> {
+    let (tree, res) = annotate_partial(buf);
+    let mut v = Validator {
+        parents: parents(&tree.regions),
+        regions: &tree.regions,
+        buf,
+        dec: Decoder::default(),
+        findings: Vec::new(),
+    };
+    v.check_trailing_bytes();
+    v.check_column_names();
+    let streams = v.check_streams();
+    v.check_offsets(&streams);
+    match res {
+        Ok(()) => v.check_winding(),
+        Err(err) => v.parse_error(&err),
+    }
+    let mut findings = v.findings;
+    findings.sort_by_key(|f| f.region.offset);
+    findings
+}
+
+struct Validator<'a> {
+    regions: &'a [Region],
+    /// Index of the enclosing container of each region.
+    parents: Vec<Option<usize>>,
+    buf: &'a [u8],
+    dec: Decoder,
+    findings: Vec<Finding>,
+}
+
+/// A stream payload and what the offset checks need to know about it.
+struct Stream {
+    /// Index of the payload blob region.
+    blob: usize,
+    /// Container holding the stream, e.g. a column or a shared dictionary child.
+    scope: Option<usize>,
+    info: BlobInfo,
+    /// Number of decoded values, `None` if the payload could not be decoded.
+    len: Option<usize>,
+}
+
+impl Validator<'_> {
+    fn report(&mut self, severity: Severity, idx: usize, message: String) {
+        let mut path = Vec::new();
+        let mut parent = self.parents[idx];
+        while let Some(p) = parent {
+            path.push(self.regions[p].label.clone());
+            parent = self.parents[p];
+        }
+        path.reverse();
+        self.findings.push(Finding {
+            severity,
+            message,
+            region: self.regions[idx].clone(),
+            path,
+        });
+    }
+
+    fn check_trailing_bytes(&mut self) {
+        let regions = self.regions;
+        for (idx, r) in regions.iter().enumerate() {
+            if r.kind == RegionKind::DataBlob && r.blob.is_none() && r.label == "trailing bytes" {
+                let msg = format!("{} unused bytes after the last column", r.len);
+                self.report(Severity::Error, idx, msg);
+            }
+        }
+    }
+
+    /// Column names must be unique among the columns of a layer or of a parent column.
+    fn check_column_names(&mut self) {
+        let mut seen: HashMap<(Option<usize>, &str), usize> = HashMap::new();
+        let mut duplicates = Vec::new();
+        let regions = self.regions;
+        for (idx, r) in regions.iter().enumerate() {
+            let Some(column) = self.parents[idx] else {
+                continue;
+            };
+            if r.container
+                || r.label != "name"
+                || !self.regions[column].label.starts_with("column[")
+            {
+                continue;
+            }
+            let Some(name) = r.value.as_deref() else {
+                continue;
+            };
+            if let Some(&first) = seen.get(&(self.parents[column], name)) {
+                duplicates.push((idx, first));
+            } else {
+                seen.insert((self.parents[column], name), idx);
+            }
+        }
+        for (idx, first) in duplicates {
+            let msg = format!(
+                "duplicate column name {}, first defined at 0x{:x}",
+                regions[idx].value.as_deref().unwrap_or_default(),
+                regions[first].offset
+            );
+            self.report(Severity::Error, idx, msg);
+        }
+    }
+
+    /// Check the encoding of every stream and decode its payload.
+    fn check_streams(&mut self) -> Vec<Stream> {
+        let mut streams = Vec::new();
+        let (regions, buf) = (self.regions, self.buf);
+        for (idx, r) in regions.iter().enumerate() {
+            let Some(info) = r.blob else {
+                continue;
+            };
+            let len = if let Some(msg) = encoding_error(info) {
+                self.report(Severity::Error, idx, msg);
+                None
+            } else {
+                let data = &buf[r.offset..r.offset + r.len];
+                match decoded_len(info, data, &mut self.dec) {
+                    Ok(len) => Some(len),
+                    Err(e) => {
+                        let msg = format!("{:?} payload cannot be decoded: {e}", info.hint);
+                        self.report(Severity::Error, idx, msg);
+                        None
+                    }
+                }
+            };
+            let scope = self.parents[idx].and_then(|stream| self.parents[stream]);
+            streams.push(Stream {
+                blob: idx,
+                scope,
+                info,
+                len,
+            });
+        }
+        streams
+    }
+
+    /// Check that offset streams stay within their dictionary or vertex buffer,
+    /// and that every dictionary entry is referenced.
+    fn check_offsets(&mut self, streams: &[Stream]) {
+        // Dictionary blob index -> which of its entries are referenced.
+        let mut used: Vec<(usize, Vec<bool>)> = Vec::new();
+        let (regions, buf) = (self.regions, self.buf);
+        for stream in streams {
+            let StreamType::Offset(typ) = stream.info.meta.stream_type else {
+                continue;
+            };
+            let Some(dict) = self.find_dictionary(streams, stream.scope, typ) else {
+                continue;
+            };
+            let Some(entries) = dict.len.map(|len| len / dict_width(dict.info)) else {
+                continue;
+            };
+            let r = &regions[stream.blob];
+            let data = &buf[r.offset..r.offset + r.len];
+            self.dec.reset_budget();
+            let Ok(offsets) =
+                RawStream::new(stream.info.meta, data).decode_ints::<u32>(&mut self.dec)
+            else {
+                continue;
+            };
+
+            let pos = used.iter().position(|(blob, _)| *blob == dict.blob);
+            let pos = pos.unwrap_or_else(|| {
+                used.push((dict.blob, vec![false; entries]));
+                used.len() - 1
+            });
+            let mut out_of_bounds = Vec::new();
+            for offset in offsets {
+                match used[pos].1.get_mut(offset.into_usize()) {
+                    Some(flag) => *flag = true,
+                    None => out_of_bounds.push(offset),
+                }
+            }
+            if let Some(&first) = out_of_bounds.first() {
+                let msg = format!(
+                    "{} of the {typ:?} offsets are out of bounds, e.g. {first} for {entries} entries at 0x{:x}",
+                    out_of_bounds.len(),
+                    regions[dict.blob].offset
+                );
+                self.report(Severity::Error, stream.blob, msg);
+            }
+        }
+
+        for (blob, flags) in used {
+            let unused = flags.iter().filter(|used| !**used).count();
+            if let Some(first) = flags.iter().position(|used| !used) {
+                let msg = format!(
+                    "{unused} of {} dictionary entries are never referenced, e.g. index {first}",
+                    flags.len()
+                );
+                self.report(Severity::Warning, blob, msg);
+            }
+        }
+    }
+
+    /// The dictionary an offset stream indexes into, searched from its own container
+    /// outwards up to the column.
+    fn find_dictionary<'s>(
+        &self,
+        streams: &'s [Stream],
+        mut scope: Option<usize>,
+        typ: OffsetType,
+    ) -> Option<&'s Stream> {
+        while let Some(s) = scope {
+            if self.regions[s].label == "column data" {
+                break;
+            }
+            let found = streams.iter().find(|d| {
+                d.scope == Some(s)
+                    && match typ {
+                        OffsetType::String => {
+                            d.info.meta.stream_type == StreamType::Length(LengthType::Dictionary)
+                        }
+                        OffsetType::Key => self.parents[d.blob]
+                            .is_some_and(|stream| self.regions[stream].label == "dictionary"),
+                        OffsetType::Vertex => matches!(
+                            d.info.meta.stream_type,
+                            StreamType::Data(DictionaryType::Vertex | DictionaryType::Morton)
+                        ),
+                        OffsetType::Index => false,
+                    }
+            });
+            if found.is_some() {
+                return found;
+            }
+            scope = self.parents[s];
+        }
+        None
+    }
+
+    /// Decode the layers and check that exterior rings have a positive area and holes a
+    /// negative one in tile coordinates, as required by the MVT specification.
+    fn check_winding(&mut self) {
+        let Ok(layers) = Parser::default().parse_layers(self.buf) else {
+            return;
+        };
+        let layer_regions: Vec<usize> = (0..self.regions.len())
+            .filter(|&idx| self.regions[idx].depth == 0)
+            .collect();
+        for (layer, &region) in layers.into_iter().zip(&layer_regions) {
+            let Layer::Tag01(layer) = layer else {
+                continue;
+            };
+            self.dec.reset_budget();
+            let tile = match layer.into_tile(&mut self.dec) {
+                Ok(tile) => tile,
+                Err(e) => {
+                    self.report(
+                        Severity::Error,
+                        region,
+                        format!("layer cannot be decoded: {e}"),
+                    );
+                    continue;
+                }
+            };
+            let geometry = (region..self.regions.len())
+                .take_while(|&idx| idx == region || self.regions[idx].depth > 0)
+                .find(|&idx| {
+                    let label = &self.regions[idx].label;
+                    label.starts_with("column[")
+                        && label
+                            .split(' ')
+                            .nth(1)
+                            .is_some_and(|t| t.starts_with("Geometry"))
+                })
+                .unwrap_or(region);
+            for (fi, feature) in tile.features.iter().enumerate() {
+                let polygons = match &feature.geometry {
+                    Geometry::Polygon(p) => std::slice::from_ref(p),
+                    Geometry::MultiPolygon(mp) => mp.0.as_slice(),
+                    _ => continue,
+                };
+                if let Some(problem) = polygons.iter().enumerate().find_map(winding_error) {
+                    self.report(
+                        Severity::Warning,
+                        geometry,
+                        format!("feature[{fi}] {problem}"),
+                    );
+                }
+            }
+        }
+    }
+
+    /// Report where and why the walk stopped.
+    fn parse_error(&mut self, err: &WalkError) {
+        let stop = self
+            .regions
+            .iter()
+            .filter(|r| !r.container)
+            .map(|r| r.offset + r.len)
+            .max()
+            .unwrap_or(0);
+        self.findings.push(Finding {
+            severity: Severity::Error,
+            message: format!("parse error: {}", err.error),
+            region: Region {
+                offset: stop,
+                len: self.buf.len() - stop,
+                depth: err.open.len(),
+                label: "unparsed".to_string(),
+                value: None,
+                bits: Vec::new(),
+                kind: RegionKind::DataBlob,
+                container: false,
+                blob: None,
+            },
+            path: err
+                .open
+                .iter()
+                .map(|&idx| self.regions[idx].label.clone())
+                .collect(),
+        });
+    }
+}
+
+/// Enclosing container of each region, from the pre-order depths.
+fn parents(regions: &[Region]) -> Vec<Option<usize>> {
+    let mut stack: Vec<usize> = Vec::new();
+    let mut parents = Vec::with_capacity(regions.len());
+    for (idx, r) in regions.iter().enumerate() {
+        while stack.last().is_some_and(|&p| regions[p].depth >= r.depth) {
+            stack.pop();
+        }
+        parents.push(stack.last().copied());
+        if r.container {
+            stack.push(idx);
+        }
+    }
+    parents
+}
+
+/// Stream type / encoding combinations that the spec does not allow.
+fn encoding_error(info: BlobInfo) -> Option<String> {
+    let BlobInfo { meta, hint } = info;
+    let st = meta.stream_type;
+    let logical = meta.encoding.logical;
+    let wide = matches!(hint, DecodeHint::I64 | DecodeHint::U64 | DecodeHint::F64);
+    if meta.encoding.physical == PhysicalEncoding::FastPFor256 && wide {
+        return Some(format!("FastPFor256 cannot encode {hint:?} values"));
+    }
+    let allowed = match logical {
+        LogicalEncoding::Morton(_)
+        | LogicalEncoding::MortonDelta(_)
+        | LogicalEncoding::MortonRle(_) => st == StreamType::Data(DictionaryType::Morton),
+        LogicalEncoding::ComponentwiseDelta => st == StreamType::Data(DictionaryType::Vertex),
+        LogicalEncoding::PseudoDecimal => matches!(hint, DecodeHint::F32 | DecodeHint::F64),
+        _ => true,
+    };
+    if !allowed {
+        return Some(format!(
+            "{logical:?} is not allowed on a {st:?} stream of {hint:?} values"
+        ));
+    }
+    match (hint == DecodeHint::Presence, st == StreamType::Present) {
+        (true, false) => Some(format!("presence stream has stream type {st:?}")),
+        (false, true) => Some(format!("{hint:?} values in a Present stream")),
+        _ => None,
+    }
+}
+
+/// Decode a payload the way the hexdump renderer does, returning the number of values.
+fn decoded_len(info: BlobInfo, data: &[u8], dec: &mut Decoder) -> MltResult<usize> {
+    // Bound memory per stream; the values are dropped immediately.
+    dec.reset_budget();
+    let stream = RawStream::new(info.meta, data);
+    Ok(match info.hint {
+        DecodeHint::Presence => stream.decode_bitvec(dec)?.len(),
+        DecodeHint::Bool => stream.decode_bools(dec)?.len(),
+        DecodeHint::I32 => stream.decode_ints::<i32>(dec)?.len(),
+        DecodeHint::Vec3 => decode_vec3_vertices(stream, dec)?.len(),
+        DecodeHint::U32 => stream.decode_ints::<u32>(dec)?.len(),
+        DecodeHint::I64 => stream.decode_ints::<i64>(dec)?.len(),
+        DecodeHint::U64 => stream.decode_ints::<u64>(dec)?.len(),
+        DecodeHint::F32 => stream.decode_floats::<f32>(dec)?.len(),
+        DecodeHint::F64 => stream.decode_floats::<f64>(dec)?.len(),
+        DecodeHint::Bytes => data.len(),
+    })
+}
+
+/// Decoded values per dictionary entry: two or three per vertex, one otherwise.
+fn dict_width(info: BlobInfo) -> usize {
+    match (info.meta.stream_type, info.hint) {
+        (_, DecodeHint::Vec3) => 3,
+        (StreamType::Data(DictionaryType::Vertex | DictionaryType::Morton), _) => 2,
+        _ => 1,
+    }
+}
+
+/// Describe the first ring of a polygon that breaks the MVT winding order.
+fn winding_error((pi, polygon): (usize, &Polygon<i32>)) -> Option<String> {
+    let exterior = ring_area(polygon.exterior());
+    if exterior <= 0 {
+        let problem = if exterior == 0 { "zero" } else { "negative" };
+        return Some(format!("polygon[{pi}] exterior ring has {problem} area"));
+    }
+    let ri = polygon
+        .interiors()
+        .iter()
+        .position(|ring| ring_area(ring) >= 0)?;
+    Some(format!(
+        "polygon[{pi}] interior ring[{ri}] is not wound opposite to the exterior ring"
+    ))
+}
+
+/// Twice the signed area of a ring by the surveyor's formula in tile coordinates
+/// (y pointing down), positive for rings that appear clockwise.
+fn ring_area(ring: &LineString<i32>) -> i64 {
+    let Some(&last) = ring.0.last() else {
+        return 0;
+    };
+    let mut prev = last;
+    let mut area = 0;
+    for &c in &ring.0 {
+        area += i64::from(prev.x) * i64::from(c.y) - i64::from(c.x) * i64::from(prev.y);
+        prev = c;
+    }
+    area
+}
+
+#[cfg(test)]
+mod tests {
+    use geo_types::{point, polygon};
+    use integer_encoding::VarInt as _;
+
+    use super::*;
+    use crate::encoder::EncoderConfig;
+    use crate::{PropKind, PropValue, TileLayer};
+
+    fn tile(polygon: Polygon<i32>) -> Vec<u8> {
+        let mut layer = TileLayer::builder("water", 4096).unwrap();
+        let class = layer.add_property("class", PropKind::Str).unwrap();
+        for (geometry, value) in [
+            (polygon.into(), "lake"),
+            (point!(x: 5, y: 5).into(), "pond"),
+        ] {
+            let mut feature = layer.feature(geometry);
+            feature
+                .property(class, PropValue::Str(Some(value.into())))
+                .unwrap();
+            feature.finish().unwrap();
+        }
+        layer.finish().encode(EncoderConfig::default()).unwrap()
+    }
+
+    fn square() -> Polygon<i32> {
+        polygon![(x: 0, y: 0), (x: 10, y: 0), (x: 10, y: 10), (x: 0, y: 10)]
+    }
+
+    #[test]
+    fn encoded_tile_is_clean() {
+        let findings = validate_tile(&tile(square()));
+        assert!(findings.is_empty(), "{findings:#?}");
+    }
+
+    #[test]
+    fn trailing_bytes_are_reported() {
+        let mut data = tile(square());
+        let (size, n) = u32::decode_var(&data).unwrap();
+        let mut patched = (size + 2).encode_var_vec();
+        patched.extend_from_slice(&data[n..]);
+        patched.extend_from_slice(&[0, 0]);
+        data = patched;
+
+        let findings = validate_tile(&data);
+        assert_eq!(findings.len(), 1, "{findings:#?}");
+        assert_eq!(findings[0].severity, Severity::Error);
+        assert_eq!(findings[0].region.offset, data.len() - 2);
+        assert_eq!(findings[0].path, ["layer[0]"]);
+    }
+
+    #[test]
+    fn non_canonical_varint_stops_the_walk() {
+        let data = tile(square());
+        let (size, n) = u32::decode_var(&data).unwrap();
+        // Pad the layer size with a redundant zero continuation byte.
+        let mut patched = size.encode_var_vec();
+        *patched.last_mut().unwrap() |= 0x80;
+        patched.push(0);
+        patched.extend_from_slice(&data[n..]);
+
+        let findings = validate_tile(&patched);
+        assert_eq!(findings.len(), 1, "{findings:#?}");
+        assert_eq!(findings[0].region.offset, 0);
+        assert_eq!(findings[0].path, ["layer[0]"]);
+        assert!(findings[0].message.contains("non-canonical"));
+    }
+
+    #[test]
+    fn reversed_rings_are_reported() {
+        let exterior = square().exterior().0.iter().rev().copied().collect();
+        let findings = validate_tile(&tile(Polygon::new(LineString(exterior), vec![])));
+        assert_eq!(findings.len(), 1, "{findings:#?}");
+        assert_eq!(findings[0].severity, Severity::Warning);
+        assert!(
+            findings[0]
+                .message
+                .ends_with("polygon[0] exterior ring has negative area")
+        );
+        assert!(findings[0].region.label.contains("Geometry"));
+    }
+}
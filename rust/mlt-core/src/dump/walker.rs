@@ -13,23 +13,43 @@ use crate::{MltError, MltRefResult, MltResult, Parser};
 ///
 /// The returned tree references offsets into `buf`; keep `buf` alive to render it.
 pub fn annotate_tile(buf: &[u8]) -> MltResult<DumpTree> {
+    let (tree, res) = annotate_partial(buf);
+    res.map(|()| tree).map_err(|e| e.error)
+}
+
+/// Why [`annotate_partial`] stopped early.
+pub(super) struct WalkError {
+    pub(super) error: MltError,
+    /// Indices of the containers that were still open, outermost first.
+    pub(super) open: Vec<usize>,
+}
+
+/// Like [`annotate_tile`], but keep the regions recorded before a parse error.
+pub(super) fn annotate_partial(buf: &[u8]) -> (DumpTree, Result<(), WalkError>) {
     let mut w = Walker {
         buf,
         out: Vec::new(),
         depth: 0,
+        open: Vec::new(),
         parser: Parser::default(),
     };
-    w.walk_tile()?;
-    Ok(DumpTree {
+    let res = w.walk_tile().map_err(|error| WalkError {
+        error,
+        open: w.open.clone(),
+    });
+    let tree = DumpTree {
         buf_len: buf.len(),
         regions: w.out,
-    })
+    };
+    (tree, res)
 }
 
 pub(super) struct Walker<'a> {
     pub(super) buf: &'a [u8],
     pub(super) out: Vec<Region>,
     pub(super) depth: usize,
+    /// Containers opened but not yet closed.
+    pub(super) open: Vec<usize>,
     /// Throwaway budget for the authoritative stream-header parsers.
     pub(super) parser: Parser,
 }
@@ -58,12 +78,14 @@ impl<'a> Walker<'a> {
             blob: None,
         });
         self.depth += 1;
+        self.open.push(idx);
         idx
     }
 
     /// Close the container opened at `idx`, ending it at `after`.
     pub(super) fn close(&mut self, idx: usize, after: &'a [u8]) {
         self.depth -= 1;
+        self.open.pop();
         let start = self.out[idx].offset;
         self.out[idx].len = self.off(after) - start;
     }
@@ -193,7 +215,7 @@ impl<'a> Walker<'a> {
         let (rest, body) = take(input, body_len)?;
 
         match tag {
-            1 => self.walk_layer01(body)?,
+            1 | 2 => self.walk_layer01(body)?,
             _ => self.raw_blob(body, body.len(), format!("value (Unknown tag 0x{tag:02X})")),
         }
 
@@ -206,6 +228,7 @@ impl<'a> Walker<'a> {
 fn tag_label(tag: u8) -> String {
     match tag {
         1 => "0x01 -> Tag01".into(),
+        2 => "0x02 -> Tag01 (nested columns)".into(),
         other => format!("0x{other:02X} -> Unknown"),
     }
 }
@@ -1,4 +1,4 @@
-//! Annotating walker for the tag `0x01` (v1) layer body.
+//! Annotating walker for the tag `0x01` (v1) layer body, also used for tag `0x02`.
 //!
 //! Mirrors the wire layout of [`crate::decoder`], but records an annotated
 //! [`Region`](super::model::Region) per field instead of building decoded
@@ -15,8 +15,8 @@ use super::model::{BitField, BlobInfo, DecodeHint};
 use super::walker::Walker;
 use crate::codecs::varint::parse_varint;
 use crate::decoder::stream::header01::parse_stream_meta;
-use crate::decoder::{Column, ColumnType, DictionaryType, StreamType};
-use crate::utils::{parse_string, take};
+use crate::decoder::{Column, ColumnType, DictionaryType, NestedMask, OffsetType, StreamType};
+use crate::utils::{parse_string, parse_u8, take};
 use crate::wire::{LogicalEncoding, LogicalTechnique, PhysicalEncoding, StreamMeta};
 use crate::{MltError, MltResult};
 
@@ -67,7 +67,7 @@ impl<'a> Walker<'a> {
         }
         let mut cols = Vec::with_capacity(column_count.into_usize());
         for i in 0..column_count {
-            let (rest, col) = self.walk_column_def(input, i)?;
+            let (rest, col) = self.walk_column_def(input, i, false)?;
             input = rest;
             cols.push(col);
         }
@@ -75,9 +75,14 @@ impl<'a> Walker<'a> {
         Ok((input, cols))
     }
 
-    /// Mirror `Column::from_bytes` (plus inline `SharedDict` children), split into
+    /// Mirror `Column::from_bytes` (plus inline `SharedDict` / `Nested` children), split into
     /// `[type u8][optional name]` (and child defs).
-    fn walk_column_def(&mut self, input: &'a [u8], i: u32) -> MltResult<(&'a [u8], Column<'a>)> {
+    fn walk_column_def(
+        &mut self,
+        input: &'a [u8],
+        i: u32,
+        is_child: bool,
+    ) -> MltResult<(&'a [u8], Column<'a>)> {
         let ci = self.open(input, format!("column[{i}]"));
 
         // Column-type byte, with the optional-flag bit broken out.
@@ -116,7 +121,15 @@ impl<'a> Walker<'a> {
         };
 
         let mut children = Vec::new();
-        if typ == ColumnType::SharedDict {
+        if matches!(
+            typ,
+            ColumnType::SharedDict
+                | ColumnType::Nested
+                | ColumnType::VertexScope
+                | ColumnType::RangeMap
+                | ColumnType::OptRangeMap
+                | ColumnType::Struct
+        ) {
             let (rest, child_count) = self.field(
                 input,
                 "child_count",
@@ -124,12 +137,17 @@ impl<'a> Walker<'a> {
                 |v| Some(v.to_string()),
             )?;
             input = rest;
+            if is_child && child_count != 0 {
+                return Err(MltError::NotImplemented(
+                    "nested column with nested children",
+                ));
+            }
             if input.len() < child_count.into_usize() {
                 return Err(MltError::BufferUnderflow(child_count, input.len()));
             }
             children.reserve(child_count.into_usize());
             for j in 0..child_count {
-                let (rest, child) = self.walk_column_def(input, j)?;
+                let (rest, child) = self.walk_column_def(input, j, true)?;
                 input = rest;
                 children.push(child);
             }
@@ -167,8 +185,13 @@ impl<'a> Walker<'a> {
                 input = self.walk_optional(input, typ)?;
                 input = self.walk_stream(input, false, "id", |_| DecodeHint::U64)?.0;
             }
-            C::Geometry => {
-                input = self.walk_geometry(input)?;
+            C::Geometry | C::GeometryZ => {
+                input = self.walk_geometry(input, typ == C::GeometryZ)?;
+            }
+            C::VertexScope => {
+                for (j, child) in col.children.iter().enumerate() {
+                    input = self.walk_column_data(input, j, child)?;
+                }
             }
             C::Bool | C::OptBool => {
                 input = self.walk_optional(input, typ)?;
@@ -202,22 +225,37 @@ impl<'a> Walker<'a> {
             }
             C::F32 | C::OptF32 => {
                 input = self.walk_optional(input, typ)?;
-                input = self
-                    .walk_stream(input, false, "data", |_| DecodeHint::F32)?
-                    .0;
+                input = self.walk_floats(input, DecodeHint::F32)?;
             }
             C::F64 | C::OptF64 => {
                 input = self.walk_optional(input, typ)?;
-                input = self
-                    .walk_stream(input, false, "data", |_| DecodeHint::F64)?
-                    .0;
+                input = self.walk_floats(input, DecodeHint::F64)?;
             }
             C::Str | C::OptStr => {
                 input = self.walk_str(input, typ)?;
             }
+            C::RangeMap | C::OptRangeMap => {
+                input = self.walk_optional(input, typ)?;
+                input = self
+                    .walk_stream(input, false, "lengths", |_| DecodeHint::U32)?
+                    .0;
+                input = self
+                    .walk_stream(input, false, "ranges", |_| DecodeHint::F64)?
+                    .0;
+                let Some(values) = col.children.first() else {
+                    return Err(MltError::RangeMapValueColumnCount(0));
+                };
+                input = self.walk_column_data(input, 0, values)?;
+            }
             C::SharedDict => {
                 input = self.walk_shared_dict(input, col)?;
             }
+            C::Struct => {
+                input = self.walk_struct(input, col)?;
+            }
+            C::Nested => {
+                input = self.walk_nested(input)?;
+            }
         }
 
         self.close(gi, input);
@@ -235,8 +273,24 @@ impl<'a> Walker<'a> {
         }
     }
 
+    /// Mirror `parse_float_column`: one data stream, or `Key` offsets + dictionary data.
+    fn walk_floats(&mut self, input: &'a [u8], hint: DecodeHint) -> MltResult<&'a [u8]> {
+        let is_dict = |t: StreamType| t == StreamType::Offset(OffsetType::Key);
+        let (input, meta) = self.walk_stream(input, false, "data", |t| {
+            if is_dict(t) { DecodeHint::U32 } else { hint }
+        })?;
+        if !is_dict(meta.stream_type) {
+            return Ok(input);
+        }
+        Ok(self.walk_stream(input, false, "dictionary", |_| hint)?.0)
+    }
+
     /// Mirror `parse_geometry_column`: `[varint stream_count]` + meta stream + rest.
-    fn walk_geometry(&mut self, input: &'a [u8]) -> MltResult<&'a [u8]> {
+    fn walk_geometry(&mut self, input: &'a [u8], has_z: bool) -> MltResult<&'a [u8]> {
+        let hint = |st: StreamType| match st {
+            StreamType::Data(_) if has_z => DecodeHint::Vec3,
+            _ => geom_hint(st),
+        };
         let (mut input, stream_count) = self.field(
             input,
             "stream_count",
@@ -249,7 +303,7 @@ impl<'a> Walker<'a> {
         input = self.walk_stream(input, false, "meta", geom_hint)?.0;
         for j in 0..stream_count - 1 {
             input = self
-                .walk_stream(input, false, &format!("stream[{j}]"), geom_hint)?
+                .walk_stream(input, false, &format!("stream[{j}]"), hint)?
                 .0;
         }
         Ok(input)
@@ -330,6 +384,108 @@ impl<'a> Walker<'a> {
         Ok(input)
     }
 
+    /// Mirror `parse_struct_column`: `[varint stream_count]` + dictionary streams, then each
+    /// child as `[optional present][offsets]` if it shares the dictionary, else as a column.
+    fn walk_struct(&mut self, input: &'a [u8], col: &Column<'a>) -> MltResult<&'a [u8]> {
+        let (mut input, stream_count) = self.field(
+            input,
+            "stream_count",
+            |i| parse_varint::<u32>(i),
+            |v| Some(v.to_string()),
+        )?;
+        for j in 0..stream_count {
+            input = self
+                .walk_stream(input, false, &format!("dict_stream[{j}]"), auto_hint)?
+                .0;
+        }
+        for (j, child) in col.children.iter().enumerate() {
+            if stream_count != 0 && matches!(child.typ, ColumnType::Str | ColumnType::OptStr) {
+                let cci = self.open(input, format!("child[{j}] {:?}", child.typ));
+                input = self.walk_optional(input, child.typ)?;
+                input = self.walk_stream(input, false, "offsets", auto_hint)?.0;
+                self.close(cci, input);
+            } else {
+                input = self.walk_column_data(input, j, child)?;
+            }
+        }
+        Ok(input)
+    }
+
+    /// Mirror `parse_nested_column`: `[varint stream_count]`, then (if non-zero) the mask byte,
+    /// the length stream, the dictionaries announced by the mask, presence, and the value stream.
+    fn walk_nested(&mut self, input: &'a [u8]) -> MltResult<&'a [u8]> {
+        let (mut input, stream_count) = self.field(
+            input,
+            "stream_count",
+            |i| parse_varint::<u32>(i),
+            |v| Some(v.to_string()),
+        )?;
+        if stream_count == 0 {
+            return Ok(input);
+        }
+        let (rest, mask) = self.field(input, "mask", parse_u8, |m| Some(format!("0x{m:02X}")))?;
+        input = self
+            .walk_stream(rest, false, "lengths", |_| DecodeHint::U32)?
+            .0;
+        let mut expected = 1_u32;
+
+        if mask & NestedMask::STRING != 0 {
+            let (rest, count) = self.field(input, "string_stream_count", parse_u8, |v| {
+                Some(v.to_string())
+            })?;
+            input = rest;
+            for j in 0..count {
+                input = self
+                    .walk_stream(input, false, &format!("string_stream[{j}]"), auto_hint)?
+                    .0;
+            }
+            expected += u32::from(count);
+        }
+
+        // A 32-bit dictionary takes precedence over its 64-bit counterpart, as in the parser
+        let int_hint = if mask & NestedMask::INT32 == 0 {
+            DecodeHint::I64
+        } else {
+            DecodeHint::I32
+        };
+        let uint_hint = if mask & NestedMask::UINT32 == 0 {
+            DecodeHint::U64
+        } else {
+            DecodeHint::U32
+        };
+        let dictionaries = [
+            (NestedMask::INT32 | NestedMask::INT64, "ints", int_hint),
+            (NestedMask::UINT32 | NestedMask::UINT64, "uints", uint_hint),
+            (NestedMask::FLOAT, "floats", DecodeHint::F32),
+            (NestedMask::DOUBLE, "doubles", DecodeHint::F64),
+        ];
+        for (bits, label, hint) in dictionaries {
+            if mask & bits != 0 {
+                input = self.walk_stream(input, false, label, |_| hint)?.0;
+                expected += 1;
+            }
+        }
+        if mask & NestedMask::PRESENCE != 0 {
+            input = self
+                .walk_stream(input, true, "present", |_| DecodeHint::Presence)?
+                .0;
+            expected += 1;
+        }
+        if stream_count > expected {
+            input = self
+                .walk_stream(input, false, "values", |_| DecodeHint::U32)?
+                .0;
+            expected += 1;
+        }
+        if stream_count != expected {
+            return Err(MltError::InvalidNestedStreamCount {
+                actual: stream_count,
+                expected,
+            });
+        }
+        Ok(input)
+    }
+
     /// Walk one stream: the annotated header (via the authoritative
     /// [`parse_stream_meta`]) followed by the payload blob.
     fn walk_stream(
@@ -1,6 +1,9 @@
 #[cfg(any(test, feature = "__private"))]
 use crate::decoder::StreamMeta;
-use crate::decoder::{ParsedScalar, ParsedSharedDict, ParsedStrings};
+use crate::decoder::{
+    NestedValue, ParsedNested, ParsedRangeMap, ParsedScalar, ParsedSharedDict, ParsedStrings,
+    ParsedStruct, ParsedStructChild, PropValue,
+};
 #[cfg(any(test, feature = "__private"))]
 use crate::encoder::EncodedStream;
 use crate::{Analyze, StatType};
@@ -34,6 +37,25 @@ impl Analyze for ParsedSharedDict<'_> {
     }
 }
 
+impl Analyze for ParsedStruct<'_> {
+    fn collect_statistic(&self, stat: StatType) -> usize {
+        let meta = if stat == StatType::DecodedMetaSize {
+            self.prefix.len()
+        } else {
+            0
+        };
+        let children = self
+            .children
+            .iter()
+            .map(|child| match child {
+                ParsedStructChild::Shared(_) => 0,
+                ParsedStructChild::Column(column) => column.collect_statistic(stat),
+            })
+            .sum::<usize>();
+        meta + self.strings.collect_statistic(stat) + children
+    }
+}
+
 impl Analyze for ParsedStrings<'_> {
     fn collect_statistic(&self, stat: StatType) -> usize {
         let meta = if stat == StatType::DecodedMetaSize {
@@ -44,3 +66,80 @@ impl Analyze for ParsedStrings<'_> {
         meta + self.dense_values().collect_statistic(stat)
     }
 }
+
+impl Analyze for ParsedRangeMap<'_> {
+    fn collect_statistic(&self, stat: StatType) -> usize {
+        let meta = if stat == StatType::DecodedMetaSize {
+            self.name.len()
+        } else {
+            0
+        };
+        meta + self
+            .entries
+            .iter()
+            .map(|(start, end, value)| {
+                start.collect_statistic(stat)
+                    + end.collect_statistic(stat)
+                    + value.collect_statistic(stat)
+            })
+            .sum::<usize>()
+    }
+}
+
+impl Analyze for PropValue {
+    fn collect_statistic(&self, stat: StatType) -> usize {
+        match self {
+            Self::Bool(v) => v.collect_statistic(stat),
+            Self::I8(v) => v.collect_statistic(stat),
+            Self::U8(v) => v.collect_statistic(stat),
+            Self::I32(v) | Self::Date(v) => v.collect_statistic(stat),
+            Self::U32(v) => v.collect_statistic(stat),
+            Self::I64(v) | Self::Timestamp(v) => v.collect_statistic(stat),
+            Self::U64(v) => v.collect_statistic(stat),
+            Self::F32(v) => v.collect_statistic(stat),
+            Self::F64(v) => v.collect_statistic(stat),
+            Self::Str(v) | Self::Json(v) => v.collect_statistic(stat),
+            Self::Nested(v) => v.collect_statistic(stat),
+            Self::Binary(v) => v.as_ref().map_or(0, Vec::len),
+            Self::RangeMap(v) => v.as_ref().map_or(0, |entries| {
+                entries
+                    .iter()
+                    .map(|(_, _, value)| 2 * size_of::<f64>() + value.collect_statistic(stat))
+                    .sum()
+            }),
+        }
+    }
+}
+
+impl Analyze for NestedValue {
+    fn collect_statistic(&self, stat: StatType) -> usize {
+        match self {
+            Self::Bool(v) => v.collect_statistic(stat),
+            Self::I64(v) => v.collect_statistic(stat),
+            Self::U64(v) => v.collect_statistic(stat),
+            Self::F32(v) => v.collect_statistic(stat),
+            Self::F64(v) => v.collect_statistic(stat),
+            Self::Str(v) => v.collect_statistic(stat),
+            Self::List(v) => v.collect_statistic(stat),
+            Self::Map(v) => v
+                .iter()
+                .map(|(key, value)| key.len() + value.collect_statistic(stat))
+                .sum(),
+        }
+    }
+}
+
+impl Analyze for ParsedNested<'_> {
+    fn collect_statistic(&self, stat: StatType) -> usize {
+        let meta = if stat == StatType::DecodedMetaSize {
+            self.prefix.len() + self.items.iter().map(|v| v.suffix.len()).sum::<usize>()
+        } else {
+            0
+        };
+        meta + self
+            .items
+            .iter()
+            .map(|v| v.values.collect_statistic(stat))
+            .sum::<usize>()
+    }
+}
@@ -0,0 +1,170 @@
+use integer_encoding::VarIntWriter as _;
+
+use crate::decoder::{Layer01, TileLayer};
+use crate::encoder::EncoderConfig;
+use crate::utils::{BinarySerializer as _, checked_sum2};
+use crate::{Layer, MltError, MltResult};
+
+/// Builds a tile from a mix of freshly encoded layers and layers parsed by
+/// [`Parser::parse_layers`](crate::Parser::parse_layers).
+///
+/// Parsed layers are copied byte for byte, so layers that did not change can be kept,
+/// renamed or moved into another tile without a decode/re-encode round trip:
+///
+/// ```
+/// use mlt_core::encoder::{EncoderConfig, TileAssembler};
+/// use mlt_core::{Layer, Parser};
+///
+/// # fn rewrite(tile: &[u8], overlay: mlt_core::TileLayer) -> mlt_core::MltResult<Vec<u8>> {
+/// let mut out = TileAssembler::default();
+/// for layer in Parser::default().parse_layers(tile)? {
+///     match &layer {
+///         Layer::Tag01(l) if l.name() == "traffic" => {}
+///         Layer::Tag01(l) if l.name() == "roads" => out.push_renamed(l, "streets")?,
+///         _ => out.push_layer(&layer)?,
+///     }
+/// }
+/// out.push_tile_layer(overlay, EncoderConfig::default())?;
+/// Ok(out.finish())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct TileAssembler {
+    data: Vec<u8>,
+}
+
+impl TileAssembler {
+    /// Append a parsed layer unchanged, including unknown layers.
+    pub fn push_layer(&mut self, layer: &Layer<'_>) -> MltResult<()> {
+        match layer {
+            Layer::Tag01(l) => self.push_renamed(l, l.name),
+            Layer::Unknown(u) => self.push_record(u.tag, &[], u.value),
+        }
+    }
+
+    /// Append a parsed layer under a new name; every column is copied verbatim.
+    pub fn push_renamed(&mut self, layer: &Layer01<'_>, name: &str) -> MltResult<()> {
+        if name.is_empty() {
+            return Err(MltError::MissingLayerName);
+        }
+        let mut header = Vec::with_capacity(name.len() + 5);
+        header.write_string(name)?;
+        self.push_record(layer.tag, &header, layer.body)
+    }
+
+    /// Encode `layer` with `cfg` and append it. Layers without features add nothing.
+    pub fn push_tile_layer(&mut self, layer: TileLayer, cfg: EncoderConfig) -> MltResult<()> {
+        let bytes = layer.encode(cfg)?;
+        self.data.extend_from_slice(&bytes);
+        Ok(())
+    }
+
+    /// Append complete, already encoded layer records,
+    /// e.g. the output of [`TileLayer::encode`] or a whole tile.
+    pub fn push_encoded(&mut self, bytes: &[u8]) {
+        self.data.extend_from_slice(bytes);
+    }
+
+    /// Number of tile bytes assembled so far.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Return the assembled tile.
+    #[must_use]
+    pub fn finish(self) -> Vec<u8> {
+        self.data
+    }
+
+    /// Write a `[varint(size)][tag][header][body]` layer record.
+    fn push_record(&mut self, tag: u8, header: &[u8], body: &[u8]) -> MltResult<()> {
+        let len = checked_sum2(u32::try_from(header.len())?, u32::try_from(body.len())?)?;
+        self.data.write_varint(checked_sum2(len, 1)?)?;
+        self.data.write_u8(tag)?;
+        self.data.extend_from_slice(header);
+        self.data.extend_from_slice(body);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use geo_types::Point;
+
+    use super::*;
+    use crate::decoder::{PropKind, PropValue};
+    use crate::encoder::{EncodedUnknown, Encoder};
+    use crate::{Decoder, Parser};
+
+    fn layer(name: &str, value: u32) -> TileLayer {
+        let mut layer = TileLayer::builder(name, 4096).unwrap();
+        let key = layer.add_property("value", PropKind::U32).unwrap();
+        let mut feature = layer.feature(Point::new(1, 2).into());
+        feature.property(key, PropValue::U32(Some(value))).unwrap();
+        feature.finish().unwrap();
+        layer.finish()
+    }
+
+    fn decode(tile: &[u8]) -> Vec<TileLayer> {
+        let mut dec = Decoder::default();
+        Parser::default()
+            .parse_layers(tile)
+            .unwrap()
+            .into_iter()
+            .filter_map(Layer::into_layer01)
+            .map(|l| l.decode_all(&mut dec).unwrap().into_tile(&mut dec).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn untouched_layers_are_copied_verbatim() {
+        let roads = layer("roads", 1).encode(EncoderConfig::default()).unwrap();
+        let unknown = EncodedUnknown::new(7, vec![1, 2, 3])
+            .unwrap()
+            .write_to(Encoder::default())
+            .unwrap()
+            .into_raw_bytes();
+        let tile = [roads.as_slice(), &unknown].concat();
+
+        let mut out = TileAssembler::default();
+        for parsed in Parser::default().parse_layers(&tile).unwrap() {
+            out.push_layer(&parsed).unwrap();
+        }
+        assert_eq!(out.finish(), tile);
+    }
+
+    #[test]
+    fn layers_are_renamed_dropped_and_added() {
+        let tile = [
+            layer("roads", 1).encode(EncoderConfig::default()).unwrap(),
+            layer("traffic", 2)
+                .encode(EncoderConfig::default())
+                .unwrap(),
+        ]
+        .concat();
+
+        let mut out = TileAssembler::default();
+        for parsed in Parser::default().parse_layers(&tile).unwrap() {
+            match &parsed {
+                Layer::Tag01(l) if l.name() == "traffic" => {}
+                Layer::Tag01(l) => out.push_renamed(l, "streets").unwrap(),
+                Layer::Unknown(_) => out.push_layer(&parsed).unwrap(),
+            }
+        }
+        out.push_tile_layer(layer("traffic", 3), EncoderConfig::default())
+            .unwrap();
+
+        let mut expected_streets = layer("roads", 1);
+        expected_streets.name = "streets".to_string();
+        assert_eq!(
+            decode(&out.finish()),
+            vec![expected_streets, layer("traffic", 3)]
+        );
+    }
+}
@@ -0,0 +1,683 @@
+//! Clipping features to a buffered tile rectangle
+
+use geo::{BoundingRect as _, MapCoords as _};
+use geo_types::{
+    Coord, Geometry, GeometryCollection, LineString, MultiLineString, MultiPoint, MultiPolygon,
+    Point, Polygon, Rect,
+};
+
+use crate::decoder::{TileFeature, TileLayer, VertexValues};
+use crate::encoder::geometry::ring_len;
+
+impl TileLayer {
+    /// Clip every feature to `[-buffer, extent + buffer]` on both axes, dropping
+    /// features that are left without geometry. The rectangle border is inside.
+    ///
+    /// Points outside the rectangle are removed, lines are split into a
+    /// `MultiLineString` where they leave and re-enter it, and polygon rings are
+    /// clipped keeping their winding order. Vertices created on the rectangle
+    /// border interpolate Z and take vertex-scoped values from the nearer
+    /// original vertex. Features fully inside the rectangle are left untouched.
+    pub fn clip(&mut self, buffer: u32) {
+        self.clip_to_rect(self.buffered_rect(buffer));
+    }
+
+    /// Clip every feature to `rect` (borders included), see [`TileLayer::clip`].
+    pub fn clip_to_rect(&mut self, rect: Rect<i32>) {
+        self.clip_transformed(Transform::IDENTITY, rect);
+    }
+
+    /// Map every coordinate `c` to `c * scale - offset`, then clip like [`TileLayer::clip`].
+    ///
+    /// The border crossings are computed in the mapped space, so edges leaving the
+    /// rectangle keep their direction however large `scale` is.
+    pub(super) fn clip_scaled(&mut self, scale: f64, offset: Coord<f64>, buffer: u32) {
+        let rect = self.buffered_rect(buffer);
+        self.clip_transformed(Transform { scale, offset }, rect);
+    }
+
+    /// `[-buffer, extent + buffer]` on both axes.
+    fn buffered_rect(&self, buffer: u32) -> Rect<i32> {
+        let buffer = i64::from(buffer);
+        let min = saturate(-buffer);
+        let max = saturate(i64::from(self.extent.get()) + buffer);
+        Rect::new((min, min), (max, max))
+    }
+
+    fn clip_transformed(&mut self, transform: Transform, rect: Rect<i32>) {
+        let clipper = Clipper {
+            min: rect.min(),
+            max: rect.max(),
+            transform,
+        };
+        self.features
+            .retain_mut(|feature| clipper.clip_feature(feature));
+    }
+}
+
+fn saturate(value: i64) -> i32 {
+    i32::try_from(value).unwrap_or(if value < 0 { i32::MIN } else { i32::MAX })
+}
+
+/// A vertex of a geometry being rewritten, kept in floating point until the output is rounded.
+#[derive(Debug, Clone, Copy)]
+pub(super) struct Vertex {
+    pub(super) x: f64,
+    pub(super) y: f64,
+    pub(super) z: f64,
+    /// Index of the nearest stored vertex of the input feature.
+    pub(super) src: usize,
+}
+
+impl Vertex {
+    fn get(self, axis: Axis) -> f64 {
+        match axis {
+            Axis::X => self.x,
+            Axis::Y => self.y,
+        }
+    }
+
+    pub(super) fn coord(self) -> Coord<i32> {
+        Coord {
+            x: round(self.x),
+            y: round(self.y),
+        }
+    }
+}
+
+#[expect(
+    clippy::cast_possible_truncation,
+    reason = "clipped values lie between two i32 values"
+)]
+pub(super) fn round(value: f64) -> i32 {
+    value.round() as i32
+}
+
+/// Maps input coordinates `c` to `c * scale - offset` before clipping.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Transform {
+    scale: f64,
+    offset: Coord<f64>,
+}
+
+impl Transform {
+    const IDENTITY: Self = Self {
+        scale: 1.0,
+        offset: Coord { x: 0.0, y: 0.0 },
+    };
+
+    fn apply(self, c: Coord<i32>) -> Coord<f64> {
+        Coord {
+            x: f64::from(c.x) * self.scale - self.offset.x,
+            y: f64::from(c.y) * self.scale - self.offset.y,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Axis {
+    X,
+    Y,
+}
+
+/// One of the four half-planes bounding the clip rectangle.
+#[derive(Debug, Clone, Copy)]
+struct Edge {
+    axis: Axis,
+    bound: f64,
+    /// Whether the inside is at values greater than or equal to `bound`.
+    above: bool,
+}
+
+impl Edge {
+    fn inside(self, v: Vertex) -> bool {
+        if self.above {
+            v.get(self.axis) >= self.bound
+        } else {
+            v.get(self.axis) <= self.bound
+        }
+    }
+
+    /// The point where the segment `a`-`b` crosses this edge.
+    fn intersect(self, a: Vertex, b: Vertex) -> Vertex {
+        let ratio = (self.bound - a.get(self.axis)) / (b.get(self.axis) - a.get(self.axis));
+        let lerp = |from: f64, to: f64| from + (to - from) * ratio;
+        let (x, y) = match self.axis {
+            Axis::X => (self.bound, lerp(a.y, b.y)),
+            Axis::Y => (lerp(a.x, b.x), self.bound),
+        };
+        Vertex {
+            x,
+            y,
+            z: lerp(a.z, b.z),
+            src: if ratio < 0.5 { a.src } else { b.src },
+        }
+    }
+}
+
+struct Clipper {
+    min: Coord<i32>,
+    max: Coord<i32>,
+    transform: Transform,
+}
+
+impl Clipper {
+    fn edges(&self) -> [Edge; 4] {
+        let edge = |axis, bound: i32, above| Edge {
+            axis,
+            bound: f64::from(bound),
+            above,
+        };
+        [
+            edge(Axis::X, self.min.x, true),
+            edge(Axis::X, self.max.x, false),
+            edge(Axis::Y, self.min.y, true),
+            edge(Axis::Y, self.max.y, false),
+        ]
+    }
+
+    fn contains(&self, c: Coord<f64>) -> bool {
+        let (min, max) = (self.min, self.max);
+        (f64::from(min.x)..=f64::from(max.x)).contains(&c.x)
+            && (f64::from(min.y)..=f64::from(max.y)).contains(&c.y)
+    }
+
+    /// Clip one feature in place, returning `false` if nothing of it is left.
+    fn clip_feature(&self, feature: &mut TileFeature) -> bool {
+        let Some(bounds) = feature.geometry.bounding_rect() else {
+            return false;
+        };
+        // The scale is positive, so the mapped bounds keep their corners.
+        let (lo, hi) = (
+            self.transform.apply(bounds.min()),
+            self.transform.apply(bounds.max()),
+        );
+        if self.contains(lo) && self.contains(hi) {
+            if self.transform != Transform::IDENTITY {
+                feature.geometry = feature.geometry.map_coords(|c| {
+                    let c = self.transform.apply(c);
+                    Coord {
+                        x: round(c.x),
+                        y: round(c.y),
+                    }
+                });
+            }
+            return true;
+        }
+        if hi.x < f64::from(self.min.x)
+            || hi.y < f64::from(self.min.y)
+            || lo.x > f64::from(self.max.x)
+            || lo.y > f64::from(self.max.y)
+        {
+            return false;
+        }
+
+        let mut reader = Reader::new(feature.z.as_deref());
+        reader.transform = self.transform;
+        let mut writer = Writer::default();
+        let Some(geometry) = self.geometry(&feature.geometry, &mut reader, &mut writer) else {
+            return false;
+        };
+        writer.finish(feature, geometry);
+        true
+    }
+
+    fn geometry(
+        &self,
+        geom: &Geometry<i32>,
+        reader: &mut Reader<'_>,
+        writer: &mut Writer,
+    ) -> Option<Geometry<i32>> {
+        match geom {
+            Geometry::Point(p) => self.point(p.0, reader, writer).map(Geometry::Point),
+            Geometry::MultiPoint(mp) => {
+                let points: Vec<_> = mp
+                    .iter()
+                    .filter_map(|p| self.point(p.0, reader, writer))
+                    .collect();
+                (!points.is_empty()).then_some(Geometry::MultiPoint(MultiPoint(points)))
+            }
+            Geometry::Line(line) => {
+                let line = LineString(vec![line.start, line.end]);
+                self.line_string(&line, reader, writer)
+            }
+            Geometry::LineString(ls) => self.line_string(ls, reader, writer),
+            Geometry::MultiLineString(mls) => {
+                let lines: Vec<_> = mls
+                    .iter()
+                    .flat_map(|ls| self.clip_line(reader.line(ls)))
+                    .map(|part| writer.line(&part))
+                    .collect();
+                (!lines.is_empty()).then_some(Geometry::MultiLineString(MultiLineString(lines)))
+            }
+            Geometry::Polygon(p) => self.polygon(p, reader, writer).map(Geometry::Polygon),
+            Geometry::MultiPolygon(mp) => {
+                let polygons: Vec<_> = mp
+                    .iter()
+                    .filter_map(|p| self.polygon(p, reader, writer))
+                    .collect();
+                (!polygons.is_empty()).then_some(Geometry::MultiPolygon(MultiPolygon(polygons)))
+            }
+            Geometry::Triangle(t) => {
+                let ring = LineString(t.to_array().to_vec());
+                self.rings(std::iter::once(&ring), reader, writer)
+                    .map(Geometry::Polygon)
+            }
+            Geometry::Rect(r) => {
+                let ring = LineString(geo::CoordsIter::coords_iter(r).collect());
+                self.rings(std::iter::once(&ring), reader, writer)
+                    .map(Geometry::Polygon)
+            }
+            Geometry::GeometryCollection(gc) => {
+                let children: Vec<_> = gc
+                    .iter()
+                    .filter_map(|child| self.geometry(child, reader, writer))
+                    .collect();
+                (!children.is_empty())
+                    .then_some(Geometry::GeometryCollection(GeometryCollection(children)))
+            }
+        }
+    }
+
+    fn point(
+        &self,
+        c: Coord<i32>,
+        reader: &mut Reader<'_>,
+        writer: &mut Writer,
+    ) -> Option<Point<i32>> {
+        let v = reader.line(&LineString(vec![c]))[0];
+        self.contains(Coord { x: v.x, y: v.y })
+            .then(|| Point(writer.line(&[v]).0[0]))
+    }
+
+    fn line_string(
+        &self,
+        ls: &LineString<i32>,
+        reader: &mut Reader<'_>,
+        writer: &mut Writer,
+    ) -> Option<Geometry<i32>> {
+        let mut parts = self.clip_line(reader.line(ls));
+        match parts.len() {
+            0 => None,
+            1 => Some(Geometry::LineString(writer.line(&parts.remove(0)))),
+            _ => Some(Geometry::MultiLineString(MultiLineString(
+                parts.iter().map(|part| writer.line(part)).collect(),
+            ))),
+        }
+    }
+
+    /// Split a line into the parts inside the rectangle, dropping parts that
+    /// collapse to a single coordinate.
+    fn clip_line(&self, line: Vec<Vertex>) -> Vec<Vec<Vertex>> {
+        let mut parts = vec![line];
+        for edge in self.edges() {
+            parts = parts
+                .iter()
+                .flat_map(|part| clip_line_to_edge(edge, part))
+                .collect();
+        }
+        parts.retain_mut(|part| {
+            part.dedup_by_key(|v| v.coord());
+            part.len() > 1
+        });
+        parts
+    }
+
+    fn polygon(
+        &self,
+        polygon: &Polygon<i32>,
+        reader: &mut Reader<'_>,
+        writer: &mut Writer,
+    ) -> Option<Polygon<i32>> {
+        let rings = std::iter::once(polygon.exterior()).chain(polygon.interiors());
+        self.rings(rings, reader, writer)
+    }
+
+    /// Clip the exterior and interior rings of a polygon, `None` if the exterior
+    /// ring is clipped away.
+    ///
+    /// Takes the rings rather than a [`Polygon`] so triangles and rectangles can
+    /// be passed without the closing coordinate that [`Polygon::new`] would add.
+    fn rings<'a>(
+        &self,
+        rings: impl Iterator<Item = &'a LineString<i32>>,
+        reader: &mut Reader<'_>,
+        writer: &mut Writer,
+    ) -> Option<Polygon<i32>> {
+        let mut rings = rings
+            .map(|ring| {
+                // The transform keeps the orientation, so the input ring gives the winding.
+                let area = signed_area(ring.coords().copied());
+                (self.clip_ring(reader.ring(ring)), area)
+            })
+            .collect::<Vec<_>>()
+            .into_iter();
+
+        let (exterior, area) = rings.next()?;
+        let exterior = writer.ring(exterior?, area)?;
+        let interiors = rings
+            .filter_map(|(ring, area)| writer.ring(ring?, area))
+            .collect();
+        Some(Polygon::new(exterior, interiors))
+    }
+
+    /// Sutherland-Hodgman clipping of an open ring, `None` if less than three
+    /// distinct coordinates are left.
+    fn clip_ring(&self, mut ring: Vec<Vertex>) -> Option<Vec<Vertex>> {
+        for edge in self.edges() {
+            let Some(&last) = ring.last() else {
+                break;
+            };
+            let input = std::mem::take(&mut ring);
+            let mut prev = last;
+            for &cur in &input {
+                match (edge.inside(prev), edge.inside(cur)) {
+                    (true, true) => ring.push(cur),
+                    (false, true) => ring.extend([edge.intersect(prev, cur), cur]),
+                    (true, false) => ring.push(edge.intersect(prev, cur)),
+                    (false, false) => {}
+                }
+                prev = cur;
+            }
+        }
+        ring.dedup_by_key(|v| v.coord());
+        if ring.len() > 1 && ring[0].coord() == ring[ring.len() - 1].coord() {
+            ring.pop();
+        }
+        (ring.len() >= 3).then_some(ring)
+    }
+}
+
+fn clip_line_to_edge(edge: Edge, line: &[Vertex]) -> Vec<Vec<Vertex>> {
+    let mut parts = Vec::new();
+    let mut current = Vec::new();
+    let mut prev: Option<Vertex> = None;
+    for &cur in line {
+        let inside = edge.inside(cur);
+        if let Some(prev) = prev {
+            match (edge.inside(prev), inside) {
+                (false, true) => current.push(edge.intersect(prev, cur)),
+                (true, false) => {
+                    current.push(edge.intersect(prev, cur));
+                    parts.push(std::mem::take(&mut current));
+                }
+                _ => {}
+            }
+        }
+        if inside {
+            current.push(cur);
+        }
+        prev = Some(cur);
+    }
+    if !current.is_empty() {
+        parts.push(current);
+    }
+    parts
+}
+
+/// Twice the signed area of a ring, positive for counter-clockwise rings in a
+/// y-up coordinate system.
+pub(super) fn signed_area(coords: impl Iterator<Item = Coord<i32>>) -> i64 {
+    let coords: Vec<_> = coords.collect();
+    let Some(&last) = coords.last() else {
+        return 0;
+    };
+    let mut prev = last;
+    let mut area = 0;
+    for &c in &coords {
+        area += i64::from(prev.x) * i64::from(c.y) - i64::from(c.x) * i64::from(prev.y);
+        prev = c;
+    }
+    area
+}
+
+/// Walks the input geometry in [`coords_iter`](geo::CoordsIter::coords_iter)
+/// order, tracking the index into the Z values and the stored vertex index.
+pub(super) struct Reader<'a> {
+    z: Option<&'a [i32]>,
+    coord: usize,
+    stored: usize,
+    transform: Transform,
+}
+
+impl<'a> Reader<'a> {
+    pub(super) fn new(z: Option<&'a [i32]>) -> Self {
+        Self {
+            z,
+            coord: 0,
+            stored: 0,
+            transform: Transform::IDENTITY,
+        }
+    }
+
+    fn vertex(&self, c: Coord<i32>, coord: usize, src: usize) -> Vertex {
+        let Coord { x, y } = self.transform.apply(c);
+        Vertex {
+            x,
+            y,
+            z: self.z.map_or(0.0, |z| f64::from(z[coord])),
+            src,
+        }
+    }
+
+    pub(super) fn line(&mut self, ls: &LineString<i32>) -> Vec<Vertex> {
+        let vertices = ls
+            .coords()
+            .enumerate()
+            .map(|(i, &c)| self.vertex(c, self.coord + i, self.stored + i))
+            .collect();
+        self.coord += ls.0.len();
+        self.stored += ls.0.len();
+        vertices
+    }
+
+    /// The vertices of a ring without its closing coordinate.
+    pub(super) fn ring(&mut self, ring: &LineString<i32>) -> Vec<Vertex> {
+        let len = ring_len(ring);
+        let vertices = ring.0[..len]
+            .iter()
+            .enumerate()
+            .map(|(i, &c)| self.vertex(c, self.coord + i, self.stored + i))
+            .collect();
+        self.coord += ring.0.len();
+        self.stored += len;
+        vertices
+    }
+}
+
+/// Builds a rewritten geometry with its Z values and, for each stored vertex,
+/// the input vertex its vertex-scoped values are taken from.
+#[derive(Default)]
+pub(super) struct Writer {
+    z: Vec<i32>,
+    srcs: Vec<usize>,
+}
+
+impl Writer {
+    pub(super) fn line(&mut self, vertices: &[Vertex]) -> LineString<i32> {
+        self.z.extend(vertices.iter().map(|v| round(v.z)));
+        self.srcs.extend(vertices.iter().map(|v| v.src));
+        vertices.iter().map(|v| v.coord()).collect()
+    }
+
+    /// Close a rewritten ring, restoring the winding of the input ring with
+    /// twice the signed area `area`. Rings without area are dropped.
+    pub(super) fn ring(&mut self, mut vertices: Vec<Vertex>, area: i64) -> Option<LineString<i32>> {
+        let clipped = signed_area(vertices.iter().map(|v| v.coord()));
+        if clipped == 0 {
+            return None;
+        }
+        if (clipped > 0) != (area > 0) && area != 0 {
+            vertices.reverse();
+        }
+        let mut ring = self.line(&vertices);
+        ring.close();
+        self.z.push(round(vertices[0].z));
+        Some(ring)
+    }
+
+    /// Replace the geometry of `feature`, along with its Z values and vertex-scoped values.
+    pub(super) fn finish(self, feature: &mut TileFeature, geometry: Geometry<i32>) {
+        feature.geometry = geometry;
+        if feature.z.is_some() {
+            feature.z = Some(self.z);
+        }
+        for values in &mut feature.vertex_properties {
+            *values = select(values, &self.srcs);
+        }
+    }
+}
+
+fn select(values: &VertexValues, indices: &[usize]) -> VertexValues {
+    fn pick<T: Copy>(values: &[T], indices: &[usize]) -> Vec<T> {
+        indices.iter().map(|&i| values[i]).collect()
+    }
+    match values {
+        VertexValues::Bool(v) => VertexValues::Bool(pick(v, indices)),
+        VertexValues::I8(v) => VertexValues::I8(pick(v, indices)),
+        VertexValues::U8(v) => VertexValues::U8(pick(v, indices)),
+        VertexValues::I32(v) => VertexValues::I32(pick(v, indices)),
+        VertexValues::U32(v) => VertexValues::U32(pick(v, indices)),
+        VertexValues::I64(v) => VertexValues::I64(pick(v, indices)),
+        VertexValues::U64(v) => VertexValues::U64(pick(v, indices)),
+        VertexValues::F32(v) => VertexValues::F32(pick(v, indices)),
+        VertexValues::F64(v) => VertexValues::F64(pick(v, indices)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use geo_types::{
+        Geometry, LineString, MultiLineString, MultiPoint, Point, Polygon, line_string, point,
+    };
+
+    use crate::decoder::{PropKind, TileLayer, VertexValues};
+
+    fn layer(features: Vec<Geometry<i32>>) -> TileLayer {
+        let mut builder = TileLayer::builder("clip", 100).unwrap();
+        for geometry in features {
+            builder.feature(geometry).finish().unwrap();
+        }
+        builder.finish()
+    }
+
+    fn geometries(layer: &TileLayer) -> Vec<Geometry<i32>> {
+        layer
+            .features()
+            .iter()
+            .map(|f| f.geometry().clone())
+            .collect()
+    }
+
+    fn square(x0: i32, y0: i32, x1: i32, y1: i32) -> LineString<i32> {
+        line_string![(x: x0, y: y0), (x: x1, y: y0), (x: x1, y: y1), (x: x0, y: y1), (x: x0, y: y0)]
+    }
+
+    #[test]
+    fn points_are_filtered() {
+        // The clip rectangle is [-5, 105] on both axes, borders included.
+        let mut layer = layer(vec![
+            point!(x: 50, y: 50).into(),
+            point!(x: -5, y: 50).into(),
+            point!(x: 105, y: 105).into(),
+            point!(x: 106, y: 50).into(),
+            MultiPoint::from(vec![(-20, 0), (0, 0), (200, 0)]).into(),
+        ]);
+        layer.clip(5);
+        assert_eq!(
+            geometries(&layer),
+            vec![
+                Geometry::Point(Point::new(50, 50)),
+                Geometry::Point(Point::new(-5, 50)),
+                Geometry::Point(Point::new(105, 105)),
+                Geometry::MultiPoint(MultiPoint::from(vec![(0, 0)])),
+            ]
+        );
+    }
+
+    #[test]
+    fn lines_are_split_where_they_leave_the_tile() {
+        let mut layer = layer(vec![
+            line_string![(x: -50, y: 10), (x: 50, y: 10), (x: 50, y: 200), (x: 80, y: 200), (x: 80, y: 50)]
+                .into(),
+            line_string![(x: 10, y: 10), (x: 20, y: 20)].into(),
+            line_string![(x: -10, y: -10), (x: 200, y: -10)].into(),
+        ]);
+        layer.clip(0);
+        assert_eq!(
+            geometries(&layer),
+            vec![
+                Geometry::MultiLineString(MultiLineString(vec![
+                    line_string![(x: 0, y: 10), (x: 50, y: 10), (x: 50, y: 100)],
+                    line_string![(x: 80, y: 100), (x: 80, y: 50)],
+                ])),
+                line_string![(x: 10, y: 10), (x: 20, y: 20)].into(),
+            ]
+        );
+    }
+
+    #[test]
+    fn polygons_keep_their_ring_orientation() {
+        let ccw = Polygon::new(square(-50, -50, 50, 50), vec![square(-10, -10, 10, 10)]);
+        let mut cw = Polygon::new(square(50, 50, 150, 150), vec![]);
+        cw.exterior_mut(|ring| ring.0.reverse());
+        let outside = Polygon::new(square(-20, 0, -10, 10), vec![]);
+        let mut layer = layer(vec![ccw.into(), cw.into(), outside.into()]);
+        layer.clip(0);
+
+        // The counter-clockwise polygon keeps its hole, the clockwise one stays clockwise.
+        let expected = vec![
+            Polygon::new(
+                line_string![(x: 0, y: 0), (x: 50, y: 0), (x: 50, y: 50), (x: 0, y: 50), (x: 0, y: 0)],
+                vec![
+                    line_string![(x: 0, y: 0), (x: 10, y: 0), (x: 10, y: 10), (x: 0, y: 10), (x: 0, y: 0)],
+                ],
+            )
+            .into(),
+            Polygon::new(
+                line_string![(x: 100, y: 100), (x: 100, y: 50), (x: 50, y: 50), (x: 50, y: 100), (x: 100, y: 100)],
+                vec![],
+            )
+            .into(),
+        ];
+        assert_eq!(geometries(&layer), expected);
+    }
+
+    #[test]
+    fn z_and_vertex_values_follow_the_clipped_vertices() {
+        let mut builder = TileLayer::builder("clip", 100).unwrap();
+        let key = builder.add_vertex_property("v", PropKind::U32).unwrap();
+        let mut feature = builder.feature(line_string![(x: -20, y: 0), (x: 60, y: 0)].into());
+        feature.z(vec![0, 400]).unwrap();
+        feature
+            .vertex_property(key, VertexValues::U32(vec![1, 2]))
+            .unwrap();
+        feature.finish().unwrap();
+        let mut feature = builder.feature(Polygon::new(square(-50, 0, 50, 50), vec![]).into());
+        feature.z(vec![10, 20, 30, 40, 10]).unwrap();
+        feature
+            .vertex_property(key, VertexValues::U32(vec![1, 2, 3, 4]))
+            .unwrap();
+        feature.finish().unwrap();
+        let mut layer = builder.finish();
+        layer.clip(0);
+
+        let [line, polygon] = layer.features() else {
+            panic!("expected two features")
+        };
+        assert_eq!(line.z(), Some(&[100, 400][..]));
+        assert_eq!(line.vertex_properties(), &[VertexValues::U32(vec![1, 2])]);
+        assert_eq!(
+            polygon.geometry(),
+            &Geometry::Polygon(Polygon::new(
+                line_string![(x: 0, y: 0), (x: 50, y: 0), (x: 50, y: 50), (x: 0, y: 50), (x: 0, y: 0)],
+                vec![],
+            ))
+        );
+        assert_eq!(polygon.z(), Some(&[15, 20, 30, 35, 15][..]));
+        assert_eq!(
+            polygon.vertex_properties(),
+            &[VertexValues::U32(vec![2, 2, 3, 4])]
+        );
+    }
+}
@@ -3,7 +3,9 @@ use arbitrary::{Arbitrary, Result, Unstructured};
 
 use crate::encoder::model::StagedLayer;
 use crate::encoder::optimizer::Presence;
-use crate::encoder::{EncoderConfig, StagedId, StagedProperty, StagedSharedDict, StagedStrings};
+use crate::encoder::{
+    Effort, EncoderConfig, Sampling, StagedId, StagedProperty, StagedSharedDict, StagedStrings,
+};
 
 impl Arbitrary<'_> for EncoderConfig {
     fn arbitrary(u: &mut Unstructured<'_>) -> Result<Self> {
@@ -12,9 +14,22 @@ impl Arbitrary<'_> for EncoderConfig {
             .with_spatial_morton_sort(u.arbitrary()?)
             .with_spatial_hilbert_sort(u.arbitrary()?)
             .with_id_sort(u.arbitrary()?)
+            .with_property_sort(u.arbitrary()?)
             .with_fsst(u.arbitrary()?)
             .with_fastpfor(u.arbitrary()?)
-            .with_shared_dict(u.arbitrary()?))
+            .with_shared_dict(u.arbitrary()?)
+            .with_pseudo_decimal(u.arbitrary()?)
+            .with_float_dict_rle(u.arbitrary()?)
+            .with_effort(match u.int_in_range(0..=2u8)? {
+                0 => Effort::Fast,
+                1 => Effort::Default,
+                _ => Effort::Max,
+            })
+            .with_sampling(match u.int_in_range(0..=5u8)? {
+                0 => Sampling::Auto,
+                1 => Sampling::Off,
+                n => Sampling::Finalists(usize::from(n - 2)),
+            }))
     }
 }
 
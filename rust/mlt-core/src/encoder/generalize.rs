@@ -0,0 +1,514 @@
+//! Building parent tiles from their children
+
+use std::collections::HashSet;
+
+use geo::MapCoords as _;
+use geo_types::{
+    Coord, Geometry, LineString, MultiLineString, MultiPoint, MultiPolygon, Point, Polygon,
+};
+
+use crate::decoder::{PropValue, TileFeature, TileLayer, VertexValues};
+use crate::encoder::clip::{Reader, Vertex, Writer, round, signed_area};
+use crate::encoder::stored_vertex_count;
+use crate::{MltError, MltResult};
+
+/// Size of a pixel in tile units is the extent divided by this tile size.
+const TILE_PIXELS: f64 = 256.0;
+
+/// Settings for [`TileLayer::generalize`] and [`merge_children`].
+///
+/// All sizes are in pixels of a 256 px tile, so they apply to any extent.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GeneralizeConfig {
+    /// Douglas-Peucker tolerance for lines and polygon rings.
+    tolerance: f64,
+    /// Polygons and holes with a smaller area are dropped.
+    min_area: f64,
+    /// At most one point is kept per grid cell of this size.
+    point_spacing: f64,
+}
+
+impl GeneralizeConfig {
+    /// Defaults for a tile at `zoom`: one pixel of tolerance and point spacing at
+    /// zoom 10 and above, growing to two pixels at zoom 0, and dropping polygons
+    /// smaller than one square pixel.
+    #[must_use]
+    pub fn for_zoom(zoom: u8) -> Self {
+        let tolerance = 1.0 + f64::from(10_u8.saturating_sub(zoom)) / 10.0;
+        Self {
+            tolerance,
+            min_area: 1.0,
+            point_spacing: tolerance,
+        }
+    }
+
+    #[must_use]
+    pub fn tolerance(self) -> f64 {
+        self.tolerance
+    }
+
+    #[must_use]
+    pub fn min_area(self) -> f64 {
+        self.min_area
+    }
+
+    #[must_use]
+    pub fn point_spacing(self) -> f64 {
+        self.point_spacing
+    }
+
+    #[must_use]
+    pub fn with_tolerance(mut self, pixels: f64) -> Self {
+        self.tolerance = pixels;
+        self
+    }
+
+    #[must_use]
+    pub fn with_min_area(mut self, square_pixels: f64) -> Self {
+        self.min_area = square_pixels;
+        self
+    }
+
+    /// Thin points to one per grid cell of `pixels`, or with `0` keep all of them.
+    #[must_use]
+    pub fn with_point_spacing(mut self, pixels: f64) -> Self {
+        self.point_spacing = pixels;
+        self
+    }
+}
+
+/// Combine the decoded layers of the four children of a tile into the layers of
+/// that tile.
+///
+/// `children` holds the layers of the children at column/row `(0, 0)`, `(1, 0)`,
+/// `(0, 1)` and `(1, 1)`, an empty `Vec` for a missing child. Each child is
+/// clipped to its extent (dropping its buffer), scaled into the parent, and
+/// appended to the parent layer of the same name, which takes the extent of the
+/// first such child layer. Property columns are united by name; features missing
+/// a column get a null value, or zeros for vertex-scoped columns. Merged layers
+/// are then [generalized](TileLayer::generalize), and layers left without
+/// features are dropped.
+///
+/// Fails if the same column has different kinds in different children.
+pub fn merge_children(
+    children: [Vec<TileLayer>; 4],
+    cfg: GeneralizeConfig,
+) -> MltResult<Vec<TileLayer>> {
+    let mut parents: Vec<TileLayer> = Vec::new();
+    for (idx, layers) in children.into_iter().enumerate() {
+        let (dx, dy) = (idx % 2, idx / 2);
+        for mut layer in layers {
+            layer.clip(0);
+            let parent = if let Some(pos) = parents.iter().position(|p| p.name == layer.name) {
+                &mut parents[pos]
+            } else {
+                parents.push(TileLayer::new(layer.name.clone(), layer.extent.get())?);
+                parents.last_mut().expect("just pushed")
+            };
+            append_child(parent, layer, dx, dy)?;
+        }
+    }
+    for parent in &mut parents {
+        parent.generalize(cfg);
+    }
+    parents.retain(|p| p.feature_count() > 0);
+    Ok(parents)
+}
+
+/// Scale the features of the child at column `dx` and row `dy` into `parent`.
+fn append_child(parent: &mut TileLayer, child: TileLayer, dx: usize, dy: usize) -> MltResult<()> {
+    let mut props = Vec::with_capacity(child.property_names.len());
+    for (name, &kind) in child.property_names.iter().zip(&child.property_kinds) {
+        let idx = match parent.property_names.iter().position(|n| n == name) {
+            Some(idx) if parent.property_kinds[idx] == kind => idx,
+            Some(idx) => {
+                return Err(MltError::PropertyKindMismatch {
+                    index: idx,
+                    expected: parent.property_kinds[idx],
+                    actual: kind,
+                });
+            }
+            None => parent.add_property(name.clone(), kind)?.index(),
+        };
+        props.push(idx);
+    }
+    let mut vertex_props = Vec::with_capacity(child.vertex_property_names.len());
+    for (name, &kind) in child
+        .vertex_property_names
+        .iter()
+        .zip(&child.vertex_property_kinds)
+    {
+        let idx = match parent.vertex_property_names.iter().position(|n| n == name) {
+            Some(idx) if parent.vertex_property_kinds[idx] == kind => idx,
+            Some(idx) => {
+                return Err(MltError::PropertyKindMismatch {
+                    index: idx,
+                    expected: parent.vertex_property_kinds[idx],
+                    actual: kind,
+                });
+            }
+            None => parent.add_vertex_property(name.clone(), kind)?.index(),
+        };
+        vertex_props.push(idx);
+    }
+
+    // Parent coordinate = (child coordinate + offset) * parent extent / (2 * child extent)
+    let child_extent = f64::from(child.extent.get());
+    let scale = f64::from(parent.extent.get()) / (2.0 * child_extent);
+    let (offset_x, offset_y) = (
+        if dx == 0 { 0.0 } else { child_extent },
+        if dy == 0 { 0.0 } else { child_extent },
+    );
+    for feature in child.features {
+        let geometry = feature.geometry.map_coords(|c| Coord {
+            x: round((f64::from(c.x) + offset_x) * scale),
+            y: round((f64::from(c.y) + offset_y) * scale),
+        });
+        let mut properties: Vec<_> = parent
+            .property_kinds
+            .iter()
+            .map(|&kind| PropValue::null(kind))
+            .collect();
+        for (value, &idx) in feature.properties.into_iter().zip(&props) {
+            properties[idx] = value;
+        }
+        let vertex_count = stored_vertex_count(&geometry);
+        let mut vertex_properties = parent
+            .vertex_property_kinds
+            .iter()
+            .map(|&kind| VertexValues::zeroed(kind, vertex_count))
+            .collect::<MltResult<Vec<_>>>()?;
+        for (values, &idx) in feature.vertex_properties.into_iter().zip(&vertex_props) {
+            vertex_properties[idx] = values;
+        }
+        parent.features.push(TileFeature {
+            id: feature.id,
+            geometry,
+            z: feature.z,
+            properties,
+            vertex_properties,
+        });
+    }
+    Ok(())
+}
+
+impl TileLayer {
+    /// Reduce the detail of this layer for display at a lower zoom level.
+    ///
+    /// Lines and polygon rings are simplified with the Douglas-Peucker algorithm,
+    /// polygons and holes smaller than the minimum area are dropped, and points are
+    /// thinned to one per grid cell, in feature order. Ring winding is kept, Z and
+    /// vertex-scoped values follow the kept vertices, and features left without
+    /// geometry are dropped. Other geometry types are left untouched.
+    pub fn generalize(&mut self, cfg: GeneralizeConfig) {
+        let pixel = f64::from(self.extent.get()) / TILE_PIXELS;
+        let tolerance = cfg.tolerance * pixel;
+        let min_area = cfg.min_area * pixel * pixel;
+        let spacing = cfg.point_spacing * pixel;
+        let mut cells = HashSet::new();
+        self.features.retain_mut(|feature| {
+            if matches!(
+                feature.geometry,
+                Geometry::Point(_) | Geometry::MultiPoint(_)
+            ) {
+                spacing <= 0.0 || thin_points(feature, spacing, &mut cells)
+            } else {
+                simplify(feature, tolerance, min_area)
+            }
+        });
+    }
+}
+
+/// Keep only the points of `feature` whose grid cell is not taken yet.
+fn thin_points(feature: &mut TileFeature, spacing: f64, cells: &mut HashSet<(i64, i64)>) -> bool {
+    #[expect(
+        clippy::cast_possible_truncation,
+        reason = "cells of i32 coordinates fit into i64"
+    )]
+    let mut take = |c: Coord<i32>| {
+        cells.insert((
+            (f64::from(c.x) / spacing).floor() as i64,
+            (f64::from(c.y) / spacing).floor() as i64,
+        ))
+    };
+    match &feature.geometry {
+        Geometry::Point(p) => take(p.0),
+        Geometry::MultiPoint(mp) => {
+            let mut reader = Reader::new(feature.z.as_deref());
+            let kept: Vec<Vertex> = mp
+                .iter()
+                .flat_map(|p| reader.line(&LineString(vec![p.0])))
+                .filter(|v| take(v.coord()))
+                .collect();
+            if kept.is_empty() {
+                return false;
+            }
+            if kept.len() < mp.0.len() {
+                let mut writer = Writer::default();
+                let points = kept
+                    .iter()
+                    .map(|v| Point(writer.line(&[*v]).0[0]))
+                    .collect();
+                writer.finish(feature, Geometry::MultiPoint(MultiPoint(points)));
+            }
+            true
+        }
+        _ => true,
+    }
+}
+
+/// Simplify the lines and polygons of `feature`, `false` if nothing is left.
+fn simplify(feature: &mut TileFeature, tolerance: f64, min_area: f64) -> bool {
+    let mut reader = Reader::new(feature.z.as_deref());
+    let mut writer = Writer::default();
+    let geometry = match &feature.geometry {
+        Geometry::LineString(ls) => {
+            simplify_line(ls, tolerance, &mut reader, &mut writer).map(Geometry::LineString)
+        }
+        Geometry::MultiLineString(mls) => {
+            let lines: Vec<_> = mls
+                .iter()
+                .filter_map(|ls| simplify_line(ls, tolerance, &mut reader, &mut writer))
+                .collect();
+            (!lines.is_empty()).then_some(Geometry::MultiLineString(MultiLineString(lines)))
+        }
+        Geometry::Polygon(p) => simplify_polygon(p, tolerance, min_area, &mut reader, &mut writer)
+            .map(Geometry::Polygon),
+        Geometry::MultiPolygon(mp) => {
+            let polygons: Vec<_> = mp
+                .iter()
+                .filter_map(|p| simplify_polygon(p, tolerance, min_area, &mut reader, &mut writer))
+                .collect();
+            (!polygons.is_empty()).then_some(Geometry::MultiPolygon(MultiPolygon(polygons)))
+        }
+        _ => return true,
+    };
+    let Some(geometry) = geometry else {
+        return false;
+    };
+    writer.finish(feature, geometry);
+    true
+}
+
+fn simplify_line(
+    ls: &LineString<i32>,
+    tolerance: f64,
+    reader: &mut Reader<'_>,
+    writer: &mut Writer,
+) -> Option<LineString<i32>> {
+    let mut vertices = douglas_peucker(&reader.line(ls), tolerance);
+    vertices.dedup_by_key(|v| v.coord());
+    (vertices.len() > 1).then(|| writer.line(&vertices))
+}
+
+fn simplify_polygon(
+    polygon: &Polygon<i32>,
+    tolerance: f64,
+    min_area: f64,
+    reader: &mut Reader<'_>,
+    writer: &mut Writer,
+) -> Option<Polygon<i32>> {
+    // Read every ring first so the reader stays in step with dropped rings.
+    let mut rings = std::iter::once(polygon.exterior())
+        .chain(polygon.interiors())
+        .map(|ring| reader.ring(ring))
+        .collect::<Vec<_>>()
+        .into_iter()
+        .map(|ring| {
+            let area = signed_area(ring.iter().map(|v| v.coord()));
+            (simplify_ring(ring, tolerance, min_area), area)
+        });
+    let (exterior, area) = rings.next()?;
+    let exterior = writer.ring(exterior?, area)?;
+    let interiors = rings
+        .filter_map(|(ring, area)| writer.ring(ring?, area))
+        .collect();
+    Some(Polygon::new(exterior, interiors))
+}
+
+/// Simplify an open ring, `None` if it gets smaller than `min_area`.
+fn simplify_ring(mut ring: Vec<Vertex>, tolerance: f64, min_area: f64) -> Option<Vec<Vertex>> {
+    let first = *ring.first()?;
+    ring.push(first);
+    let mut ring = douglas_peucker(&ring, tolerance);
+    ring.pop();
+    ring.dedup_by_key(|v| v.coord());
+    #[expect(
+        clippy::cast_precision_loss,
+        reason = "areas only need to be compared approximately"
+    )]
+    let area = signed_area(ring.iter().map(|v| v.coord())).abs() as f64 / 2.0;
+    (ring.len() >= 3 && area >= min_area).then_some(ring)
+}
+
+/// The vertices kept by the Douglas-Peucker algorithm, always including both ends.
+fn douglas_peucker(vertices: &[Vertex], tolerance: f64) -> Vec<Vertex> {
+    if vertices.len() < 3 {
+        return vertices.to_vec();
+    }
+    let mut keep = vec![false; vertices.len()];
+    keep[0] = true;
+    keep[vertices.len() - 1] = true;
+    let mut ranges = vec![(0, vertices.len() - 1)];
+    while let Some((first, last)) = ranges.pop() {
+        let (a, b) = (vertices[first], vertices[last]);
+        let farthest = (first + 1..last)
+            .map(|idx| (idx, segment_distance(vertices[idx], a, b)))
+            .max_by(|(_, d1), (_, d2)| d1.total_cmp(d2));
+        if let Some((idx, distance)) = farthest
+            && distance > tolerance
+        {
+            keep[idx] = true;
+            ranges.push((first, idx));
+            ranges.push((idx, last));
+        }
+    }
+    vertices
+        .iter()
+        .zip(keep)
+        .filter_map(|(&v, keep)| keep.then_some(v))
+        .collect()
+}
+
+/// Distance from `p` to the segment `a`-`b`.
+fn segment_distance(p: Vertex, a: Vertex, b: Vertex) -> f64 {
+    let (dx, dy) = (b.x - a.x, b.y - a.y);
+    let len2 = dx * dx + dy * dy;
+    let along = if len2 > 0.0 {
+        (((p.x - a.x) * dx + (p.y - a.y) * dy) / len2).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    (p.x - (a.x + along * dx)).hypot(p.y - (a.y + along * dy))
+}
+
+#[cfg(test)]
+mod tests {
+    use geo_types::{Geometry, LineString, Polygon, line_string, point, polygon};
+
+    use super::{GeneralizeConfig, merge_children};
+    use crate::decoder::{PropKind, PropValue, TileLayer, VertexValues};
+
+    fn geometries(layer: &TileLayer) -> Vec<Geometry<i32>> {
+        layer
+            .features()
+            .iter()
+            .map(|f| f.geometry().clone())
+            .collect()
+    }
+
+    fn square(x0: i32, y0: i32, x1: i32, y1: i32) -> Polygon<i32> {
+        polygon![(x: x0, y: y0), (x: x1, y: y0), (x: x1, y: y1), (x: x0, y: y1)]
+    }
+
+    #[test]
+    fn children_are_scaled_into_the_parent() {
+        let mut nw = TileLayer::builder("roads", 100).unwrap();
+        let class = nw.add_property("class", PropKind::Str).unwrap();
+        let mut feature = nw.feature(point!(x: 10, y: 20).into());
+        feature
+            .property(class, PropValue::Str(Some("a".into())))
+            .unwrap();
+        feature.finish().unwrap();
+        // Points in the buffer belong to the neighbouring child
+        nw.feature(point!(x: 110, y: 20).into()).finish().unwrap();
+
+        let mut se = TileLayer::builder("roads", 100).unwrap();
+        let rank = se.add_property("rank", PropKind::U32).unwrap();
+        let mut feature = se.feature(point!(x: 50, y: 50).into());
+        feature.property(rank, PropValue::U32(Some(3))).unwrap();
+        feature.finish().unwrap();
+        let mut water = TileLayer::builder("water", 100).unwrap();
+        water
+            .feature(square(0, 0, 100, 100).into())
+            .finish()
+            .unwrap();
+
+        let cfg = GeneralizeConfig::for_zoom(14).with_point_spacing(0.0);
+        let children = [
+            vec![nw.finish()],
+            vec![],
+            vec![],
+            vec![se.finish(), water.finish()],
+        ];
+        let [roads, water] = merge_children(children, cfg).unwrap().try_into().unwrap();
+
+        assert_eq!(roads.name(), "roads");
+        assert_eq!(roads.property_names(), ["class", "rank"]);
+        assert_eq!(
+            geometries(&roads),
+            vec![point!(x: 5, y: 10).into(), point!(x: 75, y: 75).into()]
+        );
+        assert_eq!(
+            roads.features()[0].properties(),
+            [PropValue::Str(Some("a".into())), PropValue::U32(None)]
+        );
+        assert_eq!(
+            roads.features()[1].properties(),
+            [PropValue::Str(None), PropValue::U32(Some(3))]
+        );
+        assert_eq!(geometries(&water), vec![square(50, 50, 100, 100).into()]);
+    }
+
+    #[test]
+    fn mismatched_column_kinds_are_rejected() {
+        let layer = |kind| {
+            let mut builder = TileLayer::builder("l", 100).unwrap();
+            builder.add_property("p", kind).unwrap();
+            builder.feature(point!(x: 1, y: 1).into()).finish().unwrap();
+            builder.finish()
+        };
+        let children = [
+            vec![layer(PropKind::U32)],
+            vec![layer(PropKind::Str)],
+            vec![],
+            vec![],
+        ];
+        assert!(merge_children(children, GeneralizeConfig::for_zoom(0)).is_err());
+    }
+
+    #[test]
+    fn generalize_simplifies_drops_and_thins() {
+        // With an extent of 256, one pixel is one unit.
+        let mut builder = TileLayer::builder("l", 256).unwrap();
+        let key = builder.add_vertex_property("v", PropKind::U8).unwrap();
+        let mut feature =
+            builder.feature(line_string![(x: 0, y: 0), (x: 50, y: 1), (x: 100, y: 0)].into());
+        feature.z(vec![1, 2, 3]).unwrap();
+        feature
+            .vertex_property(key, VertexValues::U8(vec![1, 2, 3]))
+            .unwrap();
+        feature.finish().unwrap();
+        let tiny = polygon![(x: 200, y: 200), (x: 201, y: 200), (x: 200, y: 201)];
+        builder.feature(tiny.into()).finish().unwrap();
+        for (x, y) in [(10, 10), (10, 10), (20, 20)] {
+            builder.feature(point!(x: x, y: y).into()).finish().unwrap();
+        }
+        let mut layer = builder.finish();
+        layer.generalize(GeneralizeConfig::for_zoom(14));
+
+        assert_eq!(
+            geometries(&layer),
+            vec![
+                LineString::from(vec![(0, 0), (100, 0)]).into(),
+                point!(x: 10, y: 10).into(),
+                point!(x: 20, y: 20).into(),
+            ]
+        );
+        let line = &layer.features()[0];
+        assert_eq!(line.z(), Some(&[1, 3][..]));
+        assert_eq!(line.vertex_properties(), [VertexValues::U8(vec![1, 3])]);
+    }
+
+    #[test]
+    fn simplified_polygons_keep_holes_and_winding() {
+        let mut polygon = square(0, 0, 200, 200);
+        polygon.exterior_mut(|ring| ring.0.reverse());
+        polygon.interiors_push(square(50, 50, 150, 150).exterior().clone());
+        let mut builder = TileLayer::builder("l", 256).unwrap();
+        builder.feature(polygon.clone().into()).finish().unwrap();
+        let mut layer = builder.finish();
+        layer.generalize(GeneralizeConfig::for_zoom(14));
+        assert_eq!(geometries(&layer), vec![polygon.into()]);
+    }
+}
@@ -7,9 +7,8 @@ use probabilistic_collections::hyperloglog::HyperLogLog;
 use usize_cast::{FromUsize as _, IntoUsize as _};
 
 use super::model::VertexBufferType;
-use crate::MltResult;
 use crate::codecs::hilbert::hilbert_sort_key;
-use crate::codecs::zigzag::encode_componentwise_delta_vec2s;
+use crate::codecs::zigzag::{encode_componentwise_delta_vec2s, encode_componentwise_delta_vec3s};
 use crate::decoder::GeometryType::{LineString, Point, Polygon};
 use crate::decoder::{
     ColumnType, DictionaryType, GeometryType, GeometryValues, LengthType, LogicalEncoding, Morton,
@@ -17,6 +16,8 @@ use crate::decoder::{
 };
 use crate::encoder::model::{CurveParams, StreamCtx};
 use crate::encoder::{Codecs, Encoder, PhysicalCodecs, write_stream_payload};
+use crate::errors::AsMltError as _;
+use crate::{MltError, MltResult};
 
 /// Compute `ZOrderCurve` parameters from the vertex value range.
 ///
@@ -399,6 +400,27 @@ fn encode_vec2_vertex_stream(
     write_geo_precomputed_stream(delta, ctx, logical, enc, &mut codecs.physical)
 }
 
+/// Encode the 3D vertex layout: componentwise-delta over interleaved
+/// `[x0, y0, z0, x1, y1, z1, …]` triples.
+fn encode_vec3_vertex_stream(
+    vertices: &[i32],
+    z: &[i32],
+    enc: &mut Encoder,
+    codecs: &mut Codecs,
+) -> MltResult<u8> {
+    let xyz: Vec<i32> = vertices
+        .as_chunks::<2>()
+        .0
+        .iter()
+        .zip(z)
+        .flat_map(|(&[x, y], &z)| [x, y, z])
+        .collect();
+    let delta = encode_componentwise_delta_vec3s(&xyz, &mut codecs.logical.u32_tmp);
+    let ctx = StreamCtx::geom(StreamType::Data(DictionaryType::Vertex), "vertex");
+    let logical = LogicalEncoding::ComponentwiseDelta;
+    write_geo_precomputed_stream(delta, ctx, logical, enc, &mut codecs.physical)
+}
+
 /// Encode a Morton-keyed vertex dictionary: per-vertex offsets stream
 /// followed by a delta-encoded Morton-code dictionary.
 fn encode_morton_vertex_streams(
@@ -531,6 +553,80 @@ impl GeometryValues {
     /// Write the geometry column to `enc`.
     #[hotpath::measure]
     pub fn write_to(self, enc: &mut Encoder, codecs: &mut Codecs) -> MltResult<()> {
+        self.write_with_layout(enc, codecs, false)
+    }
+
+    /// Like [`Self::write_to`], but always stores one vertex buffer entry per vertex,
+    /// as vertex-scoped property columns are indexed by vertex buffer position.
+    pub(crate) fn write_per_vertex_to(
+        self,
+        enc: &mut Encoder,
+        codecs: &mut Codecs,
+    ) -> MltResult<()> {
+        self.write_with_layout(enc, codecs, true)
+    }
+
+    /// Layer-wide triangle indices for writing this column as a bare mesh, or `None` when
+    /// the column has to keep its outlines.
+    ///
+    /// Tessellation indices are relative to each feature's first vertex, which cannot be
+    /// recovered once the outlines are gone, so they are rebased onto the whole vertex buffer.
+    /// Columns with non-polygon features or without any triangles keep their outlines.
+    fn mesh_index_buffer(&self) -> MltResult<Option<Vec<u32>>> {
+        let (Some(triangles), Some(indices), Some(parts), Some(rings)) = (
+            self.triangles(),
+            self.index_buffer(),
+            self.part_offsets.as_deref(),
+            self.ring_offsets.as_deref(),
+        ) else {
+            return Ok(None);
+        };
+        if self.is_mesh_only()
+            || indices.is_empty()
+            || triangles.len() != self.vector_types.len()
+            || !self.vector_types.iter().all(|t| t.is_polygon())
+        {
+            return Ok(None);
+        }
+        // Offsets may still be in the sparse layout built by `push_geom`, where only
+        // multi-polygons have geometry offsets; parts and rings are dense in polygon-only columns.
+        let polygons = match self.geometry_offsets.as_deref() {
+            Some(offsets) if offsets.len() == self.vector_types.len() + 1 => offsets.to_vec(),
+            Some(offsets) => normalize_geometry_offsets(&self.vector_types, offsets),
+            None => (0..=u32::try_from(self.vector_types.len())?).collect(),
+        };
+        let mut mesh = Vec::with_capacity(indices.len());
+        let mut start = 0_usize;
+        for (index, &count) in triangles.iter().enumerate() {
+            let base = polygons
+                .get(index)
+                .and_then(|&polygon| parts.get(polygon.into_usize()))
+                .and_then(|&ring| rings.get(ring.into_usize()))
+                .copied()
+                .ok_or(MltError::GeometryIndexOutOfBounds(index))?;
+            let end = start.checked_add(count.into_usize() * 3).or_overflow()?;
+            let feature = indices
+                .get(start..end)
+                .ok_or(MltError::GeometryIndexOutOfBounds(index))?;
+            for &idx in feature {
+                mesh.push(base.checked_add(idx).or_overflow()?);
+            }
+            start = end;
+        }
+        Ok(Some(mesh))
+    }
+
+    fn write_with_layout(
+        self,
+        enc: &mut Encoder,
+        codecs: &mut Codecs,
+        per_vertex: bool,
+    ) -> MltResult<()> {
+        let mesh = if enc.config().mesh_only() {
+            self.mesh_index_buffer()?
+        } else {
+            None
+        };
         let Self {
             vector_types,
             geometry_offsets,
@@ -539,16 +635,23 @@ impl GeometryValues {
             index_buffer,
             triangles,
             vertices,
+            z,
         } = self;
 
         // Flatten every Option<Vec> -> Vec  (empty == not present).
         // triangles: None means no tessellation; Some([]) can't occur in practice (each
         // push_geom appends a count), so empty == absent is safe here too.
         // vertices: None means no coordinate data (e.g. empty layer).
-        let geom_offsets = geometry_offsets.unwrap_or_default();
-        let part_offsets = part_offsets.unwrap_or_default();
-        let ring_offsets = ring_offsets.unwrap_or_default();
-        let index_buffer = index_buffer.unwrap_or_default();
+        // A bare mesh drops all topology; its triangles are located via the rebased indices.
+        let (geom_offsets, part_offsets, ring_offsets, index_buffer) = match mesh {
+            Some(mesh) => (Vec::new(), Vec::new(), Vec::new(), mesh),
+            None => (
+                geometry_offsets.unwrap_or_default(),
+                part_offsets.unwrap_or_default(),
+                ring_offsets.unwrap_or_default(),
+                index_buffer.unwrap_or_default(),
+            ),
+        };
         let triangles = triangles.unwrap_or_default();
         let vertices = vertices.unwrap_or_default();
 
@@ -578,7 +681,11 @@ impl GeometryValues {
 
         // Write column type to meta; reserve exactly 1 byte for stream count
         // (geometry never exceeds ~8 streams, always fits in a single varint byte).
-        enc.write_column_type(ColumnType::Geometry)?;
+        enc.write_column_type(if z.is_some() {
+            ColumnType::GeometryZ
+        } else {
+            ColumnType::Geometry
+        })?;
         let stream_count_pos = enc.data().len();
         enc.data_mut().push(0); // placeholder - patched below
         let mut n: u8 = 0;
@@ -675,7 +782,13 @@ impl GeometryValues {
         let ctx = StreamCtx::geom(StreamType::Offset(OffsetType::Index), "triangles_indexes");
         n += write_geo_u32_stream(&index_buffer, ctx, enc, codecs)?;
 
-        if let Some(forced) = enc.override_vertex_buffer_type() {
+        if let Some(z) = &z {
+            // The dictionary layouts are keyed on (x, y) only, so Z always uses the plain layout.
+            n += encode_vec3_vertex_stream(&vertices, z, enc, codecs)?;
+        } else if per_vertex {
+            // Dictionary layouts share entries between vertices, so they are skipped.
+            n += encode_vec2_vertex_stream(&vertices, enc, codecs)?;
+        } else if let Some(forced) = enc.override_vertex_buffer_type() {
             n += match forced {
                 VertexBufferType::Vec2 => encode_vec2_vertex_stream(&vertices, enc, codecs)?,
                 VertexBufferType::Morton => encode_morton_vertex_streams(&vertices, enc, codecs)?,
@@ -97,6 +97,28 @@ impl GeometryValues {
         self
     }
 
+    /// Add a 3D geometry to this decoded geometry collection.
+    ///
+    /// `z` holds one value per coordinate of `geom` in `coords_iter` order; values of
+    /// dropped closing ring vertices are dropped with them. Vertices without a Z value,
+    /// including those of earlier 2D geometries, get `0`.
+    pub fn push_geom_z(&mut self, geom: &Geometry<i32>, z: Option<&[i32]>) {
+        let before = self.vertex_count();
+        self.push_geom(geom);
+        let after = self.vertex_count();
+        let values = self.z.get_or_insert_with(Vec::new);
+        values.resize(before, 0);
+        if let Some(z) = z {
+            push_z(geom, &mut z.iter().copied(), values);
+        }
+        values.resize(after, 0);
+    }
+
+    /// Number of vertices in the vertex buffer.
+    pub(crate) fn vertex_count(&self) -> usize {
+        self.vertices.as_ref().map_or(0, |v| v.len() / 2)
+    }
+
     /// Add a geometry to this decoded geometry collection (mutable version).
     pub fn push_geom(&mut self, geom: &Geometry<i32>) {
         match geom {
@@ -242,14 +264,20 @@ fn push_polygon_rings(
     parts.push(ring_count);
 }
 
-/// Push a ring's coordinates (stripping closing vertex) to verts and update rings offset.
-fn push_ring(ring: &LineString<i32>, verts: &mut Vec<i32>, rings: &mut Vec<u32>) {
+/// Number of ring vertices stored, i.e. without the closing vertex if present.
+pub(crate) fn ring_len(ring: &LineString<i32>) -> usize {
     let coords = &ring.0;
-    let len = if coords.len() > 1 && coords.last() == coords.first() {
+    if coords.len() > 1 && coords.last() == coords.first() {
         coords.len() - 1
     } else {
         coords.len()
-    };
+    }
+}
+
+/// Push a ring's coordinates (stripping closing vertex) to verts and update rings offset.
+fn push_ring(ring: &LineString<i32>, verts: &mut Vec<i32>, rings: &mut Vec<u32>) {
+    let coords = &ring.0;
+    let len = ring_len(ring);
     for c in &coords[..len] {
         verts.extend([c.x, c.y]);
     }
@@ -257,6 +285,70 @@ fn push_ring(ring: &LineString<i32>, verts: &mut Vec<i32>, rings: &mut Vec<u32>)
     rings.push(prev + u32::try_from(len).expect("vertex count overflow"));
 }
 
+/// Number of vertices [`GeometryValues::push_geom`] stores for `geom`,
+/// i.e. its coordinates without the closing vertex of each polygon ring.
+pub(crate) fn stored_vertex_count(geom: &Geometry<i32>) -> usize {
+    let polygon = |p: &Polygon<i32>| -> usize {
+        std::iter::once(p.exterior())
+            .chain(p.interiors())
+            .map(ring_len)
+            .sum()
+    };
+    match geom {
+        Geometry::<i32>::Point(_) => 1,
+        Geometry::<i32>::Line(_) => 2,
+        Geometry::<i32>::Triangle(_) => 3,
+        Geometry::<i32>::Rect(_) => 4,
+        Geometry::<i32>::LineString(ls) => ls.0.len(),
+        Geometry::<i32>::MultiPoint(mp) => mp.0.len(),
+        Geometry::<i32>::MultiLineString(mls) => mls.iter().map(|ls| ls.0.len()).sum(),
+        Geometry::<i32>::Polygon(p) => polygon(p),
+        Geometry::<i32>::MultiPolygon(mp) => mp.iter().map(polygon).sum(),
+        Geometry::<i32>::GeometryCollection(gc) => gc.iter().map(stored_vertex_count).sum(),
+    }
+}
+
+/// Push the Z values of `geom`, consuming one value per coordinate from `z`
+/// and skipping those of closing ring vertices, as [`GeometryValues::push_geom`] does.
+fn push_z(geom: &Geometry<i32>, z: &mut impl Iterator<Item = i32>, out: &mut Vec<i32>) {
+    let mut take = |n: usize| out.extend(z.by_ref().take(n));
+    match geom {
+        Geometry::<i32>::Point(_) => take(1),
+        Geometry::<i32>::Line(_) => take(2),
+        Geometry::<i32>::Triangle(_) => take(3),
+        Geometry::<i32>::Rect(_) => take(4),
+        Geometry::<i32>::LineString(ls) => take(ls.0.len()),
+        Geometry::<i32>::MultiPoint(mp) => take(mp.0.len()),
+        Geometry::<i32>::MultiLineString(mls) => {
+            for ls in mls {
+                take(ls.0.len());
+            }
+        }
+        Geometry::<i32>::Polygon(p) => push_polygon_z(p, z, out),
+        Geometry::<i32>::MultiPolygon(mp) => {
+            for p in mp {
+                push_polygon_z(p, z, out);
+            }
+        }
+        Geometry::<i32>::GeometryCollection(gc) => {
+            for g in gc {
+                push_z(g, z, out);
+            }
+        }
+    }
+}
+
+fn push_polygon_z(poly: &Polygon<i32>, z: &mut impl Iterator<Item = i32>, out: &mut Vec<i32>) {
+    for ring in std::iter::once(poly.exterior()).chain(poly.interiors()) {
+        let kept = ring_len(ring);
+        for (i, value) in z.by_ref().take(ring.0.len()).enumerate() {
+            if i < kept {
+                out.push(value);
+            }
+        }
+    }
+}
+
 /// Push linestrings to vertex buffer and offset array.
 fn push_linestrings<'a>(
     iter: impl Iterator<Item = &'a LineString<i32>>,
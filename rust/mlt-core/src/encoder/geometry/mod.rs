@@ -4,4 +4,5 @@ mod model;
 #[cfg(test)]
 mod tests;
 
+pub(crate) use geotype::{ring_len, stored_vertex_count};
 pub use model::*;
@@ -1,4 +1,6 @@
 /// Describes how the vertex buffer should be encoded.
+///
+/// Only applies to 2D geometry: 3D vertices always use componentwise delta over `(x, y, z)`.
 #[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
 #[cfg_attr(test, derive(proptest_derive::Arbitrary))]
 #[cfg_attr(all(not(test), feature = "arbitrary"), derive(arbitrary::Arbitrary))]
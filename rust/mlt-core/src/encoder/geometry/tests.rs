@@ -8,7 +8,10 @@ use crate::decoder::RawGeometry;
 use crate::encoder::model::EncoderConfig;
 use crate::encoder::{Codecs, Encoder, ExplicitEncoder, IntEncoder, VertexBufferType};
 use crate::test_helpers::{assert_empty, dec, parser};
-use crate::{Decode as _, DictionaryType, GeometryValues, LengthType, StreamType};
+use crate::{
+    ColumnType, Decode as _, DictionaryType, GeometryType, GeometryValues, Layer,
+    LendingIterator as _, LengthType, MltError, StreamType, TileLayer,
+};
 
 #[rstest]
 #[case::single_point(push_geoms(&[wkt!(POINT(10 20)).into()]))]
@@ -231,6 +234,258 @@ fn manual_encode_works() {
     assert_geometry_roundtrip(enc.data(), &decoded);
 }
 
+#[rstest]
+#[case::point(&[(wkt!(POINT(10 20)).into(), Some(vec![7]))], vec![7])]
+#[case::closed_ring(
+    &[(wkt!(POLYGON((0 0, 10 0, 10 10, 0 0))).into(), Some(vec![1, 2, 3, 1]))],
+    vec![1, 2, 3],
+)]
+#[case::mixed_2d_3d(
+    &[
+        (wkt!(LINESTRING(0 0, 5 5)).into(), None),
+        (wkt!(MULTIPOINT(1 1, 2 2)).into(), Some(vec![-4, i32::MAX])),
+    ],
+    vec![0, 0, -4, i32::MAX],
+)]
+fn z_roundtrip(#[case] geoms: &[(Geometry<i32>, Option<Vec<i32>>)], #[case] stored: Vec<i32>) {
+    let mut decoded = GeometryValues::default();
+    for (geom, z) in geoms {
+        decoded.push_geom_z(geom, z.as_deref());
+    }
+    assert_eq!(decoded.z(), Some(stored.as_slice()));
+
+    let mut enc = Encoder::default();
+    let mut codecs = Codecs::default();
+    decoded
+        .clone()
+        .write_to(&mut enc, &mut codecs)
+        .expect("encode failed");
+    assert_eq!(enc.meta(), [ColumnType::GeometryZ as u8]);
+
+    let raw = assert_empty(RawGeometry::from_bytes(enc.data(), &mut parser()));
+    let result = RawGeometry { has_z: true, ..raw }
+        .decode(&mut dec())
+        .unwrap();
+
+    // Mixed layers decode to dense offsets; Z values must leave them as they are in 2D.
+    let mut flat = decoded.clone();
+    flat.z = None;
+    let mut enc = Encoder::default();
+    flat.write_to(&mut enc, &mut codecs).expect("encode failed");
+    let raw = assert_empty(RawGeometry::from_bytes(enc.data(), &mut parser()));
+    let expected = GeometryValues {
+        z: decoded.z.clone(),
+        ..raw.decode(&mut dec()).unwrap()
+    };
+    assert_eq!(result, expected);
+    for (index, (geom, z)) in geoms.iter().enumerate() {
+        let (geometry, values) = result.to_geojson_with_z(index).unwrap();
+        assert_eq!(&geometry, geom);
+        let expected = z
+            .clone()
+            .unwrap_or_else(|| vec![0; values.as_ref().unwrap().len()]);
+        assert_eq!(values, Some(expected));
+    }
+}
+
+#[test]
+fn z_ignores_forced_dictionary_layout() {
+    let mut decoded = GeometryValues::default();
+    decoded.push_geom_z(&wkt!(MULTIPOINT(5 5, 5 5, 5 5)).into(), Some(&[1, 2, 3]));
+    let streams = forced_vertex_strategy_streams_z(&decoded, VertexBufferType::Morton);
+    assert_eq!(
+        streams,
+        [
+            StreamType::Data(DictionaryType::Vertex),
+            StreamType::Length(LengthType::VarBinary),
+            StreamType::Length(LengthType::Geometries),
+        ]
+    );
+}
+
+fn forced_vertex_strategy_streams_z(
+    decoded: &GeometryValues,
+    strategy: VertexBufferType,
+) -> Vec<StreamType> {
+    let explicit = ExplicitEncoder {
+        vertex_buffer_type: strategy,
+        ..ExplicitEncoder::all(IntEncoder::varint())
+    };
+    let mut enc = Encoder::with_explicit(EncoderConfig::default(), explicit);
+    let mut codecs = Codecs::default();
+    decoded
+        .clone()
+        .write_to(&mut enc, &mut codecs)
+        .expect("encode failed");
+    let raw = assert_empty(RawGeometry::from_bytes(enc.data(), &mut parser()));
+    let result = RawGeometry { has_z: true, ..raw }
+        .decode(&mut dec())
+        .unwrap();
+    assert_eq!(&result, decoded);
+
+    let mut stream_types: Vec<StreamType> = encoded_stream_types(enc.data()).into_iter().collect();
+    stream_types.sort();
+    stream_types
+}
+
+#[test]
+fn z_layer_roundtrip() {
+    let roof: Geometry<i32> = wkt!(POLYGON((0 0, 8 0, 8 8, 0 0))).into();
+    let road: Geometry<i32> = wkt!(LINESTRING(1 1, 2 2)).into();
+    let mut builder = TileLayer::builder("buildings", 4096).unwrap();
+    let mut feature = builder.feature(roof.clone());
+    feature.z(vec![30, 31, 32, 30]).unwrap();
+    feature.finish().unwrap();
+    builder.feature(road.clone()).finish().unwrap();
+    let bytes = builder.finish().encode(EncoderConfig::default()).unwrap();
+
+    let (_, layer) = Layer::from_bytes(&bytes, &mut parser()).expect("layer parse failed");
+    let Layer::Tag01(layer01) = layer else {
+        panic!("expected Tag01 layer")
+    };
+    let mut d = dec();
+    let parsed = layer01.decode_all(&mut d).expect("decode failed");
+    let expected = [(roof, Some(vec![30, 31, 32, 30])), (road, Some(vec![0, 0]))];
+
+    // Sorting may reorder features, so match them up by geometry.
+    let mut from_refs = Vec::new();
+    {
+        let mut features = parsed.iter_features();
+        while let Some(feature) = features.next() {
+            let feature = feature.unwrap();
+            from_refs.push((feature.geometry().clone(), feature.z().map(<[i32]>::to_vec)));
+        }
+    }
+    let tile = parsed.into_tile(&mut d).expect("into_tile failed");
+    let from_tile: Vec<_> = tile
+        .features()
+        .iter()
+        .map(|f| (f.geometry().clone(), f.z().map(<[i32]>::to_vec)))
+        .collect();
+    for feature in &expected {
+        assert!(
+            from_refs.contains(feature),
+            "{feature:?} not in {from_refs:?}"
+        );
+        assert!(
+            from_tile.contains(feature),
+            "{feature:?} not in {from_tile:?}"
+        );
+    }
+}
+
+/// Encode `decoded` as a bare triangle mesh and decode it back.
+fn mesh_only_roundtrip(decoded: &GeometryValues) -> (GeometryValues, HashSet<StreamType>) {
+    let mut enc = Encoder::new(EncoderConfig::default().with_mesh_only(true));
+    let mut codecs = Codecs::default();
+    decoded
+        .clone()
+        .write_to(&mut enc, &mut codecs)
+        .expect("encode failed");
+    let raw = assert_empty(RawGeometry::from_bytes(enc.data(), &mut parser()));
+    let result = raw.decode(&mut dec()).expect("decode failed");
+    (result, encoded_stream_types(enc.data()))
+}
+
+fn polygon_corners(geom: &Geometry<i32>) -> Vec<Coord<i32>> {
+    match geom {
+        Geometry::Polygon(p) => p.exterior().0.clone(),
+        Geometry::MultiPolygon(mp) => mp.iter().flat_map(|p| p.exterior().0.clone()).collect(),
+        _ => panic!("expected a polygon, got {geom:?}"),
+    }
+}
+
+#[test]
+fn mesh_only_drops_outlines() {
+    let geoms: [Geometry<i32>; 2] = [
+        wkt!(POLYGON((0 0, 10 0, 10 10, 0 10, 0 0))).into(),
+        wkt!(MULTIPOLYGON(((20 0, 30 0, 30 10, 20 0)),((40 0, 50 0, 50 10, 40 0)))).into(),
+    ];
+    let mut decoded = GeometryValues::new_tessellated();
+    for geom in &geoms {
+        decoded.push_geom(geom);
+    }
+    let (result, streams) = mesh_only_roundtrip(&decoded);
+
+    assert!(result.is_mesh_only());
+    for topology in [LengthType::Geometries, LengthType::Parts, LengthType::Rings] {
+        assert!(
+            !streams.contains(&StreamType::Length(topology)),
+            "{streams:?}"
+        );
+    }
+    assert_eq!(result.triangles(), Some([2, 2].as_slice()));
+    assert_eq!(result.vertices(), decoded.vertices());
+    for (index, original) in geoms.iter().enumerate() {
+        let Geometry::MultiPolygon(mesh) = result.to_geojson(index).unwrap() else {
+            panic!("mesh features decode as multi-polygons");
+        };
+        assert_eq!(mesh.0.len(), 2);
+        let corners = polygon_corners(original);
+        for triangle in &mesh {
+            assert_eq!(triangle.exterior().0.len(), 4, "closed triangle ring");
+            assert!(triangle.exterior().0.iter().all(|c| corners.contains(c)));
+        }
+    }
+}
+
+#[test]
+fn mesh_only_keeps_outlines_of_mixed_layers() {
+    let mut decoded = GeometryValues::new_tessellated();
+    decoded.push_geom(&wkt!(POLYGON((0 0, 10 0, 10 10, 0 0))).into());
+    decoded.push_geom(&wkt!(LINESTRING(1 1, 2 2)).into());
+    let (result, _) = mesh_only_roundtrip(&decoded);
+    assert!(!result.is_mesh_only());
+
+    // Decodes exactly like the column written without `mesh_only`.
+    let mut enc = Encoder::default();
+    decoded
+        .write_to(&mut enc, &mut Codecs::default())
+        .expect("encode failed");
+    assert_geometry_roundtrip(enc.data(), &result);
+}
+
+#[test]
+fn mesh_only_rejects_non_polygons() {
+    let decoded = GeometryValues {
+        vector_types: vec![GeometryType::Point],
+        index_buffer: Some(vec![0, 0, 0]),
+        triangles: Some(vec![1]),
+        vertices: Some(vec![1, 1]),
+        ..GeometryValues::default()
+    };
+    let mut enc = Encoder::default();
+    decoded
+        .write_to(&mut enc, &mut Codecs::default())
+        .expect("encode failed");
+    let raw = assert_empty(RawGeometry::from_bytes(enc.data(), &mut parser()));
+    assert!(matches!(
+        raw.decode(&mut dec()),
+        Err(MltError::NonPolygonMesh(0, GeometryType::Point))
+    ));
+}
+
+#[test]
+fn mesh_only_layer_features() {
+    let mut builder = TileLayer::builder("buildings", 4096).unwrap();
+    builder
+        .feature(wkt!(POLYGON((0 0, 8 0, 8 8, 0 8, 0 0))).into())
+        .finish()
+        .unwrap();
+    let cfg = EncoderConfig::default().with_mesh_only(true);
+    let bytes = builder.finish().encode(cfg).unwrap();
+
+    let (_, layer) = Layer::from_bytes(&bytes, &mut parser()).expect("layer parse failed");
+    let Layer::Tag01(layer01) = layer else {
+        panic!("expected Tag01 layer")
+    };
+    let parsed = layer01.decode_all(&mut dec()).expect("decode failed");
+    let mut features = parsed.iter_features();
+    let feature = features.next().unwrap().unwrap();
+    assert!(feature.is_mesh());
+    assert!(matches!(feature.geometry(), Geometry::MultiPolygon(mp) if mp.0.len() == 2));
+}
+
 /// Round-trip geometry bytes: parse then decode and compare.
 fn assert_geometry_roundtrip(data: &[u8], expected: &GeometryValues) {
     let mut p = parser();
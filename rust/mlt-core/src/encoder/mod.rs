@@ -1,10 +1,14 @@
 mod analyze;
+mod assemble;
+mod clip;
 #[cfg(all(not(test), feature = "arbitrary"))]
 mod fuzzing;
+mod generalize;
 mod geometry;
 mod id;
 pub(crate) mod model;
 mod optimizer;
+mod overzoom;
 mod property;
 mod sort;
 mod stream;
@@ -14,21 +18,28 @@ mod tile;
 mod unknown;
 mod writer;
 
+pub use assemble::TileAssembler;
+pub use generalize::{GeneralizeConfig, merge_children};
 #[cfg(not(feature = "__private"))]
 pub(crate) use geometry::VertexBufferType;
 #[cfg(feature = "__private")]
 pub use geometry::VertexBufferType;
+pub(crate) use geometry::stored_vertex_count;
 pub use id::StagedId;
 #[cfg(feature = "__private")]
 pub use model::{ColumnKind, CurveParams, ExplicitEncoder, StagedLayer, StrEncoding, StreamCtx};
-pub use model::{EncodedUnknown, EncoderConfig};
+pub use model::{Effort, EncodedUnknown, EncoderConfig, Sampling};
 #[cfg(all(test, not(feature = "__private")))]
 pub(crate) use model::{ExplicitEncoder, StagedLayer, StrEncoding};
 #[cfg(any(test, feature = "__private"))]
 pub use optimizer::Presence;
+pub use overzoom::overzoom;
 pub(crate) use property::*;
 #[cfg(feature = "__private")]
-pub use property::{StagedProperty, StagedSharedDict};
+pub use property::{
+    StagedNested, StagedNestedItem, StagedProperty, StagedRangeMap, StagedSharedDict, StagedStruct,
+    StagedStructChild,
+};
 pub use sort::SortStrategy;
 pub(crate) use sort::spatial_sort_likely_to_help;
 pub(crate) use stream::*;
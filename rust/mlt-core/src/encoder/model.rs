@@ -1,11 +1,13 @@
 use std::borrow::Cow;
 use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use derive_debug::Dbg;
 
 use crate::decoder::{DictionaryType, Extent, GeometryValues, StreamType};
 use crate::encoder::geometry::VertexBufferType;
-use crate::encoder::{IntEncoder, StagedId, StagedProperty};
+use crate::encoder::{IntEncoder, PhysicalEncoder, StagedId, StagedProperty};
 use crate::{MltError, MltResult};
 
 /// Owned variant of `Unknown`.
@@ -74,6 +76,8 @@ pub struct StagedLayer {
     pub(crate) extent: Extent,
     pub(crate) id: StagedId,
     pub(crate) geometry: GeometryValues,
+    /// Vertex-scoped columns, each holding one value per vertex of `geometry`.
+    pub(crate) vertex_properties: Vec<StagedProperty>,
     pub(crate) properties: Vec<StagedProperty>,
 }
 
@@ -101,8 +105,8 @@ impl StagedLayer {
                 actual,
             });
         }
-        // Column names must be unique within a layer. A shared dictionary's `name()` is
-        // only its prefix (which may repeat); its real columns are `{prefix}{suffix}`.
+        // Column names must be unique within a layer. A shared dictionary's or struct's
+        // `name()` is only its prefix (which may repeat); its real columns are `{prefix}{suffix}`.
         // Scoped so `seen` releases its borrow of `properties` before the move below.
         {
             let mut seen: HashSet<Cow<str>> = HashSet::new();
@@ -116,6 +120,14 @@ impl StagedLayer {
                     });
                 }
                 match property {
+                    StagedProperty::Nested(n) => {
+                        for item in &n.items {
+                            let name = format!("{}{}", n.prefix, item.suffix);
+                            if !seen.insert(Cow::Owned(name.clone())) {
+                                return Err(MltError::DuplicatePropertyName(name));
+                            }
+                        }
+                    }
                     StagedProperty::SharedDict(sd) => {
                         for item in &sd.items {
                             if !seen.insert(Cow::Owned(format!("{}{}", sd.prefix, item.suffix))) {
@@ -126,6 +138,14 @@ impl StagedLayer {
                             }
                         }
                     }
+                    StagedProperty::Struct(st) => {
+                        for idx in 0..st.children.len() {
+                            let name = format!("{}{}", st.prefix, st.child_suffix(idx));
+                            if !seen.insert(Cow::Owned(name.clone())) {
+                                return Err(MltError::DuplicatePropertyName(name));
+                            }
+                        }
+                    }
                     _ => {
                         if !seen.insert(Cow::Borrowed(property.name())) {
                             return Err(MltError::DuplicatePropertyName(
@@ -141,10 +161,45 @@ impl StagedLayer {
             extent,
             id,
             geometry,
+            vertex_properties: Vec::new(),
             properties,
         })
     }
 
+    /// Attach vertex-scoped columns, each holding one value per vertex of the geometry.
+    ///
+    /// Only non-optional bool, integer and float columns can be vertex-scoped. Their names
+    /// share one namespace with the feature-scoped columns.
+    pub fn with_vertex_properties(
+        mut self,
+        vertex_properties: Vec<StagedProperty>,
+    ) -> MltResult<Self> {
+        let vertex_count = self.geometry.vertex_count();
+        for (index, property) in vertex_properties.iter().enumerate() {
+            if !property.is_vertex_scopable() {
+                return Err(MltError::NotImplemented(
+                    "vertex-scoped columns other than non-optional bool, integer or float",
+                ));
+            }
+            let actual = property.feature_count();
+            if actual != vertex_count {
+                return Err(MltError::VertexPropertyLengthMismatch {
+                    index,
+                    expected: vertex_count,
+                    actual,
+                });
+            }
+            let name = property.name();
+            if vertex_properties[..index].iter().any(|p| p.name() == name)
+                || self.properties.iter().any(|p| p.name() == name)
+            {
+                return Err(MltError::DuplicatePropertyName(name.to_string()));
+            }
+        }
+        self.vertex_properties = vertex_properties;
+        Ok(self)
+    }
+
     #[must_use]
     pub fn name(&self) -> &str {
         &self.name
@@ -165,14 +220,53 @@ impl StagedLayer {
         &self.geometry
     }
 
+    #[must_use]
+    pub fn vertex_properties(&self) -> &[StagedProperty] {
+        &self.vertex_properties
+    }
+
     #[must_use]
     pub fn properties(&self) -> &[StagedProperty] {
         &self.properties
     }
 }
 
+/// How hard [`TileLayer::encode`](crate::TileLayer::encode) searches for the smallest encoding.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Effort {
+    /// Encode once with the first enabled sort strategy, and write every stream with
+    /// a single candidate encoding: the one estimated smallest on a sample, or the
+    /// first one for streams too short to sample.
+    Fast,
+    /// Estimate the enabled sort strategies and stream encodings on a sample and compete
+    /// the two most promising ones, skipping spatial sort trials on large layers where
+    /// they are unlikely to help.
+    #[default]
+    Default,
+    /// Fully encode every enabled sort strategy and stream encoding.
+    Max,
+}
+
+/// Whether [`TileLayer::encode`](crate::TileLayer::encode) narrows down sort strategies
+/// and integer and string stream encodings by encoding a sample of the data first.
+///
+/// Every candidate is estimated on a sample of about 1% of the features or values, and
+/// only the smallest "finalists" are then encoded in full.  Layers and streams too small
+/// to sample are always encoded exhaustively.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Sampling {
+    /// Follow the [`Effort`]: one finalist for [`Effort::Fast`], two for
+    /// [`Effort::Default`], and no sampling for [`Effort::Max`].
+    #[default]
+    Auto,
+    /// Fully encode every candidate.
+    Off,
+    /// Fully encode this many finalists (at least one).
+    Finalists(usize),
+}
+
 /// Global encoder settings controlling which optimization strategies are attempted.
-#[derive(Debug, Clone, Copy, PartialEq, Hash)]
+#[derive(Debug, Clone, PartialEq, Hash)]
 #[expect(
     clippy::struct_excessive_bools,
     reason = "enums would not model this better, not a state machine"
@@ -180,75 +274,193 @@ impl StagedLayer {
 pub struct EncoderConfig {
     /// Generate tessellation data for polygons and multi-polygons.
     tessellate: bool,
+    /// Drop the outlines of pure polygon layers, keeping only their tessellation.
+    mesh_only: bool,
     /// Try sorting features by the Z-order (Morton) curve index of their first vertex.
     attempt_spatial_morton_sort: bool,
     /// Try sorting features by the Hilbert curve index of their first vertex.
     attempt_spatial_hilbert_sort: bool,
     /// Try sorting features by their feature ID in ascending order.
     attempt_id_sort: bool,
+    /// Try sorting features by the low-cardinality property columns picked by the optimizer.
+    attempt_property_sort: bool,
+    /// Try sorting features by the property column with this name.
+    sort_property: Option<Arc<str>>,
     /// Allow `FSST` string compression
     allow_fsst: bool,
     /// Allow `FastPFOR` integer compression
     allow_fastpfor: bool,
     /// Allow string grouping into shared dictionaries
     allow_shared_dict: bool,
+    /// Allow `PseudoDecimal` encoding of float streams.
+    /// Off by default: most decoders do not support it yet.
+    allow_pseudo_decimal: bool,
+    /// Allow dictionary and RLE encodings of float columns.
+    /// Off by default: their layout is not part of the specification yet.
+    allow_float_dict_rle: bool,
+    /// Clip geometries to this many units beyond the extent before encoding.
+    clip_buffer: Option<u32>,
+    /// How many alternatives to try.
+    effort: Effort,
+    /// Stop trying alternatives once this much time has passed.
+    time_budget: Option<Duration>,
+    /// When the running budget ends; set by [`EncoderConfig::start_budget`].
+    deadline: Option<Instant>,
+    /// How many sort strategies and stream encodings to fully encode after sampling.
+    sampling: Sampling,
 }
 impl Default for EncoderConfig {
     fn default() -> Self {
         Self {
             tessellate: false,
+            mesh_only: false,
             attempt_spatial_morton_sort: true,
             attempt_spatial_hilbert_sort: true,
             attempt_id_sort: true,
+            attempt_property_sort: false,
+            sort_property: None,
             allow_fsst: true,
             allow_fastpfor: true,
             allow_shared_dict: true,
+            allow_pseudo_decimal: false,
+            allow_float_dict_rle: false,
+            clip_buffer: None,
+            effort: Effort::Default,
+            time_budget: None,
+            deadline: None,
+            sampling: Sampling::Auto,
         }
     }
 }
 
 impl EncoderConfig {
+    /// Whether polygons are tessellated; always true when [`Self::mesh_only`] is set.
+    #[must_use]
+    pub fn tessellate(&self) -> bool {
+        self.tessellate || self.mesh_only
+    }
+
     #[must_use]
-    pub fn tessellate(self) -> bool {
-        self.tessellate
+    pub fn mesh_only(&self) -> bool {
+        self.mesh_only
     }
 
     #[must_use]
-    pub fn attempt_spatial_morton_sort(self) -> bool {
+    pub fn attempt_spatial_morton_sort(&self) -> bool {
         self.attempt_spatial_morton_sort
     }
 
     #[must_use]
-    pub fn attempt_spatial_hilbert_sort(self) -> bool {
+    pub fn attempt_spatial_hilbert_sort(&self) -> bool {
         self.attempt_spatial_hilbert_sort
     }
 
     #[must_use]
-    pub fn attempt_id_sort(self) -> bool {
+    pub fn attempt_id_sort(&self) -> bool {
         self.attempt_id_sort
     }
 
     #[must_use]
-    pub fn allow_fsst(self) -> bool {
+    pub fn attempt_property_sort(&self) -> bool {
+        self.attempt_property_sort
+    }
+
+    #[must_use]
+    pub fn sort_property(&self) -> Option<&str> {
+        self.sort_property.as_deref()
+    }
+
+    #[must_use]
+    pub fn allow_fsst(&self) -> bool {
         self.allow_fsst
     }
 
     #[must_use]
-    pub fn allow_fastpfor(self) -> bool {
+    pub fn allow_fastpfor(&self) -> bool {
         self.allow_fastpfor
     }
 
     #[must_use]
-    pub fn allow_shared_dict(self) -> bool {
+    pub fn allow_shared_dict(&self) -> bool {
         self.allow_shared_dict
     }
 
+    #[must_use]
+    pub fn allow_pseudo_decimal(&self) -> bool {
+        self.allow_pseudo_decimal
+    }
+
+    #[must_use]
+    pub fn allow_float_dict_rle(&self) -> bool {
+        self.allow_float_dict_rle
+    }
+
+    #[must_use]
+    pub fn clip_buffer(&self) -> Option<u32> {
+        self.clip_buffer
+    }
+
+    #[must_use]
+    pub fn effort(&self) -> Effort {
+        self.effort
+    }
+
+    #[must_use]
+    pub fn time_budget(&self) -> Option<Duration> {
+        self.time_budget
+    }
+
+    #[must_use]
+    pub fn sampling(&self) -> Sampling {
+        self.sampling
+    }
+
+    /// How many sampled candidates are fully encoded, or `None` to encode all of them.
+    #[must_use]
+    pub fn sampling_finalists(&self) -> Option<usize> {
+        match self.sampling {
+            Sampling::Auto => match self.effort {
+                Effort::Fast => Some(1),
+                Effort::Default => Some(2),
+                Effort::Max => None,
+            },
+            Sampling::Off => None,
+            Sampling::Finalists(n) => Some(n.max(1)),
+        }
+    }
+
+    /// Start the time budget unless the caller already did.
+    pub(crate) fn ensure_budget_started(self) -> Self {
+        if self.deadline.is_none() {
+            self.start_budget()
+        } else {
+            self
+        }
+    }
+
+    /// Whether the budget started by [`Self::start_budget`] has run out.
+    pub(crate) fn budget_exhausted(&self) -> bool {
+        self.deadline
+            .is_some_and(|deadline| Instant::now() >= deadline)
+    }
+
     #[must_use]
     pub fn with_tessellation(mut self, enabled: bool) -> Self {
         self.tessellate = enabled;
         self
     }
 
+    /// Write layers that contain only polygons as a bare triangle mesh for GPU rendering,
+    /// omitting the outline streams and implying tessellation.
+    ///
+    /// Decoded features of such layers are their triangles, not the original polygons.
+    /// Layers with any point or line geometry keep their outlines.
+    #[must_use]
+    pub fn with_mesh_only(mut self, enabled: bool) -> Self {
+        self.mesh_only = enabled;
+        self
+    }
+
     #[must_use]
     pub fn with_spatial_morton_sort(mut self, enabled: bool) -> Self {
         self.attempt_spatial_morton_sort = enabled;
@@ -267,6 +479,23 @@ impl EncoderConfig {
         self
     }
 
+    /// Also try sorting by up to two property columns with few distinct values, each on
+    /// its own and combined with a Hilbert curve sort within equal values.
+    /// See [`SortStrategy::Property`](crate::encoder::SortStrategy::Property).
+    #[must_use]
+    pub fn with_property_sort(mut self, enabled: bool) -> Self {
+        self.attempt_property_sort = enabled;
+        self
+    }
+
+    /// Also try sorting by the property column called `name`, on its own and combined
+    /// with a Hilbert curve sort, in every layer that has such a column.
+    #[must_use]
+    pub fn with_sort_property(mut self, name: Option<&str>) -> Self {
+        self.sort_property = name.map(Arc::from);
+        self
+    }
+
     #[must_use]
     pub fn with_fsst(mut self, enabled: bool) -> Self {
         self.allow_fsst = enabled;
@@ -284,6 +513,60 @@ impl EncoderConfig {
         self.allow_shared_dict = enabled;
         self
     }
+
+    #[must_use]
+    pub fn with_pseudo_decimal(mut self, enabled: bool) -> Self {
+        self.allow_pseudo_decimal = enabled;
+        self
+    }
+
+    #[must_use]
+    pub fn with_float_dict_rle(mut self, enabled: bool) -> Self {
+        self.allow_float_dict_rle = enabled;
+        self
+    }
+
+    /// Clip each layer to `[-buffer, extent + buffer]` before encoding, or with
+    /// `None` encode geometries as they are. See [`TileLayer::clip`](crate::TileLayer::clip).
+    #[must_use]
+    pub fn with_clip_buffer(mut self, buffer: Option<u32>) -> Self {
+        self.clip_buffer = buffer;
+        self
+    }
+
+    #[must_use]
+    pub fn with_effort(mut self, effort: Effort) -> Self {
+        self.effort = effort;
+        self
+    }
+
+    /// Once `budget` has passed, stop trying sort strategies and stream encodings
+    /// and keep the smallest result found so far. `None` means no limit.
+    ///
+    /// The budget applies to each [`TileLayer::encode`](crate::TileLayer::encode) call,
+    /// unless [`Self::start_budget`] was used to share it between the layers of a tile.
+    #[must_use]
+    pub fn with_time_budget(mut self, budget: Option<Duration>) -> Self {
+        self.time_budget = budget;
+        self.deadline = None;
+        self
+    }
+
+    /// Override how many sort strategies and stream encodings are fully encoded
+    /// after estimating them on a sample; see [`Sampling`].
+    #[must_use]
+    pub fn with_sampling(mut self, sampling: Sampling) -> Self {
+        self.sampling = sampling;
+        self
+    }
+
+    /// Start the time budget now, so that all layers encoded with the returned config
+    /// share it. Call this once per tile.
+    #[must_use]
+    pub fn start_budget(mut self) -> Self {
+        self.deadline = self.time_budget.map(|budget| Instant::now() + budget);
+        self
+    }
 }
 
 /// How to encode a string column.
@@ -375,6 +658,7 @@ impl<'a> StreamCtx<'a> {
 /// Always compiled; publicly visible only when the `__private` feature is enabled
 /// (re-exported from [`crate::encoder`]).
 #[derive(Dbg)]
+#[non_exhaustive]
 pub struct ExplicitEncoder {
     /// Vertex buffer layout for geometry streams.
     pub vertex_buffer_type: VertexBufferType,
@@ -387,4 +671,50 @@ pub struct ExplicitEncoder {
     /// Return the string encoding strategy for a string property column.
     #[dbg(skip)]
     pub get_str_encoding: Box<dyn Fn(&str) -> StrEncoding>,
+    /// Physical encoding recorded for bool and presence streams.
+    /// See [`ExplicitEncoder::with_bool_physical`].
+    pub(crate) bool_physical: PhysicalEncoder,
+    /// Physical encoding recorded for float streams.
+    /// See [`ExplicitEncoder::with_float_physical`].
+    pub(crate) float_physical: PhysicalEncoder,
+}
+
+impl ExplicitEncoder {
+    /// Create an explicit encoder from the per-stream callbacks.
+    ///
+    /// Bool, presence and float streams use `PhysicalEncoder::None` unless overridden.
+    #[must_use]
+    pub fn new(
+        vertex_buffer_type: VertexBufferType,
+        force_stream: Box<dyn for<'a> Fn(&'a StreamCtx<'a>) -> bool>,
+        get_int_encoder: Box<dyn for<'a> Fn(&'a StreamCtx<'a>) -> IntEncoder>,
+        get_str_encoding: Box<dyn Fn(&str) -> StrEncoding>,
+    ) -> Self {
+        Self {
+            vertex_buffer_type,
+            force_stream,
+            get_int_encoder,
+            get_str_encoding,
+            bool_physical: PhysicalEncoder::None,
+            float_physical: PhysicalEncoder::None,
+        }
+    }
+
+    /// Record `physical` in the header of bool and presence streams (`None` or `VarInt`).
+    ///
+    /// The payload stays byte-RLE either way, which is what all decoders read.
+    #[must_use]
+    pub fn with_bool_physical(mut self, physical: PhysicalEncoder) -> Self {
+        self.bool_physical = physical;
+        self
+    }
+
+    /// Record `physical` in the header of float streams (`None` or `VarInt`).
+    ///
+    /// The payload stays plain little-endian floats either way, which is what all decoders read.
+    #[must_use]
+    pub fn with_float_physical(mut self, physical: PhysicalEncoder) -> Self {
+        self.float_physical = physical;
+        self
+    }
 }
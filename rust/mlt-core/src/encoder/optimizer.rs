@@ -1,10 +1,12 @@
 use bitvec::vec::BitVec;
+use usize_cast::IntoUsize as _;
 
 use crate::decoder::{Morton, PropKind, TileLayer};
 use crate::encoder::model::{CurveParams, StagedLayer};
-use crate::encoder::property::encode::write_properties;
+use crate::encoder::property::encode::{write_properties, write_vertex_properties};
+use crate::encoder::stream::keep_finalists;
 use crate::encoder::{
-    Codecs, Encoder, EncoderConfig, SortStrategy, StagedId, spatial_sort_likely_to_help,
+    Codecs, Effort, Encoder, EncoderConfig, SortStrategy, StagedId, spatial_sort_likely_to_help,
 };
 use crate::{MltError, MltResult, PropValue};
 
@@ -22,6 +24,7 @@ impl StagedLayer {
         }
         let column_count = usize::from(!matches!(self.id, StagedId::None))
             + 1 // geometry
+            + usize::from(!self.vertex_properties.is_empty())
             + self.properties.len();
 
         let Self {
@@ -29,11 +32,18 @@ impl StagedLayer {
             extent,
             id,
             geometry,
+            vertex_properties,
             properties,
         } = self;
 
         id.write_to(&mut enc, codecs)?;
-        geometry.write_to(&mut enc, codecs)?;
+        if vertex_properties.is_empty() {
+            geometry.write_to(&mut enc, codecs)?;
+        } else {
+            geometry.write_per_vertex_to(&mut enc, codecs)?;
+        }
+        // Vertex-scoped columns are grouped before the feature-scoped ones.
+        write_vertex_properties(&vertex_properties, &mut enc, codecs)?;
         write_properties(&properties, &mut enc, codecs)?;
         enc.write_header(&name, extent.get(), column_count)?;
 
@@ -54,30 +64,52 @@ fn seed_curve_caches(enc: &mut Encoder, curve_params: CurveParams) {
 /// bounding-box pruning heuristic.
 const SORT_TRIAL_THRESHOLD: usize = 512;
 
+/// At most this many property columns are tried as sort keys.
+const MAX_PROPERTY_SORT_COLUMNS: usize = 2;
+
+/// A property column is only a sort candidate if sorting by it would give runs of
+/// at least this many features on average.
+const PROPERTY_SORT_MIN_AVG_RUN: usize = 8;
+
 impl TileLayer {
     /// Encode a [`TileLayer`] to bytes, automatically optimizing all encoding choices.
     ///
     /// This is the primary encoding entry point. It:
-    /// 1. Determines which sort strategies to try based on `cfg`
-    /// 2. Tries each sort strategy, encoding and measuring the output size
-    /// 3. Returns the smallest encoding as a complete layer record (including tag and length prefix)
+    /// 1. Clips the features if [`EncoderConfig::with_clip_buffer`] is set
+    /// 2. Determines which sort strategies to try based on `cfg`, including property
+    ///    columns picked by their cardinality, optionally narrowing
+    ///    them down by encoding samples (see [`EncoderConfig::with_sampling`])
+    /// 3. Tries each remaining sort strategy, encoding and measuring the output size
+    /// 4. Returns the smallest encoding as a complete layer record (including tag and length prefix)
     ///
     /// All encoding choices - sort order, per-stream integer encodings, string compression,
     /// vertex buffer layout - are selected automatically to minimize output size.
+    /// [`EncoderConfig::with_effort`] and [`EncoderConfig::with_time_budget`] trade some of
+    /// that size for encoding speed.
+    /// A layer without features (also after clipping) encodes to no bytes at all.
     #[hotpath::measure]
-    pub fn encode(self, cfg: EncoderConfig) -> MltResult<Vec<u8>> {
+    pub fn encode(mut self, cfg: EncoderConfig) -> MltResult<Vec<u8>> {
+        let cfg = cfg.ensure_budget_started();
         if self.name().is_empty() {
             return Err(MltError::MissingLayerName);
         }
+        if let Some(buffer) = cfg.clip_buffer() {
+            self.clip(buffer);
+        }
         if self.features().is_empty() {
             return Ok(Vec::new());
         }
 
+        // Also picks the property columns worth sorting by.
+        let stats = self.analyze(cfg.allow_shared_dict())?;
+
         let mut sort_by = vec![SortStrategy::Unsorted];
         let try_spatial_sort =
             cfg.attempt_spatial_morton_sort() || cfg.attempt_spatial_hilbert_sort();
         if try_spatial_sort
-            && (self.feature_count() < SORT_TRIAL_THRESHOLD || spatial_sort_likely_to_help(&self))
+            && (cfg.effort() == Effort::Max
+                || self.feature_count() < SORT_TRIAL_THRESHOLD
+                || spatial_sort_likely_to_help(&self))
         {
             if cfg.attempt_spatial_morton_sort() {
                 sort_by.push(SortStrategy::SpatialMorton);
@@ -89,16 +121,43 @@ impl TileLayer {
         if cfg.attempt_id_sort() {
             sort_by.push(SortStrategy::Id);
         }
+        let mut property_columns = Vec::new();
+        if let Some(name) = cfg.sort_property()
+            && let Some(col) = self.property_names().iter().position(|n| n == name)
+        {
+            property_columns.push(col);
+        }
+        if cfg.attempt_property_sort() {
+            for col in self.property_sort_candidates(&stats) {
+                if !property_columns.contains(&col) {
+                    property_columns.push(col);
+                }
+            }
+        }
+        for col in property_columns {
+            sort_by.push(SortStrategy::Property(col));
+            if try_spatial_sort || cfg.sort_property().is_some() {
+                sort_by.push(SortStrategy::PropertyHilbert(col));
+            }
+        }
+        if cfg.effort() == Effort::Fast {
+            // A single trial: the first requested sort order, if any.
+            sort_by = vec![sort_by.get(1).copied().unwrap_or(SortStrategy::Unsorted)];
+        }
 
-        let stats = self.analyze(cfg.allow_shared_dict())?;
         // Bounds are order-invariant, so this scan is shared across every
         // sort trial and the encoder's Hilbert/Morton dictionary builders.
         let curve_params = self.curve_params();
+        if let Some(finalists) = cfg.sampling_finalists()
+            && sort_by.len() > finalists
+        {
+            sort_by = self.sampled_sort_finalists(&sort_by, finalists, &cfg, curve_params)?;
+        }
 
         // `Encoder::preserve_results` clears caches only on the moved-out
         // archive, so a single seeding here serves every trial that reuses
         // `enc`.
-        let mut enc = Encoder::new(cfg);
+        let mut enc = Encoder::new(cfg.clone());
         seed_curve_caches(&mut enc, curve_params);
 
         let (last, init) = sort_by.split_last().expect("at least one strategy");
@@ -116,6 +175,9 @@ impl TileLayer {
             let mut best = enc.preserve_results();
             // Clone for all-but-last strategies
             for &sort in &init[1..] {
+                if cfg.budget_exhausted() {
+                    return best.into_layer_bytes();
+                }
                 let layer = StagedLayer::from_tile(
                     self.clone(),
                     sort,
@@ -131,6 +193,9 @@ impl TileLayer {
                     enc.clear_results();
                 }
             }
+            if cfg.budget_exhausted() {
+                return best.into_layer_bytes();
+            }
             // Last strategy: consume self, no clone
             let layer = StagedLayer::from_tile(self, *last, &stats, cfg.tessellate(), curve_params);
             enc = layer.encode_into(enc, &mut codecs)?;
@@ -143,6 +208,82 @@ impl TileLayer {
     }
 }
 
+impl TileLayer {
+    /// Pick the property columns worth sorting by: those with the fewest distinct values,
+    /// as long as each value would form a run of [`PROPERTY_SORT_MIN_AVG_RUN`] features
+    /// on average. Only boolean, integer and string columns qualify.
+    fn property_sort_candidates(&self, stats: &LayerStats) -> Vec<usize> {
+        let max_distinct = self.feature_count() / PROPERTY_SORT_MIN_AVG_RUN;
+        let mut candidates: Vec<(usize, usize)> = stats
+            .properties
+            .iter()
+            .enumerate()
+            .filter(|(_, prop)| {
+                prop.presence != Presence::AllNull
+                    && matches!(
+                        prop.stats,
+                        PropertyTypedStats::Bool
+                            | PropertyTypedStats::Signed { .. }
+                            | PropertyTypedStats::Unsigned { .. }
+                            | PropertyTypedStats::String { .. }
+                    )
+            })
+            .filter_map(|(col, _)| {
+                let distinct = self.property_ranks(col).into_iter().max()?.into_usize();
+                (2..=max_distinct)
+                    .contains(&distinct)
+                    .then_some((distinct, col))
+            })
+            .collect();
+        candidates.sort_unstable();
+        candidates
+            .into_iter()
+            .take(MAX_PROPERTY_SORT_COLUMNS)
+            .map(|(_, col)| col)
+            .collect()
+    }
+
+    /// Estimate the encoded size of each strategy in `sort_by` from a sample of the
+    /// features sorted that way, and keep the `finalists` smallest in their original order.
+    ///
+    /// Each sample goes through the regular stream competitions, so the estimate also
+    /// reflects the integer and string encodings that the order favours.
+    /// Returns `sort_by` unchanged if the layer is too small to sample.
+    #[hotpath::measure]
+    fn sampled_sort_finalists(
+        &self,
+        sort_by: &[SortStrategy],
+        finalists: usize,
+        cfg: &EncoderConfig,
+        curve_params: CurveParams,
+    ) -> MltResult<Vec<SortStrategy>> {
+        let mut estimates = Vec::with_capacity(sort_by.len());
+        let mut codecs = Codecs::default();
+        for &sort in sort_by {
+            if !estimates.is_empty() && cfg.budget_exhausted() {
+                break;
+            }
+            let sample = self.sort_sample(sort, curve_params);
+            if sample.feature_count() == self.feature_count() {
+                return Ok(sort_by.to_vec());
+            }
+            let stats = sample.analyze(cfg.allow_shared_dict())?;
+            let mut enc = Encoder::new(cfg.clone());
+            seed_curve_caches(&mut enc, curve_params);
+            // The sample is already in `sort` order.
+            let layer = StagedLayer::from_tile(
+                sample,
+                SortStrategy::Unsorted,
+                &stats,
+                cfg.tessellate(),
+                curve_params,
+            );
+            estimates.push((layer.encode_into(enc, &mut codecs)?.total_len(), sort));
+        }
+        Ok(keep_finalists(sort_by, estimates, finalists))
+    }
+}
+
 /// Row-order-independent presence classification for IDs and properties.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Presence {
@@ -216,13 +357,32 @@ pub enum PropertyTypedStats {
     String {
         shared_dict: SharedDictRole,
     },
+    Nested,
+    Date,
+    Timestamp,
+    Json,
+    Binary,
+    /// Range maps whose values are all of `value_kind`, or `None` if no feature has a range.
+    RangeMap {
+        value_kind: Option<PropKind>,
+    },
 }
 
 impl PropertyTypedStats {
     #[must_use]
     pub fn values_fit_u32(&self) -> bool {
         match self {
-            Self::None | Self::Bool | Self::F32 | Self::F64 | Self::String { .. } => false,
+            Self::None
+            | Self::Bool
+            | Self::F32
+            | Self::F64
+            | Self::String { .. }
+            | Self::Nested
+            | Self::Date
+            | Self::Timestamp
+            | Self::Json
+            | Self::Binary
+            | Self::RangeMap { .. } => false,
             Self::Signed { min, max } => *min >= 0 && u32::try_from(*max).is_ok(),
             Self::Unsigned { max, .. } => u32::try_from(*max).is_ok(),
         }
@@ -233,7 +393,17 @@ impl PropertyTypedStats {
     #[must_use]
     pub fn values_fit_i32(&self) -> bool {
         match self {
-            Self::None | Self::Bool | Self::F32 | Self::F64 | Self::String { .. } => false,
+            Self::None
+            | Self::Bool
+            | Self::F32
+            | Self::F64
+            | Self::String { .. }
+            | Self::Nested
+            | Self::Date
+            | Self::Timestamp
+            | Self::Json
+            | Self::Binary
+            | Self::RangeMap { .. } => false,
             Self::Signed { min, max } => i32::try_from(*min).is_ok() && i32::try_from(*max).is_ok(),
             Self::Unsigned { max, .. } => i32::try_from(*max).is_ok(),
         }
@@ -285,6 +455,24 @@ impl PropertyTypedStats {
                 self.merge_same_kind(Self::F64, column_idx, property_name)?;
             }
             PropValue::Str(Some(_)) => self.merge_string(column_idx, property_name)?,
+            PropValue::Nested(Some(_)) => {
+                self.merge_same_kind(Self::Nested, column_idx, property_name)?;
+            }
+            PropValue::Date(Some(_)) => {
+                self.merge_same_kind(Self::Date, column_idx, property_name)?;
+            }
+            PropValue::Timestamp(Some(_)) => {
+                self.merge_same_kind(Self::Timestamp, column_idx, property_name)?;
+            }
+            PropValue::Json(Some(_)) => {
+                self.merge_same_kind(Self::Json, column_idx, property_name)?;
+            }
+            PropValue::Binary(Some(_)) => {
+                self.merge_same_kind(Self::Binary, column_idx, property_name)?;
+            }
+            PropValue::RangeMap(Some(entries)) => {
+                self.merge_range_map(entries, column_idx, property_name)?;
+            }
             _ => return Ok(false),
         }
         Ok(true)
@@ -347,6 +535,30 @@ impl PropertyTypedStats {
         Ok(())
     }
 
+    fn merge_range_map(
+        &mut self,
+        entries: &[(f64, f64, PropValue)],
+        column_idx: usize,
+        property_name: &str,
+    ) -> MltResult<()> {
+        if matches!(self, Self::None) {
+            *self = Self::RangeMap { value_kind: None };
+        }
+        let Self::RangeMap { value_kind } = self else {
+            return mixed_prop_err(column_idx, property_name);
+        };
+        for (_, _, value) in entries {
+            let kind = value.kind();
+            if !kind.is_range_map_value() {
+                return Err(MltError::UnsupportedRangeMapValueKind(kind));
+            }
+            if *value_kind.get_or_insert(kind) != kind {
+                return mixed_prop_err(column_idx, property_name);
+            }
+        }
+        Ok(())
+    }
+
     fn merge_same_kind(
         &mut self,
         kind: Self,
@@ -358,6 +570,11 @@ impl PropertyTypedStats {
             Self::Bool if matches!(kind, Self::Bool) => {}
             Self::F32 if matches!(kind, Self::F32) => {}
             Self::F64 if matches!(kind, Self::F64) => {}
+            Self::Nested if matches!(kind, Self::Nested) => {}
+            Self::Date if matches!(kind, Self::Date) => {}
+            Self::Timestamp if matches!(kind, Self::Timestamp) => {}
+            Self::Json if matches!(kind, Self::Json) => {}
+            Self::Binary if matches!(kind, Self::Binary) => {}
             _ => return mixed_prop_err(column_idx, property_name),
         }
         Ok(())
@@ -0,0 +1,154 @@
+//! Deriving child tiles from a parent tile
+
+use geo_types::Coord;
+
+use crate::decoder::TileLayer;
+use crate::{MltError, MltResult};
+
+/// Derive the layers of a child tile from the decoded layers of its parent.
+///
+/// The child is `dz` zoom levels below the parent, at column `dx` and row `dy`
+/// among the `2^dz × 2^dz` children of the parent. Every layer is scaled into the
+/// child's coordinate space (keeping the parent's extent) and clipped to
+/// `[-buffer, extent + buffer]`, see [`TileLayer::overzoom`]. Layers left
+/// without features are dropped.
+pub fn overzoom(
+    layers: Vec<TileLayer>,
+    dz: u8,
+    dx: u32,
+    dy: u32,
+    buffer: u32,
+) -> MltResult<Vec<TileLayer>> {
+    let mut children = Vec::with_capacity(layers.len());
+    for layer in layers {
+        let child = layer.overzoom(dz, dx, dy, buffer)?;
+        if child.feature_count() > 0 {
+            children.push(child);
+        }
+    }
+    Ok(children)
+}
+
+impl TileLayer {
+    /// Scale and clip this layer of a parent tile into the child tile `dz` zoom
+    /// levels below, at column `dx` and row `dy` relative to the parent.
+    ///
+    /// Coordinates are multiplied by `2^dz` and shifted so the child tile spans
+    /// `[0, extent]`, then clipped like [`TileLayer::clip`]. The border crossings
+    /// are computed in the child's space, so edges leaving the tile keep their
+    /// direction at any `dz`. Z values and all properties are kept.
+    pub fn overzoom(mut self, dz: u8, dx: u32, dy: u32, buffer: u32) -> MltResult<Self> {
+        let invalid = || MltError::InvalidOverzoomTile { dz, dx, dy };
+        if dz >= 32 {
+            return Err(invalid());
+        }
+        let children = 1_u64 << dz;
+        if u64::from(dx) >= children || u64::from(dy) >= children {
+            return Err(invalid());
+        }
+
+        // Scaled coordinates can exceed `i32`, so clipping maps them to floats first.
+        // The offsets are exact while `dx * extent` stays below 2^53.
+        let extent = f64::from(self.extent.get());
+        let offset = Coord {
+            x: f64::from(dx) * extent,
+            y: f64::from(dy) * extent,
+        };
+        self.clip_scaled(2_f64.powi(i32::from(dz)), offset, buffer);
+        Ok(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use geo_types::{Geometry, line_string, point};
+
+    use super::overzoom;
+    use crate::MltError;
+    use crate::decoder::TileLayer;
+
+    fn layer(name: &str, features: Vec<Geometry<i32>>) -> TileLayer {
+        let mut builder = TileLayer::builder(name, 100).unwrap();
+        for geometry in features {
+            builder.feature(geometry).finish().unwrap();
+        }
+        builder.finish()
+    }
+
+    #[test]
+    fn layers_are_scaled_and_clipped_into_the_child() {
+        let roads = layer(
+            "roads",
+            vec![
+                point!(x: 60, y: 30).into(),
+                point!(x: 10, y: 10).into(),
+                line_string![(x: 0, y: 40), (x: 100, y: 40)].into(),
+            ],
+        );
+        let water = layer("water", vec![point!(x: 20, y: 80).into()]);
+
+        let children = overzoom(vec![roads, water], 1, 1, 0, 10).unwrap();
+        let [roads] = children.as_slice() else {
+            panic!("expected only the roads layer")
+        };
+        assert_eq!(roads.name(), "roads");
+        assert_eq!(roads.extent.get(), 100);
+        let geometries: Vec<_> = roads
+            .features()
+            .iter()
+            .map(|f| f.geometry().clone())
+            .collect();
+        assert_eq!(
+            geometries,
+            vec![
+                point!(x: 20, y: 60).into(),
+                line_string![(x: -10, y: 80), (x: 100, y: 80)].into(),
+            ]
+        );
+    }
+
+    #[test]
+    fn deep_zoom_offsets_do_not_overflow() {
+        let parent = layer(
+            "l",
+            vec![line_string![(x: 0, y: 0), (x: 100, y: 100)].into()],
+        );
+        let child = parent.overzoom(20, 1 << 19, 1 << 19, 0).unwrap();
+        let geometries: Vec<_> = child
+            .features()
+            .iter()
+            .map(|f| f.geometry().clone())
+            .collect();
+        assert_eq!(
+            geometries,
+            vec![line_string![(x: 0, y: 0), (x: 100, y: 100)].into()]
+        );
+    }
+
+    #[test]
+    fn edges_crossing_the_border_keep_their_direction() {
+        let mut builder = TileLayer::builder("l", 4096).unwrap();
+        builder
+            .feature(line_string![(x: 200, y: 10), (x: 600, y: 13)].into())
+            .finish()
+            .unwrap();
+        // The child spans x in [256, 512] of the parent, where the line is at
+        // y = 10.42 and y = 12.34; rounding those in the parent would move the
+        // ends by up to 8 child units.
+        let child = builder.finish().overzoom(4, 1, 0, 0).unwrap();
+        assert_eq!(
+            child.features()[0].geometry(),
+            &line_string![(x: 0, y: 167), (x: 4096, y: 197)].into()
+        );
+    }
+
+    #[test]
+    fn child_outside_the_parent_is_rejected() {
+        for (dz, dx, dy) in [(1, 2, 0), (2, 0, 4), (32, 0, 0)] {
+            assert!(matches!(
+                layer("l", vec![]).overzoom(dz, dx, dy, 0),
+                Err(MltError::InvalidOverzoomTile { .. })
+            ));
+        }
+    }
+}
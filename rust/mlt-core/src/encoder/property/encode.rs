@@ -1,10 +1,20 @@
-use super::model::{StagedOptScalar, StagedProperty};
+use std::collections::HashMap;
+
+use bytemuck::cast_slice;
+use integer_encoding::VarIntWriter as _;
+use num_traits::AsPrimitive;
+
+use super::model::{
+    StagedNested, StagedNestedItem, StagedOptScalar, StagedProperty, StagedRangeMap,
+};
 use crate::MltResult;
-use crate::decoder::{ColumnType, DictionaryType, StreamType};
+use crate::decoder::{
+    ColumnType, DictionaryType, NestedValue, OffsetType, PropKind, PropValue, StreamType,
+};
 use crate::encoder::model::StreamCtx;
 use crate::encoder::{
-    Codecs, Encoder, LogicalCodecs, LogicalIntCodec, LogicalIntStreamKind, StagedScalar,
-    StagedStrings,
+    Codecs, Encoder, FloatStreamKind, LogicalCodecs, LogicalIntCodec, LogicalIntStreamKind,
+    StagedScalar, StagedStrings,
 };
 
 /// Encode all property columns and write them to `enc`.
@@ -20,9 +30,38 @@ pub fn write_properties(
     Ok(())
 }
 
+/// Encode vertex-scoped columns as a single [`ColumnType::VertexScope`] group column.
+///
+/// Writes nothing if `props` is empty. Every column must be a non-optional bool,
+/// integer or float column, see [`StagedProperty::is_vertex_scopable`].
+#[hotpath::measure]
+pub fn write_vertex_properties(
+    props: &[StagedProperty],
+    enc: &mut Encoder,
+    codecs: &mut Codecs,
+) -> MltResult<()> {
+    if props.is_empty() {
+        return Ok(());
+    }
+    enc.write_column_type(ColumnType::VertexScope)?;
+    enc.meta_mut().write_varint(u32::try_from(props.len())?)?;
+    for prop in props {
+        debug_assert!(
+            prop.is_vertex_scopable(),
+            "{prop:?} cannot be vertex-scoped"
+        );
+        write_prop(prop, enc, codecs)?;
+    }
+    Ok(())
+}
+
 /// Encode a single property column, dispatching on variant.
 #[hotpath::measure]
-fn write_prop(prop: &StagedProperty, enc: &mut Encoder, codecs: &mut Codecs) -> MltResult<()> {
+pub(crate) fn write_prop(
+    prop: &StagedProperty,
+    enc: &mut Encoder,
+    codecs: &mut Codecs,
+) -> MltResult<()> {
     use ColumnType as CT;
     use StagedProperty as D;
 
@@ -39,19 +78,19 @@ fn write_prop(prop: &StagedProperty, enc: &mut Encoder, codecs: &mut Codecs) ->
         }
         D::F32(v) => {
             enc.write_column_header(CT::F32, &v.name)?;
-            codecs.write_float_stream(&v.values, StreamType::Data(DictionaryType::None), enc)
+            codecs.write_float_col(&v.name, &v.values, enc)
         }
         D::OptF32(v) => {
             codecs.begin_opt_col(CT::OptF32, &v.name, &v.presence, enc)?;
-            codecs.write_float_stream(&v.values, StreamType::Data(DictionaryType::None), enc)
+            codecs.write_float_col(&v.name, &v.values, enc)
         }
         D::F64(v) => {
             enc.write_column_header(CT::F64, &v.name)?;
-            codecs.write_float_stream(&v.values, StreamType::Data(DictionaryType::None), enc)
+            codecs.write_float_col(&v.name, &v.values, enc)
         }
         D::OptF64(v) => {
             codecs.begin_opt_col(CT::OptF64, &v.name, &v.presence, enc)?;
-            codecs.write_float_stream(&v.values, StreamType::Data(DictionaryType::None), enc)
+            codecs.write_float_col(&v.name, &v.values, enc)
         }
         D::I8(v) => codecs.write_scalar_col(CT::I8, Some(&v.name), v, enc),
         D::OptI8(v) => codecs.write_opt_scalar_col(CT::OptI8, Some(&v.name), v, enc),
@@ -74,6 +113,16 @@ fn write_prop(prop: &StagedProperty, enc: &mut Encoder, codecs: &mut Codecs) ->
             codecs.write_str_col(v, Some(v), enc)
         }
         D::SharedDict(v) => codecs.write_shared_dict(v, enc),
+        D::Struct(v) => codecs.write_struct(v, enc),
+        D::Nested(v) => codecs.write_nested(v, enc),
+        D::RangeMap(v) => {
+            enc.write_column_header(CT::RangeMap, &v.name)?;
+            codecs.write_range_map_col(v, false, enc)
+        }
+        D::OptRangeMap(v) => {
+            enc.write_column_header(CT::OptRangeMap, &v.name)?;
+            codecs.write_range_map_col(v, true, enc)
+        }
     }
 }
 
@@ -94,6 +143,41 @@ impl Codecs {
         self.write_presence_stream(presence.iter().copied(), enc)
     }
 
+    /// Write the data streams of a float column.
+    ///
+    /// A single data stream (raw, RLE or pseudo-decimal) competes against a dictionary
+    /// of distinct values addressed by per-value offsets when values repeat and
+    /// [`EncoderConfig::allow_float_dict_rle`](crate::encoder::EncoderConfig::allow_float_dict_rle)
+    /// is set.
+    fn write_float_col<T: FloatStreamKind>(
+        &mut self,
+        name: &str,
+        values: &[T],
+        enc: &mut Encoder,
+    ) -> MltResult<()>
+    where
+        f64: AsPrimitive<T>,
+    {
+        let data = StreamType::Data(DictionaryType::None);
+        let dict = if enc.is_explicit() || !enc.config().allow_float_dict_rle() {
+            None
+        } else {
+            float_dictionary(values)
+        };
+        let Some((offsets, dictionary)) = dict else {
+            return self.write_float_stream(values, data, enc);
+        };
+
+        let mut alt = enc.try_alternatives();
+        alt.with(|enc| self.write_float_stream(values, data, enc))?;
+        alt.with(|enc| {
+            let typ = StreamType::Offset(OffsetType::Key);
+            self.write_int_stream(&offsets, &StreamCtx::prop(typ, name), enc)?;
+            let typ = StreamType::Data(DictionaryType::Single);
+            self.write_float_stream(&dictionary, typ, enc)
+        })
+    }
+
     pub(crate) fn write_scalar_col<T>(
         &mut self,
         ct: ColumnType,
@@ -128,6 +212,30 @@ impl Codecs {
     }
 }
 
+/// Build a first-occurrence dictionary of `values`, returning per-value offsets and
+/// the distinct values.
+///
+/// Values are keyed by bit pattern so `-0.0` and NaN payloads survive unchanged.
+/// Returns `None` unless each distinct value occurs at least twice on average.
+fn float_dictionary<T: FloatStreamKind>(values: &[T]) -> Option<(Vec<u32>, Vec<T>)> {
+    let mut index = HashMap::new();
+    let mut dictionary = Vec::new();
+    let mut offsets = Vec::with_capacity(values.len());
+    for (&value, &bits) in values.iter().zip(cast_slice::<T, T::Bits>(values)) {
+        let next = u32::try_from(dictionary.len()).ok()?;
+        let offset = *index.entry(bits).or_insert_with(|| {
+            dictionary.push(value);
+            next
+        });
+        offsets.push(offset);
+        // The dictionary only grows, so it can be abandoned as soon as it is too large.
+        if dictionary.len() * 2 > values.len() {
+            return None;
+        }
+    }
+    Some((offsets, dictionary))
+}
+
 fn begin_scalar_col(ct: ColumnType, name: Option<&str>, enc: &mut Encoder) -> MltResult<()> {
     if let Some(name) = name {
         enc.write_column_header(ct, name)
@@ -220,6 +328,26 @@ impl StagedProperty {
         Self::OptStr(StagedStrings::from_optional(name, values))
     }
 
+    /// Nested (map / list) column without children; `None` values are nulls.
+    #[must_use]
+    pub fn nested(
+        name: impl Into<String>,
+        values: impl IntoIterator<Item = Option<NestedValue>>,
+    ) -> Self {
+        Self::Nested(StagedNested::new(name, [StagedNestedItem::new("", values)]))
+    }
+
+    /// Range map column; every range value must be of `kind`.
+    pub fn range_map(
+        name: impl Into<String>,
+        kind: PropKind,
+        values: impl IntoIterator<Item = Vec<(f64, f64, PropValue)>>,
+    ) -> MltResult<Self> {
+        Ok(Self::RangeMap(StagedRangeMap::from_values(
+            name, kind, values,
+        )?))
+    }
+
     // ── Optional constructors ─────────────────────────────────────────────────
 
     #[must_use]
@@ -261,6 +389,15 @@ impl StagedProperty {
     pub fn opt_f64(name: impl Into<String>, values: impl IntoIterator<Item = Option<f64>>) -> Self {
         Self::OptF64(StagedOptScalar::from_optional(name, values))
     }
+    pub fn opt_range_map(
+        name: impl Into<String>,
+        kind: PropKind,
+        values: impl IntoIterator<Item = Option<Vec<(f64, f64, PropValue)>>>,
+    ) -> MltResult<Self> {
+        Ok(Self::OptRangeMap(StagedRangeMap::from_optional(
+            name, kind, values,
+        )?))
+    }
 
     #[must_use]
     pub fn name(&self) -> &str {
@@ -285,6 +422,9 @@ impl StagedProperty {
             Self::OptF64(v) => &v.name,
             Self::Str(v) | Self::OptStr(v) => &v.name,
             Self::SharedDict(v) => &v.prefix,
+            Self::Nested(v) => &v.prefix,
+            Self::RangeMap(v) | Self::OptRangeMap(v) => &v.name,
+            Self::Struct(v) => &v.prefix,
         }
     }
 }
@@ -1,11 +1,14 @@
 pub(crate) mod encode;
 mod model;
+mod nested;
+mod range_map;
 mod shared_dict;
 mod strings;
+mod structs;
 #[cfg(test)]
 mod tests;
 
 pub use model::{
-    StagedOptScalar, StagedProperty, StagedScalar, StagedSharedDict, StagedSharedDictItem,
-    StagedStrings,
+    StagedNested, StagedNestedItem, StagedOptScalar, StagedProperty, StagedRangeMap, StagedScalar,
+    StagedSharedDict, StagedSharedDictItem, StagedStrings, StagedStruct, StagedStructChild,
 };
@@ -1,4 +1,5 @@
 use crate::DictRange;
+use crate::decoder::NestedValue;
 
 /// Staged property column (encode-side, fully owned).
 ///
@@ -28,9 +29,30 @@ pub enum StagedProperty {
     OptF64(StagedOptScalar<f64>),
     OptStr(StagedStrings),
     SharedDict(StagedSharedDict),
+    Nested(StagedNested),
+    RangeMap(StagedRangeMap),
+    OptRangeMap(StagedRangeMap),
+    Struct(StagedStruct),
 }
 
 impl StagedProperty {
+    /// Whether this column can be stored vertex-scoped: non-optional bool, integer or float.
+    #[must_use]
+    pub fn is_vertex_scopable(&self) -> bool {
+        matches!(
+            self,
+            Self::Bool(_)
+                | Self::I8(_)
+                | Self::U8(_)
+                | Self::I32(_)
+                | Self::U32(_)
+                | Self::I64(_)
+                | Self::U64(_)
+                | Self::F32(_)
+                | Self::F64(_)
+        )
+    }
+
     #[must_use]
     pub fn feature_count(&self) -> usize {
         match self {
@@ -54,6 +76,9 @@ impl StagedProperty {
             Self::OptF32(s) => s.presence.len(),
             Self::OptF64(s) => s.presence.len(),
             Self::SharedDict(s) => s.feature_count(),
+            Self::Nested(s) => s.feature_count(),
+            Self::RangeMap(s) | Self::OptRangeMap(s) => s.feature_count(),
+            Self::Struct(s) => s.feature_count(),
         }
     }
 }
@@ -89,6 +114,18 @@ pub struct StagedStrings {
     pub(crate) data: String,
 }
 
+/// Owned range map column prepared for encoding.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StagedRangeMap {
+    pub(crate) name: String,
+    /// Per-feature cumulative end offsets into `values` (same encoding as [`StagedStrings::lengths`]).
+    pub lengths: Vec<i32>,
+    /// `[start, end]` pairs, one pair per range.
+    pub(crate) ranges: Vec<f64>,
+    /// Unnamed column with one value per range.
+    pub(crate) values: Box<StagedProperty>,
+}
+
 /// Owned shared-dictionary column prepared for encoding.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct StagedSharedDict {
@@ -106,3 +143,38 @@ pub struct StagedSharedDictItem {
     /// It's OK to write unneeded one, but can't be false with nulls.
     pub(crate) has_presence: bool,
 }
+
+/// Owned struct column prepared for encoding, see [`StagedStruct::new`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct StagedStruct {
+    pub(crate) prefix: String,
+    /// Dictionary shared by the string children, `None` if there are none.
+    pub(crate) strings: Option<StagedSharedDict>,
+    pub children: Vec<StagedStructChild>,
+}
+
+/// A single child within a staged struct column, in column order.
+#[derive(Debug, Clone, PartialEq)]
+pub enum StagedStructChild {
+    /// Index into the items of the struct's shared string dictionary.
+    Shared(usize),
+    /// Any other child, named by its suffix.
+    Column(StagedProperty),
+}
+
+/// Owned nested (map / list) column prepared for encoding.
+///
+/// A column without children holds a single item with an empty suffix.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StagedNested {
+    pub(crate) prefix: String,
+    pub items: Vec<StagedNestedItem>,
+}
+
+/// A single child within a staged nested column.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StagedNestedItem {
+    pub(crate) suffix: String,
+    /// Per-feature values; `None` is a null.
+    pub values: Vec<Option<NestedValue>>,
+}
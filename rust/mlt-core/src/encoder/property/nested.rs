@@ -0,0 +1,348 @@
+use integer_encoding::VarIntWriter as _;
+
+use crate::MltError::InvalidNestedPayload;
+use crate::decoder::{MAX_NESTED_DEPTH, NestedMask, NestedToken, NestedValue};
+use crate::encoder::model::StreamCtx;
+use crate::encoder::property::strings::write_raw_str_data;
+use crate::encoder::{Codecs, Encoder, StagedNested, StagedNestedItem};
+use crate::errors::AsMltError as _;
+use crate::utils::strings_to_lengths;
+use crate::{ColumnType, DictionaryType, LengthType, MltResult, OffsetType, StreamType};
+
+impl StagedNested {
+    /// Build a nested column from its children.
+    ///
+    /// Use a single item with an empty suffix for a column without children.
+    #[must_use]
+    pub fn new(
+        prefix: impl Into<String>,
+        items: impl IntoIterator<Item = StagedNestedItem>,
+    ) -> Self {
+        Self {
+            prefix: prefix.into(),
+            items: items.into_iter().collect(),
+        }
+    }
+
+    #[must_use]
+    pub fn feature_count(&self) -> usize {
+        self.items.first().map_or(0, |item| item.values.len())
+    }
+
+    /// True when the column is written without named child columns.
+    fn is_single(&self) -> bool {
+        matches!(self.items.as_slice(), [item] if item.suffix.is_empty())
+    }
+}
+
+impl StagedNestedItem {
+    #[must_use]
+    pub fn new(
+        suffix: impl Into<String>,
+        values: impl IntoIterator<Item = Option<NestedValue>>,
+    ) -> Self {
+        Self {
+            suffix: suffix.into(),
+            values: values.into_iter().collect(),
+        }
+    }
+}
+
+impl Codecs {
+    /// Encode a nested (map / list) property and write it to `enc`.
+    ///
+    /// All scalars of all children share sorted per-type dictionaries, and every
+    /// present value is flattened into a single token stream indexing them.
+    #[hotpath::measure]
+    pub(crate) fn write_nested(
+        &mut self,
+        nested: &StagedNested,
+        enc: &mut Encoder,
+    ) -> MltResult<()> {
+        enc.has_nested = true;
+        enc.write_column_header(ColumnType::Nested, &nested.prefix)?;
+        if nested.is_single() {
+            enc.meta_mut().write_varint(0_u32)?;
+        } else {
+            enc.meta_mut()
+                .write_varint(u32::try_from(nested.items.len())?)?;
+            for item in &nested.items {
+                enc.write_column_header(ColumnType::Nested, &item.suffix)?;
+                enc.meta_mut().write_varint(0_u32)?;
+            }
+        }
+
+        let present = || nested.items.iter().flat_map(|i| &i.values).flatten();
+        if present().next().is_none() {
+            // Every value of every child is null
+            enc.write_varint(0_u32)?;
+            return Ok(());
+        }
+
+        let mut dict = NestedDict::default();
+        for value in present() {
+            dict.collect_root(value)?;
+        }
+        dict.sort();
+
+        let mut lengths = Vec::new();
+        let mut tokens = Vec::new();
+        for value in present() {
+            let start = tokens.len();
+            dict.flatten_root(value, &mut tokens)?;
+            lengths.push(u32::try_from(tokens.len() - start)?);
+        }
+
+        let has_presence = nested
+            .items
+            .iter()
+            .flat_map(|i| &i.values)
+            .any(Option::is_none);
+        let ints_fit_32 = dict.ints.iter().all(|&v| i32::try_from(v).is_ok());
+        let uints_fit_32 = dict.uints.iter().all(|&v| u32::try_from(v).is_ok());
+
+        let mut mask = 0_u8;
+        let mut stream_count = 1_u32;
+        if !dict.strings.is_empty() {
+            mask |= NestedMask::STRING;
+            stream_count += 2;
+        }
+        if !dict.ints.is_empty() {
+            mask |= if ints_fit_32 {
+                NestedMask::INT32
+            } else {
+                NestedMask::INT64
+            };
+            stream_count += 1;
+        }
+        if !dict.uints.is_empty() {
+            mask |= if uints_fit_32 {
+                NestedMask::UINT32
+            } else {
+                NestedMask::UINT64
+            };
+            stream_count += 1;
+        }
+        if !dict.floats.is_empty() {
+            mask |= NestedMask::FLOAT;
+            stream_count += 1;
+        }
+        if !dict.doubles.is_empty() {
+            mask |= NestedMask::DOUBLE;
+            stream_count += 1;
+        }
+        if has_presence {
+            mask |= NestedMask::PRESENCE;
+            stream_count += 1;
+        }
+        if !tokens.is_empty() {
+            stream_count += 1;
+        }
+
+        enc.write_varint(stream_count)?;
+        enc.data_mut().push(mask);
+        let name = nested.prefix.as_str();
+        let typ = StreamType::Length(LengthType::VarBinary);
+        self.write_int_stream(&lengths, &StreamCtx::prop(typ, name), enc)?;
+
+        if !dict.strings.is_empty() {
+            // Plain string layout: lengths + data
+            enc.data_mut().push(2);
+            let typ = StreamType::Length(LengthType::VarBinary);
+            let lengths = strings_to_lengths(&dict.strings)?;
+            self.write_int_stream(&lengths, &StreamCtx::prop(typ, name), enc)?;
+            write_raw_str_data(&dict.strings, DictionaryType::None, enc)?;
+        }
+        let data_ctx = StreamCtx::prop_data(name);
+        if !dict.ints.is_empty() {
+            if ints_fit_32 {
+                let ints = dict.ints.iter().map(|&v| i32::try_from(v));
+                let ints = ints.collect::<Result<Vec<_>, _>>()?;
+                self.write_int_stream(&ints, &data_ctx, enc)?;
+            } else {
+                self.write_int_stream(&dict.ints, &data_ctx, enc)?;
+            }
+        }
+        if !dict.uints.is_empty() {
+            if uints_fit_32 {
+                let uints = dict.uints.iter().map(|&v| u32::try_from(v));
+                let uints = uints.collect::<Result<Vec<_>, _>>()?;
+                self.write_int_stream(&uints, &data_ctx, enc)?;
+            } else {
+                self.write_int_stream(&dict.uints, &data_ctx, enc)?;
+            }
+        }
+        let typ = StreamType::Data(DictionaryType::None);
+        if !dict.floats.is_empty() {
+            self.write_float_stream(&dict.floats, typ, enc)?;
+        }
+        if !dict.doubles.is_empty() {
+            self.write_float_stream(&dict.doubles, typ, enc)?;
+        }
+        if has_presence {
+            let bits: Vec<bool> = nested
+                .items
+                .iter()
+                .flat_map(|i| &i.values)
+                .map(Option::is_some)
+                .collect();
+            self.write_presence_stream(bits.into_iter(), enc)?;
+        }
+        if !tokens.is_empty() {
+            let typ = StreamType::Offset(OffsetType::Vertex);
+            self.write_int_stream(&tokens, &StreamCtx::prop(typ, name), enc)?;
+        }
+        Ok(())
+    }
+}
+
+/// Sorted, de-duplicated scalar dictionaries of a nested column.
+#[derive(Default)]
+struct NestedDict<'a> {
+    strings: Vec<&'a str>,
+    ints: Vec<i64>,
+    uints: Vec<u64>,
+    floats: Vec<f32>,
+    doubles: Vec<f64>,
+}
+
+impl<'a> NestedDict<'a> {
+    /// Gather the scalars of one feature; the entries of a root map do not add a nesting level.
+    fn collect_root(&mut self, value: &'a NestedValue) -> MltResult<()> {
+        if let NestedValue::Map(entries) = value {
+            for (key, item) in entries {
+                self.strings.push(key);
+                self.collect(item, 0)?;
+            }
+            Ok(())
+        } else {
+            self.collect(value, 0)
+        }
+    }
+
+    /// Gather every scalar (and map key) of `value`, rejecting trees the decoder would refuse.
+    fn collect(&mut self, value: &'a NestedValue, depth: usize) -> MltResult<()> {
+        match value {
+            NestedValue::Bool(_) => {}
+            NestedValue::I64(v) => self.ints.push(*v),
+            NestedValue::U64(v) => self.uints.push(*v),
+            NestedValue::F32(v) => self.floats.push(*v),
+            NestedValue::F64(v) => self.doubles.push(*v),
+            NestedValue::Str(v) => self.strings.push(v),
+            NestedValue::List(items) => {
+                check_depth(depth)?;
+                for item in items {
+                    self.collect(item, depth + 1)?;
+                }
+            }
+            NestedValue::Map(entries) => {
+                check_depth(depth)?;
+                for (key, item) in entries {
+                    self.strings.push(key);
+                    self.collect(item, depth + 1)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn sort(&mut self) {
+        self.strings.sort_unstable();
+        self.strings.dedup();
+        self.ints.sort_unstable();
+        self.ints.dedup();
+        self.uints.sort_unstable();
+        self.uints.dedup();
+        self.floats.sort_unstable_by(f32::total_cmp);
+        self.floats.dedup_by(|a, b| a.to_bits() == b.to_bits());
+        self.doubles.sort_unstable_by(f64::total_cmp);
+        self.doubles.dedup_by(|a, b| a.to_bits() == b.to_bits());
+    }
+
+    /// Flatten the value of one feature: a root map is written as its bare entry list.
+    fn flatten_root(&self, value: &NestedValue, out: &mut Vec<u32>) -> MltResult<()> {
+        if let NestedValue::Map(entries) = value {
+            self.flatten_entries(entries, out)
+        } else {
+            self.flatten(value, out)
+        }
+    }
+
+    fn flatten(&self, value: &NestedValue, out: &mut Vec<u32>) -> MltResult<()> {
+        let start = out.len();
+        match value {
+            NestedValue::Bool(false) => out.push(NestedToken::FALSE),
+            NestedValue::Bool(true) => out.push(NestedToken::TRUE),
+            NestedValue::Map(entries) => {
+                // The length placeholder is patched once the payload is written
+                out.extend([NestedToken::START_MAP, 0]);
+                self.flatten_entries(entries, out)?;
+                out[start + 1] = u32::try_from(out.len() - start)?;
+            }
+            NestedValue::List(items) => {
+                out.extend([NestedToken::START_LIST, 0]);
+                for item in items {
+                    self.flatten(item, out)?;
+                }
+                out[start + 1] = u32::try_from(out.len() - start)?;
+            }
+            scalar => out.push(self.token(scalar)?),
+        }
+        Ok(())
+    }
+
+    fn flatten_entries(
+        &self,
+        entries: &[(String, NestedValue)],
+        out: &mut Vec<u32>,
+    ) -> MltResult<()> {
+        for (key, value) in entries {
+            out.push(self.str_token(key)?);
+            self.flatten(value, out)?;
+        }
+        Ok(())
+    }
+
+    fn str_token(&self, value: &str) -> MltResult<u32> {
+        let idx = self.strings.binary_search(&value);
+        index_to_token(0, idx)
+    }
+
+    fn token(&self, value: &NestedValue) -> MltResult<u32> {
+        let mut base = self.strings.len();
+        let idx = match value {
+            NestedValue::Str(v) => return self.str_token(v),
+            NestedValue::I64(v) => self.ints.binary_search(v),
+            NestedValue::U64(v) => {
+                base += self.ints.len();
+                self.uints.binary_search(v)
+            }
+            NestedValue::F32(v) => {
+                base += self.ints.len() + self.uints.len();
+                self.floats.binary_search_by(|d| d.total_cmp(v))
+            }
+            NestedValue::F64(v) => {
+                base += self.ints.len() + self.uints.len() + self.floats.len();
+                self.doubles.binary_search_by(|d| d.total_cmp(v))
+            }
+            NestedValue::Bool(_) | NestedValue::List(_) | NestedValue::Map(_) => {
+                return Err(InvalidNestedPayload("not a dictionary scalar"));
+            }
+        };
+        index_to_token(base, idx)
+    }
+}
+
+fn index_to_token(base: usize, idx: Result<usize, usize>) -> MltResult<u32> {
+    let idx = idx.map_err(|_| InvalidNestedPayload("value missing from dictionary"))?;
+    let idx = u32::try_from(base + idx)?;
+    idx.checked_add(NestedToken::COUNT).or_overflow()
+}
+
+fn check_depth(depth: usize) -> MltResult<()> {
+    if depth >= MAX_NESTED_DEPTH {
+        Err(InvalidNestedPayload("nesting too deep"))
+    } else {
+        Ok(())
+    }
+}
@@ -0,0 +1,164 @@
+use integer_encoding::VarIntWriter as _;
+
+use crate::MltError::{PropertyKindMismatch, UnsupportedRangeMapValueKind};
+use crate::decoder::strings::encode_null_end;
+use crate::decoder::{DictionaryType, LengthType, PropKind, PropValue, StreamType};
+use crate::encoder::model::StreamCtx;
+use crate::encoder::property::encode::write_prop;
+use crate::encoder::{Codecs, Encoder, StagedProperty, StagedRangeMap};
+use crate::{MltError, MltResult};
+
+impl StagedRangeMap {
+    /// Stages a range map column where every row has a value (no nulls).
+    ///
+    /// `kind` is the kind of every range value, see [`PropKind::is_range_map_value`].
+    pub fn from_values(
+        name: impl Into<String>,
+        kind: PropKind,
+        values: impl IntoIterator<Item = Vec<(f64, f64, PropValue)>>,
+    ) -> MltResult<Self> {
+        Self::from_optional(name, kind, values.into_iter().map(Some))
+    }
+
+    /// Stages a range map column with optional values.
+    ///
+    /// `kind` is the kind of every range value; individual range values may be null.
+    pub fn from_optional(
+        name: impl Into<String>,
+        kind: PropKind,
+        values: impl IntoIterator<Item = Option<Vec<(f64, f64, PropValue)>>>,
+    ) -> MltResult<Self> {
+        if !kind.is_range_map_value() {
+            return Err(UnsupportedRangeMapValueKind(kind));
+        }
+        let iter = values.into_iter();
+        let (lower, _) = iter.size_hint();
+        let mut lengths = Vec::with_capacity(lower);
+        let mut ranges = Vec::new();
+        let mut range_values = Vec::new();
+        let mut end = 0_i32;
+        for value in iter {
+            let Some(entries) = value else {
+                lengths.push(encode_null_end(end));
+                continue;
+            };
+            end = end
+                .checked_add(i32::try_from(entries.len())?)
+                .ok_or(MltError::IntegerOverflow)?;
+            lengths.push(end);
+            for (start, stop, value) in entries {
+                if value.kind() != kind {
+                    return Err(PropertyKindMismatch {
+                        index: range_values.len(),
+                        expected: kind,
+                        actual: value.kind(),
+                    });
+                }
+                ranges.extend([start, stop]);
+                range_values.push(value);
+            }
+        }
+        Ok(Self {
+            name: name.into(),
+            lengths,
+            ranges,
+            values: Box::new(stage_range_values(kind, range_values)),
+        })
+    }
+
+    #[must_use]
+    pub fn feature_count(&self) -> usize {
+        self.lengths.len()
+    }
+
+    #[must_use]
+    pub fn presence_bools(&self) -> impl ExactSizeIterator<Item = bool> + '_ {
+        self.lengths.iter().map(|&end| end >= 0)
+    }
+
+    /// Number of ranges of each present feature, in order.
+    fn dense_lengths(&self) -> Vec<u32> {
+        let mut start = 0_i32;
+        let mut lengths = Vec::with_capacity(self.lengths.len());
+        for &end in &self.lengths {
+            if end >= 0 {
+                lengths.push(end.abs_diff(start));
+                start = end;
+            }
+        }
+        lengths
+    }
+}
+
+/// Stage the flattened range values as an unnamed column, optional only if a value is null.
+///
+/// Every value must already be of `kind`.
+fn stage_range_values(kind: PropKind, values: Vec<PropValue>) -> StagedProperty {
+    let has_nulls = values.iter().any(PropValue::is_null);
+
+    macro_rules! column {
+        ($ctor:ident, $opt_ctor:ident, $variant:ident) => {{
+            let values = values.into_iter().map(|v| match v {
+                PropValue::$variant(v) => v,
+                _ => unreachable!("range values are checked against the column kind"),
+            });
+            if has_nulls {
+                StagedProperty::$opt_ctor("", values)
+            } else {
+                StagedProperty::$ctor("", values.flatten().collect())
+            }
+        }};
+    }
+
+    match kind {
+        PropKind::Bool => column!(bool, opt_bool, Bool),
+        PropKind::I8 => column!(i8, opt_i8, I8),
+        PropKind::U8 => column!(u8, opt_u8, U8),
+        PropKind::I32 => column!(i32, opt_i32, I32),
+        PropKind::U32 => column!(u32, opt_u32, U32),
+        PropKind::I64 => column!(i64, opt_i64, I64),
+        PropKind::U64 => column!(u64, opt_u64, U64),
+        PropKind::F32 => column!(f32, opt_f32, F32),
+        PropKind::F64 => column!(f64, opt_f64, F64),
+        PropKind::Str => {
+            let values = values.into_iter().map(|v| match v {
+                PropValue::Str(v) => v,
+                _ => unreachable!("range values are checked against the column kind"),
+            });
+            if has_nulls {
+                StagedProperty::opt_str("", values)
+            } else {
+                StagedProperty::str("", values.flatten())
+            }
+        }
+        PropKind::Nested
+        | PropKind::Date
+        | PropKind::Timestamp
+        | PropKind::Json
+        | PropKind::Binary
+        | PropKind::RangeMap => unreachable!("{kind:?} is not a range map value kind"),
+    }
+}
+
+impl Codecs {
+    /// Encode a range map column body: optional presence, range counts, range bounds, and
+    /// the value column (whose definition follows the child count in the layer metadata).
+    ///
+    /// The column header is written by the caller.
+    #[hotpath::measure]
+    pub(crate) fn write_range_map_col(
+        &mut self,
+        v: &StagedRangeMap,
+        optional: bool,
+        enc: &mut Encoder,
+    ) -> MltResult<()> {
+        enc.meta_mut().write_varint(1_u32)?;
+        if optional {
+            self.write_presence_stream(v.presence_bools(), enc)?;
+        }
+        let ctx = StreamCtx::prop(StreamType::Length(LengthType::VarBinary), &v.name);
+        self.write_int_stream(&v.dense_lengths(), &ctx, enc)?;
+        self.write_float_stream(&v.ranges, StreamType::Data(DictionaryType::None), enc)?;
+        write_prop(&v.values, enc, self)
+    }
+}
@@ -10,7 +10,7 @@ use union_find::{QuickUnionUf, UnionBySize, UnionFind as _};
 use usize_cast::IntoUsize as _;
 
 use crate::MltError::DictIndexOutOfBounds;
-use crate::codecs::fsst::compress_fsst_with;
+use crate::codecs::fsst::{FsstRawData, compress_fsst_with};
 use crate::decoder::strings::{decode_shared_dict_range, encode_shared_dict_range};
 use crate::decoder::{PropValue, TileLayer};
 use crate::encoder::model::{StrEncoding, StreamCtx};
@@ -337,36 +337,33 @@ impl StagedSharedDict {
     }
 }
 
-impl Codecs {
-    /// Encode a shared-dictionary property and write it to `enc`.
-    ///
-    /// When [`Encoder::override_str_enc`] returns [`None`], auto-selects the corpus encoding (FSST if viable, else plain)
-    /// and uses automatic offset encoders.
-    /// When [`Some`], uses the caller-specified encoding and [`Encoder::override_int_enc`] for offsets.
+/// Deduplicated corpus of a shared dictionary, with its encoding already chosen.
+pub(crate) struct SharedCorpus<'a> {
+    spans: Vec<(u32, u32)>,
+    dict: Vec<&'a str>,
+    index: HashMap<(u32, u32), u32>,
+    /// FSST-compressed corpus, `None` to store it as plain strings.
+    fsst: Option<FsstRawData>,
+}
+
+impl<'a> SharedCorpus<'a> {
+    /// Deduplicate the corpus of `shared_dict` and pick its encoding.
     ///
-    /// The caller (staging) is responsible for not creating empty `StagedSharedDict` instances.
-    #[hotpath::measure]
-    pub(crate) fn write_shared_dict(
-        &mut self,
-        shared_dict: &StagedSharedDict,
-        enc: &mut Encoder,
-    ) -> MltResult<()> {
-        let dict_spans = collect_staged_shared_dict_spans(&shared_dict.items);
-        let dict: Vec<&str> = dict_spans
+    /// When [`Encoder::override_str_enc`] returns [`Some`], the caller-specified encoding is
+    /// used; otherwise FSST is used if viable.
+    pub(crate) fn new(shared_dict: &'a StagedSharedDict, enc: &mut Encoder) -> MltResult<Self> {
+        let spans = collect_staged_shared_dict_spans(&shared_dict.items);
+        let dict: Vec<&str> = spans
             .iter()
             .map(|&span| {
                 shared_dict
                     .get(span)
-                    .ok_or(DictIndexOutOfBounds(span.0, dict_spans.len()))
+                    .ok_or(DictIndexOutOfBounds(span.0, spans.len()))
             })
             .collect::<Result<_, _>>()?;
-        let dict_index: HashMap<(u32, u32), u32> =
-            dict_spans.iter().copied().zip(0_u32..).collect();
+        let index: HashMap<(u32, u32), u32> = spans.iter().copied().zip(0_u32..).collect();
 
-        // Decide corpus encoding upfront to determine the stream count for the varint header.
-        // FSST uses 4 streams; plain uses 2.
-        let str_enc_override = enc.override_str_enc(&shared_dict.prefix);
-        let fsst_raw = match str_enc_override {
+        let fsst = match enc.override_str_enc(&shared_dict.prefix) {
             Some(StrEncoding::Fsst | StrEncoding::FsstDict) => {
                 let byte_slices: Vec<&[u8]> = dict.iter().map(|s| s.as_bytes()).collect();
                 let compressor = fsst::Compressor::train(&byte_slices);
@@ -384,7 +381,36 @@ impl Codecs {
                     .map(|c| compress_fsst_with(&dict, c))
             }
         };
-        let dict_stream_count = if fsst_raw.is_some() { 4u32 } else { 2u32 };
+        Ok(Self {
+            spans,
+            dict,
+            index,
+            fsst,
+        })
+    }
+
+    /// Number of streams used by the corpus: FSST uses 4 streams, plain uses 2.
+    pub(crate) fn stream_count(&self) -> u32 {
+        if self.fsst.is_some() { 4 } else { 2 }
+    }
+}
+
+impl Codecs {
+    /// Encode a shared-dictionary property and write it to `enc`.
+    ///
+    /// When [`Encoder::override_str_enc`] returns [`None`], auto-selects the corpus encoding (FSST if viable, else plain)
+    /// and uses automatic offset encoders.
+    /// When [`Some`], uses the caller-specified encoding and [`Encoder::override_int_enc`] for offsets.
+    ///
+    /// The caller (staging) is responsible for not creating empty `StagedSharedDict` instances.
+    #[hotpath::measure]
+    pub(crate) fn write_shared_dict(
+        &mut self,
+        shared_dict: &StagedSharedDict,
+        enc: &mut Encoder,
+    ) -> MltResult<()> {
+        // Decide corpus encoding upfront to determine the stream count for the varint header.
+        let corpus = SharedCorpus::new(shared_dict, enc)?;
 
         let children_count = u32::try_from(shared_dict.items.len())?;
         let optional_count = u32::try_from(
@@ -394,48 +420,70 @@ impl Codecs {
                 .filter(|p| p.has_presence())
                 .count(),
         )?;
-        let stream_len = checked_sum3(dict_stream_count, children_count, optional_count)?;
+        let stream_len = checked_sum3(corpus.stream_count(), children_count, optional_count)?;
 
         // Write stream data: total count, corpus streams, then per-child streams.
         enc.write_varint(stream_len)?;
-        if let Some(ref raw) = fsst_raw {
-            write_fsst_data(raw, DictionaryType::Single, &shared_dict.prefix, enc, self)?;
-        } else {
-            let lengths = strings_to_lengths(&dict)?;
-            let typ = StreamType::Length(LengthType::Dictionary);
-            let ctx = StreamCtx::prop(typ, &shared_dict.prefix);
-            self.write_int_stream(&lengths, &ctx, enc)?;
-            write_raw_str_data(&dict, DictionaryType::Shared, enc)?;
-        }
+        self.write_shared_corpus(&corpus, &shared_dict.prefix, enc)?;
 
         enc.write_column_header(ColumnType::SharedDict, &shared_dict.prefix)?;
         enc.meta_mut().write_varint(children_count)?;
 
         for item in &shared_dict.items {
-            if item.has_presence() {
-                enc.write_varint(2u32)?;
-                enc.write_column_type(ColumnType::OptStr)?;
-                self.write_presence_stream(item.presence_bools(), enc)?;
-            } else {
-                enc.write_varint(1u32)?;
-                enc.write_column_type(ColumnType::Str)?;
-            }
-            enc.write_column_name(&item.suffix)?;
-
-            let offsets: Vec<u32> = item
-                .dense_spans()
-                .map(|span| {
-                    dict_index
-                        .get(&span)
-                        .copied()
-                        .ok_or(DictIndexOutOfBounds(span.0, dict_spans.len()))
-                })
-                .collect::<Result<_, _>>()?;
-            let typ = StreamType::Offset(OffsetType::String);
-            let ctx = StreamCtx::prop2(typ, &shared_dict.prefix, &item.suffix);
-            self.write_int_stream(&offsets, &ctx, enc)?;
+            enc.write_varint(if item.has_presence() { 2u32 } else { 1u32 })?;
+            self.write_shared_dict_item(item, &shared_dict.prefix, &corpus, enc)?;
         }
 
         Ok(())
     }
+
+    /// Write the corpus streams of a shared dictionary.
+    pub(crate) fn write_shared_corpus(
+        &mut self,
+        corpus: &SharedCorpus<'_>,
+        prefix: &str,
+        enc: &mut Encoder,
+    ) -> MltResult<()> {
+        if let Some(ref raw) = corpus.fsst {
+            write_fsst_data(raw, DictionaryType::Single, prefix, enc, self)
+        } else {
+            let lengths = strings_to_lengths(&corpus.dict)?;
+            let typ = StreamType::Length(LengthType::Dictionary);
+            let ctx = StreamCtx::prop(typ, prefix);
+            self.write_int_stream(&lengths, &ctx, enc)?;
+            write_raw_str_data(&corpus.dict, DictionaryType::Shared, enc)
+        }
+    }
+
+    /// Write one child of a shared dictionary: its definition to the metadata, then its
+    /// optional presence stream and the offsets into `corpus`.
+    pub(crate) fn write_shared_dict_item(
+        &mut self,
+        item: &StagedSharedDictItem,
+        prefix: &str,
+        corpus: &SharedCorpus<'_>,
+        enc: &mut Encoder,
+    ) -> MltResult<()> {
+        if item.has_presence() {
+            enc.write_column_type(ColumnType::OptStr)?;
+            self.write_presence_stream(item.presence_bools(), enc)?;
+        } else {
+            enc.write_column_type(ColumnType::Str)?;
+        }
+        enc.write_column_name(&item.suffix)?;
+
+        let offsets: Vec<u32> = item
+            .dense_spans()
+            .map(|span| {
+                corpus
+                    .index
+                    .get(&span)
+                    .copied()
+                    .ok_or(DictIndexOutOfBounds(span.0, corpus.spans.len()))
+            })
+            .collect::<Result<_, _>>()?;
+        let typ = StreamType::Offset(OffsetType::String);
+        let ctx = StreamCtx::prop2(typ, prefix, &item.suffix);
+        self.write_int_stream(&offsets, &ctx, enc)
+    }
 }
@@ -9,8 +9,8 @@ use crate::decoder::stream::header01;
 use crate::decoder::strings::{checked_string_end, encode_null_end};
 use crate::decoder::{DictionaryType, LengthType, OffsetType, StreamMeta, StreamType};
 use crate::encoder::model::{StrEncoding, StreamCtx};
-use crate::encoder::stream::{dedup_strings, write_stream_payload};
-use crate::encoder::{Codecs, Encoder};
+use crate::encoder::stream::{DataProfile, dedup_strings, keep_finalists, write_stream_payload};
+use crate::encoder::{Codecs, Encoder, EncoderConfig};
 use crate::utils::strings_to_lengths;
 
 /// Minimum total raw byte size of a column before attempting FSST compression.
@@ -83,7 +83,8 @@ impl Codecs {
     ///
     /// If [`Encoder::override_str_enc`] returns `Some`, only that type is encoded.
     /// Otherwise Plain, Dict, and (when viable) FSST variants are competed via the alternatives
-    /// machinery, mirroring the `write_int_prop_*` pattern one level up.
+    /// machinery, mirroring the `write_int_prop_*` pattern one level up.  With sampling
+    /// enabled, only the variants that did best on a sample of the column compete.
     #[hotpath::measure]
     pub(crate) fn write_str_col(
         &mut self,
@@ -104,19 +105,42 @@ impl Codecs {
             // Dedup once; reused by Dict and FSST+Dict alternatives.
             let (unique, offset_indices) = dedup_strings(&non_null)?;
 
+            let cfg = enc.config().clone();
             // `None` disables FSST, so only Plain and Dict compete.
             let compressor = enc.fsst_compressor(name, &unique);
 
+            let mut candidates = vec![StrEncoding::Plain, StrEncoding::Dict];
+            if compressor.is_some() {
+                candidates.extend([StrEncoding::Fsst, StrEncoding::FsstDict]);
+            }
+            let sample = DataProfile::take_sample(&non_null);
+            if let Some(finalists) = cfg.sampling_finalists()
+                && candidates.len() > finalists
+                && sample.len() < non_null.len()
+            {
+                let estimates =
+                    sample_str_sizes(&candidates, sample, compressor, name, &cfg, self)?;
+                candidates = keep_finalists(&candidates, estimates, finalists);
+            }
+
             // Compute before try_alternatives borrows enc; FsstRawData is owned so the cache borrow ends here.
             let count = non_null.len();
-            let plain_fsst = compressor.map(|c| compress_fsst_with(&non_null, c));
-            let dict_fsst = compressor.map(|c| compress_fsst_with(&unique, c));
+            let plain_fsst = compressor
+                .filter(|_| candidates.contains(&StrEncoding::Fsst))
+                .map(|c| compress_fsst_with(&non_null, c));
+            let dict_fsst = compressor
+                .filter(|_| candidates.contains(&StrEncoding::FsstDict))
+                .map(|c| compress_fsst_with(&unique, c));
 
             let mut alt = enc.try_alternatives();
-            alt.with(|enc| write_str_plain(&non_null, presence, name, enc, self))?;
-            alt.with(|enc| {
-                write_str_dict_raw(&unique, &offset_indices, presence, name, enc, self)
-            })?;
+            if candidates.contains(&StrEncoding::Plain) {
+                alt.with(|enc| write_str_plain(&non_null, presence, name, enc, self))?;
+            }
+            if candidates.contains(&StrEncoding::Dict) {
+                alt.with(|enc| {
+                    write_str_dict_raw(&unique, &offset_indices, presence, name, enc, self)
+                })?;
+            }
 
             if let Some(ref raw) = plain_fsst {
                 alt.with(|enc| write_str_fsst_raw(raw, count, presence, name, enc, self))?;
@@ -131,6 +155,38 @@ impl Codecs {
     }
 }
 
+/// Estimate the size of each candidate encoding by writing a sample of the column
+/// without its presence stream.  FSST candidates reuse the column's `compressor`.
+fn sample_str_sizes(
+    candidates: &[StrEncoding],
+    sample: &[&str],
+    compressor: Option<&Compressor>,
+    name: &str,
+    cfg: &EncoderConfig,
+    codecs: &mut Codecs,
+) -> MltResult<Vec<(usize, StrEncoding)>> {
+    let mut estimates = Vec::with_capacity(candidates.len());
+    for &candidate in candidates {
+        let mut enc = Encoder::new(cfg.clone());
+        match (candidate, compressor) {
+            (StrEncoding::Plain, _) => write_str_plain(sample, None, name, &mut enc, codecs)?,
+            (StrEncoding::Dict, _) => write_str_dict(sample, None, name, &mut enc, codecs)?,
+            (StrEncoding::Fsst, Some(c)) => {
+                let raw = compress_fsst_with(sample, c);
+                write_str_fsst_raw(&raw, sample.len(), None, name, &mut enc, codecs)?;
+            }
+            (StrEncoding::FsstDict, Some(c)) => {
+                let (unique, offset_indices) = dedup_strings(sample)?;
+                let raw = compress_fsst_with(&unique, c);
+                write_str_fsst_dict_raw(&raw, &offset_indices, None, name, &mut enc, codecs)?;
+            }
+            (StrEncoding::Fsst | StrEncoding::FsstDict, None) => continue,
+        }
+        estimates.push((enc.total_len(), candidate));
+    }
+    Ok(estimates)
+}
+
 /// Encode with plain (`VarBinary` lengths) layout.
 ///
 /// Stream count varint is written first, then presence, then the lengths stream
@@ -0,0 +1,154 @@
+use integer_encoding::VarIntWriter as _;
+use usize_cast::IntoUsize as _;
+
+use crate::MltError::{StagedFeatureCountMismatch, UnsupportedStructChild};
+use crate::encoder::optimizer::Presence;
+use crate::encoder::property::encode::write_prop;
+use crate::encoder::property::shared_dict::SharedCorpus;
+use crate::encoder::{
+    Codecs, Encoder, StagedProperty, StagedSharedDict, StagedStrings, StagedStruct,
+    StagedStructChild,
+};
+use crate::{ColumnType, MltResult};
+
+impl StagedStruct {
+    /// Group `children` into a struct column named `prefix`.
+    ///
+    /// Child names are used as suffixes, so a child `en` of the struct `name:` is exposed
+    /// as `name:en`. String children are pooled into one shared dictionary; all other
+    /// children must be scalar columns, and every child must have the same feature count.
+    pub fn new(
+        prefix: impl Into<String>,
+        children: impl IntoIterator<Item = StagedProperty>,
+    ) -> MltResult<Self> {
+        let prefix = prefix.into();
+        let mut strings = Vec::new();
+        let mut columns = Vec::new();
+        let mut feature_count = None;
+        for child in children {
+            let actual = child.feature_count();
+            let expected = *feature_count.get_or_insert(actual);
+            if actual != expected {
+                return Err(StagedFeatureCountMismatch {
+                    column: format!("{prefix}{}", child.name()),
+                    expected,
+                    actual,
+                });
+            }
+            match child {
+                StagedProperty::Str(s) => {
+                    columns.push(StagedStructChild::Shared(strings.len()));
+                    strings.push((s, Presence::AllPresent));
+                }
+                StagedProperty::OptStr(s) => {
+                    columns.push(StagedStructChild::Shared(strings.len()));
+                    strings.push((s, Presence::Mixed));
+                }
+                StagedProperty::SharedDict(_)
+                | StagedProperty::Nested(_)
+                | StagedProperty::RangeMap(_)
+                | StagedProperty::OptRangeMap(_)
+                | StagedProperty::Struct(_) => {
+                    return Err(UnsupportedStructChild((&child).into()));
+                }
+                column => columns.push(StagedStructChild::Column(column)),
+            }
+        }
+
+        let strings = if strings.is_empty() {
+            None
+        } else {
+            Some(StagedSharedDict::new(
+                prefix.clone(),
+                strings
+                    .iter()
+                    .map(|(s, presence)| (s.name.clone(), optional_values(s), *presence)),
+            )?)
+        };
+        Ok(Self {
+            prefix,
+            strings,
+            children: columns,
+        })
+    }
+
+    /// Suffix of the child at `idx`, appended to the prefix to form its full name.
+    #[must_use]
+    pub fn child_suffix(&self, idx: usize) -> &str {
+        match &self.children[idx] {
+            StagedStructChild::Shared(item) => self
+                .strings
+                .as_ref()
+                .map_or("", |strings| &strings.items[*item].suffix),
+            StagedStructChild::Column(column) => column.name(),
+        }
+    }
+
+    #[must_use]
+    pub fn feature_count(&self) -> usize {
+        match self.children.first() {
+            Some(StagedStructChild::Column(column)) => column.feature_count(),
+            Some(StagedStructChild::Shared(_)) => self
+                .strings
+                .as_ref()
+                .map_or(0, StagedSharedDict::feature_count),
+            None => 0,
+        }
+    }
+}
+
+/// Per-feature values of a staged string column, `None` for nulls.
+fn optional_values(strings: &StagedStrings) -> Vec<Option<&str>> {
+    let mut start = 0_u32;
+    strings
+        .lengths
+        .iter()
+        .map(|&end| {
+            if end >= 0 {
+                let end = end.cast_unsigned();
+                let value = &strings.data[start.into_usize()..end.into_usize()];
+                start = end;
+                Some(value)
+            } else {
+                start = (!end).cast_unsigned();
+                None
+            }
+        })
+        .collect()
+}
+
+impl Codecs {
+    /// Encode a struct column: the column header and child definitions, the optional
+    /// dictionary shared by the string children, and then every child in order.
+    #[hotpath::measure]
+    pub(crate) fn write_struct(&mut self, v: &StagedStruct, enc: &mut Encoder) -> MltResult<()> {
+        enc.write_column_header(ColumnType::Struct, &v.prefix)?;
+        enc.meta_mut()
+            .write_varint(u32::try_from(v.children.len())?)?;
+
+        let corpus = v
+            .strings
+            .as_ref()
+            .map(|strings| SharedCorpus::new(strings, enc))
+            .transpose()?;
+        if let Some(corpus) = &corpus {
+            enc.write_varint(corpus.stream_count())?;
+            self.write_shared_corpus(corpus, &v.prefix, enc)?;
+        } else {
+            enc.write_varint(0_u32)?;
+        }
+
+        for child in &v.children {
+            match child {
+                StagedStructChild::Shared(idx) => {
+                    let (Some(corpus), Some(strings)) = (&corpus, &v.strings) else {
+                        unreachable!("shared struct children imply a dictionary");
+                    };
+                    self.write_shared_dict_item(&strings.items[*idx], &v.prefix, corpus, enc)?;
+                }
+                StagedStructChild::Column(column) => write_prop(column, enc, self)?,
+            }
+        }
+        Ok(())
+    }
+}
@@ -1,21 +1,26 @@
 use std::collections::HashSet;
+use std::f64::consts::PI;
+use std::time::Duration;
 
 use geo_types::Point;
+use integer_encoding::VarInt as _;
 use proptest::prelude::*;
 use rstest::rstest;
 
+use crate::decoder::{LogicalEncoding, RawFloatsEncoding, RawProperty};
 use crate::encoder::SortStrategy::Unsorted;
 use crate::encoder::model::{ExplicitEncoder, StagedLayer, StrEncoding};
 use crate::encoder::optimizer::{Presence, PropertyTypedStats, SharedDictRole};
 use crate::encoder::property::encode::write_properties;
 use crate::encoder::{
-    Codecs, Encoder, EncoderConfig, IntEncoder, LogicalEncoder, PhysicalEncoder, StagedId,
-    StagedProperty, StagedSharedDict, stage_tile,
+    Codecs, Effort, Encoder, EncoderConfig, IntEncoder, LogicalEncoder, PhysicalEncoder, Sampling,
+    StagedId, StagedNested, StagedNestedItem, StagedProperty, StagedSharedDict, StagedStruct,
+    stage_tile,
 };
 use crate::test_helpers::{dec, parser};
 use crate::{
-    DictRange, Extent, GeometryValues, Layer, MltError, MltResult, PropValue, TileFeature,
-    TileLayer,
+    DictRange, Extent, GeometryValues, Layer, LazyParsed, MltError, MltResult, NestedValue,
+    PropKind, PropValue, TileFeature, TileLayer,
 };
 // proptest_derive::Arbitrary is only derived for these types inside the crate
 // under #[cfg(test)], so we write the strategies by hand here.
@@ -75,7 +80,10 @@ fn staged_len(staged: &StagedProperty) -> usize {
         StagedProperty::OptF32(s) => s.presence.len(),
         StagedProperty::OptF64(s) => s.presence.len(),
         StagedProperty::Str(s) | StagedProperty::OptStr(s) => s.lengths.len(),
+        StagedProperty::RangeMap(s) | StagedProperty::OptRangeMap(s) => s.lengths.len(),
         StagedProperty::SharedDict(s) => s.items.first().map_or(0, |i| i.ranges.len()),
+        StagedProperty::Nested(s) => s.feature_count(),
+        StagedProperty::Struct(s) => s.feature_count(),
     }
 }
 
@@ -392,6 +400,7 @@ fn encode_to_bytes_auto(props: Vec<StagedProperty>, cfg: EncoderConfig) -> Vec<u
         extent: Extent::new(4096).unwrap(),
         id: StagedId::None,
         geometry: n_point_geometry(n),
+        vertex_properties: Vec::new(),
         properties: props,
     };
     let enc = Encoder::new(cfg);
@@ -428,6 +437,201 @@ fn allow_fsst_gates_fsst_selection() {
     );
 }
 
+fn tile_from_bytes(bytes: &[u8]) -> TileLayer {
+    let (_, layer) = Layer::from_bytes(bytes, &mut parser()).expect("layer parse failed");
+    let Layer::Tag01(layer01) = layer else {
+        panic!("expected Tag01 layer")
+    };
+    let mut d = dec();
+    let parsed = layer01.decode_all(&mut d).expect("decode failed");
+    parsed.into_tile(&mut d).expect("into_tile failed")
+}
+
+#[test]
+fn allow_pseudo_decimal_gates_float_encoding() {
+    // Prices with at most two decimals are exact short decimals, so their
+    // significands are far smaller than the 8 raw bytes of each f64.
+    let values: Vec<f64> = (0..400u32).map(|i| f64::from(i) + 0.99).collect();
+    let col = || StagedProperty::f64("price", values.clone());
+
+    let on = EncoderConfig::default().with_pseudo_decimal(true);
+    let off = EncoderConfig::default().with_pseudo_decimal(false);
+
+    let bytes_on = encode_to_bytes_auto(vec![col()], on);
+    let bytes_off = encode_to_bytes_auto(vec![col()], off);
+    assert!(
+        bytes_on.len() < bytes_off.len(),
+        "PseudoDecimal should shrink output when allowed: on={} off={}",
+        bytes_on.len(),
+        bytes_off.len()
+    );
+
+    for bytes in [bytes_on, bytes_off] {
+        let tile = tile_from_bytes(&bytes);
+        for (feat, &v) in tile.features().iter().zip(&values) {
+            assert_eq!(feat.properties()[0], PropValue::F64(Some(v)));
+        }
+    }
+}
+
+/// `Effort::Fast` and an exhausted time budget keep the first candidate of every
+/// competition: never smaller than the default, but still a lossless encoding.
+#[test]
+fn effort_and_time_budget_keep_a_valid_encoding() {
+    let values: Vec<i64> = (0..400).map(|i| 1000 + i * 3).collect();
+    let col = || StagedProperty::i64("rank", values.clone());
+
+    let full = encode_to_bytes_auto(vec![col()], EncoderConfig::default());
+    let fast = EncoderConfig::default().with_effort(Effort::Fast);
+    let expired = EncoderConfig::default()
+        .with_time_budget(Some(Duration::ZERO))
+        .start_budget();
+
+    for cfg in [fast, expired] {
+        let bytes = encode_to_bytes_auto(vec![col()], cfg);
+        assert!(bytes.len() >= full.len());
+        let tile = tile_from_bytes(&bytes);
+        for (feat, &v) in tile.features().iter().zip(&values) {
+            assert_eq!(feat.properties()[0], PropValue::I64(Some(v)));
+        }
+    }
+}
+
+/// Sampling only narrows the integer and string competitions down: never smaller than
+/// the exhaustive encoding, but still lossless.
+#[rstest]
+#[case::fast(EncoderConfig::default().with_effort(Effort::Fast))]
+#[case::default(EncoderConfig::default())]
+#[case::top1(EncoderConfig::default().with_sampling(Sampling::Finalists(1)))]
+fn sampled_streams_keep_a_valid_encoding(#[case] cfg: EncoderConfig) {
+    let ranks: Vec<i64> = (0..5000).map(|i| 1000 + i * 3).collect();
+    let names: Vec<String> = (0..5000).map(|i| format!("street {}", i % 37)).collect();
+    let cols = || {
+        vec![
+            StagedProperty::i64("rank", ranks.clone()),
+            StagedProperty::str("name", &names),
+        ]
+    };
+
+    let full = encode_to_bytes_auto(cols(), EncoderConfig::default().with_effort(Effort::Max));
+    let bytes = encode_to_bytes_auto(cols(), cfg);
+    assert!(bytes.len() >= full.len());
+    let tile = tile_from_bytes(&bytes);
+    for (feat, (&rank, name)) in tile.features().iter().zip(ranks.iter().zip(&names)) {
+        assert_eq!(feat.properties()[0], PropValue::I64(Some(rank)));
+        assert_eq!(feat.properties()[1], PropValue::Str(Some(name.clone())));
+    }
+}
+
+#[test]
+fn sampling_finalists_follow_effort() {
+    let cfg = EncoderConfig::default;
+    assert_eq!(cfg().sampling_finalists(), Some(2));
+    assert_eq!(
+        cfg().with_effort(Effort::Fast).sampling_finalists(),
+        Some(1)
+    );
+    assert_eq!(cfg().with_effort(Effort::Max).sampling_finalists(), None);
+    let off = cfg().with_sampling(Sampling::Off);
+    assert_eq!(off.sampling_finalists(), None);
+    let top3 = cfg()
+        .with_effort(Effort::Max)
+        .with_sampling(Sampling::Finalists(3));
+    assert_eq!(top3.sampling_finalists(), Some(3));
+    let top0 = cfg().with_sampling(Sampling::Finalists(0));
+    assert_eq!(top0.sampling_finalists(), Some(1));
+}
+
+/// Name the layout the auto encoder chose for the single float column in `bytes`.
+fn float_layout(bytes: &[u8]) -> &'static str {
+    let (_, layer) = Layer::from_bytes(bytes, &mut parser()).expect("layer parse failed");
+    let Layer::Tag01(layer01) = layer else {
+        panic!("expected Tag01 layer")
+    };
+    let Some(LazyParsed::Raw(RawProperty::F32(col) | RawProperty::F64(col))) =
+        layer01.properties.first()
+    else {
+        panic!("expected a raw float column")
+    };
+    match &col.encoding {
+        RawFloatsEncoding::Dictionary { .. } => "dictionary",
+        RawFloatsEncoding::Plain(data) => match data.meta.encoding.logical {
+            LogicalEncoding::None => "raw",
+            LogicalEncoding::Rle(_) => "rle",
+            LogicalEncoding::PseudoDecimal => "pseudo_decimal",
+            other => panic!("unexpected float encoding {other:?}"),
+        },
+    }
+}
+
+#[rstest]
+#[case::repeats((0..400u32).map(|i| f64::from(i % 7) * PI).collect(), "dictionary")]
+#[case::distinct((0..400u32).map(|i| f64::from(i) * PI).collect(), "raw")]
+#[case::quarters((0..400u32).map(|i| f64::from(i) / 4.0).collect(), "pseudo_decimal")]
+fn float_column_layout(#[case] values: Vec<f64>, #[case] expected: &str) {
+    let col = StagedProperty::f64("v", values.clone());
+    let cfg = EncoderConfig::default()
+        .with_pseudo_decimal(true)
+        .with_float_dict_rle(true);
+    let bytes = encode_to_bytes_auto(vec![col], cfg);
+    assert_eq!(float_layout(&bytes), expected);
+    let tile = tile_from_bytes(&bytes);
+    for (feat, v) in tile.features().iter().zip(values) {
+        assert_eq!(feat.properties()[0], PropValue::F64(Some(v)));
+    }
+}
+
+#[test]
+fn float_dictionary_is_opt_in() {
+    let values: Vec<f64> = (0..400u32).map(|i| f64::from(i % 7) * PI).collect();
+    let bytes = encode_to_bytes_auto(
+        vec![StagedProperty::f64("v", values)],
+        EncoderConfig::default(),
+    );
+    assert_eq!(float_layout(&bytes), "raw");
+}
+
+#[test]
+fn float_dictionary_keeps_bit_patterns_and_nulls() {
+    let distinct = [-0.0_f32, f32::NAN, f32::INFINITY, 0.1];
+    let values: Vec<Option<f32>> = (0..200)
+        .map(|i| (i % 5 != 0).then(|| distinct[i % distinct.len()]))
+        .collect();
+    let col = StagedProperty::opt_f32("v", values.clone());
+    let bytes = encode_to_bytes_auto(
+        vec![col],
+        EncoderConfig::default().with_float_dict_rle(true),
+    );
+    assert_eq!(float_layout(&bytes), "dictionary");
+
+    let tile = tile_from_bytes(&bytes);
+    for (feat, expected) in tile.features().iter().zip(values) {
+        let PropValue::F32(actual) = feat.properties()[0] else {
+            panic!("expected F32 value")
+        };
+        assert_eq!(actual.map(f32::to_bits), expected.map(f32::to_bits));
+    }
+}
+
+proptest! {
+    #[test]
+    fn pseudo_decimal_f32_auto_roundtrip(
+        significands in prop::collection::vec(any::<i16>(), 1..100),
+        digits in 0..4_i32,
+    ) {
+        let scale = 10_f32.powi(digits);
+        let values: Vec<f32> = significands.iter().map(|&m| f32::from(m) / scale).collect();
+        let bytes = encode_to_bytes_auto(
+            vec![StagedProperty::f32("score", values.clone())],
+            EncoderConfig::default().with_pseudo_decimal(true),
+        );
+        let tile = tile_from_bytes(&bytes);
+        for (feat, v) in tile.features().iter().zip(values) {
+            prop_assert_eq!(&feat.properties()[0], &PropValue::F32(Some(v)));
+        }
+    }
+}
+
 /// Round-trip a two-column `SharedDict` with auto encoders and check all feature values.
 fn check_two_col_dict(
     name: &str,
@@ -698,7 +902,9 @@ fn try_tile_from_cols(cols: &[(&str, Vec<PropValue>)]) -> MltResult<TileLayer> {
         .map(|i| TileFeature {
             id: None,
             geometry: geom.clone(),
+            z: None,
             properties: cols.iter().map(|(_, vals)| vals[i].clone()).collect(),
+            vertex_properties: Vec::new(),
         })
         .collect();
     TileLayer::from_parts("test", 4096, property_names, features)
@@ -725,7 +931,9 @@ fn tile_from_ids(ids: &[Option<u64>]) -> TileLayer {
             .map(|&id| TileFeature {
                 id,
                 geometry: geom.clone(),
+                z: None,
                 properties: vec![],
+                vertex_properties: Vec::new(),
             })
             .collect(),
     )
@@ -1372,3 +1580,278 @@ fn encode_with_explicit_encoder_works() {
     write_properties(&props, &mut enc, &mut codecs).unwrap();
     assert_ne!(enc.meta(), [] as [u8; 0]);
 }
+
+/// Shorthand for a [`NestedValue::Map`] with string keys.
+fn nmap(entries: &[(&str, NestedValue)]) -> NestedValue {
+    NestedValue::Map(
+        entries
+            .iter()
+            .map(|(k, v)| ((*k).to_string(), v.clone()))
+            .collect(),
+    )
+}
+
+fn nstr(s: &str) -> NestedValue {
+    NestedValue::Str(s.to_string())
+}
+
+#[rstest]
+#[case::bool(NestedValue::Bool(true))]
+#[case::i64_small(NestedValue::I64(-7))]
+#[case::i64_wide(NestedValue::I64(i64::MIN))]
+#[case::u64_small(NestedValue::U64(7))]
+#[case::u64_wide(NestedValue::U64(u64::MAX))]
+#[case::f32(NestedValue::F32(1.5))]
+#[case::f64(NestedValue::F64(PI))]
+#[case::str(nstr("hello"))]
+#[case::empty_map(NestedValue::Map(vec![]))]
+#[case::empty_list(NestedValue::List(vec![]))]
+#[case::list(NestedValue::List(vec![NestedValue::Bool(false), nstr("x"), NestedValue::I64(3)]))]
+#[case::map(nmap(&[("a", nstr("b")), ("n", NestedValue::U64(1))]))]
+#[case::deep(nmap(&[("a", nmap(&[("b", NestedValue::List(vec![nmap(&[]), NestedValue::List(vec![])]))]))]))]
+fn nested_root_value_roundtrip(#[case] value: NestedValue) {
+    let values = vec![Some(value.clone()), Some(value)];
+    let tile = encode_and_tile(vec![StagedProperty::nested("a", values.clone())]);
+    assert_eq!(tile.property_names(), &["a"]);
+    for (i, v) in values.into_iter().enumerate() {
+        assert_eq!(&tile.features()[i].properties()[0], &PropValue::Nested(v));
+    }
+}
+
+#[test]
+fn nested_mixed_with_nulls_roundtrip() {
+    let values = vec![
+        Some(nstr("b")),
+        None,
+        Some(nmap(&[("b", nstr("c")), ("d", NestedValue::F64(-0.5))])),
+        Some(NestedValue::List(vec![
+            NestedValue::U64(1),
+            NestedValue::I64(-1),
+        ])),
+        None,
+        Some(NestedValue::Bool(false)),
+    ];
+    let tile = encode_and_tile(vec![StagedProperty::nested("a", values.clone())]);
+    for (i, v) in values.into_iter().enumerate() {
+        assert_eq!(&tile.features()[i].properties()[0], &PropValue::Nested(v));
+    }
+}
+
+#[test]
+fn nested_all_null_roundtrip() {
+    let tile = encode_and_tile(vec![
+        StagedProperty::u32("id", vec![1, 2, 3]),
+        StagedProperty::nested("a", vec![None, None, None]),
+    ]);
+    assert_eq!(tile.property_names(), &["id", "a"]);
+    for feat in tile.features() {
+        assert_eq!(&feat.properties()[1], &PropValue::Nested(None));
+    }
+}
+
+#[test]
+fn nested_children_roundtrip() {
+    let a = vec![Some(nstr("b")), Some(nmap(&[("b", nstr("c"))])), None];
+    let b = vec![
+        None,
+        Some(NestedValue::List(vec![nstr("b")])),
+        Some(nstr("b")),
+    ];
+    let nested = StagedNested::new(
+        "name:",
+        [
+            StagedNestedItem::new("a", a.clone()),
+            StagedNestedItem::new("b", b.clone()),
+        ],
+    );
+    let bytes = encode_to_bytes(vec![StagedProperty::Nested(nested.clone())]);
+    // Layers with nested columns are framed with tag 0x02
+    let (_, size_len) = u32::decode_var(&bytes).unwrap();
+    assert_eq!(bytes[size_len], 2);
+
+    let tile = encode_and_tile(vec![StagedProperty::Nested(nested)]);
+    assert_eq!(tile.property_names(), &["name:a", "name:b"]);
+    for (i, (va, vb)) in a.into_iter().zip(b).enumerate() {
+        assert_eq!(&tile.features()[i].properties()[0], &PropValue::Nested(va));
+        assert_eq!(&tile.features()[i].properties()[1], &PropValue::Nested(vb));
+    }
+}
+
+#[test]
+fn nested_rejects_excessive_depth() {
+    let mut value = NestedValue::Bool(true);
+    for _ in 0..100 {
+        value = NestedValue::List(vec![value]);
+    }
+    let props = [StagedProperty::nested("a", vec![Some(value)])];
+    let mut enc = Encoder::default();
+    let mut codecs = Codecs::default();
+    let err = write_properties(&props, &mut enc, &mut codecs).unwrap_err();
+    assert!(matches!(err, MltError::InvalidNestedPayload(_)), "{err}");
+}
+
+#[test]
+fn nested_column_from_tile_layer() {
+    let values = vec![
+        PropValue::Nested(Some(nmap(&[("k", NestedValue::I64(5))]))),
+        PropValue::Nested(None),
+        PropValue::Nested(Some(NestedValue::List(vec![nstr("v")]))),
+    ];
+    let tile = tile_from_cols(&[("a", values.clone())]);
+    let staged = stage_tile(tile, Unsorted, false, false);
+    let roundtrip = encode_and_tile(staged.properties);
+    for (i, v) in values.into_iter().enumerate() {
+        assert_eq!(&roundtrip.features()[i].properties()[0], &v);
+    }
+}
+
+#[rstest]
+#[case::date(vec![PropValue::Date(Some(19_782)), PropValue::Date(Some(-1))])]
+#[case::opt_date(vec![PropValue::Date(None), PropValue::Date(Some(0))])]
+#[case::timestamp(vec![PropValue::Timestamp(Some(5)), PropValue::Timestamp(Some(-7))])]
+#[case::opt_timestamp(vec![PropValue::Timestamp(Some(1_709_210_096_789)), PropValue::Timestamp(None)])]
+#[case::json(vec![PropValue::Json(Some(r#"{"a":1}"#.into())), PropValue::Json(Some("[]".into()))])]
+#[case::opt_json(vec![PropValue::Json(None), PropValue::Json(Some("null".into()))])]
+#[case::binary(vec![PropValue::Binary(Some(vec![0, 1, 255])), PropValue::Binary(Some(vec![]))])]
+#[case::opt_binary(vec![PropValue::Binary(None), PropValue::Binary(Some(vec![42]))])]
+fn logical_column_from_tile_layer(#[case] values: Vec<PropValue>) {
+    let kind = values[0].kind();
+    let tile = tile_from_cols(&[("a", values.clone())]);
+    let staged = stage_tile(tile, Unsorted, false, false);
+    let mut roundtrip = encode_and_tile(staged.properties);
+    let key = roundtrip.property_key("a").unwrap();
+    roundtrip.set_logical_kind(key, kind).unwrap();
+    for (i, v) in values.into_iter().enumerate() {
+        assert_eq!(&roundtrip.features()[i].properties()[0], &v);
+    }
+}
+
+#[rstest]
+#[case::date(PropValue::Date(Some(7)), PropValue::I32(Some(7)))]
+#[case::timestamp(PropValue::Timestamp(Some(7)), PropValue::I64(Some(7)))]
+#[case::json(PropValue::Json(Some("{}".into())), PropValue::Str(Some("{}".into())))]
+#[case::binary(
+    PropValue::Binary(Some(vec![7])),
+    PropValue::Nested(Some(NestedValue::List(vec![NestedValue::U64(7)]))),
+)]
+fn logical_columns_are_stored_as_physical(#[case] value: PropValue, #[case] expected: PropValue) {
+    let tile = tile_from_cols(&[("a", vec![value])]);
+    let staged = stage_tile(tile, Unsorted, false, false);
+    let roundtrip = encode_and_tile(staged.properties);
+    assert_eq!(roundtrip.features()[0].properties()[0], expected);
+}
+
+#[test]
+fn logical_columns_are_not_narrowed() {
+    let values = vec![PropValue::Timestamp(Some(1)), PropValue::Timestamp(Some(2))];
+    let staged = stage_single_prop("ts", values);
+    assert!(matches!(staged, StagedProperty::I64(_)));
+}
+
+#[test]
+fn set_logical_kind_rejects_other_physical_kinds() {
+    let mut tile = tile_from_cols(&[
+        ("n", vec![PropValue::U32(Some(1))]),
+        (
+            "l",
+            vec![PropValue::Nested(Some(NestedValue::List(vec![nstr("x")])))],
+        ),
+    ]);
+    let n = tile.property_key("n").unwrap();
+    assert!(matches!(
+        tile.set_logical_kind(n, PropKind::Date),
+        Err(MltError::PropertyKindMismatch { index: 0, .. })
+    ));
+    let l = tile.property_key("l").unwrap();
+    assert!(matches!(
+        tile.set_logical_kind(l, PropKind::Binary),
+        Err(MltError::PropertyKindMismatch { index: 1, .. })
+    ));
+    assert_eq!(tile.features()[0].properties()[1].kind(), PropKind::Nested);
+}
+
+fn ranges(entries: &[(f64, f64, PropValue)]) -> PropValue {
+    PropValue::RangeMap(Some(entries.to_vec()))
+}
+
+#[rstest]
+#[case::u32(vec![
+    ranges(&[(0.0, 0.25, PropValue::U32(Some(30))), (0.25, 1.0, PropValue::U32(Some(50)))]),
+    ranges(&[]),
+])]
+#[case::opt_str(vec![
+    ranges(&[(0.2, 0.6, PropValue::Str(Some("wet".into()))), (0.6, 0.9, PropValue::Str(None))]),
+    PropValue::RangeMap(None),
+    ranges(&[(0.0, 1.0, PropValue::Str(Some("dry".into())))]),
+])]
+#[case::empty(vec![ranges(&[]), PropValue::RangeMap(None)])]
+fn range_map_column_from_tile_layer(#[case] values: Vec<PropValue>) {
+    let tile = tile_from_cols(&[("r", values.clone())]);
+    let staged = stage_tile(tile, Unsorted, false, false);
+    let roundtrip = encode_and_tile(staged.properties);
+    for (i, v) in values.into_iter().enumerate() {
+        assert_eq!(&roundtrip.features()[i].properties()[0], &v);
+    }
+}
+
+#[test]
+fn range_map_rejects_mixed_value_kinds() {
+    let staged = StagedProperty::range_map(
+        "r",
+        PropKind::U32,
+        [vec![(0.0, 1.0, PropValue::Str(Some("x".into())))]],
+    );
+    assert!(matches!(
+        staged,
+        Err(MltError::PropertyKindMismatch { index: 0, .. })
+    ));
+
+    let tile = tile_from_cols(&[(
+        "r",
+        vec![
+            ranges(&[(0.0, 1.0, PropValue::U32(Some(1)))]),
+            ranges(&[(0.0, 1.0, PropValue::I32(Some(1)))]),
+        ],
+    )]);
+    assert!(matches!(
+        tile.analyze(false),
+        Err(MltError::MixedPropertyTypes(..))
+    ));
+}
+
+#[test]
+fn range_map_rejects_unsupported_value_kind() {
+    let nested = ranges(&[(0.0, 1.0, PropValue::Nested(None))]);
+    let tile = tile_from_cols(&[("r", vec![nested])]);
+    assert!(matches!(
+        tile.analyze(false),
+        Err(MltError::UnsupportedRangeMapValueKind(PropKind::Nested))
+    ));
+    assert!(matches!(
+        StagedProperty::range_map("r", PropKind::Nested, [vec![]]),
+        Err(MltError::UnsupportedRangeMapValueKind(PropKind::Nested))
+    ));
+}
+
+#[test]
+fn struct_rejects_invalid_children() {
+    let inner = StagedStruct::new("inner:", [StagedProperty::u32("a", vec![1])]).unwrap();
+    assert!(matches!(
+        StagedStruct::new("outer:", [StagedProperty::Struct(inner)]),
+        Err(MltError::UnsupportedStructChild("struct"))
+    ));
+    assert!(matches!(
+        StagedStruct::new(
+            "s:",
+            [
+                StagedProperty::u32("a", vec![1, 2]),
+                StagedProperty::str("b", ["x"]),
+            ],
+        ),
+        Err(MltError::StagedFeatureCountMismatch {
+            expected: 2,
+            actual: 1,
+            ..
+        })
+    ));
+}
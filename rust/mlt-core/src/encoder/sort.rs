@@ -1,12 +1,15 @@
 //! Feature reordering for the optimizer
 
+use std::mem;
+
 use geo::CoordsIter as _;
 use geo_types::{Coord, Geometry};
 
 use crate::codecs::hilbert::{hilbert_curve_params_from_bounds, hilbert_sort_key};
 use crate::codecs::morton::morton_sort_key;
-use crate::decoder::TileLayer;
+use crate::decoder::{PropValueRef, TileFeature, TileLayer};
 use crate::encoder::model::CurveParams;
+use crate::encoder::stream::DataProfile;
 
 /// Controls how features inside a layer are reordered before encoding.
 ///
@@ -36,6 +39,17 @@ pub enum SortStrategy {
 
     /// Sort features by their feature ID in ascending order.
     Id,
+
+    /// Sort features by the value of the property column at this index, with
+    /// features lacking a value first. Features with equal values keep their order.
+    ///
+    /// Grouping equal values gives long runs for RLE and dictionary encodings of
+    /// low-cardinality columns, such as `class` in an `OpenMapTiles` `transportation` layer.
+    Property(usize),
+
+    /// Sort features like [`Self::Property`], then by the Hilbert curve index of their
+    /// first vertex within each group of equal values.
+    PropertyHilbert(usize),
 }
 
 impl TileLayer {
@@ -50,25 +64,85 @@ impl TileLayer {
     /// trivially unchanged.
     #[hotpath::measure]
     pub fn sort(&mut self, strategy: SortStrategy, params: CurveParams) {
-        match strategy {
-            SortStrategy::SpatialMorton | SortStrategy::SpatialHilbert => {
-                let curve_key = if let SortStrategy::SpatialMorton = strategy {
-                    morton_sort_key
-                } else {
-                    hilbert_sort_key
-                };
-                self.features_mut().sort_by_cached_key(|f| {
-                    first_vertex(f.geometry()).map_or(u64::MAX, |c| u64::from(curve_key(c, params)))
-                });
-            }
-            SortStrategy::Id => {
-                self.features_mut()
-                    .sort_by_cached_key(|f| f.id().map_or(0, |v| v.saturating_add(1)));
+        if strategy != SortStrategy::Unsorted {
+            let keys = self.sort_keys(strategy, params);
+            let mut keyed: Vec<_> = keys
+                .into_iter()
+                .zip(mem::take(&mut self.features))
+                .collect();
+            keyed.sort_by_key(|&(key, _)| key);
+            self.features = keyed.into_iter().map(|(_, feature)| feature).collect();
+        }
+    }
+
+    /// A contiguous window of the features as ordered by `strategy`, used to estimate
+    /// how well that order encodes without sorting and encoding the whole layer.
+    ///
+    /// The window is taken from the middle of the sorted order, and its length is chosen
+    /// like the value samples of [`DataProfile`]. Only the sampled features are cloned.
+    pub(crate) fn sort_sample(&self, strategy: SortStrategy, params: CurveParams) -> Self {
+        let mut order: Vec<usize> = (0..self.features.len()).collect();
+        if strategy != SortStrategy::Unsorted {
+            let keys = self.sort_keys(strategy, params);
+            order.sort_by_key(|&idx| keys[idx]);
+        }
+        Self {
+            name: self.name.clone(),
+            extent: self.extent,
+            property_names: self.property_names.clone(),
+            property_kinds: self.property_kinds.clone(),
+            vertex_property_names: self.vertex_property_names.clone(),
+            vertex_property_kinds: self.vertex_property_kinds.clone(),
+            features: DataProfile::take_sample(&order)
+                .iter()
+                .map(|&idx| self.features[idx].clone())
+                .collect(),
+        }
+    }
+
+    /// Sort key of every feature under `strategy`, in feature order.
+    fn sort_keys(&self, strategy: SortStrategy, params: CurveParams) -> Vec<u64> {
+        let ranks = match strategy {
+            SortStrategy::Property(col) | SortStrategy::PropertyHilbert(col) => {
+                self.property_ranks(col)
             }
-            SortStrategy::Unsorted => {
-                // do nothing
+            _ => Vec::new(),
+        };
+        self.features
+            .iter()
+            .enumerate()
+            .map(|(idx, f)| sort_key(f, strategy, params, ranks.get(idx).copied().unwrap_or(0)))
+            .collect()
+    }
+
+    /// Dense rank of every feature's value in the property column at `col`: `0` for
+    /// features without a value, then `1, 2, ...` for the distinct values in ascending
+    /// order. The highest rank is the number of distinct values.
+    ///
+    /// Nested values and range maps have no natural order and all share one rank.
+    pub(crate) fn property_ranks(&self, col: usize) -> Vec<u32> {
+        let mut present: Vec<(RankKey<'_>, usize)> = self
+            .features
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, f)| {
+                let value = f.properties().get(col)?.as_value_ref()?;
+                Some((RankKey::from(value), idx))
+            })
+            .collect();
+        present.sort_unstable();
+
+        let mut ranks = vec![0; self.features.len()];
+        let mut rank = 0_u32;
+        let mut prev = None;
+        for (key, idx) in present {
+            if prev.as_ref() != Some(&key) {
+                rank = rank.saturating_add(1);
+                prev = Some(key);
             }
+            ranks[idx] = rank;
         }
+        ranks
     }
 
     /// Hilbert/Morton `CurveParams` for this layer.
@@ -88,6 +162,65 @@ impl TileLayer {
     }
 }
 
+/// Totally ordered form of a property value, used to rank a column's values.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum RankKey<'a> {
+    Int(i128),
+    /// Float bits mapped to the order of [`f64::total_cmp`].
+    Float(i64),
+    Bytes(&'a [u8]),
+    Unordered,
+}
+
+impl<'a> From<PropValueRef<'a>> for RankKey<'a> {
+    fn from(value: PropValueRef<'a>) -> Self {
+        match value {
+            PropValueRef::Bool(v) => Self::Int(i128::from(v)),
+            PropValueRef::I8(v) => Self::Int(i128::from(v)),
+            PropValueRef::U8(v) => Self::Int(i128::from(v)),
+            PropValueRef::I32(v) | PropValueRef::Date(v) => Self::Int(i128::from(v)),
+            PropValueRef::U32(v) => Self::Int(i128::from(v)),
+            PropValueRef::I64(v) | PropValueRef::Timestamp(v) => Self::Int(i128::from(v)),
+            PropValueRef::U64(v) => Self::Int(i128::from(v)),
+            PropValueRef::F32(v) => Self::Float(total_order_bits(f64::from(v))),
+            PropValueRef::F64(v) => Self::Float(total_order_bits(v)),
+            PropValueRef::Str(v) | PropValueRef::Json(v) => Self::Bytes(v.as_bytes()),
+            PropValueRef::Binary(v) => Self::Bytes(v),
+            PropValueRef::Nested(_) | PropValueRef::RangeMap(_) => Self::Unordered,
+        }
+    }
+}
+
+/// Map a float to an integer that sorts like [`f64::total_cmp`].
+#[expect(
+    clippy::cast_possible_wrap,
+    clippy::cast_sign_loss,
+    reason = "reinterprets the bits, as f64::total_cmp does"
+)]
+fn total_order_bits(v: f64) -> i64 {
+    let bits = v.to_bits() as i64;
+    bits ^ (((bits >> 63) as u64) >> 1) as i64
+}
+
+/// Sort key of a feature under `strategy`, given the [`TileLayer::property_ranks`] rank of
+/// its value for property strategies; every feature has the same key when unsorted.
+fn sort_key(feature: &TileFeature, strategy: SortStrategy, params: CurveParams, rank: u32) -> u64 {
+    match strategy {
+        SortStrategy::SpatialMorton => first_vertex(feature.geometry())
+            .map_or(u64::MAX, |c| u64::from(morton_sort_key(c, params))),
+        SortStrategy::SpatialHilbert => first_vertex(feature.geometry())
+            .map_or(u64::MAX, |c| u64::from(hilbert_sort_key(c, params))),
+        SortStrategy::Id => feature.id().map_or(0, |v| v.saturating_add(1)),
+        SortStrategy::Property(_) => u64::from(rank),
+        SortStrategy::PropertyHilbert(_) => {
+            let curve =
+                first_vertex(feature.geometry()).map_or(u32::MAX, |c| hilbert_sort_key(c, params));
+            (u64::from(rank) << 32) | u64::from(curve)
+        }
+        SortStrategy::Unsorted => 0,
+    }
+}
+
 /// Extract the coordinate of the first vertex of a geometry.
 fn first_vertex(geom: &Geometry<i32>) -> Option<Coord<i32>> {
     match geom {
@@ -150,10 +283,11 @@ mod tests {
 
     use crate::decoder::{GeometryType, GeometryValues, RawGeometry, TileFeature, TileLayer};
     use crate::encoder::{
-        Codecs, Encoder, EncoderConfig, ExplicitEncoder, IntEncoder, SortStrategy, stage_tile,
+        Codecs, Encoder, EncoderConfig, ExplicitEncoder, IntEncoder, Sampling, SortStrategy,
+        stage_tile,
     };
     use crate::test_helpers::{assert_empty, dec, into_layer01, parser};
-    use crate::{Layer, LazyParsed};
+    use crate::{Layer, LazyParsed, PropValue};
 
     fn pt(x: i32, y: i32) -> Geometry<i32> {
         GeoGeom::Point(Point::new(x, y))
@@ -225,7 +359,9 @@ mod tests {
             .map(|(g, &id)| TileFeature {
                 id: Some(id),
                 geometry: g.clone(),
+                z: None,
                 properties: vec![],
+                vertex_properties: Vec::new(),
             })
             .collect();
 
@@ -375,7 +511,9 @@ mod tests {
                 .map(|(g, &id)| TileFeature {
                     id,
                     geometry: g.clone(),
+                    z: None,
                     properties: vec![],
+                    vertex_properties: Vec::new(),
                 })
                 .collect(),
         )
@@ -386,7 +524,10 @@ mod tests {
     /// This tests the full encode->decode roundtrip, verifying that sorting was applied.
     fn sort_encode_decode(tile: TileLayer, sort: SortStrategy) -> TileLayer {
         let enc_cfg = EncoderConfig::default();
-        let enc = Encoder::with_explicit(enc_cfg, ExplicitEncoder::for_id(IntEncoder::varint()));
+        let enc = Encoder::with_explicit(
+            enc_cfg.clone(),
+            ExplicitEncoder::for_id(IntEncoder::varint()),
+        );
         let mut codecs = Codecs::default();
         let enc = stage_tile(tile, sort, false, enc_cfg.tessellate())
             .encode_into(enc, &mut codecs)
@@ -482,4 +623,157 @@ mod tests {
         // Expected vertices: LS(0,0,0,5), P2(1,0), P1(2,0)
         assert_eq!(verts, vec![0, 0, 0, 5, 1, 0, 2, 0]);
     }
+
+    /// A layer of scattered points whose IDs run backwards.
+    fn scattered_points(count: u64) -> TileLayer {
+        let geoms: Vec<_> = (0..count)
+            .map(|i| {
+                let i = i32::try_from(i).unwrap();
+                pt((i * 7919) % 4096, (i * 104_729) % 4096)
+            })
+            .collect();
+        let ids: Vec<_> = (0..count).map(|i| Some(count - i)).collect();
+        build_tile_layer(&geoms, &ids)
+    }
+
+    #[test]
+    fn sort_sample_is_middle_window_of_sorted_features() {
+        let layer = scattered_points(2000);
+        let sample = layer.sort_sample(SortStrategy::Id, layer.curve_params());
+
+        let ids: Vec<u64> = sample
+            .features()
+            .iter()
+            .filter_map(TileFeature::id)
+            .collect();
+        // 1% of 2000 is below the minimum sample of 512, centred on the middle.
+        assert_eq!(ids, (745..=1256).collect::<Vec<_>>());
+        assert_eq!(sample.name(), layer.name());
+    }
+
+    #[test]
+    fn sort_sample_of_small_layer_is_whole_layer() {
+        let layer = scattered_points(100);
+        let sample = layer.sort_sample(SortStrategy::Unsorted, layer.curve_params());
+        assert_eq!(sample, layer);
+    }
+
+    #[test]
+    fn sampled_sort_selection_round_trips() {
+        let layer = scattered_points(2000);
+        let exhaustive = layer
+            .clone()
+            .encode(EncoderConfig::default().with_sampling(Sampling::Off))
+            .unwrap();
+        let sampled = layer
+            .encode(EncoderConfig::default().with_sampling(Sampling::Finalists(1)))
+            .unwrap();
+        // Sampling can only narrow the exhaustive search.
+        assert!(sampled.len() >= exhaustive.len());
+
+        let mut p = parser();
+        let decoded = into_layer01(assert_empty(Layer::from_bytes(&sampled, &mut p)))
+            .into_tile(&mut dec())
+            .unwrap();
+        let mut ids: Vec<u64> = decoded
+            .features()
+            .iter()
+            .filter_map(TileFeature::id)
+            .collect();
+        ids.sort_unstable();
+        assert_eq!(ids, (1..=2000).collect::<Vec<_>>());
+    }
+
+    /// A layer of points with one string property `class`.
+    fn classed_points(features: &[(Geometry<i32>, Option<&str>)]) -> TileLayer {
+        TileLayer::from_parts(
+            "test",
+            4096,
+            vec!["class".to_string()],
+            features
+                .iter()
+                .zip(1..)
+                .map(|((g, class), id)| TileFeature {
+                    id: Some(id),
+                    geometry: g.clone(),
+                    z: None,
+                    properties: vec![PropValue::Str(class.map(str::to_string))],
+                    vertex_properties: Vec::new(),
+                })
+                .collect(),
+        )
+        .unwrap()
+    }
+
+    fn ids(layer: &TileLayer) -> Vec<u64> {
+        layer
+            .features()
+            .iter()
+            .filter_map(TileFeature::id)
+            .collect()
+    }
+
+    #[test]
+    fn property_sort_groups_values_with_nulls_first() {
+        let mut layer = classed_points(&[
+            (pt(0, 0), Some("rail")),
+            (pt(1, 1), None),
+            (pt(2, 2), Some("primary")),
+            (pt(3, 3), Some("rail")),
+        ]);
+        assert_eq!(layer.property_ranks(0), vec![2, 0, 1, 2]);
+
+        let params = layer.curve_params();
+        layer.sort(SortStrategy::Property(0), params);
+        assert_eq!(ids(&layer), vec![2, 3, 1, 4]);
+    }
+
+    #[test]
+    fn property_hilbert_sort_orders_by_space_within_values() {
+        let mut layer = classed_points(&[
+            (pt(10, 10), Some("a")),
+            (pt(0, 0), Some("b")),
+            (pt(0, 0), Some("a")),
+        ]);
+        let params = layer.curve_params();
+        layer.sort(SortStrategy::PropertyHilbert(0), params);
+        assert_eq!(ids(&layer), vec![3, 1, 2]);
+    }
+
+    #[test]
+    fn property_sort_round_trips() {
+        let features: Vec<_> = (0..1000)
+            .map(|i| {
+                let class = ["motorway", "primary", "service"][i % 3];
+                (pt(i32::try_from(i).unwrap(), 0), Some(class))
+            })
+            .collect();
+        let layer = classed_points(&features);
+        let default = layer.clone().encode(EncoderConfig::default()).unwrap();
+        for cfg in [
+            EncoderConfig::default().with_property_sort(true),
+            EncoderConfig::default().with_sort_property(Some("class")),
+        ] {
+            let encoded = layer.clone().encode(cfg).unwrap();
+            // Property sorts only add trials to the default ones.
+            assert!(encoded.len() <= default.len());
+
+            let mut p = parser();
+            let decoded = into_layer01(assert_empty(Layer::from_bytes(&encoded, &mut p)))
+                .into_tile(&mut dec())
+                .unwrap();
+            let mut pairs: Vec<_> = decoded
+                .features()
+                .iter()
+                .map(|f| (f.id(), f.properties()[0].clone()))
+                .collect();
+            pairs.sort_by_key(|(id, _)| *id);
+            let expected: Vec<_> = layer
+                .features()
+                .iter()
+                .map(|f| (f.id(), f.properties()[0].clone()))
+                .collect();
+            assert_eq!(pairs, expected);
+        }
+    }
 }
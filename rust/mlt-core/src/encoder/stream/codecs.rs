@@ -1,18 +1,21 @@
 use std::collections::HashMap;
 
-use bytemuck::{NoUninit, cast_slice};
+use bytemuck::cast_slice;
 use fastpfor::FastPFor256;
+use num_traits::AsPrimitive;
 
 use crate::codecs::bytes::encode_bools_to_bytes;
 use crate::codecs::rle::encode_byte_rle;
 use crate::decoder::{LogicalEncoding, PhysicalEncoding, RleMeta, StreamMeta, StreamType};
 use crate::encoder;
-use crate::encoder::Encoder;
 use crate::encoder::model::StreamCtx;
-use crate::encoder::stream::logical::LogicalEncoder;
-use crate::encoder::stream::optimizer::DataProfile;
-use crate::encoder::write::{LogicalIntCodec, LogicalIntStreamKind, PhysicalIntStreamKind};
-use crate::errors::MltResult;
+use crate::encoder::stream::logical::{LogicalEncoder, apply_rle};
+use crate::encoder::stream::optimizer::{DataProfile, keep_finalists, pseudo_decimal_words};
+use crate::encoder::write::{
+    FloatStreamKind, LogicalIntCodec, LogicalIntStreamKind, PhysicalIntStreamKind,
+};
+use crate::encoder::{Encoder, PhysicalEncoder};
+use crate::errors::{MltError, MltResult};
 
 #[derive(Default)]
 pub struct Codecs {
@@ -72,7 +75,18 @@ impl Codecs {
     ) -> MltResult<()> {
         let num_values = values.len();
         let (logical, vals) = self.logical.encode_bools(values)?;
-        let meta = StreamMeta::new2(stream_type, logical, PhysicalEncoding::None, num_values)?;
+        // The physical encoding is only recorded in the header: decoders always read byte-RLE.
+        let pe = match enc.override_bool_physical() {
+            None | Some(PhysicalEncoder::None) => PhysicalEncoding::None,
+            Some(PhysicalEncoder::VarInt) => PhysicalEncoding::VarInt,
+            Some(PhysicalEncoder::FastPFOR) => {
+                return Err(MltError::UnsupportedPhysicalEncodingForType(
+                    PhysicalEncoding::FastPFor256,
+                    "bool streams",
+                ));
+            }
+        };
+        let meta = StreamMeta::new2(stream_type, logical, pe, num_values)?;
         encoder::write_stream_payload(enc, meta, true, vals)
     }
 
@@ -84,21 +98,73 @@ impl Codecs {
         self.write_bool_stream(values, StreamType::Present, enc)
     }
 
-    #[expect(
-        clippy::unused_self,
-        reason = "kept as a Codecs method to match the other stream writers"
-    )]
-    pub(crate) fn write_float_stream<T: NoUninit>(
+    /// Write a float stream, competing raw little-endian bytes against RLE over the
+    /// bit patterns and `PseudoDecimal`, when the config allows them.
+    pub(crate) fn write_float_stream<T: FloatStreamKind>(
         &mut self,
         values: &[T],
         stream_type: StreamType,
         enc: &mut Encoder,
-    ) -> MltResult<()> {
+    ) -> MltResult<()>
+    where
+        f64: AsPrimitive<T>,
+    {
+        use LogicalEncoding as LE;
+        use PhysicalEncoding as PE;
+
         #[cfg(not(target_endian = "little"))]
         compile_error!("not implemented for non-little-endian targets");
 
-        let meta = StreamMeta::new_none(stream_type, values.len())?;
-        encoder::write_stream_payload(enc, meta, false, cast_slice(values))
+        let raw_meta = StreamMeta::new_none(stream_type, values.len())?;
+        match enc.override_float_physical() {
+            None => {}
+            Some(PhysicalEncoder::None) => {
+                return encoder::write_stream_payload(enc, raw_meta, false, cast_slice(values));
+            }
+            Some(PhysicalEncoder::VarInt) => {
+                // Decoders read plain little-endian floats whatever the physical encoding.
+                let meta = StreamMeta::new2(stream_type, LE::None, PE::VarInt, values.len())?;
+                return encoder::write_stream_payload(enc, meta, false, cast_slice(values));
+            }
+            Some(PhysicalEncoder::FastPFOR) => {
+                return Err(MltError::UnsupportedPhysicalEncodingForType(
+                    PE::FastPFor256,
+                    "float streams",
+                ));
+            }
+        }
+
+        let allow_pseudo_decimal = enc.config().allow_pseudo_decimal();
+        let allow_rle = enc.config().allow_float_dict_rle();
+        let mut alt = enc.try_alternatives();
+        alt.with(|enc| encoder::write_stream_payload(enc, raw_meta, false, cast_slice(values)))?;
+
+        let mut words = Vec::new();
+        if allow_pseudo_decimal && let Some(words) = pseudo_decimal_words(values, &mut words) {
+            let logical = LE::PseudoDecimal;
+            alt.with(|enc| {
+                let meta = StreamMeta::new2(stream_type, logical, PE::None, words.len())?;
+                encoder::write_stream_payload(enc, meta, false, cast_slice(words))
+            })?;
+            alt.with(|enc| {
+                let meta = StreamMeta::new2(stream_type, logical, PE::VarInt, words.len())?;
+                encoder::write_stream_payload(enc, meta, false, self.physical.varint(words))
+            })?;
+        }
+
+        let bits: &[T::Bits] = cast_slice(values);
+        if allow_rle && DataProfile::runs_are_viable(DataProfile::take_sample(bits)) {
+            let logical = LE::Rle(apply_rle(bits, values.len(), &mut words)?);
+            alt.with(|enc| {
+                let meta = StreamMeta::new2(stream_type, logical, PE::None, words.len())?;
+                encoder::write_stream_payload(enc, meta, false, cast_slice(&words))
+            })?;
+            alt.with(|enc| {
+                let meta = StreamMeta::new2(stream_type, logical, PE::VarInt, words.len())?;
+                encoder::write_stream_payload(enc, meta, false, self.physical.varint(&words))
+            })?;
+        }
+        Ok(())
     }
 
     pub(crate) fn write_int_stream<T>(
@@ -118,12 +184,7 @@ impl Codecs {
 
         // FIXME: does StreamMeta encode values.len() or vals1.len()?
         if let Some(int_enc) = enc.override_int_enc(ctx) {
-            let (le, vals) = match int_enc.logical {
-                LogicalEncoder::None => (LE::None, self.logical.none(values)),
-                LogicalEncoder::Delta => (LE::Delta, self.logical.delta(values)),
-                LogicalEncoder::Rle => self.logical.rle(values)?,
-                LogicalEncoder::DeltaRle => self.logical.delta_rle(values)?,
-            };
+            let (le, vals) = self.logical.encode_as(int_enc.logical, values)?;
             let phys = int_enc.physical;
             return self
                 .physical
@@ -155,51 +216,60 @@ impl Codecs {
             return encoder::write_stream_payload(enc, meta, false, payload);
         }
 
-        let allow_fastpfor = enc.config().allow_fastpfor();
-        let mut alt = enc.try_alternatives();
-
-        let sample = logical.none(DataProfile::take_sample(values));
+        let cfg = enc.config();
+        let allow_fastpfor = cfg.allow_fastpfor();
+        let raw_sample = DataProfile::take_sample(values);
+        let sample = logical.none(raw_sample);
         let profile = DataProfile::profile::<<[T] as LogicalIntStreamKind>::Profile>(sample);
 
+        let mut candidates = Vec::with_capacity(4);
         if profile.delta_is_beneficial()
             && (profile.rle_is_viable() || profile.delta_rle_is_viable())
         {
-            let (logical_enc, values) = logical.delta_rle(values)?;
-            physical.write_alternatives::<Output<T>>(
-                &mut alt,
-                values,
-                logical_enc,
-                ctx.stream_type,
-                allow_fastpfor,
-            )?;
+            candidates.push(LogicalEncoder::DeltaRle);
         }
         if profile.delta_is_beneficial() {
-            let values = logical.delta(values);
-            physical.write_alternatives::<Output<T>>(
-                &mut alt,
-                values,
-                LE::Delta,
-                ctx.stream_type,
-                allow_fastpfor,
-            )?;
+            candidates.push(LogicalEncoder::Delta);
         }
         if profile.rle_is_viable() {
-            let (logical_enc, values) = logical.rle(values)?;
+            candidates.push(LogicalEncoder::Rle);
+        }
+        candidates.push(LogicalEncoder::None);
+
+        if let Some(finalists) = cfg.sampling_finalists()
+            && candidates.len() > finalists
+            && raw_sample.len() < values.len()
+        {
+            // Estimate every logical encoding on the sample, each with its physical competition.
+            let mut estimates = Vec::with_capacity(candidates.len());
+            for &candidate in &candidates {
+                let mut scratch = Encoder::new(cfg.clone());
+                let mut alt = scratch.try_alternatives();
+                let (le, vals) = logical.encode_as(candidate, raw_sample)?;
+                physical.write_alternatives::<Output<T>>(
+                    &mut alt,
+                    vals,
+                    le,
+                    ctx.stream_type,
+                    allow_fastpfor,
+                )?;
+                drop(alt);
+                estimates.push((scratch.total_len(), candidate));
+            }
+            candidates = keep_finalists(&candidates, estimates, finalists);
+        }
+
+        let mut alt = enc.try_alternatives();
+        for candidate in candidates {
+            let (le, vals) = logical.encode_as(candidate, values)?;
             physical.write_alternatives::<Output<T>>(
                 &mut alt,
-                values,
-                logical_enc,
+                vals,
+                le,
                 ctx.stream_type,
                 allow_fastpfor,
             )?;
         }
-        let values = logical.none(values);
-        physical.write_alternatives::<Output<T>>(
-            &mut alt,
-            values,
-            LE::None,
-            ctx.stream_type,
-            allow_fastpfor,
-        )
+        Ok(())
     }
 }
@@ -21,14 +21,17 @@ mod model;
 pub use model::*;
 
 mod optimizer;
+pub(crate) use optimizer::{DataProfile, keep_finalists};
 mod physical;
 #[cfg(feature = "__private")]
 pub use physical::PhysicalEncoder;
-#[cfg(all(test, not(feature = "__private")))]
+#[cfg(not(feature = "__private"))]
 pub(crate) use physical::PhysicalEncoder;
 
 #[cfg(test)]
 mod tests;
 
 pub(crate) mod write;
-pub(crate) use write::{LogicalIntCodec, LogicalIntStreamKind, write_stream_payload};
+pub(crate) use write::{
+    FloatStreamKind, LogicalIntCodec, LogicalIntStreamKind, write_stream_payload,
+};
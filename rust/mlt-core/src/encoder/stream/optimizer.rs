@@ -1,6 +1,10 @@
-use num_traits::{AsPrimitive as _, PrimInt as _, WrappingSub, Zero as _};
+use num_traits::{AsPrimitive, PrimInt as _, WrappingSub, Zero as _};
+use usize_cast::IntoUsize as _;
 use zigzag::ZigZag;
 
+use crate::decoder::stream::logical::{MAX_PSEUDO_DECIMAL_EXPONENT, POW10, pseudo_decimal_value};
+use crate::encoder::stream::FloatStreamKind;
+
 /// Minimum number of values to profile / compete on.
 ///
 /// Below this threshold the full slice is used regardless of its length.
@@ -12,6 +16,13 @@ const MAX_SAMPLE: usize = 16_384;
 /// RLE is only worthwhile when runs are on average at least this long.
 const RLE_MIN_AVG_RUN_LENGTH: f64 = 2.0;
 
+/// Most fractional digits tried when splitting a float into a `PseudoDecimal` significand.
+const MAX_PSEUDO_DECIMAL_DIGITS: u32 = 15;
+
+/// `PseudoDecimal` significands stay below `10^15`, so a value keeps at most 15 significant
+/// digits and every significand is an exact `f64`.
+const PSEUDO_DECIMAL_SIGNIFICAND_LIMIT: f64 = 1e15;
+
 /// Sampling-based encoder selection
 #[derive(Debug, Clone, Default)]
 pub struct DataProfile {
@@ -120,6 +131,18 @@ impl DataProfile {
         self.avg_run_length >= RLE_MIN_AVG_RUN_LENGTH
     }
 
+    /// Returns `true` if RLE is a sensible candidate for a sample of opaque words,
+    /// such as float bit patterns, that have no meaningful numeric profile.
+    #[must_use]
+    #[expect(clippy::cast_precision_loss)]
+    pub(crate) fn runs_are_viable<T: PartialEq>(sample: &[T]) -> bool {
+        if sample.is_empty() {
+            return false;
+        }
+        let runs = 1 + sample.windows(2).filter(|w| w[0] != w[1]).count();
+        sample.len() as f64 / runs as f64 >= RLE_MIN_AVG_RUN_LENGTH
+    }
+
     /// Returns `true` if RLE on the delta-transformed stream is viable.
     ///
     /// For sequential or constant-delta data the raw values are all distinct
@@ -136,8 +159,91 @@ impl DataProfile {
     }
 }
 
+/// Keep the `finalists` candidates with the smallest estimated sizes, in their original order.
+///
+/// `estimates` pairs a size with a candidate; candidates without an estimate are dropped.
+/// Ties keep the earlier estimate.
+pub(crate) fn keep_finalists<T: Copy + PartialEq>(
+    candidates: &[T],
+    mut estimates: Vec<(usize, T)>,
+    finalists: usize,
+) -> Vec<T> {
+    estimates.sort_by_key(|&(len, _)| len);
+    estimates.truncate(finalists);
+    candidates
+        .iter()
+        .copied()
+        .filter(|c| estimates.iter().any(|&(_, e)| e == *c))
+        .collect()
+}
+
+/// Split `value` into the `(exponent, significand)` of a short decimal such that
+/// `significand × 10^exponent`, computed in `f64` and narrowed to `T`, is `value` bit-exactly.
+///
+/// Uses the fewest fractional digits possible; whole numbers move trailing zeros into the exponent.
+/// Returns `None` for NaN, infinities, `-0.0` and values needing more than 15 significant digits.
+pub(crate) fn pseudo_decimal<T>(value: T) -> Option<(i32, i64)>
+where
+    T: FloatStreamKind,
+    f64: AsPrimitive<T>,
+{
+    for digits in 0..=MAX_PSEUDO_DECIMAL_DIGITS {
+        let scaled = (value.as_() * POW10[digits.into_usize()]).round();
+        // More digits only make the significand larger, so give up once it is too big.
+        if !scaled.is_finite() || scaled.abs() >= PSEUDO_DECIMAL_SIGNIFICAND_LIMIT {
+            return None;
+        }
+        #[expect(clippy::cast_possible_truncation, reason = "bounded by 10^15 above")]
+        let mut significand = scaled as i64;
+        let mut exponent = -i32::try_from(digits).ok()?;
+        let decoded: T = pseudo_decimal_value(significand, exponent).as_();
+        if decoded == value && decoded.is_sign_negative() == value.is_sign_negative() {
+            let max_exponent = i32::try_from(MAX_PSEUDO_DECIMAL_EXPONENT).ok()?;
+            while exponent >= 0
+                && exponent < max_exponent
+                && significand != 0
+                && significand % 10 == 0
+            {
+                significand /= 10;
+                exponent += 1;
+            }
+            return Some((exponent, significand));
+        }
+    }
+    None
+}
+
+/// Split `values` into the words of a `PseudoDecimal` stream: all zigzag-encoded exponents,
+/// followed by all zigzag-encoded significands.
+///
+/// `target` is treated as a scratch buffer: cleared before writing.
+/// Returns `None` if `values` is empty or any value has no exact short decimal form.
+pub(crate) fn pseudo_decimal_words<'a, T>(
+    values: &[T],
+    target: &'a mut Vec<T::Bits>,
+) -> Option<&'a [T::Bits]>
+where
+    T: FloatStreamKind,
+    f64: AsPrimitive<T>,
+{
+    if values.is_empty() {
+        return None;
+    }
+    target.clear();
+    target.resize(values.len() * 2, T::Bits::zero());
+    let (exponents, significands) = target.split_at_mut(values.len());
+    for ((&v, exponent), significand) in values.iter().zip(exponents).zip(significands) {
+        let (exp, sig) = pseudo_decimal(v)?;
+        *exponent = T::pseudo_decimal_word(i64::from(exp))?;
+        *significand = T::pseudo_decimal_word(sig)?;
+    }
+    Some(target)
+}
+
 #[cfg(test)]
 mod tests {
+    use rstest::rstest;
+
     use super::*;
 
     fn assert_profile_flags(profile: &DataProfile, delta: bool, rle: bool, delta_rle: bool) {
@@ -172,6 +278,29 @@ mod tests {
         assert_profile_flags(&profile, true, false, true);
     }
 
+    #[test]
+    fn runs_are_viable_requires_average_run_of_two() {
+        assert!(!DataProfile::runs_are_viable::<u32>(&[]));
+        assert!(!DataProfile::runs_are_viable(&[1u32, 2, 3, 4]));
+        assert!(!DataProfile::runs_are_viable(&[1u32, 1, 2, 3]));
+        assert!(DataProfile::runs_are_viable(&[1u32, 1, 2, 2]));
+        assert!(DataProfile::runs_are_viable(&[7u64; 10]));
+    }
+
+    #[test]
+    fn keep_finalists_keeps_smallest_in_candidate_order() {
+        let candidates = ['a', 'b', 'c', 'd'];
+        let estimates = vec![(30, 'a'), (10, 'b'), (20, 'c'), (10, 'd')];
+        assert_eq!(keep_finalists(&candidates, estimates.clone(), 1), ['b']);
+        assert_eq!(
+            keep_finalists(&candidates, estimates.clone(), 2),
+            ['b', 'd']
+        );
+        assert_eq!(keep_finalists(&candidates, estimates, 3), ['b', 'c', 'd']);
+        // Candidates without an estimate are dropped.
+        assert_eq!(keep_finalists(&candidates, vec![(5, 'c')], 2), ['c']);
+    }
+
     #[test]
     fn profile_empty_has_no_optimizing_flags() {
         let values: &[u32] = &[];
@@ -179,4 +308,45 @@ mod tests {
         assert!(!profile.is_sorted);
         assert_profile_flags(&profile, false, false, false);
     }
+
+    #[rstest]
+    #[case::integer(300.0, Some((2, 3)))]
+    #[case::negative(-2.0, Some((0, -2)))]
+    #[case::zero(0.0, Some((0, 0)))]
+    #[case::cents(1.99, Some((-2, 199)))]
+    #[case::eighths(0.125, Some((-3, 125)))]
+    #[case::billion(1e9, Some((9, 1)))]
+    #[case::negative_zero(-0.0, None)]
+    #[case::nan(f64::NAN, None)]
+    #[case::infinite(f64::INFINITY, None)]
+    #[case::irrational(std::f64::consts::PI, None)]
+    #[case::too_large(1e300, None)]
+    fn pseudo_decimal_f64(#[case] value: f64, #[case] expected: Option<(i32, i64)>) {
+        assert_eq!(pseudo_decimal(value), expected);
+    }
+
+    #[rstest]
+    #[case::tenths(0.1, Some((-1, 1)))]
+    #[case::coord(13.405, Some((-3, 13405)))]
+    #[case::subnormal_digits(f32::MIN_POSITIVE, None)]
+    fn pseudo_decimal_f32(#[case] value: f32, #[case] expected: Option<(i32, i64)>) {
+        assert_eq!(pseudo_decimal(value), expected);
+    }
+
+    #[test]
+    fn pseudo_decimal_words_are_exponents_then_significands() {
+        let mut buf = Vec::new();
+        let words = pseudo_decimal_words(&[1.99_f64, -5.0], &mut buf).unwrap();
+        assert_eq!(words, [3, 0, 398, 9]);
+        assert!(pseudo_decimal_words::<f64>(&[], &mut buf).is_none());
+        assert!(pseudo_decimal_words(&[0.5_f64, std::f64::consts::E], &mut buf).is_none());
+    }
+
+    #[test]
+    fn pseudo_decimal_f32_significand_must_fit_i32() {
+        // 2^31 + 256 is an exact f32 whose significand needs more than 31 bits
+        let value = 2_147_483_904.0_f32;
+        assert_eq!(pseudo_decimal(value), Some((0, 2_147_483_904)));
+        assert!(pseudo_decimal_words(&[value], &mut Vec::new()).is_none());
+    }
 }
@@ -1,3 +1,4 @@
+use bytemuck::cast_slice;
 use proptest::prelude::*;
 use rstest::rstest;
 
@@ -245,6 +246,133 @@ fn allow_fastpfor_gates_fastpfor_selection() {
     );
 }
 
+/// Auto-encode a float stream and return its logical encoding plus the decoded bits.
+fn auto_float_stream(values: &[f32], cfg: EncoderConfig) -> (LogicalEncoding, Vec<u32>) {
+    let mut enc = Encoder::new(cfg);
+    let mut codecs = Codecs::default();
+    codecs
+        .write_float_stream(values, StreamType::Data(DictionaryType::None), &mut enc)
+        .unwrap();
+    let parsed = assert_empty(header01::parse_stream(enc.data(), &mut parser()));
+    let logical = parsed.meta.encoding.logical;
+    let decoded = parsed.decode_floats::<f32>(&mut dec()).unwrap();
+    (logical, decoded.iter().map(|v| v.to_bits()).collect())
+}
+
+#[rstest]
+#[case::long_runs((0..300u16).map(|i| f32::from(i / 30) * std::f32::consts::PI).collect())]
+#[case::special_values([-0.0, f32::NAN, f32::INFINITY, f32::MIN_POSITIVE].iter().flat_map(|&v| [v; 25]).collect())]
+fn float_stream_runs_use_rle(#[case] values: Vec<f32>) {
+    let expected = values.iter().map(|v| v.to_bits()).collect::<Vec<_>>();
+    let cfg = EncoderConfig::default().with_float_dict_rle(true);
+    let (logical, bits) = auto_float_stream(&values, cfg);
+    assert!(matches!(logical, LogicalEncoding::Rle(_)), "{logical:?}");
+    assert_eq!(bits, expected);
+
+    // RLE is opt-in
+    let (logical, bits) = auto_float_stream(&values, EncoderConfig::default());
+    assert_eq!(logical, LogicalEncoding::None);
+    assert_eq!(bits, expected);
+}
+
+#[test]
+fn float_stream_without_runs_stays_raw() {
+    let values: Vec<f32> = (0..300u16)
+        .map(|i| f32::from(i) * std::f32::consts::PI)
+        .collect();
+    let cfg = EncoderConfig::default().with_float_dict_rle(true);
+    let (logical, bits) = auto_float_stream(&values, cfg);
+    assert_eq!(logical, LogicalEncoding::None);
+    assert_eq!(bits, values.iter().map(|v| v.to_bits()).collect::<Vec<_>>());
+}
+
+/// Encode with an explicit encoder pinning bool and float streams to `physical`.
+fn explicit_physical(physical: PhysicalEncoder) -> Encoder {
+    let explicit = ExplicitEncoder::all(IntEncoder::varint())
+        .with_bool_physical(physical)
+        .with_float_physical(physical);
+    Encoder::with_explicit(EncoderConfig::default(), explicit)
+}
+
+#[rstest]
+#[case::empty(vec![])]
+#[case::single(vec![true])]
+#[case::mixed((0..100).map(|i| i % 3 == 0).collect())]
+#[case::runs((0..300).map(|i| i < 150).collect())]
+fn varint_bool_stream_roundtrip(#[case] values: Vec<bool>) {
+    for (physical, expected) in [
+        (PhysicalEncoder::None, PhysicalEncoding::None),
+        (PhysicalEncoder::VarInt, PhysicalEncoding::VarInt),
+    ] {
+        let mut enc = explicit_physical(physical);
+        Codecs::default()
+            .write_bool_stream(
+                values.iter().copied(),
+                StreamType::Data(DictionaryType::None),
+                &mut enc,
+            )
+            .unwrap();
+        let parsed = assert_empty(header01::parse_bool_stream(enc.data(), &mut parser()));
+        assert_eq!(parsed.meta.encoding.physical, expected);
+        assert_eq!(parsed.decode_bools(&mut dec()).unwrap(), values);
+    }
+}
+
+#[rstest]
+#[case::no_logical(LogicalEncoding::None)]
+#[case::byte_rle(LogicalEncoding::Rle(RleMeta { runs: 2, num_rle_values: 3 }))]
+fn varint_presence_stream_is_byte_rle(#[case] logical: LogicalEncoding) {
+    // two literal bytes, as the Java and TypeScript decoders read them
+    let data = [0xFE, 0x81, 0x05];
+    let stream = RawStream::new(
+        StreamMeta::new(
+            StreamType::Present,
+            IntEncoding::new(logical, PhysicalEncoding::VarInt),
+            11,
+        ),
+        &data,
+    );
+    let bits = stream.decode_bitvec(&mut dec()).unwrap();
+    let expected = [
+        true, false, false, false, false, false, false, true, true, false, true,
+    ];
+    assert_eq!(bits.iter().by_vals().collect::<Vec<_>>(), expected);
+}
+
+#[test]
+fn varint_float_stream_is_plain_floats() {
+    let values = [0.0_f32, -1.5, f32::MAX, f32::NAN, 1.0e-3];
+    let mut enc = explicit_physical(PhysicalEncoder::VarInt);
+    Codecs::default()
+        .write_float_stream(&values, StreamType::Data(DictionaryType::None), &mut enc)
+        .unwrap();
+    let parsed = assert_empty(header01::parse_stream(enc.data(), &mut parser()));
+    assert_eq!(parsed.meta.encoding.logical, LogicalEncoding::None);
+    assert_eq!(parsed.meta.encoding.physical, PhysicalEncoding::VarInt);
+    assert_eq!(parsed.data, cast_slice::<f32, u8>(&values));
+    let decoded = parsed.decode_floats::<f32>(&mut dec()).unwrap();
+    assert_eq!(
+        decoded.iter().map(|v| v.to_bits()).collect::<Vec<_>>(),
+        values.iter().map(|v| v.to_bits()).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn explicit_fastpfor_rejected_for_bools_and_floats() {
+    let mut enc = explicit_physical(PhysicalEncoder::FastPFOR);
+    let mut codecs = Codecs::default();
+    assert!(
+        codecs
+            .write_bool_stream([true].into_iter(), StreamType::Present, &mut enc)
+            .is_err()
+    );
+    assert!(
+        codecs
+            .write_float_stream(&[1.0_f64], StreamType::Data(DictionaryType::None), &mut enc)
+            .is_err()
+    );
+}
+
 /// Test roundtrip: write -> parse -> equality for stream serialization
 #[rstest]
 #[case::new_encoded(StreamType::Data(DictionaryType::None), 2, LogicalEncoding::None, PhysicalEncoding::None, vec![0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08], false
@@ -257,7 +385,7 @@ fn allow_fastpfor_gates_fastpfor_selection() {
 )]
 #[case::varint(StreamType::Data(DictionaryType::None), 5, LogicalEncoding::Delta, PhysicalEncoding::VarInt, vec![0x00, 0x02, 0x02, 0x02, 0x02], false
 )]
-#[case::varint(StreamType::Data(DictionaryType::None), 3, LogicalEncoding::PseudoDecimal, PhysicalEncoding::VarInt, vec![0x01, 0x02, 0x03], false
+#[case::varint(StreamType::Data(DictionaryType::None), 4, LogicalEncoding::PseudoDecimal, PhysicalEncoding::VarInt, vec![0x03, 0x00, 0x8E, 0x03, 0x09], false
 )]
 #[case::varint(StreamType::Length(LengthType::VarBinary), 3, LogicalEncoding::Delta, PhysicalEncoding::VarInt, vec![0x00, 0x02, 0x02], false
 )]
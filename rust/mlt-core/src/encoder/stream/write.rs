@@ -1,7 +1,10 @@
-use bytemuck::{NoUninit, cast_slice};
+use std::fmt::Debug;
+use std::hash::Hash;
+
+use bytemuck::{AnyBitPattern, NoUninit, cast_slice};
 use fastpfor::AnyLenCodec as _;
 use integer_encoding::VarInt;
-use num_traits::{PrimInt, WrappingSub};
+use num_traits::{AsPrimitive, Float, PrimInt, WrappingSub};
 use zigzag::ZigZag;
 
 use crate::MltError::UnsupportedPhysicalEncoding;
@@ -12,7 +15,7 @@ use crate::decoder::{LogicalEncoding, PhysicalEncoding, StreamMeta, StreamType};
 use crate::encoder::Encoder;
 use crate::encoder::model::StreamCtx;
 use crate::encoder::stream::codecs::{LogicalCodecs, PhysicalCodecs};
-use crate::encoder::stream::logical::apply_rle;
+use crate::encoder::stream::logical::{LogicalEncoder, apply_rle};
 use crate::encoder::stream::physical::PhysicalEncoder;
 use crate::encoder::writer::AltSession;
 
@@ -145,6 +148,34 @@ impl PhysicalIntStreamKind for [u64] {
     }
 }
 
+/// Float element types written by `Codecs::write_float_stream`.
+///
+/// `Bits` is the same-width unsigned word holding the bit pattern; it carries RLE
+/// runs and serves as a bit-exact dictionary key.
+pub(crate) trait FloatStreamKind: NoUninit + Float + AsPrimitive<f64> {
+    type Bits: NoUninit + AnyBitPattern + PrimInt + Hash + Debug + Into<u64>;
+
+    /// Zigzag-encode a `PseudoDecimal` exponent or significand into a stream word,
+    /// or `None` if it does not fit.
+    fn pseudo_decimal_word(value: i64) -> Option<Self::Bits>;
+}
+
+impl FloatStreamKind for f32 {
+    type Bits = u32;
+
+    fn pseudo_decimal_word(value: i64) -> Option<u32> {
+        i32::try_from(value).ok().map(i32::encode)
+    }
+}
+
+impl FloatStreamKind for f64 {
+    type Bits = u64;
+
+    fn pseudo_decimal_word(value: i64) -> Option<u64> {
+        Some(i64::encode(value))
+    }
+}
+
 pub(crate) trait LogicalIntStreamKind {
     type Input;
     type Output: PhysicalIntStreamKind + ?Sized;
@@ -171,6 +202,23 @@ pub(crate) trait LogicalIntCodec<T: LogicalIntStreamKind + ?Sized> {
         LogicalEncoding,
         &'a [<T::Output as PhysicalIntStreamKind>::Value],
     )>;
+
+    /// Apply `logical`, returning the stream's logical encoding and its words.
+    fn encode_as<'a>(
+        &'a mut self,
+        logical: LogicalEncoder,
+        values: &'a T,
+    ) -> MltResult<(
+        LogicalEncoding,
+        &'a [<T::Output as PhysicalIntStreamKind>::Value],
+    )> {
+        Ok(match logical {
+            LogicalEncoder::None => (LogicalEncoding::None, self.none(values)),
+            LogicalEncoder::Delta => (LogicalEncoding::Delta, self.delta(values)),
+            LogicalEncoder::Rle => self.rle(values)?,
+            LogicalEncoder::DeltaRle => self.delta_rle(values)?,
+        })
+    }
 }
 
 fn encode_u8_as_u32<'a>(values: &[u8], target: &'a mut Vec<u32>) -> &'a [u32] {
@@ -6,12 +6,12 @@ impl ExplicitEncoder {
     /// Use `enc` for all integer streams, plain string encoding, and `Vec2` vertex layout.
     #[must_use]
     pub fn all(enc: IntEncoder) -> Self {
-        Self {
-            vertex_buffer_type: VertexBufferType::Vec2,
-            force_stream: Box::new(|_| false),
-            get_int_encoder: Box::new(move |_| enc),
-            get_str_encoding: Box::new(|_| crate::encoder::StrEncoding::Plain),
-        }
+        Self::new(
+            VertexBufferType::Vec2,
+            Box::new(|_| false),
+            Box::new(move |_| enc),
+            Box::new(|_| crate::encoder::StrEncoding::Plain),
+        )
     }
 
     /// Like [`Self::all`] but use `str_enc` for string property columns.
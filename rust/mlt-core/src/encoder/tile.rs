@@ -9,7 +9,9 @@
 //! Conversion from [`TileLayer`] to [`StagedLayer`] is done via
 //! [`StagedLayer::from_tile`] with pre-computed layer statistics.
 
-use crate::decoder::{GeometryValues, PropValue, TileFeature, TileLayer};
+use crate::decoder::{
+    GeometryValues, NestedValue, PropKind, PropValue, TileFeature, TileLayer, VertexValues,
+};
 use crate::encoder::model::{CurveParams, StagedLayer};
 use crate::encoder::optimizer::{LayerStats, Presence, PropertyTypedStats, SharedDictRole};
 use crate::encoder::{SortStrategy, StagedId, StagedProperty, StagedSharedDict};
@@ -40,6 +42,8 @@ impl StagedLayer {
             extent,
             mut property_names,
             property_kinds: _,
+            vertex_property_names,
+            vertex_property_kinds: _,
             mut features,
         } = source;
         let mut geometry = if tessellate {
@@ -47,10 +51,22 @@ impl StagedLayer {
         } else {
             GeometryValues::default()
         };
+        // One 3D feature makes the whole geometry column 3D.
+        let has_z = features.iter().any(|f| f.z().is_some());
         for f in &features {
-            geometry.push_geom(f.geometry());
+            if has_z {
+                geometry.push_geom_z(f.geometry(), f.z());
+            } else {
+                geometry.push_geom(f.geometry());
+            }
         }
 
+        let vertex_properties = vertex_property_names
+            .into_iter()
+            .enumerate()
+            .map(|(col_idx, name)| build_vertex_column(name, col_idx, &mut features))
+            .collect();
+
         let id = StagedId::from_optional_with_presence(
             features.iter().map(TileFeature::id),
             stats.id.as_ref(),
@@ -98,11 +114,43 @@ impl StagedLayer {
             extent,
             id,
             geometry,
+            vertex_properties,
             properties,
         }
     }
 }
 
+/// Concatenate the per-vertex values of one vertex-scoped column in feature order.
+fn build_vertex_column(name: String, col: usize, features: &mut [TileFeature]) -> StagedProperty {
+    macro_rules! vertex_col {
+        ($ctor:ident, $variant:ident) => {
+            StagedProperty::$ctor(
+                name,
+                features
+                    .iter_mut()
+                    .flat_map(|f| match &mut f.vertex_properties[col] {
+                        VertexValues::$variant(v) => std::mem::take(v),
+                        _ => unreachable!("layer validation guarantees one kind per column"),
+                    })
+                    .collect(),
+            )
+        };
+    }
+
+    match features.first().map(|f| f.vertex_properties[col].kind()) {
+        Some(PropKind::Bool) => vertex_col!(bool, Bool),
+        Some(PropKind::I8) => vertex_col!(i8, I8),
+        Some(PropKind::U8) => vertex_col!(u8, U8),
+        Some(PropKind::I32) => vertex_col!(i32, I32),
+        Some(PropKind::U32) => vertex_col!(u32, U32),
+        Some(PropKind::I64) => vertex_col!(i64, I64),
+        Some(PropKind::U64) => vertex_col!(u64, U64),
+        Some(PropKind::F32) => vertex_col!(f32, F32),
+        Some(PropKind::F64) => vertex_col!(f64, F64),
+        _ => unreachable!("layers are never empty here and vertex columns are numeric"),
+    }
+}
+
 fn shared_dict_columns(stats: &LayerStats) -> Vec<Vec<usize>> {
     let mut columns = vec![Vec::new(); stats.properties.len()];
     for (col_idx, prop) in stats.properties.iter().enumerate() {
@@ -204,6 +252,81 @@ fn build_scalar_column(
         Some(PropValue::U64(_)) => scalar_col!(opt_u64, u64, u64, U64),
         Some(PropValue::F32(_)) => scalar_col!(opt_f32, f32, f32, F32),
         Some(PropValue::F64(_)) => scalar_col!(opt_f64, f64, f64, F64),
+        Some(PropValue::Nested(_)) => Some(StagedProperty::nested(
+            name,
+            features
+                .iter_mut()
+                .map(|f| match f.properties_mut().get_mut(col) {
+                    Some(PropValue::Nested(v)) => v.take(),
+                    _ => None,
+                }),
+        )),
+        // Logical types are written as their physical column type and are never narrowed
+        Some(PropValue::Date(_)) => scalar_col!(opt_i32, i32, i32, Date),
+        Some(PropValue::Timestamp(_)) => scalar_col!(opt_i64, i64, i64, Timestamp),
+        Some(PropValue::Json(_)) => Some(match presence {
+            Presence::AllNull => unreachable!("handled before variant dispatch"),
+            Presence::AllPresent => StagedProperty::str(
+                name,
+                features
+                    .iter_mut()
+                    .map(|f| match f.properties_mut().get_mut(col) {
+                        Some(PropValue::Json(Some(v))) => std::mem::take(v),
+                        _ => unreachable!("analysis guarantees present JSON values"),
+                    }),
+            ),
+            Presence::Mixed | Presence::SameAsProp(_) => StagedProperty::opt_str(
+                name,
+                features
+                    .iter_mut()
+                    .map(|f| match f.properties_mut().get_mut(col) {
+                        Some(PropValue::Json(v)) => v.take(),
+                        _ => None,
+                    }),
+            ),
+        }),
+        // Binary is a `List<UInt8>`, stored as a nested list of bytes
+        Some(PropValue::Binary(_)) => Some(StagedProperty::nested(
+            name,
+            features
+                .iter_mut()
+                .map(|f| match f.properties_mut().get_mut(col) {
+                    Some(PropValue::Binary(v)) => v.take().map(|bytes| {
+                        NestedValue::List(
+                            bytes
+                                .into_iter()
+                                .map(|b| NestedValue::U64(b.into()))
+                                .collect(),
+                        )
+                    }),
+                    _ => None,
+                }),
+        )),
+        Some(PropValue::RangeMap(_)) => {
+            let PropertyTypedStats::RangeMap { value_kind } = stats else {
+                unreachable!("analysis guarantees range map stats")
+            };
+            // `None` only if every range map is empty, so any value kind will do
+            let kind = value_kind.unwrap_or(PropKind::Str);
+            let values = features
+                .iter_mut()
+                .map(|f| match f.properties_mut().get_mut(col) {
+                    Some(PropValue::RangeMap(v)) => v.take(),
+                    _ => None,
+                });
+            Some(
+                match presence {
+                    Presence::AllNull => unreachable!("handled before variant dispatch"),
+                    Presence::AllPresent => {
+                        StagedProperty::range_map(name, kind, values.map(Option::unwrap_or_default))
+                    }
+                    Presence::Mixed | Presence::SameAsProp(_) => {
+                        StagedProperty::opt_range_map(name, kind, values)
+                    }
+                }
+                .expect("analysis guarantees valid range map values"),
+            )
+        }
         Some(PropValue::Str(_)) | None => Some(match presence {
             Presence::AllNull => unreachable!("handled before variant dispatch"),
             Presence::AllPresent => StagedProperty::str(
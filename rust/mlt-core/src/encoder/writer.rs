@@ -6,7 +6,7 @@ use integer_encoding::VarIntWriter as _;
 
 use crate::decoder::{ColumnType, Morton};
 use crate::encoder::model::{CurveParams, ExplicitEncoder, StrEncoding, StreamCtx};
-use crate::encoder::{EncoderConfig, IntEncoder, VertexBufferType};
+use crate::encoder::{Effort, EncoderConfig, IntEncoder, PhysicalEncoder, VertexBufferType};
 use crate::utils::BinarySerializer as _;
 use crate::{MltError, MltResult};
 
@@ -121,6 +121,9 @@ pub struct Encoder {
     /// Trained on deduplicated values on the first sort trial, reused on subsequent trials.
     pub(crate) fsst_cache: HashMap<String, Option<Compressor>>,
 
+    /// Set once a nested column has been written; such layers are framed with tag `0x02`.
+    pub(crate) has_nested: bool,
+
     // -----------------------------------------------------------------------
     // Alternatives state - a stack that supports nested competitions.
     //
@@ -176,6 +179,7 @@ impl Encoder {
             morton_cache: None,
             hilbert_cache: None,
             fsst_cache: HashMap::new(),
+            has_nested: mem::take(&mut self.has_nested),
             alt_stack: vec![],
         }
     }
@@ -192,8 +196,8 @@ impl Encoder {
 
     #[inline]
     #[must_use]
-    pub fn config(&self) -> EncoderConfig {
-        self.cfg
+    pub fn config(&self) -> &EncoderConfig {
+        &self.cfg
     }
 
     #[inline]
@@ -271,6 +275,24 @@ impl Encoder {
         self.explicit.as_ref().map(|e| (e.get_str_encoding)(name))
     }
 
+    /// Pinned physical encoding for bool and presence streams when an explicit encoder is active.
+    #[inline]
+    pub(crate) fn override_bool_physical(&self) -> Option<PhysicalEncoder> {
+        self.explicit.as_ref().map(|e| e.bool_physical)
+    }
+
+    /// Pinned physical encoding for float streams when an explicit encoder is active.
+    #[inline]
+    pub(crate) fn override_float_physical(&self) -> Option<PhysicalEncoder> {
+        self.explicit.as_ref().map(|e| e.float_physical)
+    }
+
+    /// Whether an explicit encoder is active, pinning every stream encoding.
+    #[inline]
+    pub(crate) fn is_explicit(&self) -> bool {
+        self.explicit.is_some()
+    }
+
     /// Pinned vertex layout when an explicit encoder is active.
     #[inline]
     #[allow(clippy::unused_self)]
@@ -304,6 +326,7 @@ impl Encoder {
         self.hdr.clear();
         self.meta.clear();
         self.data.clear();
+        self.has_nested = false;
     }
 
     /// Concatenate `hdr + meta + data` into a single buffer **without** a
@@ -323,9 +346,10 @@ impl Encoder {
         out
     }
 
-    /// Assemble the complete Tag-01 layer record.
+    /// Assemble the complete layer record: tag `0x01`, or `0x02` if it contains nested columns.
     pub fn into_layer_bytes(self) -> MltResult<Vec<u8>> {
-        self.into_layer_bytes_with_tag(1)
+        let tag = if self.has_nested { 2 } else { 1 };
+        self.into_layer_bytes_with_tag(tag)
     }
 
     /// Assemble a complete layer record for the given `tag`:
@@ -484,11 +508,23 @@ impl AltSession<'_> {
     /// - **`Err`** - truncates the partial write back to the pre-call checkpoint
     ///   and returns the error.  The guard's `Drop` still finalises the
     ///   competition cleanly using whichever candidates succeeded so far.
+    ///
+    /// Once a candidate has been committed, further candidates are skipped with
+    /// [`Effort::Fast`](crate::encoder::Effort::Fast) or after the time budget has run out.
     #[hotpath::measure]
     pub fn with<F>(&mut self, f: F) -> MltResult<()>
     where
         F: FnOnce(&mut Encoder) -> MltResult<()>,
     {
+        let has_best = self
+            .enc
+            .alt_stack
+            .last()
+            .is_some_and(|level| level.best_data.is_some());
+        let cfg = &self.enc.cfg;
+        if has_best && (cfg.effort() == Effort::Fast || cfg.budget_exhausted()) {
+            return Ok(());
+        }
         let data_cp = self.enc.data.len();
         let meta_cp = self.enc.meta.len();
         match f(self.enc) {
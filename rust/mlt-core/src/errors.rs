@@ -26,12 +26,26 @@ pub enum MltError {
     MissingLayerName,
     #[error("invalid extent: {0}")]
     InvalidExtent(u32),
+    #[error("tile {dx}/{dy} is not a child at zoom offset {dz}")]
+    InvalidOverzoomTile { dz: u8, dx: u32, dy: u32 },
     #[error("missing property name")]
     MissingPropertyName,
     #[error("duplicate property name: {0}")]
     DuplicatePropertyName(String),
     #[error("feature property count mismatch: expected {expected}, got {actual}")]
     PropertyLengthMismatch { expected: usize, actual: usize },
+    #[error("feature Z value count mismatch: expected {expected}, got {actual}")]
+    VertexZLengthMismatch { expected: usize, actual: usize },
+    #[error("vertex property {index} value count mismatch: expected {expected}, got {actual}")]
+    VertexPropertyLengthMismatch {
+        index: usize,
+        expected: usize,
+        actual: usize,
+    },
+    #[error("property kind {0:?} cannot be vertex-scoped")]
+    UnsupportedVertexPropertyKind(crate::decoder::PropKind),
+    #[error("property kind {0:?} cannot be a range map value")]
+    UnsupportedRangeMapValueKind(crate::decoder::PropKind),
     #[error("property {index} kind mismatch: expected {expected:?}, got {actual:?}")]
     PropertyKindMismatch {
         index: usize,
@@ -50,6 +64,16 @@ pub enum MltError {
     MultipleGeometryColumns,
     #[error("multiple ID columns found (only one allowed)")]
     MultipleIdColumns,
+    #[error("column type code {0} cannot be vertex-scoped")]
+    InvalidVertexScopedColumn(u8),
+    #[error("range map requires exactly one value column, got {0}")]
+    RangeMapValueColumnCount(usize),
+    #[error("column type code {0} cannot hold range map values")]
+    InvalidRangeMapValueColumn(u8),
+    #[error("column type code {0} cannot be a struct child")]
+    InvalidStructChildColumn(u8),
+    #[error("{0} columns cannot be struct children")]
+    UnsupportedStructChild(&'static str),
     #[error("varint uses more bytes than necessary (non-canonical encoding)")]
     NonCanonicalVarInt,
     #[error("{0} is not decoded")]
@@ -96,8 +120,16 @@ pub enum MltError {
     GeometryWithoutStreams,
     #[error("FastPFor data byte length expected multiple of 4, got {0}")]
     InvalidFastPforByteLength(usize),
+    #[error("range map has {ranges} ranges but {bounds} range bounds and {values} values")]
+    RangeMapLengthMismatch {
+        ranges: usize,
+        bounds: usize,
+        values: usize,
+    },
     #[error("vec2 delta stream size expected to be non-empty and multiple of 2, got {0}")]
     InvalidPairStreamSize(usize),
+    #[error("vec3 delta stream size expected to be non-empty and multiple of 3, got {0}")]
+    InvalidTripleStreamSize(usize),
     #[error("decodable stream size expected {1}, got {0}")]
     InvalidDecodingStreamSize(usize, usize),
     #[error("IDs missing for encoding (expected Some IDs, got None)")]
@@ -137,6 +169,10 @@ pub enum MltError {
     // Note that {expected}+1 is allowed for the legacy Java encoder bug
     #[error("SharedDict stream count is {actual}, expected {expected}")]
     InvalidSharedDictStreamCount { actual: u32, expected: u32 },
+    #[error("Nested stream count is {actual}, expected {expected}")]
+    InvalidNestedStreamCount { actual: u32, expected: u32 },
+    #[error("invalid nested value payload: {0}")]
+    InvalidNestedPayload(&'static str),
     #[error("unsupported physical encoding: {0}")]
     UnsupportedPhysicalEncoding(&'static str),
     #[error("unsupported physical encoding: {0:?} for {1}")]
@@ -147,6 +183,18 @@ pub enum MltError {
     VertexMortonNotCompatibleWithExtent { extent: u32, required_bits: u32 },
     #[error("Morton stream uses {0} bits, but at most 16 bits are supported")]
     InvalidMortonBits(u32),
+    #[error("pseudo-decimal exponent {0} is outside the supported range of ±22")]
+    InvalidPseudoDecimalExponent(i64),
+    #[error("pseudo-decimal stream has an odd number of words ({0})")]
+    InvalidPseudoDecimalWordCount(usize),
+    #[error("invalid tileset metadata: {0}")]
+    InvalidTileSetMetadata(&'static str),
+    #[error("unknown {0} value {1} in tileset metadata")]
+    UnknownTileSetMetadataEnum(&'static str, i32),
+    #[error("column {column} of feature table {table} has incompatible types across tiles")]
+    IncompatibleColumnSchemas { table: String, column: String },
+    #[error("invalid filter expression: {0}")]
+    InvalidFilter(String),
 
     // Geometry decode errors (field = variable name, geom_type for context)
     #[error("MVT error: {0}")]
@@ -174,6 +222,10 @@ pub enum MltError {
     NoRingOffsets(usize, GeometryType),
     #[error("geometry[{0}]: unexpected offset combination for {1}")]
     UnexpectedOffsetCombination(usize, GeometryType),
+    #[error("geometry[{0}]: {1} cannot be stored as a triangle mesh without outlines")]
+    NonPolygonMesh(usize, GeometryType),
+    #[error("mesh-only geometry triangle count mismatch: expected {expected}, got {actual}")]
+    MeshTriangleCountMismatch { expected: usize, actual: usize },
 
     #[error("FastPFor error: {0}")]
     FastPfor(#[from] fastpfor::FastPForError),
@@ -193,6 +245,18 @@ pub enum MltError {
     Mvt(#[from] fast_mvt::MvtError),
     #[error("MVT JSON value error: {0}")]
     MvtJsonValue(#[from] fast_mvt::MvtJsonValueError),
+    #[cfg(feature = "arrow")]
+    #[error("Arrow error: {0}")]
+    Arrow(#[from] arrow_schema::ArrowError),
+    #[cfg(feature = "arrow")]
+    #[error("column {column}: unsupported Arrow type {data_type}")]
+    UnsupportedArrowType {
+        column: String,
+        data_type: arrow_schema::DataType,
+    },
+    #[cfg(feature = "arrow")]
+    #[error("geometry[{row}]: {reason}")]
+    InvalidArrowGeometry { row: usize, reason: &'static str },
 }
 
 impl From<MltError> for std::io::Error {
@@ -0,0 +1,290 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::ops::Range;
+use std::slice;
+
+use bitvec::order::Lsb0;
+use bitvec::vec::BitVec;
+
+use crate::decoder::{
+    ColValIter, ParsedProperty, ParsedSharedDict, ParsedSharedDictItem, ParsedStructChild,
+    build_col_iters,
+};
+use crate::filter::Filter;
+use crate::filter::expr::{CompareOp, Expr, Literal, Operand};
+use crate::{ColNames as _, GeometryType, ParsedLayer01, PropValueRef};
+
+/// Features of a layer selected by a [`Filter`], one bit per feature.
+///
+/// Features beyond [`Self::len`] are not selected.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Selection {
+    bits: BitVec<u8, Lsb0>,
+}
+
+impl Selection {
+    /// Select all of `len` features.
+    #[must_use]
+    pub fn all(len: usize) -> Self {
+        Self {
+            bits: BitVec::repeat(true, len),
+        }
+    }
+
+    /// Select none of `len` features.
+    #[must_use]
+    pub fn none(len: usize) -> Self {
+        Self {
+            bits: BitVec::repeat(false, len),
+        }
+    }
+
+    /// Number of features covered, selected or not.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.bits.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.bits.is_empty()
+    }
+
+    /// Number of selected features.
+    #[must_use]
+    pub fn count(&self) -> usize {
+        self.bits.count_ones()
+    }
+
+    /// Returns `true` if feature `index` is selected.
+    #[must_use]
+    pub fn contains(&self, index: usize) -> bool {
+        self.bits.get(index).is_some_and(|bit| *bit)
+    }
+
+    /// Indices of the selected features, in ascending order.
+    #[must_use]
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = usize> + '_ {
+        self.bits.iter_ones()
+    }
+
+    /// The bitmap as packed bytes: bit `i` is `(bytes[i / 8] >> (i % 8)) & 1`.
+    #[must_use]
+    pub fn as_bytes(&self) -> &[u8] {
+        self.bits.as_raw_slice()
+    }
+
+    /// Number of selected features with an index in `range`.
+    pub(crate) fn count_in(&self, range: Range<usize>) -> usize {
+        let end = range.end.min(self.bits.len());
+        self.bits[range.start.min(end)..end].count_ones()
+    }
+}
+
+impl FromIterator<bool> for Selection {
+    fn from_iter<I: IntoIterator<Item = bool>>(iter: I) -> Self {
+        Self {
+            bits: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl Filter {
+    /// Evaluate the filter against every feature of `layer`.
+    ///
+    /// Properties the layer does not have are null, so e.g. `["!=", "class", "primary"]`
+    /// selects every feature of a layer without a `class` column.
+    #[must_use]
+    pub fn evaluate(&self, layer: &ParsedLayer01<'_>) -> Selection {
+        Selection {
+            bits: eval(&self.expr, layer, layer.feature_count()),
+        }
+    }
+}
+
+fn eval(expr: &Expr, layer: &ParsedLayer01<'_>, len: usize) -> BitVec<u8, Lsb0> {
+    match expr {
+        Expr::Const(value) => BitVec::repeat(*value, len),
+        Expr::All(exprs) => {
+            let mut bits = BitVec::repeat(true, len);
+            for expr in exprs {
+                if bits.not_any() {
+                    break;
+                }
+                bits &= eval(expr, layer, len).as_bitslice();
+            }
+            bits
+        }
+        Expr::Any(exprs) => {
+            let mut bits = BitVec::repeat(false, len);
+            for expr in exprs {
+                if bits.all() {
+                    break;
+                }
+                bits |= eval(expr, layer, len).as_bitslice();
+            }
+            bits
+        }
+        Expr::Not(expr) => !eval(expr, layer, len),
+        Expr::Has(operand) => eval_operand(operand, layer, len, |value| value.is_some()),
+        Expr::Compare(op, operand, literal) => {
+            eval_operand(operand, layer, len, |value| compare(*op, value, literal))
+        }
+        Expr::In(operand, literals) => eval_operand(operand, layer, len, |value| {
+            literals.iter().any(|literal| equals(value, literal))
+        }),
+    }
+}
+
+/// A property value as seen by a filter.
+#[derive(Debug, Clone, Copy)]
+enum Val<'a> {
+    Bool(bool),
+    Num(f64),
+    Str(&'a str),
+    /// A value that only `has` can test, e.g. binary or nested data.
+    Other,
+}
+
+impl<'a> From<PropValueRef<'a>> for Val<'a> {
+    fn from(value: PropValueRef<'a>) -> Self {
+        use PropValueRef as V;
+        match value {
+            V::Bool(v) => Self::Bool(v),
+            V::I8(v) => Self::Num(v.into()),
+            V::U8(v) => Self::Num(v.into()),
+            V::I32(v) | V::Date(v) => Self::Num(v.into()),
+            V::U32(v) => Self::Num(v.into()),
+            V::I64(v) | V::Timestamp(v) => Self::Num(i64_to_f64(v)),
+            V::U64(v) => Self::Num(u64_to_f64(v)),
+            V::F32(v) => Self::Num(v.into()),
+            V::F64(v) => Self::Num(v),
+            V::Str(v) => Self::Str(v),
+            V::Nested(_) | V::Json(_) | V::Binary(_) | V::RangeMap(_) => Self::Other,
+        }
+    }
+}
+
+#[expect(
+    clippy::cast_precision_loss,
+    reason = "filter numbers are f64, as in MapLibre"
+)]
+fn i64_to_f64(v: i64) -> f64 {
+    v as f64
+}
+
+#[expect(
+    clippy::cast_precision_loss,
+    reason = "filter numbers are f64, as in MapLibre"
+)]
+fn u64_to_f64(v: u64) -> f64 {
+    v as f64
+}
+
+#[expect(clippy::float_cmp, reason = "filter equality is exact, as in MapLibre")]
+fn equals(value: Option<Val<'_>>, literal: &Literal) -> bool {
+    match (value, literal) {
+        (None, Literal::Null) => true,
+        (Some(Val::Bool(a)), Literal::Bool(b)) => a == *b,
+        (Some(Val::Num(a)), Literal::Number(b)) => a == *b,
+        (Some(Val::Str(a)), Literal::Str(b)) => a == b,
+        _ => false,
+    }
+}
+
+fn compare(op: CompareOp, value: Option<Val<'_>>, literal: &Literal) -> bool {
+    let ordering = || match (value, literal) {
+        (Some(Val::Num(a)), Literal::Number(b)) => a.partial_cmp(b),
+        (Some(Val::Str(a)), Literal::Str(b)) => Some(a.cmp(b.as_str())),
+        _ => None,
+    };
+    match op {
+        CompareOp::Eq => equals(value, literal),
+        CompareOp::Ne => !equals(value, literal),
+        CompareOp::Lt => ordering().is_some_and(Ordering::is_lt),
+        CompareOp::Le => ordering().is_some_and(Ordering::is_le),
+        CompareOp::Gt => ordering().is_some_and(Ordering::is_gt),
+        CompareOp::Ge => ordering().is_some_and(Ordering::is_ge),
+    }
+}
+
+/// Evaluate `pred` on the value of `operand` for every feature.
+fn eval_operand(
+    operand: &Operand,
+    layer: &ParsedLayer01<'_>,
+    len: usize,
+    pred: impl Fn(Option<Val<'_>>) -> bool,
+) -> BitVec<u8, Lsb0> {
+    let mut bits: BitVec<u8, Lsb0> = match operand {
+        Operand::Id => match &layer.id {
+            Some(ids) => ids
+                .iter_optional()
+                .map(|id| pred(id.map(|id| Val::Num(u64_to_f64(id)))))
+                .collect(),
+            None => BitVec::repeat(pred(None), len),
+        },
+        Operand::GeometryType => layer
+            .geometry
+            .vector_types()
+            .iter()
+            .map(|&geom_type| pred(Some(Val::Str(type_name(geom_type)))))
+            .collect(),
+        Operand::Property(name) => match find_column(&layer.properties, name) {
+            Some(Column::Shared(dict, item)) => {
+                // Many features share few distinct strings; test each of them only once.
+                let mut memo = HashMap::new();
+                item.ranges
+                    .iter()
+                    .enumerate()
+                    .map(|(i, range)| {
+                        *memo
+                            .entry((range.start, range.end))
+                            .or_insert_with(|| pred(item.get(dict, i).map(Val::Str)))
+                    })
+                    .collect()
+            }
+            Some(Column::Values(values)) => values.map(|v| pred(v.map(Val::from))).collect(),
+            None => BitVec::repeat(pred(None), len),
+        },
+    };
+    bits.resize(len, false);
+    bits
+}
+
+/// The `geometry-type` of a feature; multi geometries report their single counterpart.
+fn type_name(geom_type: GeometryType) -> &'static str {
+    match geom_type {
+        GeometryType::Point | GeometryType::MultiPoint => "Point",
+        GeometryType::LineString | GeometryType::MultiLineString => "LineString",
+        GeometryType::Polygon | GeometryType::MultiPolygon => "Polygon",
+    }
+}
+
+/// The values of one named property slot.
+enum Column<'p> {
+    /// A shared dictionary sub-item, tested per distinct dictionary range.
+    Shared(&'p ParsedSharedDict<'p>, &'p ParsedSharedDictItem<'p>),
+    /// Any other column, tested per feature.
+    Values(ColValIter<'p>),
+}
+
+fn find_column<'p>(properties: &'p [ParsedProperty<'p>], name: &str) -> Option<Column<'p>> {
+    let (col, sub) = properties.iter().find_map(|col| {
+        (0..col.name_count())
+            .find(|&sub| col.name_at(sub) == name)
+            .map(|sub| (col, sub))
+    })?;
+    Some(match col {
+        ParsedProperty::SharedDict(dict) => Column::Shared(dict, &dict.items[sub]),
+        ParsedProperty::Struct(st) => match &st.children[sub] {
+            ParsedStructChild::Shared(item) => {
+                let dict = st.strings.as_ref()?;
+                Column::Shared(dict, &dict.items[*item])
+            }
+            ParsedStructChild::Column(child) => {
+                Column::Values(build_col_iters(slice::from_ref(child)).into_iter().next()?)
+            }
+        },
+        _ => Column::Values(build_col_iters(slice::from_ref(col)).into_iter().nth(sub)?),
+    })
+}
@@ -0,0 +1,262 @@
+use std::str::FromStr;
+
+use serde_json::Value;
+
+use crate::{MltError, MltResult};
+
+/// A parsed `MapLibre` style filter, see the [module docs](crate::filter).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Filter {
+    pub(crate) expr: Expr,
+}
+
+/// Boolean filter expression tree.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Expr {
+    Const(bool),
+    All(Vec<Self>),
+    Any(Vec<Self>),
+    Not(Box<Self>),
+    /// The property is present and not null.
+    Has(Operand),
+    Compare(CompareOp, Operand, Literal),
+    In(Operand, Vec<Literal>),
+}
+
+/// A per-feature value that filters can test.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Operand {
+    Property(String),
+    Id,
+    GeometryType,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Literal {
+    Null,
+    Bool(bool),
+    Number(f64),
+    Str(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum CompareOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl CompareOp {
+    /// The operator to use once both sides are swapped, e.g. `5 > x` becomes `x < 5`.
+    fn swapped(self) -> Self {
+        match self {
+            Self::Eq | Self::Ne => self,
+            Self::Lt => Self::Gt,
+            Self::Le => Self::Ge,
+            Self::Gt => Self::Lt,
+            Self::Ge => Self::Le,
+        }
+    }
+}
+
+impl Filter {
+    /// Parse a filter from its JSON form, in legacy or expression syntax.
+    pub fn from_json(value: &Value) -> MltResult<Self> {
+        Ok(Self {
+            expr: parse_expr(value)?,
+        })
+    }
+}
+
+impl FromStr for Filter {
+    type Err = MltError;
+
+    fn from_str(s: &str) -> MltResult<Self> {
+        Self::from_json(&serde_json::from_str(s)?)
+    }
+}
+
+fn invalid(msg: impl Into<String>) -> MltError {
+    MltError::InvalidFilter(msg.into())
+}
+
+fn parse_expr(value: &Value) -> MltResult<Expr> {
+    let args = match value {
+        Value::Bool(b) => return Ok(Expr::Const(*b)),
+        Value::Array(args) => args,
+        other => {
+            return Err(invalid(format!(
+                "expected a boolean expression, got {other}"
+            )));
+        }
+    };
+    let Some((Value::String(op), args)) = args.split_first() else {
+        return Err(invalid("expression must start with an operator name"));
+    };
+    let all = |args: &[Value]| args.iter().map(parse_expr).collect::<MltResult<Vec<_>>>();
+    let not = |expr: Expr| Expr::Not(Box::new(expr));
+    Ok(match op.as_str() {
+        "all" => Expr::All(all(args)?),
+        "any" => Expr::Any(all(args)?),
+        "none" => not(Expr::Any(all(args)?)),
+        "!" => match args {
+            [expr] => not(parse_expr(expr)?),
+            _ => return Err(invalid("`!` takes exactly one argument")),
+        },
+        "has" => Expr::Has(parse_has(args)?),
+        "!has" => not(Expr::Has(parse_has(args)?)),
+        "==" => parse_compare(CompareOp::Eq, args)?,
+        "!=" => parse_compare(CompareOp::Ne, args)?,
+        "<" => parse_compare(CompareOp::Lt, args)?,
+        "<=" => parse_compare(CompareOp::Le, args)?,
+        ">" => parse_compare(CompareOp::Gt, args)?,
+        ">=" => parse_compare(CompareOp::Ge, args)?,
+        "in" => parse_in(args)?,
+        "!in" => not(parse_in(args)?),
+        "match" => parse_match(args)?,
+        other => return Err(invalid(format!("unsupported operator `{other}`"))),
+    })
+}
+
+/// `["has", "name"]`, also accepting `$id` and `$type`.
+fn parse_has(args: &[Value]) -> MltResult<Operand> {
+    match args {
+        [Value::String(key)] => Ok(legacy_key(key)),
+        _ => Err(invalid("`has` takes exactly one property name")),
+    }
+}
+
+/// Comparison in legacy (`[op, key, value]`) or expression (`[op, value, value]`) form.
+fn parse_compare(op: CompareOp, args: &[Value]) -> MltResult<Expr> {
+    let [lhs, rhs] = args else {
+        return Err(invalid("comparisons take exactly two arguments"));
+    };
+    if let Some(operand) = parse_operand(lhs)? {
+        Ok(Expr::Compare(op, operand, parse_literal(rhs)?))
+    } else if let Some(operand) = parse_operand(rhs)? {
+        Ok(Expr::Compare(op.swapped(), operand, parse_literal(lhs)?))
+    } else if let Value::String(key) = lhs {
+        Ok(Expr::Compare(op, legacy_key(key), parse_literal(rhs)?))
+    } else {
+        Err(invalid(
+            "comparisons need a `get`, `id` or `geometry-type` argument",
+        ))
+    }
+}
+
+/// `["in", key, v0, v1, ...]` or `["in", value, ["literal", [v0, v1, ...]]]`.
+fn parse_in(args: &[Value]) -> MltResult<Expr> {
+    match args {
+        [Value::String(key), values @ ..] => Ok(Expr::In(
+            legacy_key(key),
+            values.iter().map(parse_literal).collect::<MltResult<_>>()?,
+        )),
+        [needle, haystack] => {
+            let operand = parse_operand(needle)?
+                .ok_or_else(|| invalid("`in` needs a `get`, `id` or `geometry-type` argument"))?;
+            Ok(Expr::In(operand, parse_literal_list(haystack)?))
+        }
+        _ => Err(invalid("`in` takes a value and a list of literals")),
+    }
+}
+
+/// `["match", value, label(s), bool, ..., fallback]`.
+///
+/// Labels are unique, so the expression holds if the value matches the labels of any
+/// `true` output, or matches no label at all and the fallback is `true`.
+fn parse_match(args: &[Value]) -> MltResult<Expr> {
+    let Some((input, rest)) = args.split_first() else {
+        return Err(invalid("`match` needs an input"));
+    };
+    let operand = parse_operand(input)?.ok_or_else(|| invalid("`match` input must be a value"))?;
+    let Some((Value::Bool(fallback), cases)) = rest.split_last() else {
+        return Err(invalid("`match` must end with a boolean fallback"));
+    };
+    if cases.is_empty() || !cases.len().is_multiple_of(2) {
+        return Err(invalid("`match` needs label and output pairs"));
+    }
+    let mut matching = Vec::new();
+    let mut labeled = Vec::new();
+    for case in cases.chunks_exact(2) {
+        let labels = match &case[0] {
+            Value::Array(labels) => labels.iter().map(parse_literal).collect::<MltResult<_>>()?,
+            label => vec![parse_literal(label)?],
+        };
+        let Value::Bool(output) = case[1] else {
+            return Err(invalid("`match` outputs must be booleans"));
+        };
+        if output {
+            matching.extend_from_slice(&labels);
+        }
+        labeled.extend(labels);
+    }
+    let matched = Expr::In(operand.clone(), matching);
+    Ok(if *fallback {
+        let unlabeled = Expr::Not(Box::new(Expr::In(operand, labeled)));
+        Expr::Any(vec![matched, unlabeled])
+    } else {
+        matched
+    })
+}
+
+/// A legacy filter key: `$id`, `$type`, or a property name.
+fn legacy_key(key: &str) -> Operand {
+    match key {
+        "$id" => Operand::Id,
+        "$type" => Operand::GeometryType,
+        name => Operand::Property(name.to_string()),
+    }
+}
+
+/// `["get", name]`, `["id"]` or `["geometry-type"]`; `None` if `value` is not one of them.
+fn parse_operand(value: &Value) -> MltResult<Option<Operand>> {
+    let Value::Array(args) = value else {
+        return Ok(None);
+    };
+    Ok(match args.as_slice() {
+        [Value::String(op), Value::String(name)] if op == "get" => {
+            Some(Operand::Property(name.clone()))
+        }
+        [Value::String(op)] if op == "id" => Some(Operand::Id),
+        [Value::String(op)] if op == "geometry-type" => Some(Operand::GeometryType),
+        [Value::String(op), ..] if op == "literal" => None,
+        [Value::String(op), ..] => {
+            return Err(invalid(format!("unsupported value expression `{op}`")));
+        }
+        _ => None,
+    })
+}
+
+/// A scalar JSON value, optionally wrapped in `["literal", value]`.
+fn parse_literal(value: &Value) -> MltResult<Literal> {
+    Ok(match value {
+        Value::Null => Literal::Null,
+        Value::Bool(b) => Literal::Bool(*b),
+        Value::Number(n) => Literal::Number(
+            n.as_f64()
+                .ok_or_else(|| invalid(format!("unsupported number {n}")))?,
+        ),
+        Value::String(s) => Literal::Str(s.clone()),
+        Value::Array(args) => match args.as_slice() {
+            [Value::String(op), value] if op == "literal" && !value.is_array() => {
+                parse_literal(value)?
+            }
+            _ => return Err(invalid(format!("expected a literal, got {value}"))),
+        },
+        Value::Object(_) => return Err(invalid(format!("expected a literal, got {value}"))),
+    })
+}
+
+/// `["literal", [v0, v1, ...]]`.
+fn parse_literal_list(value: &Value) -> MltResult<Vec<Literal>> {
+    if let Value::Array(args) = value
+        && let [Value::String(op), Value::Array(values)] = args.as_slice()
+        && op == "literal"
+    {
+        return values.iter().map(parse_literal).collect();
+    }
+    Err(invalid(format!("expected a literal list, got {value}")))
+}
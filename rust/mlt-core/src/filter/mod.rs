@@ -0,0 +1,42 @@
+//! Columnar feature filtering with `MapLibre` style filter expressions.
+//!
+//! A [`Filter`] is parsed from the JSON of a `MapLibre` GL style `filter`, and evaluated
+//! against a decoded layer with [`Filter::evaluate`].  Evaluation runs over whole property
+//! columns rather than per-feature [`FeatureRef`](crate::FeatureRef)s: strings are compared
+//! in place, and strings from shared dictionaries are compared once per distinct value.
+//! The result is a [`Selection`] bitmap, which can drive
+//! [`ParsedLayer01::iter_selected`](crate::ParsedLayer01::iter_selected) or
+//! [`ParsedLayer01::into_tile_selected`](crate::ParsedLayer01::into_tile_selected).
+//!
+//! ```
+//! use mlt_core::filter::Filter;
+//!
+//! let filter: Filter = r#"["all", ["==", ["get", "class"], "primary"], ["<", "rank", 5]]"#
+//!     .parse()
+//!     .unwrap();
+//! ```
+//!
+//! # Supported expressions
+//!
+//! Both the legacy filter syntax (`["==", "class", "primary"]`) and the expression syntax
+//! (`["==", ["get", "class"], "primary"]`) are accepted, and may be mixed:
+//!
+//! - `true` / `false`
+//! - `all`, `any`, `none`, `!`
+//! - `==`, `!=`, `<`, `<=`, `>`, `>=` between a value and a literal
+//! - `in` / `!in` of a value in a list of literals (legacy list or `["literal", [...]]`)
+//! - `has` / `!has` of a property
+//! - `match` of a value against literal labels, with boolean outputs
+//!
+//! Values are `["get", name]` (or a bare name in legacy filters), `["id"]` / `"$id"`, and
+//! `["geometry-type"]` / `"$type"`, which yields `"Point"`, `"LineString"` or `"Polygon"`
+//! for single and multi geometries alike.  All numeric property types compare as numbers.
+//! Comparing values of different types is never true, except for `!=`.
+
+mod eval;
+mod expr;
+#[cfg(test)]
+mod tests;
+
+pub use eval::Selection;
+pub use expr::Filter;
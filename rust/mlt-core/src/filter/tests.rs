@@ -0,0 +1,186 @@
+use geo_types::{Geometry, LineString, Point, Polygon};
+use rstest::rstest;
+
+use crate::decoder::GeometryValues;
+use crate::encoder::{
+    Codecs, Encoder, Presence, StagedId, StagedLayer, StagedProperty, StagedSharedDict,
+};
+use crate::filter::{Filter, Selection};
+use crate::test_helpers::{dec, parser};
+use crate::{Layer, LendingIterator as _, MltError, ParsedLayer01, PropValueRef};
+
+/// Four features of mixed geometry types, with sparse string, number, shared
+/// dictionary and id columns.
+fn roads() -> Vec<u8> {
+    let mut geometry = GeometryValues::default();
+    geometry.push_geom(&Geometry::<i32>::Point(Point::new(1, 2)));
+    geometry.push_geom(&Geometry::<i32>::LineString(LineString::from(vec![
+        (0, 0),
+        (10, 10),
+    ])));
+    geometry.push_geom(&Geometry::<i32>::Polygon(Polygon::new(
+        LineString::from(vec![(0, 0), (4, 0), (4, 4), (0, 0)]),
+        vec![],
+    )));
+    geometry.push_geom(&Geometry::<i32>::Point(Point::new(3, 4)));
+    let name = StagedSharedDict::new(
+        "name:",
+        [(
+            "en",
+            vec![Some("Main"), Some("Main"), None, Some("Side")],
+            Presence::Mixed,
+        )],
+    )
+    .unwrap();
+    StagedLayer::new(
+        "roads",
+        4096,
+        StagedId::from_optional(vec![Some(10), Some(11), None, Some(13)]),
+        geometry,
+        vec![
+            StagedProperty::opt_str(
+                "class",
+                [Some("primary"), Some("primary"), Some("secondary"), None],
+            ),
+            StagedProperty::opt_u32("rank", [Some(3), Some(7), Some(1), None]),
+            StagedProperty::SharedDict(name),
+        ],
+    )
+    .unwrap()
+    .encode_into(Encoder::default(), &mut Codecs::default())
+    .unwrap()
+    .into_layer_bytes()
+    .unwrap()
+}
+
+fn parsed(buf: &[u8]) -> ParsedLayer01<'_> {
+    let (_, layer) = Layer::from_bytes(buf, &mut parser()).unwrap();
+    let Layer::Tag01(lazy) = layer else { panic!() };
+    lazy.decode_all(&mut dec()).unwrap()
+}
+
+fn filter(json: &str) -> Filter {
+    json.parse().unwrap()
+}
+
+#[rstest]
+#[case::literal_true("true", &[0, 1, 2, 3])]
+#[case::literal_false("false", &[])]
+#[case::legacy_eq(r#"["==", "class", "primary"]"#, &[0, 1])]
+#[case::legacy_ne_includes_nulls(r#"["!=", "class", "primary"]"#, &[2, 3])]
+#[case::all(r#"["all", ["==", ["get", "class"], "primary"], ["<", "rank", 5]]"#, &[0])]
+#[case::any(r#"["any", ["==", "rank", 1], ["==", "name:en", "Side"]]"#, &[2, 3])]
+#[case::none(r#"["none", ["==", "class", "primary"]]"#, &[2, 3])]
+#[case::swapped_operands(r#"["<", 3, ["get", "rank"]]"#, &[1])]
+#[case::ge(r#"[">=", ["get", "rank"], 3]"#, &[0, 1])]
+#[case::has(r#"["has", "rank"]"#, &[0, 1, 2])]
+#[case::not_has(r#"["!has", "class"]"#, &[3])]
+#[case::legacy_in(r#"["in", "class", "secondary", "tertiary"]"#, &[2])]
+#[case::expression_in(r#"["in", ["get", "class"], ["literal", ["primary"]]]"#, &[0, 1])]
+#[case::not_in(r#"["!in", "class", "primary"]"#, &[2, 3])]
+#[case::shared_dict(r#"["==", ["get", "name:en"], "Main"]"#, &[0, 1])]
+#[case::shared_dict_null(r#"["!", ["has", "name:en"]]"#, &[2])]
+#[case::shared_dict_order(r#"[">", "name:en", "Main"]"#, &[3])]
+#[case::legacy_type(r#"["==", "$type", "Polygon"]"#, &[2])]
+#[case::geometry_type(r#"["==", ["geometry-type"], "LineString"]"#, &[1])]
+#[case::id(r#"["==", ["id"], 11]"#, &[1])]
+#[case::has_id(r#"["has", "$id"]"#, &[0, 1, 3])]
+#[case::match_true(r#"["match", ["get", "class"], "primary", true, false]"#, &[0, 1])]
+#[case::match_fallback(r#"["match", ["get", "class"], ["primary"], false, true]"#, &[2, 3])]
+#[case::missing_is_null(r#"["==", "missing", null]"#, &[0, 1, 2, 3])]
+#[case::mixed_types(r#"["<", "class", 5]"#, &[])]
+fn evaluate(#[case] json: &str, #[case] expected: &[usize]) {
+    let buf = roads();
+    let selection = filter(json).evaluate(&parsed(&buf));
+    assert_eq!(selection.len(), 4);
+    assert_eq!(selection.iter().collect::<Vec<_>>(), expected);
+}
+
+#[test]
+fn legacy_and_expression_syntax_agree() {
+    assert_eq!(
+        filter(r#"["==", "class", "primary"]"#),
+        filter(r#"["==", ["get", "class"], "primary"]"#),
+    );
+    assert_eq!(
+        filter(r#"["<", 5, ["get", "rank"]]"#),
+        filter(r#"[">", ["get", "rank"], 5]"#),
+    );
+    assert_eq!(
+        filter(r#"["in", "class", "a", "b"]"#),
+        filter(r#"["in", ["get", "class"], ["literal", ["a", "b"]]]"#),
+    );
+}
+
+#[rstest]
+#[case::not_json("[")]
+#[case::not_boolean(r#""class""#)]
+#[case::unknown_operator(r#"["within", {}]"#)]
+#[case::unsupported_value(r#"["==", ["to-string", ["get", "rank"]], "3"]"#)]
+#[case::no_operand(r#"["==", 1, 2]"#)]
+#[case::non_literal(r#"["==", "class", {}]"#)]
+#[case::match_output(r#"["match", ["get", "class"], "primary", 1, false]"#)]
+#[case::match_pairs(r#"["match", ["get", "class"], "primary", false]"#)]
+#[case::not_arity(r#"["!", true, false]"#)]
+fn invalid_filters_are_rejected(#[case] json: &str) {
+    assert!(matches!(
+        json.parse::<Filter>(),
+        Err(MltError::InvalidFilter(_) | MltError::SerdeJsonError(_))
+    ));
+}
+
+#[test]
+fn selection_bitmap() {
+    let selection: Selection = [true, false, true, true, false, false, false, false, true]
+        .into_iter()
+        .collect();
+    assert_eq!(selection.len(), 9);
+    assert_eq!(selection.count(), 4);
+    assert_eq!(selection.as_bytes(), &[0b0000_1101, 0b1]);
+    assert!(selection.contains(8));
+    assert!(!selection.contains(9));
+    assert_eq!(Selection::all(3).count(), 3);
+    assert_eq!(Selection::none(3).iter().next(), None);
+}
+
+#[test]
+fn iter_selected_skips_unselected_features() {
+    let buf = roads();
+    let layer = parsed(&buf);
+    let selection =
+        filter(r#"["any", ["==", "$type", "LineString"], ["!has", "class"]]"#).evaluate(&layer);
+
+    let mut iter = layer.iter_selected(&selection);
+    assert_eq!(iter.len(), 2);
+    let feat = iter.next().unwrap().unwrap();
+    assert_eq!(feat.id(), Some(11));
+    assert_eq!(feat.get_property("rank"), Some(PropValueRef::U32(7)));
+    assert_eq!(
+        feat.get_property("name:en"),
+        Some(PropValueRef::Str("Main"))
+    );
+    assert_eq!(iter.len(), 1);
+    let feat = iter.next().unwrap().unwrap();
+    assert_eq!(feat.id(), Some(13));
+    assert_eq!(feat.geometry(), &Geometry::<i32>::Point(Point::new(3, 4)));
+    assert_eq!(feat.get_property("class"), None);
+    assert_eq!(
+        feat.get_property("name:en"),
+        Some(PropValueRef::Str("Side"))
+    );
+    assert!(iter.is_empty());
+    assert!(iter.next().is_none());
+}
+
+#[test]
+fn into_tile_selected_keeps_selected_features() {
+    let buf = roads();
+    let layer = parsed(&buf);
+    let selection = filter(r#"["==", "class", "primary"]"#).evaluate(&layer);
+    let all = layer.clone().into_tile(&mut dec()).unwrap();
+    let tile = layer.into_tile_selected(&selection, &mut dec()).unwrap();
+
+    assert_eq!(tile.name(), "roads");
+    assert_eq!(tile.property_names(), all.property_names());
+    assert_eq!(tile.features(), &all.features()[..2]);
+}
@@ -20,17 +20,23 @@ pub use geo_types;
 pub(crate) mod codecs;
 pub(crate) mod convert;
 pub(crate) mod decoder;
+pub mod diff;
 pub mod dump;
 pub mod encoder;
 pub(crate) mod errors;
+pub mod filter;
+pub mod metadata;
 pub(crate) mod utils;
 
+#[cfg(feature = "arrow")]
+pub use convert::arrow;
 pub use convert::{geojson, mvt};
 pub use decoder::{
     ColNames, ColumnRef, Decoder, Extent, FeatureRef, GeometryType, GeometryValues, Layer, Layer01,
-    Layer01FeatureIter, LendingIterator, ParsedLayer, ParsedLayer01, Parser, PropKind, PropName,
-    PropNamesIter, PropValue, PropValueRef, PropertyKey, TileFeature, TileFeatureBuilder,
-    TileLayer, TileLayerBuilder, Unknown,
+    Layer01FeatureIter, LayerTriangles, LendingIterator, NestedValue, ParsedLayer, ParsedLayer01,
+    Parser, PropKind, PropName, PropNamesIter, PropValue, PropValueRef, PropertyKey, TileFeature,
+    TileFeatureBuilder, TileLayer, TileLayerBuilder, TrianglesRef, Unknown, VertexColumnRef,
+    VertexPropertyKey, VertexValues, VertexValuesRef,
 };
 // Crate-internal re-exports: allow internal modules to use `crate::Lazy` etc.
 // without exposing these implementation details to external users.
@@ -0,0 +1,12 @@
+//! Tileset metadata (`mlt_tileset_metadata.proto`).
+//!
+//! Describes the layers and column types of a whole tileset, so clients can learn the schema
+//! without decoding a tile. Build it from decoded layers with [`TileSetMetadata::from_layers`]
+//! and store it with [`TileSetMetadata::to_bytes`].
+
+mod model;
+mod proto;
+mod schema;
+
+pub use model::*;
+pub use schema::{GEOMETRY_COLUMN_NAME, ID_COLUMN_NAME};
@@ -0,0 +1,156 @@
+use num_enum::{IntoPrimitive, TryFromPrimitive};
+
+/// Current [`TileSetMetadata::version`] written by this crate.
+pub const TILESET_METADATA_VERSION: i32 = 1;
+
+/// Tileset-wide schema, mirroring `TileSetMetadata` in `mlt_tileset_metadata.proto`.
+///
+/// Lets clients learn the layers and column types of a tileset without decoding a tile.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TileSetMetadata {
+    pub version: i32,
+    pub feature_tables: Vec<FeatureTableSchema>,
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub attribution: Option<String>,
+    pub min_zoom: Option<i32>,
+    pub max_zoom: Option<i32>,
+    /// `[left, bottom, right, top]` in WGS84, or empty if unknown.
+    pub bounds: Vec<f64>,
+    /// `[longitude, latitude]` in WGS84, or empty if unknown.
+    pub center: Vec<f64>,
+}
+
+impl Default for TileSetMetadata {
+    fn default() -> Self {
+        Self {
+            version: TILESET_METADATA_VERSION,
+            feature_tables: Vec::new(),
+            name: None,
+            description: None,
+            attribution: None,
+            min_zoom: None,
+            max_zoom: None,
+            bounds: Vec::new(),
+            center: Vec::new(),
+        }
+    }
+}
+
+/// Schema of one layer (feature table) of the tileset.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FeatureTableSchema {
+    pub name: String,
+    pub columns: Vec<Column>,
+}
+
+/// Top-level column of a feature table.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Column {
+    pub name: String,
+    /// Values may be missing, so a presence stream is used.
+    pub nullable: bool,
+    pub scope: ColumnScope,
+    pub kind: ColumnKind,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ColumnKind {
+    Scalar(ScalarColumn),
+    Complex(ComplexColumn),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScalarColumn {
+    /// The ID column uses 64-bit values.
+    pub long_id: bool,
+    pub kind: ScalarKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScalarKind {
+    Physical(ScalarType),
+    Logical(LogicalScalarType),
+}
+
+/// Complex column or field; the proto's `ComplexColumn` and `ComplexField` share this shape.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ComplexColumn {
+    pub kind: ComplexKind,
+    /// Pre-order flattened child types. Empty for `GEOMETRY` and `BINARY`.
+    pub children: Vec<Field>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ComplexKind {
+    Physical(ComplexType),
+    Logical(LogicalComplexType),
+}
+
+/// Nested or leaf type inside a [`ComplexColumn`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Field {
+    /// Only used for struct children.
+    pub name: Option<String>,
+    pub nullable: Option<bool>,
+    pub kind: FieldKind,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum FieldKind {
+    Scalar(ScalarKind),
+    Complex(ComplexColumn),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, TryFromPrimitive, IntoPrimitive)]
+#[repr(i32)]
+pub enum ColumnScope {
+    /// One value per feature (ID, geometry and regular properties).
+    #[default]
+    Feature = 0,
+    /// One value per vertex, e.g. M-values.
+    Vertex = 1,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, TryFromPrimitive, IntoPrimitive)]
+#[repr(i32)]
+pub enum ScalarType {
+    Boolean = 0,
+    Int8 = 1,
+    UInt8 = 2,
+    Int32 = 3,
+    UInt32 = 4,
+    Int64 = 5,
+    UInt64 = 6,
+    Float = 7,
+    Double = 8,
+    String = 9,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, TryFromPrimitive, IntoPrimitive)]
+#[repr(i32)]
+pub enum ComplexType {
+    Geometry = 0,
+    Struct = 1,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, TryFromPrimitive, IntoPrimitive)]
+#[repr(i32)]
+pub enum LogicalScalarType {
+    Id = 0,
+    /// Physical type `Int32`, days since the Unix epoch. Experimental, Rust-only.
+    Date = 1,
+    /// Physical type `Int64`, milliseconds since the Unix epoch. Experimental, Rust-only.
+    Timestamp = 2,
+    /// Physical type `String`, holding a JSON document. Experimental, Rust-only.
+    Json = 3,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, TryFromPrimitive, IntoPrimitive)]
+#[repr(i32)]
+pub enum LogicalComplexType {
+    /// Physical type `list<UInt8>`.
+    Binary = 0,
+    /// Physical type `map<vec2<double>, T>`, used for linear referencing.
+    RangeMap = 1,
+}
@@ -0,0 +1,575 @@
+//! Protobuf wire format of [`TileSetMetadata`] (`spec/schema/mlt_tileset_metadata.proto`).
+//!
+//! Follows proto3 rules: implicit-presence fields are omitted when they hold their
+//! default value, `optional` and `oneof` fields are written whenever set.
+//! Unknown fields are skipped when reading, so newer writers stay readable.
+
+use integer_encoding::VarInt as _;
+use num_enum::TryFromPrimitive;
+use usize_cast::FromUsize as _;
+
+use crate::MltError::{InvalidTileSetMetadata, UnknownTileSetMetadataEnum};
+use crate::MltResult;
+use crate::codecs::varint::parse_varint;
+use crate::metadata::{
+    Column, ColumnKind, ColumnScope, ComplexColumn, ComplexKind, FeatureTableSchema, Field,
+    FieldKind, ScalarColumn, ScalarKind, TileSetMetadata,
+};
+use crate::utils::take;
+
+const WIRE_VARINT: u64 = 0;
+const WIRE_FIXED64: u64 = 1;
+const WIRE_LEN: u64 = 2;
+const WIRE_FIXED32: u64 = 5;
+
+/// Field numbers of a complex type, which differ between `ComplexColumn` and `ComplexField`.
+struct ComplexFieldNumbers {
+    physical: u32,
+    logical: u32,
+    children: u32,
+}
+
+const COMPLEX_COLUMN: ComplexFieldNumbers = ComplexFieldNumbers {
+    physical: 4,
+    logical: 5,
+    children: 6,
+};
+
+const COMPLEX_FIELD: ComplexFieldNumbers = ComplexFieldNumbers {
+    physical: 1,
+    logical: 2,
+    children: 3,
+};
+
+impl TileSetMetadata {
+    /// Serialize to the protobuf `TileSetMetadata` message.
+    #[must_use]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        put_int32(&mut out, 1, self.version);
+        for table in &self.feature_tables {
+            put_message(&mut out, 2, |out| write_table(out, table));
+        }
+        put_opt_string(&mut out, 3, self.name.as_deref());
+        put_opt_string(&mut out, 4, self.description.as_deref());
+        put_opt_string(&mut out, 5, self.attribution.as_deref());
+        if let Some(v) = self.min_zoom {
+            put_int32_always(&mut out, 6, v);
+        }
+        if let Some(v) = self.max_zoom {
+            put_int32_always(&mut out, 7, v);
+        }
+        put_doubles(&mut out, 8, &self.bounds);
+        put_doubles(&mut out, 9, &self.center);
+        out
+    }
+
+    /// Parse a protobuf `TileSetMetadata` message.
+    pub fn from_bytes(data: &[u8]) -> MltResult<Self> {
+        let mut meta = Self {
+            version: 0,
+            ..Self::default()
+        };
+        let mut fields = ProtoFields(data);
+        while let Some((number, value)) = fields.next_field()? {
+            match number {
+                1 => meta.version = value.int32()?,
+                2 => meta.feature_tables.push(read_table(value.bytes()?)?),
+                3 => meta.name = Some(value.string()?),
+                4 => meta.description = Some(value.string()?),
+                5 => meta.attribution = Some(value.string()?),
+                6 => meta.min_zoom = Some(value.int32()?),
+                7 => meta.max_zoom = Some(value.int32()?),
+                8 => value.doubles(&mut meta.bounds)?,
+                9 => value.doubles(&mut meta.center)?,
+                _ => {}
+            }
+        }
+        Ok(meta)
+    }
+}
+
+fn write_table(out: &mut Vec<u8>, table: &FeatureTableSchema) {
+    put_string(out, 1, &table.name);
+    for column in &table.columns {
+        put_message(out, 2, |out| write_column(out, column));
+    }
+}
+
+fn write_column(out: &mut Vec<u8>, column: &Column) {
+    put_string(out, 1, &column.name);
+    put_bool(out, 2, column.nullable);
+    put_int32(out, 3, column.scope.into());
+    match &column.kind {
+        ColumnKind::Scalar(scalar) => put_message(out, 4, |out| {
+            put_bool(out, 1, scalar.long_id);
+            write_scalar_kind(out, scalar.kind, 4, 5);
+        }),
+        ColumnKind::Complex(complex) => {
+            put_message(out, 5, |out| write_complex(out, complex, &COMPLEX_COLUMN));
+        }
+    }
+}
+
+fn write_scalar_kind(out: &mut Vec<u8>, kind: ScalarKind, physical: u32, logical: u32) {
+    match kind {
+        ScalarKind::Physical(t) => put_int32_always(out, physical, t.into()),
+        ScalarKind::Logical(t) => put_int32_always(out, logical, t.into()),
+    }
+}
+
+fn write_complex(out: &mut Vec<u8>, complex: &ComplexColumn, numbers: &ComplexFieldNumbers) {
+    match complex.kind {
+        ComplexKind::Physical(t) => put_int32_always(out, numbers.physical, t.into()),
+        ComplexKind::Logical(t) => put_int32_always(out, numbers.logical, t.into()),
+    }
+    for child in &complex.children {
+        put_message(out, numbers.children, |out| write_field(out, child));
+    }
+}
+
+fn write_field(out: &mut Vec<u8>, field: &Field) {
+    put_opt_string(out, 1, field.name.as_deref());
+    if let Some(nullable) = field.nullable {
+        put_uint(out, 2, u64::from(nullable));
+    }
+    match &field.kind {
+        FieldKind::Scalar(kind) => put_message(out, 3, |out| write_scalar_kind(out, *kind, 1, 2)),
+        FieldKind::Complex(complex) => {
+            put_message(out, 4, |out| write_complex(out, complex, &COMPLEX_FIELD));
+        }
+    }
+}
+
+fn read_table(data: &[u8]) -> MltResult<FeatureTableSchema> {
+    let mut table = FeatureTableSchema {
+        name: String::new(),
+        columns: Vec::new(),
+    };
+    let mut fields = ProtoFields(data);
+    while let Some((number, value)) = fields.next_field()? {
+        match number {
+            1 => table.name = value.string()?,
+            2 => table.columns.push(read_column(value.bytes()?)?),
+            _ => {}
+        }
+    }
+    Ok(table)
+}
+
+fn read_column(data: &[u8]) -> MltResult<Column> {
+    let mut name = String::new();
+    let mut nullable = false;
+    let mut scope = ColumnScope::Feature;
+    let mut kind = None;
+    let mut fields = ProtoFields(data);
+    while let Some((number, value)) = fields.next_field()? {
+        match number {
+            1 => name = value.string()?,
+            2 => nullable = value.bool()?,
+            3 => scope = value.enumeration("ColumnScope")?,
+            4 => kind = Some(ColumnKind::Scalar(read_scalar_column(value.bytes()?)?)),
+            5 => {
+                let complex = read_complex(value.bytes()?, &COMPLEX_COLUMN)?;
+                kind = Some(ColumnKind::Complex(complex));
+            }
+            _ => {}
+        }
+    }
+    let kind = kind.ok_or(InvalidTileSetMetadata("column without a type"))?;
+    Ok(Column {
+        name,
+        nullable,
+        scope,
+        kind,
+    })
+}
+
+fn read_scalar_column(data: &[u8]) -> MltResult<ScalarColumn> {
+    let mut long_id = false;
+    let mut kind = None;
+    let mut fields = ProtoFields(data);
+    while let Some((number, value)) = fields.next_field()? {
+        match number {
+            1 => long_id = value.bool()?,
+            4 => kind = Some(ScalarKind::Physical(value.enumeration("ScalarType")?)),
+            5 => kind = Some(ScalarKind::Logical(value.enumeration("LogicalScalarType")?)),
+            _ => {}
+        }
+    }
+    let kind = kind.ok_or(InvalidTileSetMetadata("scalar column without a type"))?;
+    Ok(ScalarColumn { long_id, kind })
+}
+
+fn read_scalar_field(data: &[u8]) -> MltResult<ScalarKind> {
+    let mut kind = None;
+    let mut fields = ProtoFields(data);
+    while let Some((number, value)) = fields.next_field()? {
+        match number {
+            1 => kind = Some(ScalarKind::Physical(value.enumeration("ScalarType")?)),
+            2 => kind = Some(ScalarKind::Logical(value.enumeration("LogicalScalarType")?)),
+            _ => {}
+        }
+    }
+    kind.ok_or(InvalidTileSetMetadata("scalar field without a type"))
+}
+
+fn read_complex(data: &[u8], numbers: &ComplexFieldNumbers) -> MltResult<ComplexColumn> {
+    let mut kind = None;
+    let mut children = Vec::new();
+    let mut fields = ProtoFields(data);
+    while let Some((number, value)) = fields.next_field()? {
+        if number == numbers.physical {
+            kind = Some(ComplexKind::Physical(value.enumeration("ComplexType")?));
+        } else if number == numbers.logical {
+            kind = Some(ComplexKind::Logical(
+                value.enumeration("LogicalComplexType")?,
+            ));
+        } else if number == numbers.children {
+            children.push(read_field(value.bytes()?)?);
+        }
+    }
+    let kind = kind.ok_or(InvalidTileSetMetadata("complex type without a type"))?;
+    Ok(ComplexColumn { kind, children })
+}
+
+fn read_field(data: &[u8]) -> MltResult<Field> {
+    let mut name = None;
+    let mut nullable = None;
+    let mut kind = None;
+    let mut fields = ProtoFields(data);
+    while let Some((number, value)) = fields.next_field()? {
+        match number {
+            1 => name = Some(value.string()?),
+            2 => nullable = Some(value.bool()?),
+            3 => kind = Some(FieldKind::Scalar(read_scalar_field(value.bytes()?)?)),
+            4 => {
+                let complex = read_complex(value.bytes()?, &COMPLEX_FIELD)?;
+                kind = Some(FieldKind::Complex(complex));
+            }
+            _ => {}
+        }
+    }
+    let kind = kind.ok_or(InvalidTileSetMetadata("field without a type"))?;
+    Ok(Field {
+        name,
+        nullable,
+        kind,
+    })
+}
+
+/// One decoded field value, still untyped.
+enum WireValue<'a> {
+    Varint(u64),
+    Fixed64(u64),
+    Len(&'a [u8]),
+    Fixed32,
+}
+
+impl<'a> WireValue<'a> {
+    fn varint(self) -> MltResult<u64> {
+        match self {
+            Self::Varint(v) => Ok(v),
+            _ => Err(InvalidTileSetMetadata("expected a varint field")),
+        }
+    }
+
+    fn bool(self) -> MltResult<bool> {
+        Ok(self.varint()? != 0)
+    }
+
+    /// `int32` values are sign-extended to 64 bits on the wire.
+    fn int32(self) -> MltResult<i32> {
+        let value = i64::from_ne_bytes(self.varint()?.to_ne_bytes());
+        i32::try_from(value).map_err(|_| InvalidTileSetMetadata("int32 field out of range"))
+    }
+
+    fn enumeration<T: TryFromPrimitive<Primitive = i32>>(self, name: &'static str) -> MltResult<T> {
+        let value = self.int32()?;
+        T::try_from_primitive(value).map_err(|_| UnknownTileSetMetadataEnum(name, value))
+    }
+
+    fn bytes(self) -> MltResult<&'a [u8]> {
+        match self {
+            Self::Len(v) => Ok(v),
+            _ => Err(InvalidTileSetMetadata("expected a length-delimited field")),
+        }
+    }
+
+    fn string(self) -> MltResult<String> {
+        Ok(str::from_utf8(self.bytes()?)?.to_owned())
+    }
+
+    /// Repeated `double`s, accepting both packed and unpacked encodings.
+    fn doubles(self, target: &mut Vec<f64>) -> MltResult<()> {
+        match self {
+            Self::Fixed64(v) => target.push(f64::from_bits(v)),
+            Self::Len(data) => {
+                let chunks = data.chunks_exact(8);
+                if !chunks.remainder().is_empty() {
+                    return Err(InvalidTileSetMetadata("packed doubles not a multiple of 8"));
+                }
+                target.extend(chunks.map(|c| f64::from_le_bytes(c.try_into().expect("8 bytes"))));
+            }
+            _ => return Err(InvalidTileSetMetadata("expected a double field")),
+        }
+        Ok(())
+    }
+}
+
+/// Iterates over the `(field number, value)` pairs of one message.
+struct ProtoFields<'a>(&'a [u8]);
+
+impl<'a> ProtoFields<'a> {
+    fn next_field(&mut self) -> MltResult<Option<(u32, WireValue<'a>)>> {
+        if self.0.is_empty() {
+            return Ok(None);
+        }
+        let (input, key) = parse_varint::<u64>(self.0)?;
+        let number = u32::try_from(key >> 3)?;
+        let (input, value) = match key & 7 {
+            WIRE_VARINT => {
+                let (input, v) = parse_varint::<u64>(input)?;
+                (input, WireValue::Varint(v))
+            }
+            WIRE_FIXED64 => {
+                let (input, v) = take(input, 8)?;
+                let v = u64::from_le_bytes(v.try_into().expect("8 bytes"));
+                (input, WireValue::Fixed64(v))
+            }
+            WIRE_LEN => {
+                let (input, len) = parse_varint::<u32>(input)?;
+                let (input, v) = take(input, len)?;
+                (input, WireValue::Len(v))
+            }
+            WIRE_FIXED32 => (take(input, 4)?.0, WireValue::Fixed32),
+            _ => return Err(InvalidTileSetMetadata("unsupported protobuf wire type")),
+        };
+        self.0 = input;
+        Ok(Some((number, value)))
+    }
+}
+
+fn put_varint(out: &mut Vec<u8>, value: u64) {
+    let mut buf = [0u8; 10];
+    let n = value.encode_var(&mut buf);
+    out.extend_from_slice(&buf[..n]);
+}
+
+fn put_key(out: &mut Vec<u8>, number: u32, wire_type: u64) {
+    put_varint(out, (u64::from(number) << 3) | wire_type);
+}
+
+fn put_uint(out: &mut Vec<u8>, number: u32, value: u64) {
+    put_key(out, number, WIRE_VARINT);
+    put_varint(out, value);
+}
+
+fn put_bool(out: &mut Vec<u8>, number: u32, value: bool) {
+    if value {
+        put_uint(out, number, 1);
+    }
+}
+
+fn put_int32(out: &mut Vec<u8>, number: u32, value: i32) {
+    if value != 0 {
+        put_int32_always(out, number, value);
+    }
+}
+
+/// Write an `int32` even if it is zero, as required for `optional` and `oneof` fields.
+fn put_int32_always(out: &mut Vec<u8>, number: u32, value: i32) {
+    put_uint(
+        out,
+        number,
+        u64::from_ne_bytes(i64::from(value).to_ne_bytes()),
+    );
+}
+
+fn put_bytes(out: &mut Vec<u8>, number: u32, value: &[u8]) {
+    put_key(out, number, WIRE_LEN);
+    put_varint(out, u64::from_usize(value.len()));
+    out.extend_from_slice(value);
+}
+
+fn put_string(out: &mut Vec<u8>, number: u32, value: &str) {
+    if !value.is_empty() {
+        put_bytes(out, number, value.as_bytes());
+    }
+}
+
+fn put_opt_string(out: &mut Vec<u8>, number: u32, value: Option<&str>) {
+    if let Some(value) = value {
+        put_bytes(out, number, value.as_bytes());
+    }
+}
+
+fn put_doubles(out: &mut Vec<u8>, number: u32, values: &[f64]) {
+    if !values.is_empty() {
+        let packed: Vec<u8> = values.iter().flat_map(|v| v.to_le_bytes()).collect();
+        put_bytes(out, number, &packed);
+    }
+}
+
+fn put_message(out: &mut Vec<u8>, number: u32, write: impl FnOnce(&mut Vec<u8>)) {
+    let mut message = Vec::new();
+    write(&mut message);
+    put_bytes(out, number, &message);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metadata::{ComplexType, LogicalComplexType, LogicalScalarType, ScalarType};
+
+    fn sample() -> TileSetMetadata {
+        TileSetMetadata {
+            name: Some("demo".into()),
+            attribution: Some(String::new()),
+            min_zoom: Some(0),
+            max_zoom: Some(14),
+            bounds: vec![-180.0, -85.0, 180.0, 85.0],
+            center: vec![11.5, 48.1],
+            feature_tables: vec![FeatureTableSchema {
+                name: "roads".into(),
+                columns: vec![
+                    Column {
+                        name: "id".into(),
+                        nullable: true,
+                        scope: ColumnScope::Feature,
+                        kind: ColumnKind::Scalar(ScalarColumn {
+                            long_id: true,
+                            kind: ScalarKind::Logical(LogicalScalarType::Id),
+                        }),
+                    },
+                    Column {
+                        name: "geometry".into(),
+                        nullable: false,
+                        scope: ColumnScope::Feature,
+                        kind: ColumnKind::Complex(ComplexColumn {
+                            kind: ComplexKind::Physical(ComplexType::Geometry),
+                            children: Vec::new(),
+                        }),
+                    },
+                    Column {
+                        name: "flag".into(),
+                        nullable: false,
+                        scope: ColumnScope::Feature,
+                        kind: ColumnKind::Scalar(ScalarColumn {
+                            long_id: false,
+                            kind: ScalarKind::Physical(ScalarType::Boolean),
+                        }),
+                    },
+                    Column {
+                        name: "m".into(),
+                        nullable: false,
+                        scope: ColumnScope::Vertex,
+                        kind: ColumnKind::Complex(ComplexColumn {
+                            kind: ComplexKind::Logical(LogicalComplexType::RangeMap),
+                            children: vec![Field {
+                                name: None,
+                                nullable: Some(false),
+                                kind: FieldKind::Scalar(ScalarKind::Physical(ScalarType::Double)),
+                            }],
+                        }),
+                    },
+                    Column {
+                        name: "names".into(),
+                        nullable: true,
+                        scope: ColumnScope::Feature,
+                        kind: ColumnKind::Complex(ComplexColumn {
+                            kind: ComplexKind::Physical(ComplexType::Struct),
+                            children: vec![Field {
+                                name: Some("de".into()),
+                                nullable: Some(true),
+                                kind: FieldKind::Complex(ComplexColumn {
+                                    kind: ComplexKind::Logical(LogicalComplexType::Binary),
+                                    children: Vec::new(),
+                                }),
+                            }],
+                        }),
+                    },
+                ],
+            }],
+            ..TileSetMetadata::default()
+        }
+    }
+
+    #[test]
+    fn roundtrip() {
+        let meta = sample();
+        assert_eq!(TileSetMetadata::from_bytes(&meta.to_bytes()).unwrap(), meta);
+    }
+
+    #[test]
+    fn default_values_are_omitted() {
+        let meta = TileSetMetadata {
+            version: 0,
+            ..TileSetMetadata::default()
+        };
+        assert!(meta.to_bytes().is_empty());
+        assert_eq!(TileSetMetadata::from_bytes(&[]).unwrap(), meta);
+    }
+
+    #[test]
+    fn known_encoding() {
+        // version=1, featureTables { name="a", columns { name="b", scalarType { physicalType=BOOLEAN } } }
+        let bytes = [
+            0x08, 0x01, 0x12, 0x0C, 0x0A, 0x01, b'a', 0x12, 0x07, 0x0A, 0x01, b'b', 0x22, 0x02,
+            0x20, 0x00,
+        ];
+        let meta = TileSetMetadata::from_bytes(&bytes).unwrap();
+        assert_eq!(meta.version, 1);
+        let column = &meta.feature_tables[0].columns[0];
+        assert_eq!(column.name, "b");
+        assert_eq!(
+            column.kind,
+            ColumnKind::Scalar(ScalarColumn {
+                long_id: false,
+                kind: ScalarKind::Physical(ScalarType::Boolean),
+            })
+        );
+        assert_eq!(meta.to_bytes(), bytes);
+    }
+
+    #[test]
+    fn skips_unknown_fields() {
+        // field 15 varint, field 16 fixed32, field 17 bytes, then version=2
+        let bytes = [
+            0x78, 0x05, 0x85, 0x01, 1, 2, 3, 4, 0x8A, 0x01, 0x01, 0xFF, 0x08, 0x02,
+        ];
+        assert_eq!(TileSetMetadata::from_bytes(&bytes).unwrap().version, 2);
+    }
+
+    #[test]
+    fn negative_int32() {
+        let meta = TileSetMetadata {
+            min_zoom: Some(-1),
+            ..TileSetMetadata::default()
+        };
+        let bytes = meta.to_bytes();
+        assert_eq!(bytes.len(), 2 + 11);
+        assert_eq!(TileSetMetadata::from_bytes(&bytes).unwrap(), meta);
+    }
+
+    #[test]
+    fn rejects_unknown_enum() {
+        // featureTables { columns { scalarType { physicalType=42 } } }
+        let bytes = [0x12, 0x06, 0x12, 0x04, 0x22, 0x02, 0x20, 42];
+        let err = TileSetMetadata::from_bytes(&bytes).unwrap_err();
+        assert!(matches!(err, UnknownTileSetMetadataEnum("ScalarType", 42)));
+    }
+
+    #[test]
+    fn rejects_column_without_type() {
+        let bytes = [0x12, 0x05, 0x12, 0x03, 0x0A, 0x01, b'x'];
+        let err = TileSetMetadata::from_bytes(&bytes).unwrap_err();
+        assert!(matches!(err, InvalidTileSetMetadata(_)));
+    }
+
+    #[test]
+    fn rejects_truncated_input() {
+        let bytes = sample().to_bytes();
+        assert!(TileSetMetadata::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+    }
+}
@@ -0,0 +1,659 @@
+use crate::MltError::IncompatibleColumnSchemas;
+use crate::metadata::{
+    Column, ColumnKind, ColumnScope, ComplexColumn, ComplexKind, ComplexType, FeatureTableSchema,
+    Field, FieldKind, LogicalComplexType, LogicalScalarType, ScalarColumn, ScalarKind, ScalarType,
+    TileSetMetadata,
+};
+use crate::{MltResult, PropKind, PropValue, TileFeature, TileLayer};
+
+/// Column name used for the feature ID.
+pub const ID_COLUMN_NAME: &str = "id";
+/// Column name used for the geometry.
+pub const GEOMETRY_COLUMN_NAME: &str = "geometry";
+
+impl TileSetMetadata {
+    /// Build metadata describing `layers`, merging the schemas of layers with the same name.
+    pub fn from_layers<'a>(layers: impl IntoIterator<Item = &'a TileLayer>) -> MltResult<Self> {
+        let mut meta = Self::default();
+        for layer in layers {
+            meta.add_layer(layer)?;
+        }
+        Ok(meta)
+    }
+
+    /// Merge the schema of one decoded layer into this metadata.
+    pub fn add_layer(&mut self, layer: &TileLayer) -> MltResult<()> {
+        self.merge_table(FeatureTableSchema::from_layer(layer))
+    }
+
+    /// Merge the feature tables of `other`, e.g. metadata collected from a different set of tiles.
+    ///
+    /// Columns missing from some tiles become nullable, and integer and float columns are
+    /// widened to a type that can hold values from both sides. Other type conflicts fail with
+    /// [`MltError::IncompatibleColumnSchemas`](crate::MltError::IncompatibleColumnSchemas).
+    pub fn merge(&mut self, other: Self) -> MltResult<()> {
+        for table in other.feature_tables {
+            self.merge_table(table)?;
+        }
+        Ok(())
+    }
+
+    /// Restore the logical property kinds of a decoded `layer` from the feature table with
+    /// the same name, see [`FeatureTableSchema::restore_logical_kinds`].
+    ///
+    /// Layers without a feature table are left unchanged.
+    pub fn restore_logical_kinds(&self, layer: &mut TileLayer) -> MltResult<()> {
+        match self.feature_tables.iter().find(|t| t.name == layer.name()) {
+            Some(table) => table.restore_logical_kinds(layer),
+            None => Ok(()),
+        }
+    }
+
+    fn merge_table(&mut self, table: FeatureTableSchema) -> MltResult<()> {
+        if let Some(existing) = self
+            .feature_tables
+            .iter_mut()
+            .find(|t| t.name == table.name)
+        {
+            existing.merge(table)
+        } else {
+            self.feature_tables.push(table);
+            Ok(())
+        }
+    }
+}
+
+impl FeatureTableSchema {
+    /// Describe the columns of a single decoded layer: ID (if any feature has one),
+    /// geometry, the vertex-scoped properties, then the feature-scoped ones in layer order.
+    #[must_use]
+    pub fn from_layer(layer: &TileLayer) -> Self {
+        let features = layer.features();
+        let mut columns =
+            Vec::with_capacity(layer.vertex_property_names.len() + layer.property_names.len() + 2);
+        if let Some(id) = id_column(features) {
+            columns.push(id);
+        }
+        columns.push(Column {
+            name: GEOMETRY_COLUMN_NAME.to_owned(),
+            nullable: false,
+            scope: ColumnScope::Feature,
+            kind: ColumnKind::Complex(ComplexColumn {
+                kind: ComplexKind::Physical(ComplexType::Geometry),
+                children: Vec::new(),
+            }),
+        });
+        for (name, &kind) in layer
+            .vertex_property_names
+            .iter()
+            .zip(&layer.vertex_property_kinds)
+        {
+            columns.push(Column {
+                name: name.clone(),
+                nullable: false,
+                scope: ColumnScope::Vertex,
+                kind: property_kind(kind),
+            });
+        }
+        for (idx, (name, &kind)) in layer
+            .property_names
+            .iter()
+            .zip(&layer.property_kinds)
+            .enumerate()
+        {
+            columns.push(Column {
+                name: name.clone(),
+                nullable: features.iter().any(|f| f.properties[idx].is_null()),
+                scope: ColumnScope::Feature,
+                kind: match kind {
+                    PropKind::RangeMap => range_map_kind(features, idx),
+                    kind => property_kind(kind),
+                },
+            });
+        }
+        Self {
+            name: layer.name().to_owned(),
+            columns,
+        }
+    }
+
+    /// Restore the logical kinds (`Date`, `Timestamp`, `JSON` and `Binary`) of the property
+    /// columns of a decoded `layer`, which only knows their physical storage.
+    ///
+    /// Columns missing from the layer are skipped. A column whose physical kind does not
+    /// match the schema fails with [`MltError::PropertyKindMismatch`](crate::MltError::PropertyKindMismatch).
+    pub fn restore_logical_kinds(&self, layer: &mut TileLayer) -> MltResult<()> {
+        for column in &self.columns {
+            if column.scope != ColumnScope::Feature {
+                continue;
+            }
+            let kind = match column.kind {
+                ColumnKind::Scalar(ScalarColumn { kind, .. }) => match kind {
+                    ScalarKind::Logical(LogicalScalarType::Date) => PropKind::Date,
+                    ScalarKind::Logical(LogicalScalarType::Timestamp) => PropKind::Timestamp,
+                    ScalarKind::Logical(LogicalScalarType::Json) => PropKind::Json,
+                    _ => continue,
+                },
+                ColumnKind::Complex(ComplexColumn {
+                    kind: ComplexKind::Logical(LogicalComplexType::Binary),
+                    ..
+                }) => PropKind::Binary,
+                ColumnKind::Complex(_) => continue,
+            };
+            if let Some(key) = layer.property_key(&column.name) {
+                layer.set_logical_kind(key, kind)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn merge(&mut self, other: Self) -> MltResult<()> {
+        // A column absent from some tiles has no value for their features.
+        for column in &mut self.columns {
+            if !other.columns.iter().any(|c| c.is_same_column(column)) {
+                column.nullable = true;
+            }
+        }
+        for column in other.columns {
+            match self.columns.iter_mut().find(|c| c.is_same_column(&column)) {
+                Some(existing) => existing.merge(&self.name, &column)?,
+                None => self.columns.push(Column {
+                    nullable: true,
+                    ..column
+                }),
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Column {
+    /// Whether both describe the same column of a feature table. The ID column and
+    /// vertex-scoped properties may share their name with a feature property.
+    fn is_same_column(&self, other: &Self) -> bool {
+        let is_id = |c: &Self| {
+            matches!(
+                c.kind,
+                ColumnKind::Scalar(ScalarColumn {
+                    kind: ScalarKind::Logical(LogicalScalarType::Id),
+                    ..
+                })
+            )
+        };
+        self.name == other.name && self.scope == other.scope && is_id(self) == is_id(other)
+    }
+
+    fn merge(&mut self, table: &str, other: &Self) -> MltResult<()> {
+        let kind = match (&self.kind, &other.kind) {
+            (ColumnKind::Scalar(a), ColumnKind::Scalar(b)) => merge_scalar_kind(a.kind, b.kind)
+                .map(|kind| {
+                    ColumnKind::Scalar(ScalarColumn {
+                        long_id: a.long_id || b.long_id,
+                        kind,
+                    })
+                }),
+            (a, b) => (a == b).then(|| a.clone()),
+        };
+        match kind {
+            Some(kind) if self.scope == other.scope => {
+                self.kind = kind;
+                self.nullable |= other.nullable;
+                Ok(())
+            }
+            _ => Err(IncompatibleColumnSchemas {
+                table: table.to_owned(),
+                column: self.name.clone(),
+            }),
+        }
+    }
+}
+
+fn id_column(features: &[TileFeature]) -> Option<Column> {
+    let mut has_id = false;
+    let mut nullable = false;
+    let mut long_id = false;
+    for feature in features {
+        match feature.id() {
+            Some(id) => {
+                has_id = true;
+                long_id |= u32::try_from(id).is_err();
+            }
+            None => nullable = true,
+        }
+    }
+    has_id.then(|| Column {
+        name: ID_COLUMN_NAME.to_owned(),
+        nullable,
+        scope: ColumnScope::Feature,
+        kind: ColumnKind::Scalar(ScalarColumn {
+            long_id,
+            kind: ScalarKind::Logical(LogicalScalarType::Id),
+        }),
+    })
+}
+
+/// Map a property kind to its schema type.
+///
+/// `Date`, `Timestamp` and `JSON` are stored by their physical type in the tile, so the
+/// schema is the only place keeping them, see [`FeatureTableSchema::restore_logical_kinds`].
+/// Nested map/list columns have no per-field schema and are described as a struct without
+/// children, and range maps without their value type, see [`range_map_kind`].
+fn property_kind(kind: PropKind) -> ColumnKind {
+    let scalar = |kind| {
+        ColumnKind::Scalar(ScalarColumn {
+            long_id: false,
+            kind,
+        })
+    };
+    let physical = |typ| scalar(ScalarKind::Physical(typ));
+    let logical = |typ| scalar(ScalarKind::Logical(typ));
+    match kind {
+        PropKind::Bool => physical(ScalarType::Boolean),
+        PropKind::I8 => physical(ScalarType::Int8),
+        PropKind::U8 => physical(ScalarType::UInt8),
+        PropKind::I32 => physical(ScalarType::Int32),
+        PropKind::U32 => physical(ScalarType::UInt32),
+        PropKind::I64 => physical(ScalarType::Int64),
+        PropKind::U64 => physical(ScalarType::UInt64),
+        PropKind::F32 => physical(ScalarType::Float),
+        PropKind::F64 => physical(ScalarType::Double),
+        PropKind::Str => physical(ScalarType::String),
+        PropKind::Date => logical(LogicalScalarType::Date),
+        PropKind::Timestamp => logical(LogicalScalarType::Timestamp),
+        PropKind::Json => logical(LogicalScalarType::Json),
+        PropKind::Nested => ColumnKind::Complex(ComplexColumn {
+            kind: ComplexKind::Physical(ComplexType::Struct),
+            children: Vec::new(),
+        }),
+        PropKind::Binary => ColumnKind::Complex(ComplexColumn {
+            kind: ComplexKind::Logical(LogicalComplexType::Binary),
+            children: Vec::new(),
+        }),
+        PropKind::RangeMap => ColumnKind::Complex(ComplexColumn {
+            kind: ComplexKind::Logical(LogicalComplexType::RangeMap),
+            children: Vec::new(),
+        }),
+    }
+}
+
+/// Describe the range map property `idx`, with the type of its values as the only child.
+///
+/// The value type is unknown, and left out, if no feature has any range.
+fn range_map_kind(features: &[TileFeature], idx: usize) -> ColumnKind {
+    let mut value_kind = None;
+    let mut nullable = false;
+    let values = features
+        .iter()
+        .filter_map(|f| match &f.properties[idx] {
+            PropValue::RangeMap(Some(entries)) => Some(entries),
+            _ => None,
+        })
+        .flatten();
+    for (_, _, value) in values {
+        value_kind = Some(value.kind());
+        nullable |= value.is_null();
+    }
+    let children = value_kind
+        .and_then(|kind| match property_kind(kind) {
+            ColumnKind::Scalar(scalar) => Some(Field {
+                name: None,
+                nullable: Some(nullable),
+                kind: FieldKind::Scalar(scalar.kind),
+            }),
+            ColumnKind::Complex(_) => None,
+        })
+        .into_iter()
+        .collect();
+    ColumnKind::Complex(ComplexColumn {
+        kind: ComplexKind::Logical(LogicalComplexType::RangeMap),
+        children,
+    })
+}
+
+fn merge_scalar_kind(a: ScalarKind, b: ScalarKind) -> Option<ScalarKind> {
+    match (a, b) {
+        _ if a == b => Some(a),
+        (ScalarKind::Physical(a), ScalarKind::Physical(b)) => widen(a, b).map(ScalarKind::Physical),
+        _ => None,
+    }
+}
+
+/// Signedness and bit width of an integer type.
+fn int_shape(typ: ScalarType) -> Option<(bool, u32)> {
+    match typ {
+        ScalarType::Int8 => Some((true, 8)),
+        ScalarType::UInt8 => Some((false, 8)),
+        ScalarType::Int32 => Some((true, 32)),
+        ScalarType::UInt32 => Some((false, 32)),
+        ScalarType::Int64 => Some((true, 64)),
+        ScalarType::UInt64 => Some((false, 64)),
+        _ => None,
+    }
+}
+
+fn int_type(signed: bool, bits: u32) -> ScalarType {
+    match (signed, bits) {
+        (true, ..=8) => ScalarType::Int8,
+        (false, ..=8) => ScalarType::UInt8,
+        (true, ..=32) => ScalarType::Int32,
+        (false, ..=32) => ScalarType::UInt32,
+        (true, _) => ScalarType::Int64,
+        (false, _) => ScalarType::UInt64,
+    }
+}
+
+/// Smallest numeric type holding the values of both `a` and `b`.
+///
+/// Mixed-sign integers need a wider signed type; if none exists, or if an integer meets
+/// a float, the result is `Double`.
+fn widen(a: ScalarType, b: ScalarType) -> Option<ScalarType> {
+    let is_numeric =
+        |t| int_shape(t).is_some() || matches!(t, ScalarType::Float | ScalarType::Double);
+    match (int_shape(a), int_shape(b)) {
+        (Some((sa, ba)), Some((sb, bb))) if sa == sb => Some(int_type(sa, ba.max(bb))),
+        (Some((sa, ba)), Some((_, bb))) => {
+            let (signed_bits, unsigned_bits) = if sa { (ba, bb) } else { (bb, ba) };
+            Some(if unsigned_bits < 64 {
+                int_type(true, signed_bits.max(unsigned_bits + 1))
+            } else {
+                ScalarType::Double
+            })
+        }
+        _ if is_numeric(a) && is_numeric(b) => Some(ScalarType::Double),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use geo_types::{Geometry, Point};
+    use rstest::rstest;
+
+    use serde_json::{Value, json};
+
+    use super::*;
+    use crate::encoder::EncoderConfig;
+    use crate::{Decoder, Layer, Parser, PropValue};
+
+    fn layer(
+        name: &str,
+        props: &[(&str, PropKind)],
+        rows: Vec<(Option<u64>, Vec<PropValue>)>,
+    ) -> TileLayer {
+        let mut layer = TileLayer::new(name, 4096).unwrap();
+        for (name, kind) in props {
+            layer.add_property(*name, *kind).unwrap();
+        }
+        for (id, properties) in rows {
+            let geometry = Geometry::Point(Point::new(1, 2));
+            let mut feature = match id {
+                Some(id) => TileFeature::with_id(geometry, id),
+                None => TileFeature::new(geometry),
+            };
+            feature.properties = properties;
+            layer.push_feature(feature).unwrap();
+        }
+        layer
+    }
+
+    fn scalar(meta: &TileSetMetadata, table: &str, column: &str) -> (bool, ScalarColumn) {
+        let table = meta
+            .feature_tables
+            .iter()
+            .find(|t| t.name == table)
+            .unwrap();
+        let column = table.columns.iter().find(|c| c.name == column).unwrap();
+        match column.kind {
+            ColumnKind::Scalar(s) => (column.nullable, s),
+            ColumnKind::Complex(_) => panic!("{} is not scalar", column.name),
+        }
+    }
+
+    #[test]
+    fn single_layer() {
+        let layer = layer(
+            "poi",
+            &[
+                ("name", PropKind::Str),
+                ("rank", PropKind::U32),
+                ("day", PropKind::Date),
+            ],
+            vec![
+                (
+                    Some(1),
+                    vec![
+                        PropValue::Str(Some("a".into())),
+                        PropValue::U32(Some(1)),
+                        PropValue::Date(Some(3)),
+                    ],
+                ),
+                (
+                    Some(u64::MAX),
+                    vec![
+                        PropValue::Str(None),
+                        PropValue::U32(Some(2)),
+                        PropValue::Date(None),
+                    ],
+                ),
+            ],
+        );
+        let meta = TileSetMetadata::from_layers([&layer]).unwrap();
+        let table = &meta.feature_tables[0];
+        assert_eq!(table.name, "poi");
+        let names: Vec<_> = table.columns.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["id", "geometry", "name", "rank", "day"]);
+
+        let (nullable, id) = scalar(&meta, "poi", "id");
+        assert!(!nullable);
+        assert!(id.long_id);
+        assert_eq!(id.kind, ScalarKind::Logical(LogicalScalarType::Id));
+        let (nullable, name) = scalar(&meta, "poi", "name");
+        assert!(nullable);
+        assert_eq!(name.kind, ScalarKind::Physical(ScalarType::String));
+        let (nullable, rank) = scalar(&meta, "poi", "rank");
+        assert!(!nullable);
+        assert_eq!(rank.kind, ScalarKind::Physical(ScalarType::UInt32));
+        assert_eq!(
+            scalar(&meta, "poi", "day").1.kind,
+            ScalarKind::Logical(LogicalScalarType::Date)
+        );
+    }
+
+    #[test]
+    fn layer_without_ids_has_no_id_column() {
+        let layer = layer("water", &[], vec![(None, vec![])]);
+        let meta = TileSetMetadata::from_layers([&layer]).unwrap();
+        let names: Vec<_> = meta.feature_tables[0]
+            .columns
+            .iter()
+            .map(|c| c.name.as_str())
+            .collect();
+        assert_eq!(names, ["geometry"]);
+    }
+
+    #[test]
+    fn vertex_columns_follow_geometry() {
+        let mut layer = layer(
+            "roads",
+            &[("lanes", PropKind::U8)],
+            vec![(None, vec![PropValue::U8(Some(2))])],
+        );
+        layer.add_vertex_property("speed", PropKind::F32).unwrap();
+        let meta = TileSetMetadata::from_layers([&layer]).unwrap();
+        let columns = &meta.feature_tables[0].columns;
+        let names: Vec<_> = columns.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["geometry", "speed", "lanes"]);
+        assert_eq!(columns[1].scope, ColumnScope::Vertex);
+        let (nullable, speed) = scalar(&meta, "roads", "speed");
+        assert!(!nullable);
+        assert_eq!(speed.kind, ScalarKind::Physical(ScalarType::Float));
+    }
+
+    #[test]
+    fn merges_tables_across_tiles() {
+        let a = layer(
+            "roads",
+            &[("lanes", PropKind::U8)],
+            vec![(Some(1), vec![PropValue::U8(Some(2))])],
+        );
+        let b = layer(
+            "roads",
+            &[("name", PropKind::Str)],
+            vec![(None, vec![PropValue::Str(Some("x".into()))])],
+        );
+        let c = layer("water", &[], vec![(None, vec![])]);
+        let meta = TileSetMetadata::from_layers([&a, &b, &c]).unwrap();
+
+        let tables: Vec<_> = meta
+            .feature_tables
+            .iter()
+            .map(|t| t.name.as_str())
+            .collect();
+        assert_eq!(tables, ["roads", "water"]);
+        let names: Vec<_> = meta.feature_tables[0]
+            .columns
+            .iter()
+            .map(|c| c.name.as_str())
+            .collect();
+        assert_eq!(names, ["id", "geometry", "lanes", "name"]);
+        assert!(scalar(&meta, "roads", "id").0);
+        assert!(scalar(&meta, "roads", "lanes").0);
+        assert!(scalar(&meta, "roads", "name").0);
+    }
+
+    #[rstest]
+    #[case::same(ScalarType::Int32, ScalarType::Int32, Some(ScalarType::Int32))]
+    #[case::unsigned(ScalarType::UInt8, ScalarType::UInt32, Some(ScalarType::UInt32))]
+    #[case::mixed_sign(ScalarType::Int8, ScalarType::UInt8, Some(ScalarType::Int32))]
+    #[case::mixed_sign_wide(ScalarType::Int32, ScalarType::UInt32, Some(ScalarType::Int64))]
+    #[case::mixed_sign_u64(ScalarType::Int8, ScalarType::UInt64, Some(ScalarType::Double))]
+    #[case::int_float(ScalarType::Int64, ScalarType::Float, Some(ScalarType::Double))]
+    #[case::floats(ScalarType::Float, ScalarType::Double, Some(ScalarType::Double))]
+    #[case::string_int(ScalarType::String, ScalarType::Int64, None)]
+    #[case::bool_float(ScalarType::Boolean, ScalarType::Float, None)]
+    fn widening(
+        #[case] a: ScalarType,
+        #[case] b: ScalarType,
+        #[case] expected: Option<ScalarType>,
+    ) {
+        let a = ScalarKind::Physical(a);
+        let b = ScalarKind::Physical(b);
+        let expected = expected.map(ScalarKind::Physical);
+        assert_eq!(merge_scalar_kind(a, b), expected);
+        assert_eq!(merge_scalar_kind(b, a), expected);
+    }
+
+    #[test]
+    fn id_property_is_not_the_id_column() {
+        let rows = || vec![(Some(1), vec![PropValue::I64(Some(7))])];
+        let a = layer("water", &[("id", PropKind::I64)], rows());
+        let b = layer("water", &[("id", PropKind::I64)], rows());
+        let meta = TileSetMetadata::from_layers([&a, &b]).unwrap();
+
+        let kinds: Vec<_> = meta.feature_tables[0]
+            .columns
+            .iter()
+            .filter(|c| c.name == "id")
+            .map(|c| &c.kind)
+            .collect();
+        assert_eq!(
+            kinds,
+            [
+                &ColumnKind::Scalar(ScalarColumn {
+                    long_id: false,
+                    kind: ScalarKind::Logical(LogicalScalarType::Id),
+                }),
+                &ColumnKind::Scalar(ScalarColumn {
+                    long_id: false,
+                    kind: ScalarKind::Physical(ScalarType::Int64),
+                }),
+            ]
+        );
+    }
+
+    #[test]
+    fn incompatible_columns_fail() {
+        let a = layer(
+            "poi",
+            &[("v", PropKind::Str)],
+            vec![(None, vec![PropValue::Str(None)])],
+        );
+        let b = layer(
+            "poi",
+            &[("v", PropKind::Bool)],
+            vec![(None, vec![PropValue::Bool(None)])],
+        );
+        let err = TileSetMetadata::from_layers([&a, &b]).unwrap_err();
+        assert!(matches!(err, IncompatibleColumnSchemas { column, .. } if column == "v"));
+    }
+
+    #[test]
+    fn logical_kinds_survive_encoding() {
+        let layer = layer(
+            "events",
+            &[
+                ("day", PropKind::Date),
+                ("at", PropKind::Timestamp),
+                ("extra", PropKind::Json),
+                ("count", PropKind::I32),
+            ],
+            vec![(
+                None,
+                vec![
+                    PropValue::Date(Some(19_782)),
+                    PropValue::Timestamp(Some(0)),
+                    PropValue::Json(Some(r#"{"a":1}"#.into())),
+                    PropValue::I32(Some(5)),
+                ],
+            )],
+        );
+        let meta = TileSetMetadata::from_bytes(
+            &TileSetMetadata::from_layers([&layer]).unwrap().to_bytes(),
+        )
+        .unwrap();
+
+        let bytes = layer.encode(EncoderConfig::default()).unwrap();
+        let mut layers = Parser::default().parse_layers(&bytes).unwrap();
+        let Layer::Tag01(decoded) = layers.remove(0) else {
+            panic!("expected a Tag01 layer");
+        };
+        let mut decoded = decoded.into_tile(&mut Decoder::default()).unwrap();
+        assert_eq!(decoded.property_kinds[0], PropKind::I32);
+        meta.restore_logical_kinds(&mut decoded).unwrap();
+        assert_eq!(
+            decoded.property_kinds,
+            [
+                PropKind::Date,
+                PropKind::Timestamp,
+                PropKind::Json,
+                PropKind::I32
+            ]
+        );
+
+        let geojson: Vec<_> = decoded.features()[0]
+            .properties
+            .iter()
+            .map(|v| Value::from(v.as_value_ref().unwrap()))
+            .collect();
+        assert_eq!(
+            geojson,
+            [
+                json!("2024-02-29"),
+                json!("1970-01-01T00:00:00.000Z"),
+                json!({"a": 1}),
+                json!(5),
+            ]
+        );
+    }
+
+    #[test]
+    fn merged_metadata_roundtrips() {
+        let a = layer(
+            "poi",
+            &[("v", PropKind::Binary)],
+            vec![(Some(7), vec![PropValue::Binary(Some(vec![1]))])],
+        );
+        let meta = TileSetMetadata::from_layers([&a]).unwrap();
+        assert_eq!(TileSetMetadata::from_bytes(&meta.to_bytes()).unwrap(), meta);
+    }
+}
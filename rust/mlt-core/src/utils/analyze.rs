@@ -3,7 +3,10 @@ use std::ops::Deref;
 use enum_dispatch::enum_dispatch;
 
 use crate::LazyParsed;
-use crate::decoder::{ParsedProperty, ParsedScalar, ParsedSharedDict, ParsedStrings, StreamMeta};
+use crate::decoder::{
+    ParsedNested, ParsedProperty, ParsedRangeMap, ParsedScalar, ParsedSharedDict, ParsedStrings,
+    ParsedStruct, StreamMeta,
+};
 
 /// What to calculate with [`Analyze::collect_statistic`].
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -1,13 +1,15 @@
 use std::fs;
 use std::path::Path;
+use std::str::FromStr as _;
 
 use mlt_core::geojson::FeatureCollection;
 use mlt_core::test_helpers::{dec, parser};
 use test_each_file::test_each_path;
 
 test_each_path! { for ["mlt"] in "../test/expected/tag0x01" as geojson => geojson_test }
+test_each_path! { for ["mlt", "json"] in "../test/synthetic/0x02" as geojson_0x02 => geojson_matches_json }
 
-fn geojson_test([mlt]: [&Path; 1]) {
+fn decode(mlt: &Path) -> FeatureCollection {
     let buffer = fs::read(mlt).unwrap();
     let mut p = parser();
     let layers = p.parse_layers(&buffer).unwrap();
@@ -16,6 +18,21 @@ fn geojson_test([mlt]: [&Path; 1]) {
     let mut d = dec();
     let decoded = d.decode_all(layers).unwrap();
     assert!(d.consumed() > 0);
-    let fc = FeatureCollection::from_layers(decoded).unwrap();
+    FeatureCollection::from_layers(decoded).unwrap()
+}
+
+fn geojson_test([mlt]: [&Path; 1]) {
+    let fc = decode(mlt);
     assert!(!fc.features.is_empty(), "expected at least one feature");
 }
+
+fn geojson_matches_json([mlt, json]: [&Path; 2]) {
+    let actual = decode(mlt);
+    let expected = FeatureCollection::from_str(&fs::read_to_string(json).unwrap()).unwrap();
+    assert!(
+        actual.equals(&expected).unwrap(),
+        "decoded {} differs from {}",
+        mlt.display(),
+        json.display()
+    );
+}
@@ -0,0 +1,23 @@
+#ifndef MltEffort_D_H
+#define MltEffort_D_H
+
+#include "diplomat_runtime.h"
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdio.h>
+
+typedef enum MltEffort {
+    MltEffort_Fast = 0,
+    MltEffort_Default = 1,
+    MltEffort_Max = 2,
+} MltEffort;
+
+typedef struct MltEffort_option {
+    union {
+        MltEffort ok;
+    };
+    bool is_ok;
+} MltEffort_option;
+
+#endif // MltEffort_D_H
@@ -0,0 +1,14 @@
+#ifndef MltEffort_H
+#define MltEffort_H
+
+#include "diplomat_runtime.h"
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdio.h>
+
+#include "MltEffort.d.h"
+
+// No Content
+
+#endif // MltEffort_H
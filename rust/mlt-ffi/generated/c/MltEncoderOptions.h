@@ -7,6 +7,8 @@
 #include <stdint.h>
 #include <stdio.h>
 
+#include "MltEffort.d.h"
+
 #include "MltEncoderOptions.d.h"
 
 MltEncoderOptions* MltEncoderOptions_new(void);
@@ -25,6 +27,12 @@ void MltEncoderOptions_set_allow_fastpfor(MltEncoderOptions* self, bool enabled)
 
 void MltEncoderOptions_set_allow_shared_dict(MltEncoderOptions* self, bool enabled);
 
+void MltEncoderOptions_set_effort(MltEncoderOptions* self, MltEffort effort);
+
+void MltEncoderOptions_set_time_budget_ms(MltEncoderOptions* self, uint64_t ms);
+
+void MltEncoderOptions_clear_time_budget(MltEncoderOptions* self);
+
 void MltEncoderOptions_destroy(MltEncoderOptions* self);
 
 #endif // MltEncoderOptions_H
@@ -0,0 +1,68 @@
+#ifndef MltEffort_D_HPP
+#define MltEffort_D_HPP
+
+#include "diplomat_runtime.hpp"
+#include <cstdlib>
+#include <functional>
+#include <memory>
+#include <optional>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdio.h>
+
+namespace diplomat {
+namespace capi {
+enum MltEffort {
+    MltEffort_Fast = 0,
+    MltEffort_Default = 1,
+    MltEffort_Max = 2,
+};
+
+typedef struct MltEffort_option {
+    union {
+        MltEffort ok;
+    };
+    bool is_ok;
+} MltEffort_option;
+} // namespace capi
+} // namespace diplomat
+
+/**
+ * How hard the encoder searches for a smaller encoding.
+ */
+class MltEffort {
+public:
+    enum Value {
+        /**
+         * Take a single candidate for each choice, picked on a sample of the data where possible.
+         */
+        Fast = 0,
+        /**
+         * Try the candidates that did best on a sample of the data.
+         */
+        Default = 1,
+        /**
+         * Try every candidate, even those that rarely help.
+         */
+        Max = 2,
+    };
+
+    MltEffort()
+        : value(Value::Fast) {}
+
+    // Implicit conversions between enum and ::Value
+    constexpr MltEffort(Value v)
+        : value(v) {}
+    constexpr operator Value() const { return value; }
+    // Prevent usage as boolean value
+    explicit operator bool() const = delete;
+
+    inline diplomat::capi::MltEffort AsFFI() const;
+    inline static MltEffort FromFFI(diplomat::capi::MltEffort c_enum);
+
+private:
+    Value value;
+};
+
+#endif // MltEffort_D_HPP
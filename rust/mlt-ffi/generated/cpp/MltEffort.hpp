@@ -0,0 +1,34 @@
+#ifndef MltEffort_HPP
+#define MltEffort_HPP
+
+#include "MltEffort.d.hpp"
+
+#include "diplomat_runtime.hpp"
+#include <cstdlib>
+#include <functional>
+#include <memory>
+#include <optional>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdio.h>
+
+namespace diplomat {
+namespace capi {} // namespace capi
+} // namespace diplomat
+
+inline diplomat::capi::MltEffort MltEffort::AsFFI() const {
+    return static_cast<diplomat::capi::MltEffort>(value);
+}
+
+inline MltEffort MltEffort::FromFFI(diplomat::capi::MltEffort c_enum) {
+    switch (c_enum) {
+        case diplomat::capi::MltEffort_Fast:
+        case diplomat::capi::MltEffort_Default:
+        case diplomat::capi::MltEffort_Max:
+            return static_cast<MltEffort::Value>(c_enum);
+        default:
+            std::abort();
+    }
+}
+#endif // MltEffort_HPP
@@ -11,6 +11,8 @@
 #include <stdint.h>
 #include <stdio.h>
 
+class MltEffort;
+
 namespace diplomat {
 namespace capi {
 struct MltEncoderOptions;
@@ -68,6 +70,22 @@ public:
      */
     inline void set_allow_shared_dict(bool enabled);
 
+    /**
+     * Set how hard the encoder searches for a smaller encoding.
+     */
+    inline void set_effort(MltEffort effort);
+
+    /**
+     * Limit the time spent on optional encoding trials per tile, in
+     * milliseconds. Once exceeded, the best encoding found so far is used.
+     */
+    inline void set_time_budget_ms(uint64_t ms);
+
+    /**
+     * Remove the per-tile time budget.
+     */
+    inline void clear_time_budget();
+
     inline const diplomat::capi::MltEncoderOptions* AsFFI() const;
     inline diplomat::capi::MltEncoderOptions* AsFFI();
     inline static const MltEncoderOptions* FromFFI(const diplomat::capi::MltEncoderOptions* ptr);
@@ -12,6 +12,7 @@
 #include <stddef.h>
 #include <stdint.h>
 #include <stdio.h>
+#include "MltEffort.hpp"
 
 namespace diplomat {
 namespace capi {
@@ -33,6 +34,12 @@ void MltEncoderOptions_set_allow_fastpfor(diplomat::capi::MltEncoderOptions* sel
 
 void MltEncoderOptions_set_allow_shared_dict(diplomat::capi::MltEncoderOptions* self, bool enabled);
 
+void MltEncoderOptions_set_effort(diplomat::capi::MltEncoderOptions* self, diplomat::capi::MltEffort effort);
+
+void MltEncoderOptions_set_time_budget_ms(diplomat::capi::MltEncoderOptions* self, uint64_t ms);
+
+void MltEncoderOptions_clear_time_budget(diplomat::capi::MltEncoderOptions* self);
+
 void MltEncoderOptions_destroy(MltEncoderOptions* self);
 
 } // extern "C"
@@ -72,6 +79,18 @@ inline void MltEncoderOptions::set_allow_shared_dict(bool enabled) {
     diplomat::capi::MltEncoderOptions_set_allow_shared_dict(this->AsFFI(), enabled);
 }
 
+inline void MltEncoderOptions::set_effort(MltEffort effort) {
+    diplomat::capi::MltEncoderOptions_set_effort(this->AsFFI(), effort.AsFFI());
+}
+
+inline void MltEncoderOptions::set_time_budget_ms(uint64_t ms) {
+    diplomat::capi::MltEncoderOptions_set_time_budget_ms(this->AsFFI(), ms);
+}
+
+inline void MltEncoderOptions::clear_time_budget() {
+    diplomat::capi::MltEncoderOptions_clear_time_budget(this->AsFFI());
+}
+
 inline const diplomat::capi::MltEncoderOptions* MltEncoderOptions::AsFFI() const {
     return reinterpret_cast<const diplomat::capi::MltEncoderOptions*>(this);
 }
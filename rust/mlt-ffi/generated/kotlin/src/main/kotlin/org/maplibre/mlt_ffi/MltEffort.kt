@@ -0,0 +1,29 @@
+package org.maplibre.mlt_ffi
+
+import com.sun.jna.Callback
+import com.sun.jna.Library
+import com.sun.jna.Native
+import com.sun.jna.Pointer
+import com.sun.jna.Structure
+
+internal interface MltEffortLib : Library
+
+/** How hard the encoder searches for a smaller encoding.
+*/
+enum class MltEffort {
+    Fast,
+    Default,
+    Max,
+    ;
+
+    fun toNative(): Int = this.ordinal
+
+    companion object {
+        internal val libClass: Class<MltEffortLib> = MltEffortLib::class.java
+        internal val lib: MltEffortLib = Native.load("mlt_ffi", libClass)
+
+        fun fromNative(native: Int): MltEffort = MltEffort.entries[native]
+
+        fun default(): MltEffort = Fast
+    }
+}
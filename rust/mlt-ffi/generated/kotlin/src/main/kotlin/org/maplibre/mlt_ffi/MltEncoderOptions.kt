@@ -44,6 +44,18 @@ internal interface MltEncoderOptionsLib : Library {
         handle: Pointer,
         enabled: Boolean,
     ): Unit
+
+    fun MltEncoderOptions_set_effort(
+        handle: Pointer,
+        effort: Int,
+    ): Unit
+
+    fun MltEncoderOptions_set_time_budget_ms(
+        handle: Pointer,
+        ms: Long,
+    ): Unit
+
+    fun MltEncoderOptions_clear_time_budget(handle: Pointer): Unit
 }
 
 /** Encoder options controlling which optimisations are attempted for
@@ -137,4 +149,23 @@ class MltEncoderOptions internal constructor(
     fun setAllowSharedDict(enabled: Boolean) {
         val returnVal = lib.MltEncoderOptions_set_allow_shared_dict(handle, enabled)
     }
+
+    /** Set how hard the encoder searches for a smaller encoding.
+     */
+    fun setEffort(effort: MltEffort) {
+        val returnVal = lib.MltEncoderOptions_set_effort(handle, effort.toNative())
+    }
+
+    /** Limit the time spent on optional encoding trials per tile, in
+     *milliseconds. Once exceeded, the best encoding found so far is used.
+     */
+    fun setTimeBudgetMs(ms: ULong) {
+        val returnVal = lib.MltEncoderOptions_set_time_budget_ms(handle, ms.toLong())
+    }
+
+    /** Remove the per-tile time budget.
+     */
+    fun clearTimeBudget() {
+        val returnVal = lib.MltEncoderOptions_clear_time_budget(handle)
+    }
 }
@@ -8,7 +8,9 @@
 )]
 #[diplomat::bridge]
 mod ffi {
-    use mlt_core::encoder::EncoderConfig;
+    use std::time::Duration;
+
+    use mlt_core::encoder::{Effort, EncoderConfig};
     use mlt_core::mvt::{mvt_to_tile_layers, tile_layers_to_mvt};
     use mlt_core::{Decoder, Layer, Parser};
 
@@ -46,6 +48,17 @@ mod ffi {
         }
     }
 
+    /// How hard the encoder searches for a smaller encoding.
+    #[expect(dead_code, reason = "variants are only constructed by foreign callers")]
+    pub enum MltEffort {
+        /// Take a single candidate for each choice, picked on a sample of the data where possible.
+        Fast,
+        /// Try the candidates that did best on a sample of the data.
+        Default,
+        /// Try every candidate, even those that rarely help.
+        Max,
+    }
+
     /// Encoder options controlling which optimisations are attempted for
     /// MVT -> MLT conversion.
     ///
@@ -65,37 +78,60 @@ mod ffi {
 
         /// Generate tessellation data for polygons and multi-polygons.
         pub fn set_tessellate(&mut self, enabled: bool) {
-            self.0 = self.0.with_tessellation(enabled);
+            self.0 = self.0.clone().with_tessellation(enabled);
         }
 
         /// Try sorting features by the Z-order (Morton) curve index.
         pub fn set_attempt_spatial_morton_sort(&mut self, enabled: bool) {
-            self.0 = self.0.with_spatial_morton_sort(enabled);
+            self.0 = self.0.clone().with_spatial_morton_sort(enabled);
         }
 
         /// Try sorting features by the Hilbert curve index.
         pub fn set_attempt_spatial_hilbert_sort(&mut self, enabled: bool) {
-            self.0 = self.0.with_spatial_hilbert_sort(enabled);
+            self.0 = self.0.clone().with_spatial_hilbert_sort(enabled);
         }
 
         /// Try sorting features by their feature ID in ascending order.
         pub fn set_attempt_id_sort(&mut self, enabled: bool) {
-            self.0 = self.0.with_id_sort(enabled);
+            self.0 = self.0.clone().with_id_sort(enabled);
         }
 
         /// Allow FSST string compression.
         pub fn set_allow_fsst(&mut self, enabled: bool) {
-            self.0 = self.0.with_fsst(enabled);
+            self.0 = self.0.clone().with_fsst(enabled);
         }
 
         /// Allow `FastPFOR` integer compression.
         pub fn set_allow_fastpfor(&mut self, enabled: bool) {
-            self.0 = self.0.with_fastpfor(enabled);
+            self.0 = self.0.clone().with_fastpfor(enabled);
         }
 
         /// Allow string grouping into shared dictionaries.
         pub fn set_allow_shared_dict(&mut self, enabled: bool) {
-            self.0 = self.0.with_shared_dict(enabled);
+            self.0 = self.0.clone().with_shared_dict(enabled);
+        }
+
+        /// Set how hard the encoder searches for a smaller encoding.
+        pub fn set_effort(&mut self, effort: MltEffort) {
+            self.0 = self.0.clone().with_effort(match effort {
+                MltEffort::Fast => Effort::Fast,
+                MltEffort::Default => Effort::Default,
+                MltEffort::Max => Effort::Max,
+            });
+        }
+
+        /// Limit the time spent on optional encoding trials per tile, in
+        /// milliseconds. Once exceeded, the best encoding found so far is used.
+        pub fn set_time_budget_ms(&mut self, ms: u64) {
+            self.0 = self
+                .0
+                .clone()
+                .with_time_budget(Some(Duration::from_millis(ms)));
+        }
+
+        /// Remove the per-tile time budget.
+        pub fn clear_time_budget(&mut self) {
+            self.0 = self.0.clone().with_time_budget(None);
         }
     }
 
@@ -129,10 +165,11 @@ mod ffi {
             options: &MltEncoderOptions,
         ) -> Result<Box<MltBuffer>, ConvertError> {
             let mut out = Vec::new();
+            let cfg = options.0.clone().start_budget();
             let layers = mvt_to_tile_layers(mvt).map_err(|_| ConvertError::EncodingFailed)?;
             for tile in layers {
                 let encoded_tile = tile
-                    .encode(options.0)
+                    .encode(cfg.clone())
                     .map_err(|_| ConvertError::EncodingFailed)?;
                 out.extend_from_slice(&encoded_tile);
             }
@@ -26,6 +26,13 @@ class MltFeature:
     def wkb(self) -> bytes: ...
     @property
     def properties(self) -> dict: ...
+    @property
+    def triangles(self) -> typing.Optional[memoryview]:
+        r"""
+        Pre-computed triangulation as a `memoryview` of `uint32`, three indices per triangle
+        into the layer's `vertices`.
+        `None` unless this is a polygon feature of a tessellated layer.
+        """
     def __repr__(self) -> builtins.str: ...
 
 @typing.final
@@ -39,9 +46,30 @@ class MltLayer:
     def extent(self) -> builtins.int: ...
     @property
     def features(self) -> builtins.list[MltFeature]: ...
+    @property
+    def vertices(self) -> memoryview:
+        r"""
+        Flat `[x0, y0, x1, y1, …]` vertex buffer in tile coordinates, as a `memoryview` of `int32`.
+
+        Triangle indices address this buffer: vertex `n` is `(vertices[2n], vertices[2n+1])`.
+        """
+    @property
+    def triangle_offsets(self) -> typing.Optional[memoryview]:
+        r"""
+        Cumulative triangle counts per feature as a `memoryview` of `uint32`,
+        or `None` if the layer was not tessellated.
+
+        Feature `i` owns triangles `triangle_offsets[i]` to `triangle_offsets[i+1]`.
+        """
+    @property
+    def triangle_indices(self) -> typing.Optional[memoryview]:
+        r"""
+        Three vertex indices per triangle as a `memoryview` of `uint32`,
+        or `None` if the layer was not tessellated.
+        """
     def __repr__(self) -> builtins.str: ...
 
-def decode_mlt(data: bytes, z: typing.Optional[builtins.int] = None, x: typing.Optional[builtins.int] = None, y: typing.Optional[builtins.int] = None, tms: builtins.bool = True) -> builtins.list[MltLayer]:
+def decode_mlt(data: bytes, z: typing.Optional[builtins.int] = None, x: typing.Optional[builtins.int] = None, y: typing.Optional[builtins.int] = None, tms: builtins.bool = True, columns: typing.Optional[typing.Sequence[builtins.str]] = None) -> builtins.list[MltLayer]:
     r"""
     Decode an MLT binary blob into a list of `MltLayer` objects.
 
@@ -52,6 +80,9 @@ def decode_mlt(data: bytes, z: typing.Optional[builtins.int] = None, x: typing.O
     `tms`: when True (the default), treat `y` as TMS convention (y=0 at south,
     used by OpenMapTiles / MBTiles). Set to False for XYZ / slippy-map tiles
     (y=0 at north, e.g. OSM raster tiles).
+
+    `columns`: when given, only the properties with these names are decoded;
+    geometry and ids are always decoded.
     """
 
 def decode_mlt_to_geojson(data: bytes) -> builtins.str:
@@ -59,7 +90,7 @@ def decode_mlt_to_geojson(data: bytes) -> builtins.str:
     Decode an MLT binary blob and return GeoJSON as a string.
     """
 
-def encode_geojson(geojson: typing.Mapping[builtins.str, builtins.object], name: builtins.str, extent: builtins.int = 4096, *, tessellate: builtins.bool = False, sort: typing.Literal['all', 'auto', 'morton', 'hilbert', 'id', 'none'] = "auto", shared_dict: builtins.bool = True, fsst: builtins.bool = True, fastpfor: builtins.bool = True) -> bytes:
+def encode_geojson(geojson: typing.Mapping[builtins.str, builtins.object], name: builtins.str, extent: builtins.int = 4096, *, tessellate: builtins.bool = False, sort: typing.Literal['all', 'auto', 'morton', 'hilbert', 'id', 'none'] = "auto", shared_dict: builtins.bool = True, fsst: builtins.bool = True, fastpfor: builtins.bool = True, buffer: typing.Optional[builtins.int] = None, effort: typing.Literal['fast', 'default', 'max'] = "default", time_budget_ms: typing.Optional[builtins.int] = None) -> bytes:
     r"""
     Encode a GeoJSON `FeatureCollection` into MLT bytes.
 
@@ -74,10 +105,13 @@ def encode_geojson(geojson: typing.Mapping[builtins.str, builtins.object], name:
     `shared_dict` allows grouping strings into shared dictionaries.
     `fsst` allows FSST string compression.
     `fastpfor` allows FastPFOR integer compression.
+    `buffer` clips geometries to this many units beyond the extent, dropping features left empty.
+    `effort` is `fast`, `default` or `max`: how hard the encoder searches for the smallest output.
+    `time_budget_ms` stops the search after this many milliseconds per tile, keeping the best result so far.
     See the module docs.
     """
 
-def encode_mvt(data: bytes, *, tessellate: builtins.bool = False, sort: typing.Literal['all', 'auto', 'morton', 'hilbert', 'id', 'none'] = "auto", shared_dict: builtins.bool = True, fsst: builtins.bool = True, fastpfor: builtins.bool = True) -> bytes:
+def encode_mvt(data: bytes, *, tessellate: builtins.bool = False, sort: typing.Literal['all', 'auto', 'morton', 'hilbert', 'id', 'none'] = "auto", shared_dict: builtins.bool = True, fsst: builtins.bool = True, fastpfor: builtins.bool = True, buffer: typing.Optional[builtins.int] = None, effort: typing.Literal['fast', 'default', 'max'] = "default", time_budget_ms: typing.Optional[builtins.int] = None) -> bytes:
     r"""
     Encode an entire MVT tile to MLT using default encoding options.
 
@@ -91,6 +125,9 @@ def encode_mvt(data: bytes, *, tessellate: builtins.bool = False, sort: typing.L
     `shared_dict` allows grouping strings into shared dictionaries.
     `fsst` allows FSST string compression.
     `fastpfor` allows FastPFOR integer compression.
+    `buffer` clips geometries to this many units beyond the extent, dropping features left empty.
+    `effort` is `fast`, `default` or `max`: how hard the encoder searches for the smallest output.
+    `time_budget_ms` stops the search after this many milliseconds per tile, keeping the best result so far.
     """
 
 def list_layers(data: bytes) -> builtins.list[builtins.str]:
@@ -34,10 +34,13 @@ use super::shared::{encoder_config, val_err};
 /// `shared_dict` allows grouping strings into shared dictionaries.
 /// `fsst` allows FSST string compression.
 /// `fastpfor` allows FastPFOR integer compression.
+/// `buffer` clips geometries to this many units beyond the extent, dropping features left empty.
+/// `effort` is `fast`, `default` or `max`: how hard the encoder searches for the smallest output.
+/// `time_budget_ms` stops the search after this many milliseconds per tile, keeping the best result so far.
 /// See the module docs.
 #[gen_stub_pyfunction]
 #[pyfunction]
-#[pyo3(signature = (geojson, name, extent=4096, *, tessellate=false, sort="auto", shared_dict=true, fsst=true, fastpfor=true))]
+#[pyo3(signature = (geojson, name, extent=4096, *, tessellate=false, sort="auto", shared_dict=true, fsst=true, fastpfor=true, buffer=None, effort="default", time_budget_ms=None))]
 #[expect(
     clippy::too_many_arguments,
     reason = "argument list mirrors the intentional Python keyword-argument API"
@@ -56,6 +59,9 @@ pub fn encode_geojson(
     shared_dict: bool,
     fsst: bool,
     fastpfor: bool,
+    buffer: Option<u32>,
+    #[gen_stub(override_type(type_repr = "typing.Literal['fast', 'default', 'max']"))] effort: &str,
+    time_budget_ms: Option<u64>,
 ) -> PyResult<Py<PyBytes>> {
     if name.is_empty() {
         return Err(val_err("'name' must be non-empty"));
@@ -73,7 +79,16 @@ pub fn encode_geojson(
     }
 
     let tile = build_layer(fc, name, extent)?;
-    let cfg = encoder_config(tessellate, sort, shared_dict, fsst, fastpfor)?;
+    let cfg = encoder_config(
+        tessellate,
+        sort,
+        shared_dict,
+        fsst,
+        fastpfor,
+        buffer,
+        effort,
+        time_budget_ms,
+    )?;
     // The steps above read Python input, so they keep the GIL; release it for the pure-Rust encode.
     let bytes = py
         .detach(|| tile.encode(cfg))
@@ -21,9 +21,16 @@ use super::shared::encoder_config;
 /// `shared_dict` allows grouping strings into shared dictionaries.
 /// `fsst` allows FSST string compression.
 /// `fastpfor` allows FastPFOR integer compression.
+/// `buffer` clips geometries to this many units beyond the extent, dropping features left empty.
+/// `effort` is `fast`, `default` or `max`: how hard the encoder searches for the smallest output.
+/// `time_budget_ms` stops the search after this many milliseconds per tile, keeping the best result so far.
 #[gen_stub_pyfunction]
 #[pyfunction]
-#[pyo3(signature = (data, *, tessellate=false, sort="auto", shared_dict=true, fsst=true, fastpfor=true))]
+#[pyo3(signature = (data, *, tessellate=false, sort="auto", shared_dict=true, fsst=true, fastpfor=true, buffer=None, effort="default", time_budget_ms=None))]
+#[expect(
+    clippy::too_many_arguments,
+    reason = "argument list mirrors the intentional Python keyword-argument API"
+)]
 pub fn encode_mvt(
     py: Python<'_>,
     #[gen_stub(override_type(type_repr = "bytes"))] data: &[u8],
@@ -35,14 +42,27 @@ pub fn encode_mvt(
     shared_dict: bool,
     fsst: bool,
     fastpfor: bool,
+    buffer: Option<u32>,
+    #[gen_stub(override_type(type_repr = "typing.Literal['fast', 'default', 'max']"))] effort: &str,
+    time_budget_ms: Option<u64>,
 ) -> PyResult<Py<PyBytes>> {
-    let cfg = encoder_config(tessellate, sort, shared_dict, fsst, fastpfor)?;
+    let cfg = encoder_config(
+        tessellate,
+        sort,
+        shared_dict,
+        fsst,
+        fastpfor,
+        buffer,
+        effort,
+        time_budget_ms,
+    )?;
     let bytes = py
         .detach(|| -> MltResult<Vec<u8>> {
             let data = data.to_vec();
+            let cfg = cfg.start_budget();
             let mut out = Vec::new();
             for tile in mvt_to_tile_layers(data)? {
-                out.extend_from_slice(&tile.encode(cfg)?);
+                out.extend_from_slice(&tile.encode(cfg.clone())?);
             }
             Ok(out)
         })
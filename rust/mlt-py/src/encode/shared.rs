@@ -1,6 +1,8 @@
 //! Helpers shared across the encoding entry points (independent of input format).
 
-use mlt_core::encoder::EncoderConfig;
+use std::time::Duration;
+
+use mlt_core::encoder::{Effort, EncoderConfig};
 use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
 
@@ -11,12 +13,19 @@ pub(crate) fn val_err(msg: impl Into<String>) -> PyErr {
 
 /// Build an [`EncoderConfig`] from the Python-facing options.
 /// `sort` collapses the three sort-strategy toggles into one choice, mirroring the `mlt convert` CLI.
+#[expect(
+    clippy::too_many_arguments,
+    reason = "one parameter per Python keyword argument"
+)]
 pub(crate) fn encoder_config(
     tessellate: bool,
     sort: &str,
     shared_dict: bool,
     fsst: bool,
     fastpfor: bool,
+    buffer: Option<u32>,
+    effort: &str,
+    time_budget_ms: Option<u64>,
 ) -> PyResult<EncoderConfig> {
     let (morton, hilbert, id) = match sort {
         "all" => (true, true, true),
@@ -30,6 +39,16 @@ pub(crate) fn encoder_config(
             )));
         }
     };
+    let effort = match effort {
+        "fast" => Effort::Fast,
+        "default" => Effort::Default,
+        "max" => Effort::Max,
+        other => {
+            return Err(val_err(format!(
+                "invalid 'effort' {other:?}; expected one of: fast, default, max"
+            )));
+        }
+    };
     Ok(EncoderConfig::default()
         .with_tessellation(tessellate)
         .with_spatial_morton_sort(morton)
@@ -37,5 +56,8 @@ pub(crate) fn encoder_config(
         .with_id_sort(id)
         .with_shared_dict(shared_dict)
         .with_fsst(fsst)
-        .with_fastpfor(fastpfor))
+        .with_fastpfor(fastpfor)
+        .with_clip_buffer(buffer)
+        .with_effort(effort)
+        .with_time_budget(time_budget_ms.map(Duration::from_millis)))
 }
@@ -1,5 +1,5 @@
 use pyo3::types::{PyBytes, PyDict};
-use pyo3::{Py, pyclass, pymethods};
+use pyo3::{Py, PyAny, Python, pyclass, pymethods};
 use pyo3_stub_gen::derive::{gen_stub_pyclass, gen_stub_pymethods};
 
 /// A decoded MLT feature with geometry, id, and properties.
@@ -14,11 +14,21 @@ pub struct MltFeature {
     wkb: Py<PyBytes>,
     #[pyo3(get)]
     properties: Py<PyDict>,
+    triangles: Option<Py<PyAny>>,
 }
 
 #[gen_stub_pymethods]
 #[pymethods]
 impl MltFeature {
+    /// Pre-computed triangulation as a `memoryview` of `uint32`, three indices per triangle
+    /// into the layer's `vertices`.
+    /// `None` unless this is a polygon feature of a tessellated layer.
+    #[getter]
+    #[gen_stub(override_return_type(type_repr = "typing.Optional[memoryview]", imports = ("typing",)))]
+    fn triangles(&self, py: Python<'_>) -> Option<Py<PyAny>> {
+        self.triangles.as_ref().map(|v| v.clone_ref(py))
+    }
+
     fn __repr__(&self) -> String {
         format!(
             "MltFeature(id={:?}, geometry_type={:?})",
@@ -33,12 +43,14 @@ impl MltFeature {
         geometry_type: String,
         wkb: Py<PyBytes>,
         properties: Py<PyDict>,
+        triangles: Option<Py<PyAny>>,
     ) -> Self {
         MltFeature {
             id,
             geometry_type,
             wkb,
             properties,
+            triangles,
         }
     }
 }
@@ -8,12 +8,12 @@ use std::ops::Deref;
 use mlt_core::geo_types::{Geometry, LineString, Polygon};
 use mlt_core::geojson::FeatureCollection;
 use mlt_core::{
-    Decoder, GeometryType, Layer, LendingIterator, MltError, MltResult, ParsedLayer01, Parser,
-    PropValueRef,
+    Decoder, GeometryType, Layer, LayerTriangles, LendingIterator, MltError, MltResult,
+    NestedValue, ParsedLayer01, Parser, PropValue, PropValueRef,
 };
 use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
-use pyo3::types::{PyBytes, PyDict};
+use pyo3::types::{PyBytes, PyDict, PyList, PyMemoryView};
 use pyo3_stub_gen::define_stub_info_gatherer;
 use pyo3_stub_gen::derive::{gen_stub_pyclass, gen_stub_pyfunction, gen_stub_pymethods};
 use tile_transform::TileTransform;
@@ -34,11 +34,41 @@ struct MltLayer {
     extent: u32,
     #[pyo3(get)]
     features: Vec<Py<MltFeature>>,
+    vertices: Py<PyAny>,
+    triangle_offsets: Option<Py<PyAny>>,
+    triangle_indices: Option<Py<PyAny>>,
 }
 
 #[gen_stub_pymethods]
 #[pymethods]
 impl MltLayer {
+    /// Flat `[x0, y0, x1, y1, …]` vertex buffer in tile coordinates, as a `memoryview` of `int32`.
+    ///
+    /// Triangle indices address this buffer: vertex `n` is `(vertices[2n], vertices[2n+1])`.
+    #[getter]
+    #[gen_stub(override_return_type(type_repr = "memoryview"))]
+    fn vertices(&self, py: Python<'_>) -> Py<PyAny> {
+        self.vertices.clone_ref(py)
+    }
+
+    /// Cumulative triangle counts per feature as a `memoryview` of `uint32`,
+    /// or `None` if the layer was not tessellated.
+    ///
+    /// Feature `i` owns triangles `triangle_offsets[i]` to `triangle_offsets[i+1]`.
+    #[getter]
+    #[gen_stub(override_return_type(type_repr = "typing.Optional[memoryview]", imports = ("typing",)))]
+    fn triangle_offsets(&self, py: Python<'_>) -> Option<Py<PyAny>> {
+        self.triangle_offsets.as_ref().map(|v| v.clone_ref(py))
+    }
+
+    /// Three vertex indices per triangle as a `memoryview` of `uint32`,
+    /// or `None` if the layer was not tessellated.
+    #[getter]
+    #[gen_stub(override_return_type(type_repr = "typing.Optional[memoryview]", imports = ("typing",)))]
+    fn triangle_indices(&self, py: Python<'_>) -> Option<Py<PyAny>> {
+        self.triangle_indices.as_ref().map(|v| v.clone_ref(py))
+    }
+
     fn __repr__(&self) -> String {
         format!(
             "MltLayer(name={:?}, extent={}, features=<{} features>)",
@@ -49,6 +79,23 @@ impl MltLayer {
     }
 }
 
+/// Wrap native-endian values in a typed `memoryview` (e.g. format `"I"` for `u32`),
+/// which `numpy.asarray` consumes without copying.
+fn typed_buffer(py: Python<'_>, bytes: &[u8], format: &str) -> PyResult<Py<PyAny>> {
+    let view = PyMemoryView::from(PyBytes::new(py, bytes).as_any())?;
+    Ok(view.call_method1("cast", (format,))?.unbind())
+}
+
+fn u32_buffer(py: Python<'_>, values: &[u32]) -> PyResult<Py<PyAny>> {
+    let bytes: Vec<u8> = values.iter().flat_map(|v| v.to_ne_bytes()).collect();
+    typed_buffer(py, &bytes, "I")
+}
+
+fn i32_buffer(py: Python<'_>, values: &[i32]) -> PyResult<Py<PyAny>> {
+    let bytes: Vec<u8> = values.iter().flat_map(|v| v.to_ne_bytes()).collect();
+    typed_buffer(py, &bytes, "i")
+}
+
 fn push_coord_raw(buf: &mut Vec<u8>, coord: [i32; 2]) {
     buf.extend_from_slice(&f64::from(coord[0]).to_le_bytes());
     buf.extend_from_slice(&f64::from(coord[1]).to_le_bytes());
@@ -154,17 +201,94 @@ fn prop_value_to_py(py: Python<'_>, v: PropValueRef<'_>) -> Py<PyAny> {
         PropValueRef::F32(n) => n.into_pyobject(py).unwrap().into_any().unbind(),
         PropValueRef::F64(n) => n.into_pyobject(py).unwrap().into_any().unbind(),
         PropValueRef::Str(s) => s.into_pyobject(py).unwrap().into_any().unbind(),
+        PropValueRef::Nested(v) => nested_to_py(py, v),
+        PropValueRef::Binary(b) => PyBytes::new(py, b).into_any().unbind(),
+        PropValueRef::RangeMap(entries) => range_map_to_py(py, entries),
+        // Values Python cannot represent (e.g. dates past year 9999) fall back to the raw value
+        PropValueRef::Date(days) => date_to_py(py, days)
+            .unwrap_or_else(|_| days.into_pyobject(py).unwrap().into_any().unbind()),
+        PropValueRef::Timestamp(ms) => timestamp_to_py(py, ms)
+            .unwrap_or_else(|_| ms.into_pyobject(py).unwrap().into_any().unbind()),
+        PropValueRef::Json(s) => {
+            json_to_py(py, s).unwrap_or_else(|_| s.into_pyobject(py).unwrap().into_any().unbind())
+        }
+    }
+}
+
+/// Convert days since the Unix epoch to a `datetime.date`.
+fn date_to_py(py: Python<'_>, days: i32) -> PyResult<Py<PyAny>> {
+    let datetime = py.import("datetime")?;
+    let epoch = datetime.getattr("date")?.call1((1970, 1, 1))?;
+    let delta = datetime.getattr("timedelta")?.call1((days,))?;
+    Ok(epoch.add(delta)?.unbind())
+}
+
+/// Convert milliseconds since the Unix epoch to a UTC `datetime.datetime`.
+fn timestamp_to_py(py: Python<'_>, ms: i64) -> PyResult<Py<PyAny>> {
+    let datetime = py.import("datetime")?;
+    let utc = datetime.getattr("timezone")?.getattr("utc")?;
+    let epoch = datetime
+        .getattr("datetime")?
+        .call1((1970, 1, 1, 0, 0, 0, 0, utc))?;
+    let delta = datetime.getattr("timedelta")?.call1((0, 0, 0, ms))?;
+    Ok(epoch.add(delta)?.unbind())
+}
+
+/// Parse a JSON document into Python objects with `json.loads`.
+fn json_to_py(py: Python<'_>, s: &str) -> PyResult<Py<PyAny>> {
+    Ok(py.import("json")?.call_method1("loads", (s,))?.unbind())
+}
+
+/// Convert a nested map / list value into Python `dict` / `list` objects.
+fn nested_to_py(py: Python<'_>, v: &NestedValue) -> Py<PyAny> {
+    match v {
+        NestedValue::Bool(b) => b.into_pyobject(py).unwrap().to_owned().into_any().unbind(),
+        NestedValue::I64(n) => n.into_pyobject(py).unwrap().into_any().unbind(),
+        NestedValue::U64(n) => n.into_pyobject(py).unwrap().into_any().unbind(),
+        NestedValue::F32(n) => n.into_pyobject(py).unwrap().into_any().unbind(),
+        NestedValue::F64(n) => n.into_pyobject(py).unwrap().into_any().unbind(),
+        NestedValue::Str(s) => s.into_pyobject(py).unwrap().into_any().unbind(),
+        NestedValue::List(items) => {
+            let items: Vec<_> = items.iter().map(|item| nested_to_py(py, item)).collect();
+            PyList::new(py, items).unwrap().into_any().unbind()
+        }
+        NestedValue::Map(entries) => {
+            let dict = PyDict::new(py);
+            for (key, value) in entries {
+                dict.set_item(key, nested_to_py(py, value)).unwrap();
+            }
+            dict.into_any().unbind()
+        }
     }
 }
 
+/// Convert range map entries to a list of `{"between": [start, end], "value": value}` dicts.
+fn range_map_to_py(py: Python<'_>, entries: &[(f64, f64, PropValue)]) -> Py<PyAny> {
+    let items: Vec<_> = entries
+        .iter()
+        .map(|(start, end, value)| {
+            let dict = PyDict::new(py);
+            dict.set_item("between", [*start, *end]).unwrap();
+            let value = value
+                .as_value_ref()
+                .map_or_else(|| py.None(), |v| prop_value_to_py(py, v));
+            dict.set_item("value", value).unwrap();
+            dict.into_any().unbind()
+        })
+        .collect();
+    PyList::new(py, items).unwrap().into_any().unbind()
+}
+
 fn build_features(
     py: Python<'_>,
     layer: &ParsedLayer01<'_>,
     xf: Option<TileTransform>,
+    triangles: Option<&LayerTriangles>,
 ) -> PyResult<Vec<Py<MltFeature>>> {
     let mut features = Vec::new();
     let mut feat_iter = layer.iter_features();
     while let Some(feat_result) = feat_iter.next() {
+        let index = features.len();
         let feat = feat_result.map_err(mlt_err)?;
         let geometry_type = GeometryType::try_from(feat.geometry())
             .map(|gt| gt.to_string())
@@ -175,7 +299,18 @@ fn build_features(
         for p in feat.iter_properties() {
             prop_dict.set_item(p.name().to_string(), prop_value_to_py(py, p.value()))?;
         }
-        let feature = MltFeature::new(feat.id(), geometry_type, wkb, prop_dict.unbind());
+        let feature_triangles = triangles
+            .filter(|_| feat.triangles().is_some())
+            .and_then(|t| t.feature(index))
+            .map(|t| u32_buffer(py, t))
+            .transpose()?;
+        let feature = MltFeature::new(
+            feat.id(),
+            geometry_type,
+            wkb,
+            prop_dict.unbind(),
+            feature_triangles,
+        );
         features.push(Py::new(py, feature)?);
     }
     Ok(features)
@@ -190,9 +325,16 @@ fn build_features(
 /// `tms`: when True (the default), treat `y` as TMS convention (y=0 at south,
 /// used by OpenMapTiles / MBTiles). Set to False for XYZ / slippy-map tiles
 /// (y=0 at north, e.g. OSM raster tiles).
+///
+/// `columns`: when given, only the properties with these names are decoded;
+/// geometry and ids are always decoded.
 #[gen_stub_pyfunction]
 #[pyfunction]
-#[pyo3(signature = (data, z=None, x=None, y=None, tms=true))]
+#[pyo3(signature = (data, z=None, x=None, y=None, tms=true, columns=None))]
+#[expect(
+    clippy::needless_pass_by_value,
+    reason = "pyo3 cannot extract borrowed string slices"
+)]
 fn decode_mlt(
     py: Python<'_>,
     #[gen_stub(override_type(type_repr = "bytes"))] data: &[u8],
@@ -200,6 +342,7 @@ fn decode_mlt(
     x: Option<u32>,
     y: Option<u32>,
     tms: bool,
+    columns: Option<Vec<String>>,
 ) -> PyResult<Vec<MltLayer>> {
     let mut dec = Decoder::default();
     let mut result = Vec::new();
@@ -209,16 +352,27 @@ fn decode_mlt(
                 "unsupported layer tag (expected 0x01)",
             ));
         };
-        let decoded = layer01.decode_all(&mut dec).map_err(mlt_err)?;
+        let decoded = match &columns {
+            Some(columns) => layer01.decode_columns(&mut dec, columns),
+            None => layer01.decode_all(&mut dec),
+        }
+        .map_err(mlt_err)?;
         let extent = decoded.extent().get();
         let xf = match (z, x, y) {
             (Some(z), Some(x), Some(y)) => Some(TileTransform::from_zxy(z, x, y, extent, tms)?),
             _ => None,
         };
+        let triangles = decoded.triangles().map_err(mlt_err)?;
+        let buffer = |f: fn(&LayerTriangles) -> &[u32]| {
+            triangles.as_ref().map(|t| u32_buffer(py, f(t))).transpose()
+        };
         result.push(MltLayer {
             name: decoded.name().to_string(),
             extent,
-            features: build_features(py, &decoded, xf)?,
+            features: build_features(py, &decoded, xf, triangles.as_ref())?,
+            vertices: i32_buffer(py, decoded.geometry_values().vertices().unwrap_or(&[]))?,
+            triangle_offsets: buffer(LayerTriangles::offsets)?,
+            triangle_indices: buffer(LayerTriangles::indices)?,
         });
     }
 
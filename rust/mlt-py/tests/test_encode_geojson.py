@@ -32,6 +32,18 @@ def test_point_feature_collection_roundtrips():
     assert fc["features"][0]["geometry"] == POINT
 
 
+def test_decode_selected_columns():
+    blob = mlt.encode_geojson(
+        _fc([_feature(POINT, id=7, properties={"name": "a", "rank": 2, "note": "x"})]),
+        "poi",
+    )
+    feature = mlt.decode_mlt(blob, columns=["name", "rank", "missing"])[0].features[0]
+    assert feature.id == 7
+    assert feature.geometry_type == "Point"
+    assert dict(feature.properties) == {"name": "a", "rank": 2}
+    assert mlt.decode_mlt(blob, columns=[])[0].features[0].properties == {}
+
+
 def test_extent_defaults_to_4096():
     blob = mlt.encode_geojson(_fc([_feature(POINT)]), "roads")
     assert mlt.decode_mlt(blob)[0].extent == 4096
@@ -178,6 +190,30 @@ def test_tessellate_polygon_returns_bytes():
     assert isinstance(blob, bytes)
 
 
+def test_tessellated_triangles_are_exposed():
+    blob = mlt.encode_geojson(
+        _fc([_feature(LINE), _feature(POLYGON)]), "l", tessellate=True, sort="none"
+    )
+    layer = mlt.decode_mlt(blob)[0]
+    assert layer.vertices.format == "i"
+    assert layer.triangle_offsets.tolist() == [0, 0, 2]
+    assert layer.triangle_indices.format == "I"
+    assert len(layer.triangle_indices) == 6
+
+    line, polygon = layer.features
+    assert line.triangles is None
+    assert polygon.triangles.tolist() == layer.triangle_indices.tolist()
+    # The line uses vertices 0-1, the square 2-5
+    assert sorted(set(polygon.triangles.tolist())) == [2, 3, 4, 5]
+
+
+def test_untessellated_layer_has_no_triangles():
+    layer = mlt.decode_mlt(mlt.encode_geojson(_fc([_feature(POLYGON)]), "l"))[0]
+    assert layer.triangle_offsets is None
+    assert layer.triangle_indices is None
+    assert layer.features[0].triangles is None
+
+
 @pytest.mark.parametrize("sort", ["auto", "morton", "hilbert", "id", "none"])
 def test_sort_modes_return_bytes(sort):
     blob = mlt.encode_geojson(_fc([_feature(POLYGON, id=1)]), "l", sort=sort)
@@ -189,6 +225,24 @@ def test_invalid_sort_errors():
         mlt.encode_geojson(_fc([_feature(POINT)]), "l", sort="bogus")
 
 
+@pytest.mark.parametrize("effort", ["fast", "default", "max"])
+def test_effort_levels_round_trip(effort):
+    fc = _fc([_feature(POLYGON, id=1), _feature(POINT, id=2)])
+    layer = mlt.decode_mlt(mlt.encode_geojson(fc, "l", effort=effort))[0]
+    assert sorted(f.id for f in layer.features) == [1, 2]
+
+
+def test_expired_time_budget_still_encodes():
+    fc = _fc([_feature(POLYGON, id=1), _feature(POINT, id=2)])
+    layer = mlt.decode_mlt(mlt.encode_geojson(fc, "l", sort="all", time_budget_ms=0))[0]
+    assert sorted(f.id for f in layer.features) == [1, 2]
+
+
+def test_invalid_effort_errors():
+    with pytest.raises(ValueError):
+        mlt.encode_geojson(_fc([_feature(POINT)]), "l", effort="bogus")
+
+
 @pytest.mark.parametrize("shared_dict", [True, False])
 def test_shared_dict_returns_bytes(shared_dict):
     blob = mlt.encode_geojson(
@@ -208,3 +262,19 @@ def test_compression_toggles_return_bytes(flag, value):
         **{flag: value},
     )
     assert isinstance(blob, bytes)
+
+
+def test_buffer_clips_features():
+    outside = {"type": "Point", "coordinates": [-20, 5]}
+    crossing = {"type": "LineString", "coordinates": [[-20, 5], [5, 5]]}
+    blob = mlt.encode_geojson(
+        _fc([_feature(outside), _feature(crossing)]),
+        "l",
+        extent=10,
+        sort="none",
+        buffer=2,
+    )
+    fc = json.loads(mlt.decode_mlt_to_geojson(blob))
+    assert [f["geometry"] for f in fc["features"]] == [
+        {"type": "LineString", "coordinates": [[-2, 5], [5, 5]]}
+    ]
@@ -5,8 +5,9 @@ use std::path::Path;
 
 use mlt_core::GeometryValues;
 use mlt_core::encoder::{
-    Codecs, ColumnKind, Encoder, EncoderConfig, ExplicitEncoder, IntEncoder, Presence, StagedId,
-    StagedLayer, StagedProperty, StagedSharedDict, StrEncoding, StreamCtx, VertexBufferType,
+    Codecs, ColumnKind, Encoder, EncoderConfig, ExplicitEncoder, IntEncoder, PhysicalEncoder,
+    Presence, StagedId, StagedLayer, StagedProperty, StagedSharedDict, StrEncoding, StreamCtx,
+    VertexBufferType,
 };
 use mlt_core::geo_types::{Coord, Geometry};
 use mlt_core::wire::{LengthType, OffsetType, StreamType};
@@ -122,6 +123,10 @@ pub struct Layer {
     /// Geometry stream names that must be written even when their data is empty.
     /// See [`ExplicitEncoder::force_stream`] for details.
     force_empty_streams: HashSet<&'static str>,
+    /// Physical encoding of all bool and presence streams.
+    bool_physical: PhysicalEncoder,
+    /// Physical encoding of all float streams.
+    float_physical: PhysicalEncoder,
     geometry_items: Vec<Geometry<i32>>,
     props: Vec<(StagedProperty, PropConfig)>,
     extent: Option<u32>,
@@ -136,6 +141,8 @@ impl Layer {
             vertex_buffer_type: VertexBufferType::Vec2,
             tessellate: false,
             force_empty_streams: HashSet::new(),
+            bool_physical: PhysicalEncoder::None,
+            float_physical: PhysicalEncoder::None,
             geometry_items: vec![],
             props: vec![],
             extent: None,
@@ -183,6 +190,18 @@ impl Layer {
         self.tessellate = true;
         self
     }
+    /// Physical encoding recorded for bool and presence streams; `None` unless set.
+    #[must_use]
+    pub fn bool_physical(mut self, e: PhysicalEncoder) -> Self {
+        self.bool_physical = e;
+        self
+    }
+    /// Physical encoding recorded for float streams; `None` unless set.
+    #[must_use]
+    pub fn float_physical(mut self, e: PhysicalEncoder) -> Self {
+        self.float_physical = e;
+        self
+    }
 
     /// Force a geometry stream to be written even when its data is empty.
     ///
@@ -367,6 +386,8 @@ impl Layer {
             vertex_buffer_type,
             tessellate,
             force_empty_streams,
+            bool_physical,
+            float_physical,
             geometry_items,
             props,
             extent,
@@ -395,12 +416,12 @@ impl Layer {
             .map(|(p, c)| (p.name().to_string(), c.clone()))
             .collect();
 
-        let cfg = ExplicitEncoder {
+        let cfg = ExplicitEncoder::new(
             vertex_buffer_type,
-            force_stream: Box::new(move |ctx: &StreamCtx<'_>| {
+            Box::new(move |ctx: &StreamCtx<'_>| {
                 ctx.kind == ColumnKind::Geometry && force_empty_streams.contains(ctx.name)
             }),
-            get_int_encoder: {
+            {
                 let prop_map = prop_map.clone();
                 Box::new(move |ctx: &StreamCtx<'_>| match ctx.kind {
                     ColumnKind::Id => id_int_enc.unwrap_or_else(IntEncoder::varint),
@@ -413,14 +434,14 @@ impl Layer {
                         .map_or_else(IntEncoder::varint, |c| c.int_enc_for_stream_ctx(ctx)),
                 })
             },
-            get_str_encoding: {
-                Box::new(move |name: &str| {
-                    prop_map
-                        .get(name)
-                        .map_or(StrEncoding::Plain, PropConfig::str_encoding)
-                })
-            },
-        };
+            Box::new(move |name: &str| {
+                prop_map
+                    .get(name)
+                    .map_or(StrEncoding::Plain, PropConfig::str_encoding)
+            }),
+        )
+        .with_bool_physical(bool_physical)
+        .with_float_physical(float_physical);
 
         let mut codecs = Codecs::default();
         StagedLayer::new(
@@ -25,8 +25,8 @@ use std::sync::LazyLock;
 
 use clap::Parser;
 use mlt_core::encoder::{
-    IntEncoder as E, LogicalEncoder as L, StagedId as Id, StagedProperty as P, StrEncoding,
-    VertexBufferType,
+    IntEncoder as E, LogicalEncoder as L, PhysicalEncoder as PE, StagedId as Id,
+    StagedProperty as P, StrEncoding, VertexBufferType,
 };
 use mlt_core::geo_types::{
     Coord, Geometry, LineString, MultiLineString, MultiPoint, MultiPolygon, Point, Polygon, coord,
@@ -583,6 +583,16 @@ fn generate_properties(w: &mut SynthWriter) {
         .geos([P0, P0])
         .add_prop(e_any, P::opt_bool("val", vec![None, Some(false)]))
         .write(w, "prop_bool_null_false");
+    // Bool and presence streams flagged as VarInt still hold byte-RLE, which all decoders read
+    // (Java never sets the flag, so these are Rust-only)
+    p0().bool_physical(PE::VarInt)
+        .add_prop(e_any, P::bool("val", vec![true]))
+        .write(w, "prop_bool_varint_np-rust");
+    geo_varint_with_rle()
+        .geos([P0, P0])
+        .bool_physical(PE::VarInt)
+        .add_prop(e_any, P::opt_bool("val", vec![None, Some(true)]))
+        .write(w, "prop_bool_null_true_varint-rust");
 
     let e_int = E::varint();
     p0().add_prop(e_int, P::i32("val", vec![42]))
@@ -825,6 +835,22 @@ fn generate_properties(w: &mut SynthWriter) {
         .geos([P0, P0])
         .add_prop(e_fl, P::opt_f32("val", vec![None, Some(3.14)]))
         .write(w, "prop_f32_null_val");
+    // Float streams flagged as VarInt still hold little-endian floats, which all decoders read
+    // (Java never sets the flag, so these are Rust-only)
+    #[expect(clippy::approx_constant)]
+    p0().float_physical(PE::VarInt)
+        .add_prop(e_fl, P::f32("val", vec![3.14]))
+        .write(w, "prop_f32_varint_np-rust");
+    #[expect(clippy::approx_constant)]
+    geo_varint_with_rle()
+        .geos([P0, P0])
+        .bool_physical(PE::VarInt)
+        .float_physical(PE::VarInt)
+        .add_prop(e_fl, P::opt_f32("val", vec![None, Some(3.14)]))
+        .write(w, "prop_f32_null_val_varint-rust");
+    p0().float_physical(PE::VarInt)
+        .add_prop(e_fl, P::f64("val", vec![f64::NAN]))
+        .write(w, "prop_f64_nan_varint_np-rust");
 
     p0().add_prop(e_fl, P::f64("val", vec![std::f64::consts::PI]))
         .write(w, "prop_f64_np");
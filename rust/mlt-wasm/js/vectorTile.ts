@@ -19,6 +19,10 @@ interface LayerGeometry {
   ring_offsets(): Uint32Array;
   /** Flat [x0, y0, x1, y1, …] vertex buffer in tile coordinates. */
   vertices(): Int32Array;
+  /** Cumulative per-feature triangle counts. Zero-length when the layer is not tessellated. */
+  triangle_offsets(): Uint32Array;
+  /** Three vertex indices per triangle. Zero-length when the layer is not tessellated. */
+  triangle_indices(): Uint32Array;
 }
 
 interface WasmMltTile {
@@ -257,6 +261,8 @@ export class MltFeature implements VectorTileFeatureLike {
     private readonly _partOffsets: Uint32Array,
     private readonly _ringOffsets: Uint32Array,
     private readonly _verts: Int32Array,
+    private readonly _triOffsets: Uint32Array,
+    private readonly _triIndices: Uint32Array,
     private readonly propertyKeys: string[],
     private readonly propertyColumns: Array<
       | Int8Array
@@ -325,6 +331,18 @@ export class MltFeature implements VectorTileFeatureLike {
       this._verts,
     );
   }
+
+  /**
+   * Returns the pre-computed triangulation as three vertex indices per triangle,
+   * or `undefined` if the layer was not tessellated. Indices address the layer's vertex
+   * buffer, so vertex `n` is `(verts[2n], verts[2n+1])`. Non-polygon features have none.
+   */
+  loadTriangles(): Uint32Array | undefined {
+    if (this._triOffsets.length === 0) return undefined;
+    const start = this._triOffsets[this._featureIdx] * 3;
+    const end = this._triOffsets[this._featureIdx + 1] * 3;
+    return this._triIndices.subarray(start, end);
+  }
 }
 
 // ---------------------------------------------------------------------------
@@ -344,6 +362,8 @@ export class MltLayer implements VectorTileLayerLike {
   private readonly _partOffsets: Uint32Array;
   private readonly _ringOffsets: Uint32Array;
   private readonly _verts: Int32Array;
+  private readonly _triOffsets: Uint32Array;
+  private readonly _triIndices: Uint32Array;
   readonly propertyKeys: string[];
   readonly propertyColumns: Array<
     | Int8Array
@@ -371,6 +391,8 @@ export class MltLayer implements VectorTileLayerLike {
     this._partOffsets = geom.part_offsets();
     this._ringOffsets = geom.ring_offsets();
     this._verts = geom.vertices();
+    this._triOffsets = geom.triangle_offsets();
+    this._triIndices = geom.triangle_indices();
     this.propertyKeys = _tile.layer_property_keys(_layerIdx);
     this.propertyColumns = _tile.layer_properties(_layerIdx);
   }
@@ -386,14 +408,23 @@ export class MltLayer implements VectorTileLayerLike {
       this._partOffsets,
       this._ringOffsets,
       this._verts,
+      this._triOffsets,
+      this._triIndices,
       this.propertyKeys,
       this.propertyColumns,
     );
   }
 }
 
-export function decodeTile(data: Uint8Array): VectorTileLike {
-  const tile = wasmDecodeTile(data) as WasmMltTile;
+/**
+ * Decodes an MLT tile. When `columns` is given, only the property columns with
+ * those names are decoded; geometry and IDs are always available.
+ */
+export function decodeTile(
+  data: Uint8Array,
+  columns?: string[],
+): VectorTileLike {
+  const tile = wasmDecodeTile(data, columns) as WasmMltTile;
   const layers: Record<string, VectorTileLayerLike> = {};
   for (let i = 0; i < tile.layer_count(); i++) {
     const name = tile.layer_name(i);
@@ -1,5 +1,5 @@
 use js_sys::{Int32Array, Uint32Array};
-use mlt_core::GeometryValues;
+use mlt_core::{GeometryValues, LayerTriangles};
 use wasm_bindgen::prelude::*;
 
 /// All decoded geometry arrays for a single layer, fetched in one WASM call.
@@ -20,6 +20,8 @@ use wasm_bindgen::prelude::*;
 /// | `part_offsets`     | `LineString`, `Polygon`, `MultiLineString`, `MultiPolygon`|
 /// | `ring_offsets`     | `Polygon`, `MultiPolygon` (+ `LineString` when mixed)     |
 /// | `vertices`         | always                                                    |
+/// | `triangle_offsets` | tessellated layers                                        |
+/// | `triangle_indices` | tessellated layers                                        |
 ///
 /// Absent offset arrays are returned as zero-length `Uint32Array`s so JS can
 /// always branch on `.length` without a null-check.
@@ -29,6 +31,8 @@ pub struct LayerGeometry {
     pub(crate) part_offsets: Uint32Array,
     pub(crate) ring_offsets: Uint32Array,
     pub(crate) vertices: Int32Array,
+    pub(crate) triangle_offsets: Uint32Array,
+    pub(crate) triangle_indices: Uint32Array,
 }
 
 #[wasm_bindgen]
@@ -60,11 +64,26 @@ impl LayerGeometry {
     pub fn vertices(&self) -> Int32Array {
         self.vertices.clone()
     }
+
+    /// Cumulative triangle counts per feature: feature `i` owns triangles
+    /// `triangle_offsets[i]..triangle_offsets[i+1]`.
+    /// Zero-length when the layer was not tessellated.
+    #[must_use]
+    pub fn triangle_offsets(&self) -> Uint32Array {
+        self.triangle_offsets.clone()
+    }
+
+    /// Three vertex indices per triangle, addressing `vertices` like the offsets do.
+    /// Zero-length when the layer was not tessellated.
+    #[must_use]
+    pub fn triangle_indices(&self) -> Uint32Array {
+        self.triangle_indices.clone()
+    }
 }
 
 impl LayerGeometry {
-    /// Build a [`LayerGeometry`] from a decoded [`GeometryValues`].
-    pub(crate) fn from_values(geom: &GeometryValues) -> Self {
+    /// Build a [`LayerGeometry`] from a decoded [`GeometryValues`] and its triangles.
+    pub(crate) fn from_values(geom: &GeometryValues, triangles: Option<&LayerTriangles>) -> Self {
         let geometry_offsets = geom
             .geometry_offsets()
             .map_or_else(|| Uint32Array::new_with_length(0), Uint32Array::from);
@@ -81,11 +100,23 @@ impl LayerGeometry {
             .vertices()
             .map_or_else(|| Int32Array::new_with_length(0), Int32Array::from);
 
+        let triangle_offsets = triangles.map_or_else(
+            || Uint32Array::new_with_length(0),
+            |t| Uint32Array::from(t.offsets()),
+        );
+
+        let triangle_indices = triangles.map_or_else(
+            || Uint32Array::new_with_length(0),
+            |t| Uint32Array::from(t.indices()),
+        );
+
         Self {
             geometry_offsets,
             part_offsets,
             ring_offsets,
             vertices,
+            triangle_offsets,
+            triangle_indices,
         }
     }
 }
@@ -1,4 +1,4 @@
-use mlt_core::{GeometryValues, TileLayer};
+use mlt_core::{GeometryValues, LayerTriangles, TileLayer};
 
 /// All per-layer state owned by [`crate::tile::MltTile`].
 ///
@@ -13,4 +13,7 @@ pub(crate) struct DecodedLayer {
     pub(crate) mlt_types_array: js_sys::Uint8Array,
 
     pub(crate) geometry: GeometryValues,
+
+    /// Tessellation triangles, `None` when the layer was encoded without them.
+    pub(crate) triangles: Option<LayerTriangles>,
 }
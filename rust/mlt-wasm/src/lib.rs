@@ -13,6 +13,7 @@
 //! `MltTile::layer_geometry` returns a `LayerGeometry`
 //! whose typed-array getters expose the raw offset and vertex buffers.
 //! JS walks these directly - zero WASM boundary crossings per feature.
+//! Tessellated layers also expose their triangles, so renderers can skip Earcut.
 //!
 //! ## IDs
 //!
@@ -42,8 +43,15 @@ use wasm_bindgen::prelude::*;
 ///
 /// All geometry, IDs and properties are decoded eagerly into row-oriented
 /// [`mlt_core::TileLayer`] values.
+///
+/// When `columns` is given, only the property columns with those names are decoded;
+/// see [`mlt_core::Layer01::decode_columns`].
 #[wasm_bindgen]
-pub fn decode_tile(data: &[u8]) -> Result<MltTile, JsError> {
+#[expect(
+    clippy::needless_pass_by_value,
+    reason = "wasm_bindgen cannot pass borrowed string slices"
+)]
+pub fn decode_tile(data: &[u8], columns: Option<Vec<String>>) -> Result<MltTile, JsError> {
     let mut parser = Parser::default();
     let raw_layers = parser.parse_layers(data).map_err(|e| to_js_err(&e))?;
     let mut dec = Decoder::default();
@@ -56,11 +64,16 @@ pub fn decode_tile(data: &[u8]) -> Result<MltTile, JsError> {
         };
 
         // Decode all columns at once, then extract geometry arrays before consuming into tile.
-        let parsed_layer = layer01.decode_all(&mut dec).map_err(|e| to_js_err(&e))?;
+        let parsed_layer = match &columns {
+            Some(columns) => layer01.decode_columns(&mut dec, columns),
+            None => layer01.decode_all(&mut dec),
+        }
+        .map_err(|e| to_js_err(&e))?;
 
         // Clone geometry values for building WASM typed arrays (zero wire-decode overhead:
         // geometry is already in columnar form from decode_all).
         let parsed_geometry = parsed_layer.geometry_values().clone();
+        let triangles = parsed_layer.triangles().map_err(|e| to_js_err(&e))?;
 
         let (types_bytes, mlt_types_bytes): (Vec<u8>, Vec<u8>) = parsed_geometry
             .vector_types()
@@ -88,6 +101,7 @@ pub fn decode_tile(data: &[u8]) -> Result<MltTile, JsError> {
             types_array,
             mlt_types_array,
             geometry: parsed_geometry,
+            triangles,
         });
     }
 
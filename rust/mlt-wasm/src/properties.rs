@@ -1,5 +1,8 @@
-use js_sys::{Array, Float64Array, Int8Array, Int32Array, Uint8Array, Uint32Array};
-use mlt_core::{PropValue, TileLayer};
+use js_sys::{
+    Array, Date, Float64Array, Int8Array, Int32Array, JSON, Object, Reflect, Uint8Array,
+    Uint32Array,
+};
+use mlt_core::{NestedValue, PropValue, TileLayer};
 use wasm_bindgen::prelude::*;
 
 /// Cached bulk-property data for a single layer.
@@ -276,6 +279,31 @@ fn build_column(tile: &TileLayer, col_idx: usize, n: usize) -> JsValue {
                 Float64Array::from(buf.as_slice()).into()
             }
         }
+        Some(PropValue::Nested(_)) => {
+            let arr = Array::new_with_length(feature_count_u32(n));
+            for (i, f) in tile.features().iter().enumerate() {
+                if let Some(PropValue::Nested(Some(v))) = f.properties().get(col_idx) {
+                    arr.set(idx_u32(i), nested_to_js(v));
+                }
+            }
+            arr.into()
+        }
+        Some(
+            PropValue::Date(_)
+            | PropValue::Timestamp(_)
+            | PropValue::Json(_)
+            | PropValue::Binary(_)
+            | PropValue::RangeMap(_),
+        ) => {
+            // Logical values map to JS objects, so they cannot live in a typed array
+            let arr = Array::new_with_length(feature_count_u32(n));
+            for (i, f) in tile.features().iter().enumerate() {
+                if let Some(v) = f.properties().get(col_idx).and_then(prop_value_to_js) {
+                    arr.set(idx_u32(i), v);
+                }
+            }
+            arr.into()
+        }
         Some(PropValue::Str(_)) | None => {
             let arr = Array::new_with_length(feature_count_u32(n));
             for (i, f) in tile.features().iter().enumerate() {
@@ -303,5 +331,62 @@ pub(crate) fn prop_value_to_js(val: &PropValue) -> Option<JsValue> {
         PropValue::F32(v) => v.map(|n| JsValue::from_f64(f64::from(n))),
         PropValue::F64(v) => v.map(JsValue::from_f64),
         PropValue::Str(v) => v.as_deref().map(JsValue::from_str),
+        PropValue::Nested(v) => v.as_ref().map(nested_to_js),
+        PropValue::Date(v) => v.map(|days| timestamp_to_js(i64::from(days) * 86_400_000)),
+        PropValue::Timestamp(v) => v.map(timestamp_to_js),
+        // Malformed documents are passed through as the raw string
+        PropValue::Json(v) => v
+            .as_deref()
+            .map(|s| JSON::parse(s).unwrap_or_else(|_| JsValue::from_str(s))),
+        PropValue::Binary(v) => v.as_deref().map(|b| Uint8Array::from(b).into()),
+        PropValue::RangeMap(v) => v.as_deref().map(range_map_to_js),
+    }
+}
+
+/// Convert range map entries to an array of `{between: [start, end], value}` objects.
+fn range_map_to_js(entries: &[(f64, f64, PropValue)]) -> JsValue {
+    entries
+        .iter()
+        .map(|(start, end, value)| {
+            let obj = Object::new();
+            let between: Array = [JsValue::from_f64(*start), JsValue::from_f64(*end)]
+                .into_iter()
+                .collect();
+            let value = prop_value_to_js(value).unwrap_or(JsValue::NULL);
+            Reflect::set(&obj, &JsValue::from_str("between"), &between)
+                .expect("setting a property on a plain object succeeds");
+            Reflect::set(&obj, &JsValue::from_str("value"), &value)
+                .expect("setting a property on a plain object succeeds");
+            JsValue::from(obj)
+        })
+        .collect::<Array>()
+        .into()
+}
+
+/// Convert milliseconds since the Unix epoch to a JS `Date`.
+#[allow(clippy::cast_precision_loss)]
+fn timestamp_to_js(ms: i64) -> JsValue {
+    Date::new(&JsValue::from_f64(ms as f64)).into()
+}
+
+/// Convert a nested map / list value into plain JS objects and arrays.
+#[allow(clippy::cast_precision_loss)]
+fn nested_to_js(val: &NestedValue) -> JsValue {
+    match val {
+        NestedValue::Bool(v) => JsValue::from_bool(*v),
+        NestedValue::I64(v) => JsValue::from_f64(*v as f64),
+        NestedValue::U64(v) => JsValue::from_f64(*v as f64),
+        NestedValue::F32(v) => JsValue::from_f64(f64::from(*v)),
+        NestedValue::F64(v) => JsValue::from_f64(*v),
+        NestedValue::Str(v) => JsValue::from_str(v),
+        NestedValue::List(items) => items.iter().map(nested_to_js).collect::<Array>().into(),
+        NestedValue::Map(entries) => {
+            let obj = Object::new();
+            for (key, value) in entries {
+                Reflect::set(&obj, &JsValue::from_str(key), &nested_to_js(value))
+                    .expect("setting a property on a plain object succeeds");
+            }
+            obj.into()
+        }
     }
 }
@@ -81,7 +81,8 @@ impl MltTile {
     /// All decoded geometry arrays for layer `layer_idx`, in one call.
     #[must_use]
     pub fn layer_geometry(&self, layer_idx: usize) -> LayerGeometry {
-        LayerGeometry::from_values(&self.layers[layer_idx].geometry)
+        let layer = &self.layers[layer_idx];
+        LayerGeometry::from_values(&layer.geometry, layer.triangles.as_ref())
     }
 
     // -----------------------------------------------------------------------
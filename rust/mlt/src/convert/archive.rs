@@ -0,0 +1,121 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::pin::pin;
+use std::sync::Arc;
+
+use anyhow::{Result as AnyResult, anyhow, bail};
+use futures::StreamExt as _;
+use martin_tile_utils::{Encoding, Format};
+use mbtiles::Mbtiles;
+use pmtiles::{AsyncPmTilesReader, HashMapCache, TileCoord, TileId, TileType};
+use sqlx::SqliteConnection;
+
+use super::decompress;
+use super::from_pmtiles::{PmReader, collect_pmtiles_ids, compression_to_encoding};
+
+/// Read-only access to the tiles of an `.mbtiles` or `.pmtiles` archive by XYZ coordinate.
+pub struct TileArchive {
+    source: ArchiveSource,
+    format: Format,
+    encoding: Encoding,
+}
+
+enum ArchiveSource {
+    Mbtiles(Mbtiles, SqliteConnection),
+    Pmtiles(Arc<PmReader>),
+}
+
+impl TileArchive {
+    /// Open an archive of MLT or MVT tiles; the container is chosen by extension.
+    pub async fn open(path: &Path) -> AnyResult<Self> {
+        if path.extension().is_some_and(|ext| ext == "pmtiles") {
+            let reader = Arc::new(
+                AsyncPmTilesReader::new_with_cached_path(HashMapCache::default(), path).await?,
+            );
+            let header = reader.get_header();
+            let format = match header.tile_type {
+                TileType::Mlt => Format::Mlt,
+                TileType::Mvt => Format::Mvt,
+                other => bail!(
+                    "Expected MLT or MVT tiles, got {other:?} in {}",
+                    path.display()
+                ),
+            };
+            let encoding = compression_to_encoding(header.tile_compression)?;
+            return Ok(Self {
+                source: ArchiveSource::Pmtiles(reader),
+                format,
+                encoding,
+            });
+        }
+
+        let mbt = Mbtiles::new(path)?;
+        let mut conn = mbt.open_readonly().await?;
+        let meta = mbt.get_metadata(&mut conn).await?;
+        let tile_info = mbt
+            .detect_format(&meta.tilejson, &mut conn)
+            .await?
+            .ok_or_else(|| anyhow!("{} appears to be empty", path.display()))?;
+        if tile_info.format != Format::Mlt && tile_info.format != Format::Mvt {
+            bail!(
+                "Expected MLT or MVT tiles, got {} in {}",
+                tile_info.format,
+                path.display()
+            );
+        }
+        Ok(Self {
+            source: ArchiveSource::Mbtiles(mbt, conn),
+            format: tile_info.format,
+            encoding: tile_info.encoding,
+        })
+    }
+
+    /// Tile format, either [`Format::Mlt`] or [`Format::Mvt`].
+    #[must_use]
+    pub fn format(&self) -> Format {
+        self.format
+    }
+
+    /// Coordinates of all tiles as `(z, x, y)`, with their index in the archive.
+    pub async fn tiles(&mut self) -> AnyResult<BTreeMap<(u8, u32, u32), TileRef>> {
+        let mut tiles = BTreeMap::new();
+        match &mut self.source {
+            ArchiveSource::Mbtiles(mbt, conn) => {
+                let mut stream = pin!(mbt.stream_tiles(conn));
+                while let Some(row) = stream.next().await {
+                    let (coord, data) = row?;
+                    if data.is_some() {
+                        tiles.insert((coord.z, coord.x, coord.y), TileRef(None));
+                    }
+                }
+            }
+            ArchiveSource::Pmtiles(reader) => {
+                for id in collect_pmtiles_ids(reader).await? {
+                    let coord = TileCoord::from(id);
+                    tiles.insert((coord.z(), coord.x(), coord.y()), TileRef(Some(id)));
+                }
+            }
+        }
+        Ok(tiles)
+    }
+
+    /// Read and decompress one tile.
+    pub async fn get_tile(
+        &mut self,
+        (z, x, y): (u8, u32, u32),
+        tile: TileRef,
+    ) -> AnyResult<Option<Vec<u8>>> {
+        let data = match (&mut self.source, tile.0) {
+            (ArchiveSource::Mbtiles(mbt, conn), _) => mbt.get_tile(conn, z, x, y).await?,
+            (ArchiveSource::Pmtiles(reader), Some(id)) => {
+                reader.get_tile(id).await?.map(|data| data.to_vec())
+            }
+            (ArchiveSource::Pmtiles(_), None) => bail!("tile {z}/{x}/{y} is not in the archive"),
+        };
+        data.map(|data| decompress(data, self.encoding)).transpose()
+    }
+}
+
+/// Locates a tile inside its archive; returned by [`TileArchive::tiles`].
+#[derive(Clone, Copy)]
+pub struct TileRef(Option<TileId>);
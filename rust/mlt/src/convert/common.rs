@@ -78,17 +78,17 @@ pub fn encode_tile(
     cache: &EncodeCache,
     data: &[u8],
     encoding: Encoding,
-    cfg: EncoderConfig,
+    cfg: &EncoderConfig,
 ) -> AnyResult<(Bytes, u64, bool)> {
     if data.len() > MAX_TILE_CACHE_TRACK_SIZE_BYTES {
-        let (encoded, raw_mvt_size) = encode_one(data.to_vec(), encoding, cfg)?;
+        let (encoded, raw_mvt_size) = encode_one(data.to_vec(), encoding, cfg, None)?;
         return Ok((encoded, raw_mvt_size, false));
     }
     let mut hit = true;
     let encoded = cache
         .try_get_with_by_ref(data, || {
             hit = false;
-            encode_one(data.to_vec(), encoding, cfg)
+            encode_one(data.to_vec(), encoding, cfg, None)
         })
         .map_err(|e| anyhow!("{e}"))?;
     Ok((encoded.0, encoded.1, hit))
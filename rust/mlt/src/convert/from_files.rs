@@ -7,6 +7,7 @@ use std::time::Duration;
 
 use anyhow::{Context as _, Result as AnyResult, anyhow, bail};
 use indicatif::{ProgressBar, ProgressStyle};
+use mlt_core::metadata::TileSetMetadata;
 use moka::sync::Cache;
 use rayon::iter::{ParallelBridge as _, ParallelIterator as _};
 use size_format::SizeFormatterSI;
@@ -14,7 +15,8 @@ use usize_cast::FromUsize as _;
 use walkdir::WalkDir;
 use xxhash_rust::xxh3::xxh3_128;
 
-use super::{EncoderConfig, TileFormat, convert_buffer, whole_rate_per_sec};
+use super::tileset::{SchemaCollector, TILESET_METADATA_FILE};
+use super::{EncoderConfig, TileFormat, convert_buffer, merged_tileset, whole_rate_per_sec};
 
 /// Only tiles below this size are cached; larger tiles rarely repeat across a tileset.
 const MAX_TILE_TRACK_SIZE: usize = 1024;
@@ -86,9 +88,18 @@ struct WalkCtx<'a> {
     to: TileFormat,
     cache: &'a EncodedCache,
     stats: &'a DedupStats,
+    schema: Option<&'a SchemaCollector>,
+    /// Metadata of the input tiles, read from their [`TILESET_METADATA_FILE`].
+    tileset: Option<&'a TileSetMetadata>,
 }
 
-pub fn convert(input: &Path, output: &Path, cfg: EncoderConfig, to: TileFormat) -> AnyResult<()> {
+pub fn convert(
+    input: &Path,
+    output: &Path,
+    cfg: EncoderConfig,
+    to: TileFormat,
+    tileset_metadata: bool,
+) -> AnyResult<()> {
     // For a single file, use the parent so `strip_prefix` yields just the filename.
     let base = if input.is_dir() {
         input
@@ -99,6 +110,9 @@ pub fn convert(input: &Path, output: &Path, cfg: EncoderConfig, to: TileFormat)
     let cache: EncodedCache = make_cache(CACHE_MAX_BYTES);
     let stats = DedupStats::default();
     let failed = AtomicUsize::new(0);
+    // Tileset metadata describes MLT columns, so it is only written for MLT output.
+    let schema = (tileset_metadata && to == TileFormat::Mlt).then(SchemaCollector::default);
+    let input_tileset = read_tileset(base)?;
     let ctx = WalkCtx {
         base,
         output,
@@ -106,6 +120,8 @@ pub fn convert(input: &Path, output: &Path, cfg: EncoderConfig, to: TileFormat)
         to,
         cache: &cache,
         stats: &stats,
+        schema: schema.as_ref(),
+        tileset: input_tileset.as_ref(),
     };
 
     let bar = ProgressBar::new_spinner();
@@ -136,7 +152,11 @@ pub fn convert(input: &Path, output: &Path, cfg: EncoderConfig, to: TileFormat)
                 None
             }
         })
-        .filter(|e| e.file_type().is_file() && is_convert_extension(e.path()))
+        .filter(|e| {
+            e.file_type().is_file()
+                && is_convert_extension(e.path())
+                && e.file_name() != TILESET_METADATA_FILE
+        })
         .par_bridge()
         .for_each(|entry| {
             let in_path = entry.into_path();
@@ -161,9 +181,26 @@ pub fn convert(input: &Path, output: &Path, cfg: EncoderConfig, to: TileFormat)
         return Ok(());
     }
     eprintln!("{}", format_dedup_line(&stats, &cache));
+    if let Some(metadata) = merged_tileset(schema.as_ref()) {
+        let path = output.join(TILESET_METADATA_FILE);
+        fs::write(&path, metadata.to_bytes())
+            .with_context(|| format!("writing {}", path.display()))?;
+    }
     Ok(())
 }
 
+/// Read the tileset metadata stored next to the input tiles, if any.
+fn read_tileset(dir: &Path) -> AnyResult<Option<TileSetMetadata>> {
+    let path = dir.join(TILESET_METADATA_FILE);
+    if !path.is_file() {
+        return Ok(None);
+    }
+    let bytes = fs::read(&path).with_context(|| format!("reading {}", path.display()))?;
+    let metadata = TileSetMetadata::from_bytes(&bytes)
+        .with_context(|| format!("parsing {}", path.display()))?;
+    Ok(Some(metadata))
+}
+
 fn convert_file(file: &Path, ctx: &WalkCtx<'_>) -> AnyResult<()> {
     let rel = file
         .strip_prefix(ctx.base)
@@ -186,7 +223,8 @@ fn convert_file(file: &Path, ctx: &WalkCtx<'_>) -> AnyResult<()> {
     };
 
     if buffer.len() > MAX_TILE_TRACK_SIZE {
-        let out_bytes = convert_buffer(buffer, from, ctx.to, ctx.cfg).with_context(err_ctx)?;
+        let out_bytes = convert_buffer(buffer, from, ctx.to, &ctx.cfg, ctx.schema, ctx.tileset)
+            .with_context(err_ctx)?;
         ctx.stats.record_encode();
         fs::write(&out_path, &out_bytes)
             .with_context(|| format!("writing {}", out_path.display()))?;
@@ -198,7 +236,8 @@ fn convert_file(file: &Path, ctx: &WalkCtx<'_>) -> AnyResult<()> {
         .cache
         .entry(key)
         .or_try_insert_with(|| -> AnyResult<Arc<Vec<u8>>> {
-            let out_bytes = convert_buffer(buffer, from, ctx.to, ctx.cfg).with_context(err_ctx)?;
+            let out_bytes = convert_buffer(buffer, from, ctx.to, &ctx.cfg, ctx.schema, ctx.tileset)
+                .with_context(err_ctx)?;
             Ok(Arc::new(out_bytes))
         })
         .map_err(|e: Arc<anyhow::Error>| anyhow!("{e:#}"))?;
@@ -17,9 +17,12 @@ use super::common::{
     ENCODE_CACHE_BYTES, EncodedTile, MAX_TILE_CACHE_TRACK_SIZE_BYTES, PmTilesGeography, TileStats,
     encode_tile, make_encode_cache, make_progress_bar,
 };
-use super::{ContainerFormat, MbtFormat, encode_one, update_mlt_pmtiles_metadata};
+use super::tileset::{
+    SchemaCollector, TILESET_METADATA_KEY, describe_tileset, scan_schema, tileset_metadata_hex,
+};
+use super::{ContainerFormat, MbtFormat, encode_one, merged_tileset, update_mlt_pmtiles_metadata};
 
-fn geography_from_metadata(metadata: &Metadata) -> PmTilesGeography {
+pub(super) fn geography_from_metadata(metadata: &Metadata) -> PmTilesGeography {
     let tilejson = &metadata.tilejson;
     PmTilesGeography {
         min_zoom: tilejson.minzoom,
@@ -36,13 +39,14 @@ pub async fn convert(
     cfg: EncoderConfig,
     mbtiles_format: Option<MbtFormat>,
     tile_compression: Compression,
+    tileset_metadata: bool,
 ) -> AnyResult<()> {
     match output {
         (output, ContainerFormat::Mbtiles) => {
-            convert_mbtiles_to_mbtiles(input, output, mbtiles_format, cfg).await
+            convert_mbtiles_to_mbtiles(input, output, mbtiles_format, cfg, tileset_metadata).await
         }
         (output, ContainerFormat::Pmtiles) => {
-            convert_mbtiles_to_pmtiles(input, output, cfg, tile_compression).await
+            convert_mbtiles_to_pmtiles(input, output, cfg, tile_compression, tileset_metadata).await
         }
         (output, ContainerFormat::Files) => bail!(
             "Output must be either an .mbtiles or a .pmtiles file when input is an .mbtiles file, got: {}",
@@ -95,8 +99,9 @@ async fn convert_mbtiles_to_mbtiles(
     output: &Path,
     mbtiles_format: Option<MbtFormat>,
     cfg: EncoderConfig,
+    tileset_metadata: bool,
 ) -> AnyResult<()> {
-    let (encoding, src_type, _, total) = get_metadata(input).await?;
+    let (encoding, src_type, metadata, total) = get_metadata(input).await?;
     let mbt_type = mbtiles_format.map_or(src_type, Into::into);
 
     eprintln!("{} -> {} ({mbt_type}):", input.display(), output.display());
@@ -107,12 +112,14 @@ async fn convert_mbtiles_to_mbtiles(
     let bar_ref = bar.clone();
     let sizes = Arc::new(EncodeSizes::default());
     let sizes_ref = Arc::clone(&sizes);
+    let schema = tileset_metadata.then(SchemaCollector::default);
+    let schema_ref = schema.clone();
 
     let mut transcoder = MbtilesTranscoder::new(input, output, move |data| {
         sizes_ref
             .bytes_in
             .fetch_add(u64::from_usize(data.len()), Ordering::Relaxed);
-        let result = encode_one(data, encoding, cfg)
+        let result = encode_one(data, encoding, &cfg, schema_ref.as_ref())
             .map(|(data, _raw_mvt_size)| data)
             .map_err(|e| -> Box<dyn std::error::Error + Send + Sync> { e.to_string().into() });
         if let Ok(ref encoded) = result {
@@ -141,6 +148,17 @@ async fn convert_mbtiles_to_mbtiles(
     let mut dst_conn = dst.open_or_new().await?;
     dst.set_metadata_value(&mut dst_conn, "format", Format::Mlt.metadata_format_value())
         .await?;
+    if let Some(mut tileset) = merged_tileset(schema.as_ref()) {
+        if let serde_json::Value::Object(tilejson) = serde_json::to_value(&metadata.tilejson)? {
+            describe_tileset(&mut tileset, &tilejson, &geography_from_metadata(&metadata));
+        }
+        dst.set_metadata_value(
+            &mut dst_conn,
+            TILESET_METADATA_KEY,
+            &tileset_metadata_hex(&tileset),
+        )
+        .await?;
+    }
 
     let in_bytes = sizes.bytes_in.load(Ordering::Relaxed);
     let out_bytes = sizes.bytes_out.load(Ordering::Relaxed);
@@ -162,6 +180,7 @@ async fn convert_mbtiles_to_pmtiles(
     output: &Path,
     cfg: EncoderConfig,
     tile_compression: Compression,
+    tileset_metadata: bool,
 ) -> AnyResult<()> {
     // FIXME: add a fastpath for normalised schemas. We don't need to cache them
     let (encoding, _, metadata, total) = get_metadata(input).await?;
@@ -173,12 +192,34 @@ async fn convert_mbtiles_to_pmtiles(
     let bar = make_progress_bar(total);
 
     let geography = geography_from_metadata(&metadata);
+    let mbt = Mbtiles::new(input)?;
+    let mut conn = mbt.open_readonly().await?;
+    // PMTiles stores metadata ahead of the tiles, so the schema must be known before writing.
+    let schema = if tileset_metadata {
+        let tiles = mbt.stream_tiles(&mut conn).filter_map(|r| async move {
+            match r {
+                Ok((_, data)) => data.map(Ok),
+                Err(e) => Some(Err(anyhow::Error::from(e))),
+            }
+        });
+        Some(scan_schema(tiles, encoding).await?)
+    } else {
+        None
+    };
+
     let file = std::fs::File::create(output)?;
     let mut metadata_json = serde_json::to_value(&metadata.tilejson)?;
     let metadata_obj = metadata_json
         .as_object_mut()
         .ok_or_else(|| anyhow!("MBTiles metadata must serialize to a JSON object"))?;
     update_mlt_pmtiles_metadata(metadata_obj, tile_compression);
+    if let Some(mut tileset) = merged_tileset(schema.as_ref()) {
+        describe_tileset(&mut tileset, metadata_obj, &geography);
+        metadata_obj.insert(
+            TILESET_METADATA_KEY.into(),
+            serde_json::Value::String(tileset_metadata_hex(&tileset)),
+        );
+    }
     let metadata_str = serde_json::to_string(&metadata_json)?;
     let mut stream_writer = geography
         .apply(PmTilesWriter::new(TileType::Mlt))
@@ -190,8 +231,6 @@ async fn convert_mbtiles_to_pmtiles(
 
     let cache = make_encode_cache();
 
-    let mbt = Mbtiles::new(input)?;
-    let mut conn = mbt.open_readonly().await?;
     let encoded = mbt
         .stream_tiles(&mut conn)
         .filter_map(|r| async move {
@@ -208,8 +247,9 @@ async fn convert_mbtiles_to_pmtiles(
         })
         .map(|(coord, data)| {
             let cache = cache.clone();
+            let cfg = cfg.clone();
             tokio::task::spawn_blocking(move || -> AnyResult<EncodedTile> {
-                let (data, raw_mvt_size, hit) = encode_tile(&cache, &data, encoding, cfg)?;
+                let (data, raw_mvt_size, hit) = encode_tile(&cache, &data, encoding, &cfg)?;
                 Ok(EncodedTile {
                     coord,
                     data,
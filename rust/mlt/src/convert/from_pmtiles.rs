@@ -6,9 +6,10 @@ use std::time::Instant;
 
 use anyhow::{Result as AnyResult, bail};
 use bytes::Bytes;
-use futures::TryStreamExt;
+use futures::{StreamExt as _, TryStreamExt};
 use martin_tile_utils::Encoding;
 use mlt_core::encoder::EncoderConfig;
+use mlt_core::metadata::TileSetMetadata;
 use pmtiles::{
     AsyncPmTilesReader, Compression, HashMapCache, Header, MmapBackend, PmTilesWriter, TileCoord,
     TileId, TileType,
@@ -18,7 +19,8 @@ use super::common::{
     EncodeCache, EncodedTile, PmTilesGeography, TileStats, encode_tile, make_encode_cache,
     make_progress_bar,
 };
-use super::{ContainerFormat, update_mlt_pmtiles_metadata};
+use super::tileset::{TILESET_METADATA_KEY, describe_tileset, scan_schema, tileset_metadata_hex};
+use super::{ContainerFormat, merged_tileset, update_mlt_pmtiles_metadata};
 
 /// Re-encode a `.pmtiles` input (MVT) into the requested container.
 pub async fn convert(
@@ -26,10 +28,11 @@ pub async fn convert(
     output: (&Path, ContainerFormat),
     cfg: EncoderConfig,
     tile_compression: Compression,
+    tileset_metadata: bool,
 ) -> AnyResult<()> {
     match output {
         (output, ContainerFormat::Pmtiles) => {
-            convert_pmtiles_to_pmtiles(input, output, cfg, tile_compression).await
+            convert_pmtiles_to_pmtiles(input, output, cfg, tile_compression, tileset_metadata).await
         }
         (output, _) => bail!(
             "Output must be a .pmtiles file when input is a .pmtiles file, got: {}",
@@ -40,11 +43,11 @@ pub async fn convert(
 
 /// Mmap-backed reader over a local `.pmtiles` file.
 /// The [`HashMapCache`] avoids re-decoding leaf directories on every `get_tile`.
-type PmReader = AsyncPmTilesReader<MmapBackend, HashMapCache>;
+pub(super) type PmReader = AsyncPmTilesReader<MmapBackend, HashMapCache>;
 
 /// Maps `PMTiles` tile compression to the [`Encoding`] used by `encode_tile`.
 /// `PMTiles` has no zlib/deflate variant.
-fn compression_to_encoding(compression: Compression) -> AnyResult<Encoding> {
+pub(super) fn compression_to_encoding(compression: Compression) -> AnyResult<Encoding> {
     match compression {
         Compression::None => Ok(Encoding::Uncompressed),
         Compression::Gzip => Ok(Encoding::Gzip),
@@ -54,17 +57,30 @@ fn compression_to_encoding(compression: Compression) -> AnyResult<Encoding> {
     }
 }
 
-/// Copy the source metadata JSON, overriding the MLT format and outer compression.
-fn mlt_pmtiles_metadata(metadata: &str, tile_compression: Compression) -> AnyResult<String> {
+/// Copy the source metadata JSON, overriding the MLT format and outer compression
+/// and adding the tileset metadata, if any.
+fn mlt_pmtiles_metadata(
+    metadata: &str,
+    tile_compression: Compression,
+    tileset: Option<TileSetMetadata>,
+    geography: &PmTilesGeography,
+) -> AnyResult<String> {
     let mut value: serde_json::Value =
         serde_json::from_str(metadata).unwrap_or_else(|_| serde_json::json!({}));
     if let Some(obj) = value.as_object_mut() {
         update_mlt_pmtiles_metadata(obj, tile_compression);
+        if let Some(mut tileset) = tileset {
+            describe_tileset(&mut tileset, obj, geography);
+            obj.insert(
+                TILESET_METADATA_KEY.into(),
+                serde_json::Value::String(tileset_metadata_hex(&tileset)),
+            );
+        }
     }
     Ok(serde_json::to_string(&value)?)
 }
 
-fn geography_from_header(source: &Header) -> PmTilesGeography {
+pub(super) fn geography_from_header(source: &Header) -> PmTilesGeography {
     PmTilesGeography {
         min_zoom: Some(source.min_zoom),
         max_zoom: Some(source.max_zoom),
@@ -99,7 +115,7 @@ async fn open_mvt_pmtiles(input: &Path) -> AnyResult<(Arc<PmReader>, Encoding)>
 }
 
 /// Flatten the archive's run-length data entries into individual tile ids.
-async fn collect_pmtiles_ids(reader: &Arc<PmReader>) -> AnyResult<Vec<TileId>> {
+pub(super) async fn collect_pmtiles_ids(reader: &Arc<PmReader>) -> AnyResult<Vec<TileId>> {
     let mut ids = Vec::new();
     let mut entries = reader.clone().entries();
     while let Some(entry) = entries.try_next().await? {
@@ -204,9 +220,10 @@ fn spawn_encode_pipeline(
                 let raw_rx = raw_rx.clone();
                 let res_tx = res_tx.clone();
                 let cache = cache.clone();
+                let cfg = cfg.clone();
                 thread::spawn(move || {
                     for (seq, coord, data) in raw_rx {
-                        let result = encode_tile(&cache, &data, encoding, cfg).map(
+                        let result = encode_tile(&cache, &data, encoding, &cfg).map(
                             |(data, raw_mvt_size, hit)| {
                                 (
                                     seq,
@@ -272,6 +289,7 @@ async fn convert_pmtiles_to_pmtiles(
     output: &Path,
     cfg: EncoderConfig,
     tile_compression: Compression,
+    tileset_metadata: bool,
 ) -> AnyResult<()> {
     let (reader, encoding) = open_mvt_pmtiles(input).await?;
     let ids = collect_pmtiles_ids(&reader).await?;
@@ -281,9 +299,33 @@ async fn convert_pmtiles_to_pmtiles(
     let start = Instant::now();
     let bar = make_progress_bar(ids.len() as u64);
 
-    let metadata_str = mlt_pmtiles_metadata(&reader.get_metadata().await?, tile_compression)?;
+    // PMTiles stores metadata ahead of the tiles, so the schema must be known before writing.
+    let tileset = if tileset_metadata {
+        let tiles = futures::stream::iter(ids.iter().copied())
+            .then(|id| {
+                let reader = Arc::clone(&reader);
+                async move { reader.get_tile(id).await }
+            })
+            .filter_map(|r| async move {
+                match r {
+                    Ok(data) => data.map(|data| Ok(data.to_vec())),
+                    Err(e) => Some(Err(anyhow::Error::from(e))),
+                }
+            });
+        merged_tileset(Some(&scan_schema(tiles, encoding).await?))
+    } else {
+        None
+    };
+
+    let geography = geography_from_header(reader.get_header());
+    let metadata_str = mlt_pmtiles_metadata(
+        &reader.get_metadata().await?,
+        tile_compression,
+        tileset,
+        &geography,
+    )?;
     let file = std::fs::File::create(output)?;
-    let mut writer = geography_from_header(reader.get_header())
+    let mut writer = geography
         .apply(PmTilesWriter::new(TileType::Mlt))
         .tile_compression(tile_compression)
         .metadata(&metadata_str)
@@ -414,6 +456,7 @@ mod tests {
             &output.0,
             EncoderConfig::default(),
             Compression::Gzip,
+            true,
         )
         .await
         .expect("conversion succeeds");
@@ -431,6 +474,11 @@ mod tests {
                 .expect("metadata is JSON");
         assert_eq!(metadata["format"], "mlt");
         assert_eq!(metadata["compression"], "gzip");
+        let tileset = hex::decode(metadata[TILESET_METADATA_KEY].as_str().expect("hex string"))
+            .expect("valid hex");
+        let tileset = TileSetMetadata::from_bytes(&tileset).expect("tileset metadata parses");
+        assert!(!tileset.feature_tables.is_empty());
+        assert!(tileset.min_zoom.is_some());
 
         let tile_ids = collect_pmtiles_ids(&reader)
             .await
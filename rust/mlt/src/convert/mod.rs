@@ -1,9 +1,13 @@
+mod archive;
 mod common;
 mod from_files;
 mod from_mbtiles;
 mod from_pmtiles;
+mod pyramid;
+mod tileset;
 
 use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 use anyhow::{Result as AnyResult, bail};
 use bytes::Bytes;
@@ -11,10 +15,15 @@ use clap::{Args, ValueEnum};
 use indicatif::ProgressState;
 use martin_tile_utils::{Encoding, Format, decode_brotli, decode_gzip, decode_zlib, decode_zstd};
 use mbtiles::{MbtType, NormalizedSchema};
-use mlt_core::encoder::{EncodedUnknown, Encoder, EncoderConfig};
+use mlt_core::encoder::{Effort, EncodedUnknown, Encoder, EncoderConfig, Sampling};
+use mlt_core::metadata::TileSetMetadata;
 use mlt_core::mvt::{mvt_to_tile_layers, tile_layers_to_mvt};
 use mlt_core::{Decoder, Layer, Parser};
 use pmtiles::Compression;
+use tileset::SchemaCollector;
+
+pub use self::archive::{TileArchive, TileRef};
+pub use self::pyramid::{PyramidArgs, pyramid};
 
 #[expect(
     clippy::cast_possible_truncation,
@@ -106,7 +115,7 @@ enum SortMode {
     /// Hilbert and feature-ID sort each win <1% of layers, at double the encode work.
     #[default]
     Auto,
-    /// Try every sort strategy (no-sort, Morton, Hilbert, feature-ID) and keep the smallest.
+    /// Try every sort strategy (no-sort, Morton, Hilbert, feature-ID, property) and keep the smallest.
     /// Slowest, for marginally smaller output.
     All,
     /// Do not reorder features (original order only)
@@ -117,6 +126,45 @@ enum SortMode {
     Hilbert,
     /// Only try feature-ID ascending sort
     Id,
+    /// Only try sorting by up to two low-cardinality property columns, alone and then by space
+    Property,
+}
+
+/// Value of `--sort`: a [`SortMode`], or `property:<name>` for a given column.
+#[derive(Clone)]
+enum SortArg {
+    Mode(SortMode),
+    /// Only try sorting by this property column, alone and then by space
+    Column(String),
+}
+
+fn parse_sort(value: &str) -> Result<SortArg, String> {
+    match value.strip_prefix("property:") {
+        Some("") => Err("expected a column name after `property:`".to_string()),
+        Some(name) => Ok(SortArg::Column(name.to_string())),
+        None => SortMode::from_str(value, false).map(SortArg::Mode),
+    }
+}
+
+#[derive(Clone, Copy, Default, ValueEnum)]
+enum EffortMode {
+    /// Encode once per layer and write every stream with the encoding that did best on a sample
+    Fast,
+    /// Compete the sort strategies and stream encodings that did best on a sample (default)
+    #[default]
+    Default,
+    /// Fully encode every selected sort strategy and stream encoding
+    Max,
+}
+
+impl From<EffortMode> for Effort {
+    fn from(effort: EffortMode) -> Self {
+        match effort {
+            EffortMode::Fast => Self::Fast,
+            EffortMode::Default => Self::Default,
+            EffortMode::Max => Self::Max,
+        }
+    }
 }
 
 #[derive(Clone, Copy, Default, Eq, PartialEq, ValueEnum)]
@@ -158,6 +206,17 @@ fn update_mlt_pmtiles_metadata(
     }
 }
 
+/// The merged tileset metadata, or `None` with a warning if the tiles had conflicting schemas.
+fn merged_tileset(schema: Option<&SchemaCollector>) -> Option<TileSetMetadata> {
+    match schema?.finish() {
+        Ok(metadata) => Some(metadata),
+        Err(e) => {
+            eprintln!("warning: not writing tileset metadata: {e}");
+            None
+        }
+    }
+}
+
 #[derive(Args)]
 #[expect(
     clippy::struct_excessive_bools,
@@ -171,9 +230,27 @@ pub struct ConvertArgs {
     /// Add tessellation
     #[clap(short, long)]
     tessellate: bool,
-    /// Sort strategy to try when re-encoding (encoder keeps the smallest result)
-    #[clap(long, default_value = "auto")]
-    sort: SortMode,
+    /// Store polygon-only layers as a bare triangle mesh without outlines (implies `--tessellate`)
+    #[clap(long)]
+    mesh_only: bool,
+    /// Clip geometries to this many units beyond the tile extent when re-encoding,
+    /// dropping features left empty
+    #[clap(long)]
+    buffer: Option<u32>,
+    /// Sort strategy to try when re-encoding (encoder keeps the smallest result):
+    /// auto, all, none, morton, hilbert, id, property, or `property:<name>` to sort by that column
+    #[clap(long, default_value = "auto", value_parser = parse_sort)]
+    sort: SortArg,
+    /// How hard to search for the smallest encoding
+    #[clap(long, value_enum, default_value = "default")]
+    effort: EffortMode,
+    /// Fully encode only the N sort strategies and stream encodings that did best on a sample,
+    /// or all of them with 0; defaults to 1 for `--effort fast`, 2 for default and all for max
+    #[clap(long, value_name = "N")]
+    sampling: Option<usize>,
+    /// Per-tile time budget in milliseconds; once spent, the smallest encoding found so far is kept
+    #[clap(long, value_name = "MS")]
+    time_budget: Option<u64>,
     /// Schema type for the output `.mbtiles` file; defaults to the input file's schema
     #[clap(long)]
     mbtiles_format: Option<MbtFormat>,
@@ -186,6 +263,16 @@ pub struct ConvertArgs {
     /// Disable `FSST` string compression
     #[clap(long)]
     no_fsst: bool,
+    /// Allow `PseudoDecimal` float encoding; the output can only be read by decoders that support it
+    #[clap(long)]
+    pseudo_decimal: bool,
+    /// Allow dictionary and RLE float encodings; the output can only be read by this decoder
+    #[clap(long)]
+    float_dict_rle: bool,
+    /// Write the tileset metadata (column schema of every layer) into the output.
+    /// `.pmtiles` outputs need an extra pass over the input to collect it.
+    #[clap(long)]
+    tileset_metadata: bool,
     /// Output tile format (`mlt` re-encodes; `mvt` decodes MLT inputs back to MVT)
     #[clap(long, default_value = "mlt")]
     to: TileFormat,
@@ -206,17 +293,37 @@ impl ConvertArgs {
 }
 
 pub fn convert(args: &ConvertArgs) -> AnyResult<()> {
-    let morton = matches!(args.sort, SortMode::All | SortMode::Auto | SortMode::Morton);
-    let hilbert = matches!(args.sort, SortMode::All | SortMode::Hilbert);
-    let id_sort = matches!(args.sort, SortMode::All | SortMode::Id);
+    let (mode, sort_property) = match &args.sort {
+        SortArg::Mode(mode) => (mode.clone(), None),
+        SortArg::Column(name) => (SortMode::None, Some(name.as_str())),
+    };
+    let morton = matches!(mode, SortMode::All | SortMode::Auto | SortMode::Morton);
+    let hilbert = matches!(mode, SortMode::All | SortMode::Hilbert);
+    let id_sort = matches!(mode, SortMode::All | SortMode::Id);
+    let property_sort = matches!(mode, SortMode::All | SortMode::Property);
     let cfg = EncoderConfig::default()
         .with_tessellation(args.tessellate)
+        .with_mesh_only(args.mesh_only)
         .with_spatial_morton_sort(morton)
         .with_spatial_hilbert_sort(hilbert)
         .with_id_sort(id_sort)
+        .with_property_sort(property_sort)
+        .with_sort_property(sort_property)
         .with_shared_dict(!args.no_shared_dict)
         .with_fastpfor(!args.no_fastpfor)
-        .with_fsst(!args.no_fsst);
+        .with_fsst(!args.no_fsst)
+        .with_pseudo_decimal(args.pseudo_decimal)
+        .with_float_dict_rle(args.float_dict_rle)
+        .with_clip_buffer(args.buffer)
+        .with_effort(args.effort.into())
+        .with_sampling(match args.sampling {
+            None => Sampling::Auto,
+            Some(0) => Sampling::Off,
+            Some(n) => Sampling::Finalists(n),
+        })
+        .with_time_budget(args.time_budget.map(Duration::from_millis));
+
+    let tileset_metadata = args.tileset_metadata;
 
     let input_container = args.input_container();
     let output_container = args.output_container();
@@ -260,6 +367,7 @@ pub fn convert(args: &ConvertArgs) -> AnyResult<()> {
                 output,
                 cfg,
                 args.tile_compression.into(),
+                tileset_metadata,
             )),
             // mbtiles is the only other container possible here.
             _ => runtime.block_on(from_mbtiles::convert(
@@ -268,23 +376,38 @@ pub fn convert(args: &ConvertArgs) -> AnyResult<()> {
                 cfg,
                 args.mbtiles_format,
                 args.tile_compression.into(),
+                tileset_metadata,
             )),
         };
     }
 
-    from_files::convert(&args.input, &args.output, cfg, args.to)
+    from_files::convert(&args.input, &args.output, cfg, args.to, tileset_metadata)
 }
 
-fn convert_mlt_buffer(buffer: &[u8], cfg: EncoderConfig) -> AnyResult<Vec<u8>> {
+/// Re-encode an MLT buffer, restoring the logical column kinds from `tileset` first so the
+/// collected `schema` keeps them.
+fn convert_mlt_buffer(
+    buffer: &[u8],
+    cfg: &EncoderConfig,
+    schema: Option<&SchemaCollector>,
+    tileset: Option<&TileSetMetadata>,
+) -> AnyResult<Vec<u8>> {
     let layers = Parser::default().parse_layers(buffer)?;
     let mut dec = Decoder::default();
     let mut out: Vec<u8> = Vec::new();
+    let cfg = cfg.clone().start_budget();
 
     for layer in layers {
         match layer {
             Layer::Tag01(l) => {
-                let tile = l.into_tile(&mut dec)?;
-                out.extend_from_slice(&tile.encode(cfg)?);
+                let mut tile = l.into_tile(&mut dec)?;
+                if let Some(tileset) = tileset {
+                    tileset.restore_logical_kinds(&mut tile)?;
+                }
+                if let Some(schema) = schema {
+                    schema.add(std::slice::from_ref(&tile));
+                }
+                out.extend_from_slice(&tile.encode(cfg.clone())?);
             }
             Layer::Unknown(u) => {
                 out.extend(
@@ -300,19 +423,38 @@ fn convert_mlt_buffer(buffer: &[u8], cfg: EncoderConfig) -> AnyResult<Vec<u8>> {
     Ok(out)
 }
 
-fn convert_mvt_buffer(buffer: Vec<u8>, cfg: EncoderConfig) -> AnyResult<Vec<u8>> {
+fn convert_mvt_buffer(
+    buffer: Vec<u8>,
+    cfg: &EncoderConfig,
+    schema: Option<&SchemaCollector>,
+) -> AnyResult<Vec<u8>> {
+    let tiles = mvt_to_tile_layers(buffer)?;
+    if let Some(schema) = schema {
+        schema.add(&tiles);
+    }
     let mut out: Vec<u8> = Vec::new();
-    for tile in mvt_to_tile_layers(buffer)? {
-        out.extend_from_slice(&tile.encode(cfg)?);
+    let cfg = cfg.clone().start_budget();
+    for tile in tiles {
+        out.extend_from_slice(&tile.encode(cfg.clone())?);
     }
     Ok(out)
 }
 
+/// What [`mlt_buffer_to_tile_layers`] does with unknown/extension layers,
+/// which have no row-oriented form.
+#[derive(Clone, Copy)]
+pub(crate) enum UnknownLayers {
+    /// Leave them out.
+    Skip,
+    /// Fail, naming the operation that cannot represent them.
+    Reject(&'static str),
+}
+
 /// Decode an MLT buffer to row-oriented [`mlt_core::TileLayer`]s.
-///
-/// MVT has no equivalent for unknown/extension MLT layer tags, so conversion
-/// is rejected instead of silently dropping data.
-fn mlt_buffer_to_tile_layers(buffer: &[u8]) -> AnyResult<Vec<mlt_core::TileLayer>> {
+pub(crate) fn mlt_buffer_to_tile_layers(
+    buffer: &[u8],
+    unknown: UnknownLayers,
+) -> AnyResult<Vec<mlt_core::TileLayer>> {
     let layers = Parser::default().parse_layers(buffer)?;
     let mut dec = Decoder::default();
     let mut tiles = Vec::new();
@@ -321,41 +463,68 @@ fn mlt_buffer_to_tile_layers(buffer: &[u8]) -> AnyResult<Vec<mlt_core::TileLayer
             Layer::Tag01(l) => {
                 tiles.push(l.into_tile(&mut dec)?);
             }
-            Layer::Unknown(_) => {
-                bail!(
-                    "cannot convert MLT tile to MVT: tile contains unknown/extension layers that MVT cannot represent"
-                );
-            }
+            Layer::Unknown(_) => match unknown {
+                UnknownLayers::Skip => {}
+                UnknownLayers::Reject(op) => {
+                    bail!("cannot {op}: tile contains unknown/extension layers");
+                }
+            },
             _ => {}
         }
     }
     Ok(tiles)
 }
 
-fn encode_one(data: Vec<u8>, encoding: Encoding, cfg: EncoderConfig) -> AnyResult<(Bytes, u64)> {
-    let mvt = match encoding {
+/// Undo the container's tile compression.
+fn decompress(data: Vec<u8>, encoding: Encoding) -> AnyResult<Vec<u8>> {
+    Ok(match encoding {
         Encoding::Gzip => decode_gzip(&data)?,
         Encoding::Zlib => decode_zlib(&data)?,
         Encoding::Brotli => decode_brotli(&data)?,
         Encoding::Zstd => decode_zstd(&data)?,
         Encoding::Uncompressed | Encoding::Internal => data,
-    };
+    })
+}
+
+fn encode_one(
+    data: Vec<u8>,
+    encoding: Encoding,
+    cfg: &EncoderConfig,
+    schema: Option<&SchemaCollector>,
+) -> AnyResult<(Bytes, u64)> {
+    let mvt = decompress(data, encoding)?;
     let raw_mvt_size = mvt.len() as u64;
-    convert_mvt_buffer(mvt, cfg).map(|data| (Bytes::from_owner(data), raw_mvt_size))
+    convert_mvt_buffer(mvt, cfg, schema).map(|data| (Bytes::from_owner(data), raw_mvt_size))
 }
 
 /// Convert one input buffer to the requested target format.
+///
+/// `tileset` is the metadata of the input tiles, used to restore the logical column kinds
+/// (e.g. dates) of MLT input.
 fn convert_buffer(
     buffer: Vec<u8>,
     from: TileFormat,
     to: TileFormat,
-    cfg: EncoderConfig,
+    cfg: &EncoderConfig,
+    schema: Option<&SchemaCollector>,
+    tileset: Option<&TileSetMetadata>,
 ) -> AnyResult<Vec<u8>> {
     match (from, to) {
-        (TileFormat::Mlt, TileFormat::Mlt) => convert_mlt_buffer(&buffer, cfg),
-        (TileFormat::Mvt, TileFormat::Mlt) => convert_mvt_buffer(buffer, cfg),
+        (TileFormat::Mlt, TileFormat::Mlt) => convert_mlt_buffer(&buffer, cfg, schema, tileset),
+        (TileFormat::Mvt, TileFormat::Mlt) => convert_mvt_buffer(buffer, cfg, schema),
         (TileFormat::Mlt, TileFormat::Mvt) => {
-            Ok(tile_layers_to_mvt(mlt_buffer_to_tile_layers(&buffer)?)?)
+            // MVT has no equivalent for unknown/extension MLT layer tags, so
+            // conversion is rejected instead of silently dropping data.
+            let mut tiles = mlt_buffer_to_tile_layers(
+                &buffer,
+                UnknownLayers::Reject("convert MLT tile to MVT"),
+            )?;
+            if let Some(tileset) = tileset {
+                for tile in &mut tiles {
+                    tileset.restore_logical_kinds(tile)?;
+                }
+            }
+            Ok(tile_layers_to_mvt(tiles)?)
         }
         // Re-encoding through TileLayer is lossy (e.g. SInt vs Int wire choice)
         // and offers no benefit.
@@ -385,4 +554,39 @@ mod tests {
         assert_eq!(metadata["format"], "mlt");
         assert_eq!(metadata["compression"], "gzip");
     }
+
+    #[test]
+    fn mlt_to_mvt_restores_dates_from_tileset() {
+        use mlt_core::geo_types::{Geometry, Point};
+        use mlt_core::{PropKind, PropValue, TileLayer};
+
+        let mut builder = TileLayer::builder("events", 4096).unwrap();
+        let day = builder.add_property("day", PropKind::Date).unwrap();
+        let mut feature = builder.feature(Geometry::Point(Point::new(1, 2)));
+        feature
+            .property(day, PropValue::Date(Some(19_782)))
+            .unwrap();
+        feature.finish().unwrap();
+        let layer = builder.finish();
+        let tileset = TileSetMetadata::from_layers([&layer]).unwrap();
+        let mlt = layer.encode(EncoderConfig::default()).unwrap();
+
+        let convert = |tileset| {
+            let mvt = convert_buffer(
+                mlt.clone(),
+                TileFormat::Mlt,
+                TileFormat::Mvt,
+                &EncoderConfig::default(),
+                None,
+                tileset,
+            )
+            .unwrap();
+            mvt_to_tile_layers(mvt).unwrap()[0].features()[0].properties()[0].clone()
+        };
+        assert_eq!(convert(None), PropValue::I64(Some(19_782)));
+        assert_eq!(
+            convert(Some(&tileset)),
+            PropValue::Str(Some("2024-02-29".into()))
+        );
+    }
 }
@@ -0,0 +1,262 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::pin::pin;
+use std::sync::Arc;
+use std::time::Instant;
+
+use anyhow::{Result as AnyResult, anyhow, bail};
+use clap::Args;
+use futures::StreamExt as _;
+use martin_tile_utils::Format;
+use mbtiles::Mbtiles;
+use mlt_core::encoder::{EncoderConfig, GeneralizeConfig, merge_children};
+use pmtiles::{AsyncPmTilesReader, Compression, HashMapCache, PmTilesWriter, TileCoord, TileType};
+use rayon::iter::{IntoParallelIterator as _, ParallelIterator as _};
+
+use super::common::PmTilesGeography;
+use super::from_mbtiles::geography_from_metadata;
+use super::from_pmtiles::{collect_pmtiles_ids, compression_to_encoding, geography_from_header};
+use super::{
+    ContainerFormat, UnknownLayers, decompress, mlt_buffer_to_tile_layers,
+    update_mlt_pmtiles_metadata,
+};
+
+/// Decompressed MLT tiles keyed by `(z, x, y)` in XYZ order.
+type Tiles = BTreeMap<(u8, u32, u32), Vec<u8>>;
+
+#[derive(Args)]
+pub struct PyramidArgs {
+    /// Input: an .mbtiles or .pmtiles archive of MLT tiles; its highest zoom level is the source
+    input: PathBuf,
+    /// Output: an .mbtiles or .pmtiles archive; must not exist yet
+    output: PathBuf,
+    /// Lowest zoom level to generate
+    #[arg(long, default_value_t = 0)]
+    min_zoom: u8,
+}
+
+/// An input archive loaded into memory.
+struct Source {
+    tiles: Tiles,
+    metadata: serde_json::Value,
+    geography: PmTilesGeography,
+}
+
+/// Build every zoom level from `max - 1` down to `--min-zoom` by merging four children
+/// into their parent and generalizing the result.
+///
+/// All tiles are held in memory, so this is meant for extracts rather than planet-scale archives.
+pub fn pyramid(args: &PyramidArgs) -> AnyResult<()> {
+    let input_container = ContainerFormat::from_path(&args.input);
+    let output_container = ContainerFormat::from_path(&args.output);
+    if input_container == ContainerFormat::Files || output_container == ContainerFormat::Files {
+        bail!("Input and output must be .mbtiles or .pmtiles archives");
+    }
+    if args.output.exists() {
+        bail!(
+            "Output {} already exists; refusing to append. \
+             Delete it first or choose a different path.",
+            args.output.display()
+        );
+    }
+
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_io()
+        .enable_time()
+        .build()?;
+    runtime.block_on(async {
+        let mut source = match input_container {
+            ContainerFormat::Pmtiles => read_pmtiles(&args.input).await?,
+            _ => read_mbtiles(&args.input).await?,
+        };
+        let Some(&(max_zoom, _, _)) = source.tiles.keys().next_back() else {
+            bail!("{} has no tiles", args.input.display());
+        };
+        if args.min_zoom >= max_zoom {
+            bail!(
+                "--min-zoom {} must be below the highest zoom level {max_zoom} of the input",
+                args.min_zoom
+            );
+        }
+
+        eprintln!("{} -> {}:", args.input.display(), args.output.display());
+        let start = Instant::now();
+        source
+            .tiles
+            .retain(|&(z, _, _), _| z < args.min_zoom || z == max_zoom);
+        for z in (args.min_zoom..max_zoom).rev() {
+            let generated = build_level(&source.tiles, z)?;
+            eprintln!("  z{z}: {} tiles", generated.len());
+            source.tiles.extend(generated);
+        }
+        eprintln!(
+            "  built zoom levels {}..{max_zoom} in {:.1?}",
+            args.min_zoom,
+            start.elapsed()
+        );
+
+        // Input tiles below `--min-zoom` are kept as they are.
+        let min_zoom = source
+            .tiles
+            .keys()
+            .next()
+            .map_or(args.min_zoom, |&(z, _, _)| z);
+        match output_container {
+            ContainerFormat::Pmtiles => write_pmtiles(&args.output, source, min_zoom),
+            _ => write_mbtiles(&args.output, source.tiles, min_zoom, max_zoom).await,
+        }
+    })
+}
+
+/// Merge the tiles of zoom `z + 1` into their parents at zoom `z`.
+fn build_level(tiles: &Tiles, z: u8) -> AnyResult<Tiles> {
+    let mut parents: BTreeMap<(u32, u32), [Option<&[u8]>; 4]> = BTreeMap::new();
+    for (&(_, x, y), data) in tiles.range((z + 1, 0, 0)..=(z + 1, u32::MAX, u32::MAX)) {
+        let quadrant = usize::from(x % 2 == 1) + 2 * usize::from(y % 2 == 1);
+        parents.entry((x / 2, y / 2)).or_default()[quadrant] = Some(data);
+    }
+    let cfg = GeneralizeConfig::for_zoom(z);
+    let built: Vec<_> = parents
+        .into_iter()
+        .collect::<Vec<_>>()
+        .into_par_iter()
+        .map(|((x, y), children)| -> AnyResult<_> {
+            // Unknown layers cannot be merged.
+            let [nw, ne, sw, se] = children.map(|child| {
+                child.map_or_else(
+                    || Ok(Vec::new()),
+                    |data| mlt_buffer_to_tile_layers(data, UnknownLayers::Reject("merge MLT tile")),
+                )
+            });
+            let mut out = Vec::new();
+            for layer in merge_children([nw?, ne?, sw?, se?], cfg)? {
+                out.extend_from_slice(&layer.encode(EncoderConfig::default())?);
+            }
+            Ok(((z, x, y), out))
+        })
+        .collect::<AnyResult<_>>()?;
+    Ok(built
+        .into_iter()
+        .filter(|(_, data)| !data.is_empty())
+        .collect())
+}
+
+async fn read_pmtiles(input: &Path) -> AnyResult<Source> {
+    let reader =
+        Arc::new(AsyncPmTilesReader::new_with_cached_path(HashMapCache::default(), input).await?);
+    let header = reader.get_header();
+    if header.tile_type != TileType::Mlt {
+        bail!(
+            "Expected MLT tiles, got {:?} in {}",
+            header.tile_type,
+            input.display()
+        );
+    }
+    let encoding = compression_to_encoding(header.tile_compression)?;
+    let geography = geography_from_header(header);
+
+    let mut tiles = Tiles::new();
+    for id in collect_pmtiles_ids(&reader).await? {
+        if let Some(data) = reader.get_tile(id).await? {
+            let coord = TileCoord::from(id);
+            let data = decompress(data.to_vec(), encoding)?;
+            tiles.insert((coord.z(), coord.x(), coord.y()), data);
+        }
+    }
+    let metadata = serde_json::from_str(&reader.get_metadata().await?)
+        .unwrap_or_else(|_| serde_json::json!({}));
+    Ok(Source {
+        tiles,
+        metadata,
+        geography,
+    })
+}
+
+async fn read_mbtiles(input: &Path) -> AnyResult<Source> {
+    let mbt = Mbtiles::new(input)?;
+    let mut conn = mbt.open_readonly().await?;
+    let meta = mbt.get_metadata(&mut conn).await?;
+    let tile_info = mbt
+        .detect_format(&meta.tilejson, &mut conn)
+        .await?
+        .ok_or_else(|| anyhow!("{} appears to be empty", input.display()))?;
+    if tile_info.format != Format::Mlt {
+        bail!(
+            "Expected MLT tiles, got {} in {}",
+            tile_info.format,
+            input.display()
+        );
+    }
+
+    let mut tiles = Tiles::new();
+    let mut stream = pin!(mbt.stream_tiles(&mut conn));
+    while let Some(row) = stream.next().await {
+        if let (coord, Some(data)) = row? {
+            let data = decompress(data, tile_info.encoding)?;
+            tiles.insert((coord.z, coord.x, coord.y), data);
+        }
+    }
+    Ok(Source {
+        tiles,
+        metadata: serde_json::to_value(&meta.tilejson)?,
+        geography: geography_from_metadata(&meta),
+    })
+}
+
+fn write_pmtiles(output: &Path, source: Source, min_zoom: u8) -> AnyResult<()> {
+    let mut metadata = source.metadata;
+    if let Some(obj) = metadata.as_object_mut() {
+        update_mlt_pmtiles_metadata(obj, Compression::None);
+        obj.insert("minzoom".into(), min_zoom.into());
+    }
+    let geography = PmTilesGeography {
+        min_zoom: Some(min_zoom),
+        ..source.geography
+    };
+    let mut writer = geography
+        .apply(PmTilesWriter::new(TileType::Mlt))
+        .tile_compression(Compression::None)
+        .metadata(&serde_json::to_string(&metadata)?)
+        .create(std::fs::File::create(output)?)?;
+    for ((z, x, y), data) in source.tiles {
+        writer.add_tile(TileCoord::new(z, x, y)?, &data)?;
+    }
+    writer.finalize()?;
+    Ok(())
+}
+
+/// Write a flat `MBTiles` archive; rows are flipped to the TMS scheme used by `MBTiles`.
+async fn write_mbtiles(output: &Path, tiles: Tiles, min_zoom: u8, max_zoom: u8) -> AnyResult<()> {
+    let mbt = Mbtiles::new(output)?;
+    let mut conn = mbt.open_or_new().await?;
+    for sql in [
+        "CREATE TABLE metadata (name text NOT NULL PRIMARY KEY, value text)",
+        "CREATE TABLE tiles (zoom_level integer NOT NULL, tile_column integer NOT NULL, \
+         tile_row integer NOT NULL, tile_data blob, \
+         PRIMARY KEY (zoom_level, tile_column, tile_row))",
+        "BEGIN",
+    ] {
+        sqlx::query(sql).execute(&mut conn).await?;
+    }
+    for ((z, x, y), data) in tiles {
+        let row = (1_u32 << z) - 1 - y;
+        sqlx::query(
+            "INSERT INTO tiles (zoom_level, tile_column, tile_row, tile_data) VALUES (?, ?, ?, ?)",
+        )
+        .bind(z)
+        .bind(x)
+        .bind(row)
+        .bind(data)
+        .execute(&mut conn)
+        .await?;
+    }
+    sqlx::query("COMMIT").execute(&mut conn).await?;
+
+    mbt.set_metadata_value(&mut conn, "format", Format::Mlt.metadata_format_value())
+        .await?;
+    mbt.set_metadata_value(&mut conn, "minzoom", &min_zoom.to_string())
+        .await?;
+    mbt.set_metadata_value(&mut conn, "maxzoom", &max_zoom.to_string())
+        .await?;
+    Ok(())
+}
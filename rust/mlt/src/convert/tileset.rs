@@ -0,0 +1,174 @@
+use std::sync::{Arc, Mutex, PoisonError};
+use std::thread;
+
+use anyhow::Result as AnyResult;
+use futures::{Stream, StreamExt as _};
+use martin_tile_utils::Encoding;
+use mlt_core::metadata::TileSetMetadata;
+use mlt_core::mvt::mvt_to_tile_layers;
+use mlt_core::{MltError, MltResult, TileLayer};
+
+use super::common::PmTilesGeography;
+use super::decompress;
+
+/// Container metadata key (`MBTiles` metadata table, `PMTiles` JSON metadata) holding the
+/// hex-encoded `TileSetMetadata` protobuf.
+pub const TILESET_METADATA_KEY: &str = "mlt_tileset_metadata";
+/// File name of the `TileSetMetadata` protobuf written next to converted tile directories.
+pub const TILESET_METADATA_FILE: &str = "tileset.pbf";
+
+#[derive(Default)]
+struct SchemaState {
+    metadata: TileSetMetadata,
+    conflict: Option<MltError>,
+}
+
+/// Tileset schema merged from every converted tile, shared across encoder threads.
+#[derive(Clone, Default)]
+pub struct SchemaCollector(Arc<Mutex<SchemaState>>);
+
+impl SchemaCollector {
+    /// Merge the schemas of one tile's layers.
+    ///
+    /// After the first type conflict the tileset has no single schema, so later tiles are ignored.
+    pub fn add(&self, layers: &[TileLayer]) {
+        let tile = TileSetMetadata::from_layers(layers);
+        let mut state = self.0.lock().unwrap_or_else(PoisonError::into_inner);
+        if state.conflict.is_none()
+            && let Err(e) = tile.and_then(|tile| state.metadata.merge(tile))
+        {
+            state.conflict = Some(e);
+        }
+    }
+
+    /// Decode one (possibly compressed) MVT tile and merge its schema.
+    pub fn add_mvt(&self, data: Vec<u8>, encoding: Encoding) -> AnyResult<()> {
+        self.add(&mvt_to_tile_layers(decompress(data, encoding)?)?);
+        Ok(())
+    }
+
+    /// The merged metadata, or the first schema conflict between tiles.
+    pub fn finish(&self) -> MltResult<TileSetMetadata> {
+        let mut state = self.0.lock().unwrap_or_else(PoisonError::into_inner);
+        match state.conflict.take() {
+            Some(conflict) => Err(conflict),
+            None => Ok(state.metadata.clone()),
+        }
+    }
+}
+
+/// Decode every tile once and merge the schemas, for containers that store metadata
+/// before the tile data and therefore need it up front.
+pub async fn scan_schema<S>(tiles: S, encoding: Encoding) -> AnyResult<SchemaCollector>
+where
+    S: Stream<Item = AnyResult<Vec<u8>>>,
+{
+    let parallelism = thread::available_parallelism().map_or(1, std::num::NonZeroUsize::get);
+    let schema = SchemaCollector::default();
+    let collector = schema.clone();
+    let scanned = tiles
+        .map(move |data| {
+            let schema = collector.clone();
+            async move {
+                let data = data?;
+                tokio::task::spawn_blocking(move || schema.add_mvt(data, encoding)).await?
+            }
+        })
+        .buffer_unordered(parallelism);
+    tokio::pin!(scanned);
+    while let Some(result) = scanned.next().await {
+        result?;
+    }
+    Ok(schema)
+}
+
+/// Copy tileset-level fields from the source `TileJSON` metadata and archive geography.
+pub fn describe_tileset(
+    metadata: &mut TileSetMetadata,
+    tilejson: &serde_json::Map<String, serde_json::Value>,
+    geography: &PmTilesGeography,
+) {
+    let text = |key: &str| tilejson.get(key)?.as_str().map(str::to_owned);
+    metadata.name = text("name");
+    metadata.description = text("description");
+    metadata.attribution = text("attribution");
+    metadata.min_zoom = geography.min_zoom.map(i32::from);
+    metadata.max_zoom = geography.max_zoom.map(i32::from);
+    metadata.bounds = geography
+        .bounds
+        .map(|(left, bottom, right, top)| vec![left, bottom, right, top])
+        .unwrap_or_default();
+    metadata.center = geography
+        .center
+        .map(|(longitude, latitude, _)| vec![longitude, latitude])
+        .unwrap_or_default();
+}
+
+/// Hex-encoded protobuf, for text-only container metadata fields.
+#[must_use]
+pub fn tileset_metadata_hex(metadata: &TileSetMetadata) -> String {
+    hex::encode(metadata.to_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use mlt_core::geo_types::{Geometry, Point};
+    use mlt_core::metadata::{ColumnKind, ScalarKind, ScalarType};
+    use mlt_core::{PropKind, PropValue};
+
+    use super::*;
+
+    fn layer(kind: PropKind, value: PropValue) -> TileLayer {
+        let mut builder = TileLayer::builder("poi", 4096).unwrap();
+        let key = builder.add_property("v", kind).unwrap();
+        let mut feature = builder.feature(Geometry::Point(Point::new(1, 2)));
+        feature.property(key, value).unwrap();
+        feature.finish().unwrap();
+        builder.finish()
+    }
+
+    #[test]
+    fn collects_widened_schema() {
+        let schema = SchemaCollector::default();
+        schema.add(&[layer(PropKind::I32, PropValue::I32(Some(-1)))]);
+        schema.add(&[layer(PropKind::F64, PropValue::F64(Some(0.5)))]);
+        let metadata = schema.finish().unwrap();
+        let column = &metadata.feature_tables[0].columns[1];
+        assert_eq!(column.name, "v");
+        assert!(matches!(
+            column.kind,
+            ColumnKind::Scalar(s) if s.kind == ScalarKind::Physical(ScalarType::Double)
+        ));
+    }
+
+    #[test]
+    fn conflicting_schema_is_dropped() {
+        let schema = SchemaCollector::default();
+        schema.add(&[layer(PropKind::Str, PropValue::Str(Some("a".into())))]);
+        schema.add(&[layer(PropKind::Bool, PropValue::Bool(Some(true)))]);
+        schema.add(&[layer(PropKind::Str, PropValue::Str(None))]);
+        assert!(matches!(
+            schema.finish(),
+            Err(MltError::IncompatibleColumnSchemas { column, .. }) if column == "v"
+        ));
+    }
+
+    #[test]
+    fn describes_tileset_from_source() {
+        let mut metadata = TileSetMetadata::default();
+        let tilejson = serde_json::json!({"name": "demo", "attribution": "OSM", "minzoom": 99});
+        let geography = PmTilesGeography {
+            min_zoom: Some(2),
+            max_zoom: Some(14),
+            bounds: Some((-10.0, -20.0, 30.0, 40.0)),
+            center: Some((1.5, 2.5, 6)),
+        };
+        describe_tileset(&mut metadata, tilejson.as_object().unwrap(), &geography);
+        assert_eq!(metadata.name.as_deref(), Some("demo"));
+        assert_eq!(metadata.description, None);
+        assert_eq!(metadata.attribution.as_deref(), Some("OSM"));
+        assert_eq!((metadata.min_zoom, metadata.max_zoom), (Some(2), Some(14)));
+        assert_eq!(metadata.bounds, [-10.0, -20.0, 30.0, 40.0]);
+        assert_eq!(metadata.center, [1.5, 2.5]);
+    }
+}
@@ -0,0 +1,157 @@
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{Result as AnyResult, bail};
+use clap::{Args, ValueEnum};
+use martin_tile_utils::Format;
+use mlt_core::TileLayer;
+use mlt_core::diff::{TileDiff, diff_layers};
+use mlt_core::mvt::mvt_to_tile_layers;
+use serde::Serialize;
+
+use crate::convert::{
+    ContainerFormat, TileArchive, TileRef, UnknownLayers, mlt_buffer_to_tile_layers,
+};
+use crate::ls::is_mlt_extension;
+
+#[derive(Args)]
+pub struct DiffArgs {
+    /// Left side: a tile file (.mlt, .mvt, .pbf) or an .mbtiles/.pmtiles archive
+    left: PathBuf,
+    /// Right side: a tile file or an archive, like the left side. MLT and MVT may be mixed
+    right: PathBuf,
+    /// Output format (text or JSON)
+    #[arg(short, long, default_value = "text", value_enum)]
+    format: DiffFormat,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum DiffFormat {
+    /// One line per added, removed or changed layer, feature and value
+    Text,
+    /// JSON output
+    Json,
+}
+
+/// A tile that differs between two archives.
+#[derive(Serialize)]
+struct TileChange {
+    z: u8,
+    x: u32,
+    y: u32,
+    status: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    diff: Option<TileDiff>,
+}
+
+/// Compare two tiles or two archives tile by tile.
+/// Returns `true` if they are semantically equal.
+pub fn diff(args: &DiffArgs) -> AnyResult<bool> {
+    let left_container = ContainerFormat::from_path(&args.left);
+    let right_container = ContainerFormat::from_path(&args.right);
+    match (left_container, right_container) {
+        (ContainerFormat::Files, ContainerFormat::Files) => diff_files(args),
+        (ContainerFormat::Files, _) | (_, ContainerFormat::Files) => {
+            bail!("cannot compare a tile file with an .mbtiles/.pmtiles archive")
+        }
+        _ => tokio::runtime::Builder::new_current_thread()
+            .enable_io()
+            .enable_time()
+            .build()?
+            .block_on(diff_archives(args)),
+    }
+}
+
+fn diff_files(args: &DiffArgs) -> AnyResult<bool> {
+    let left = decode_tile(fs::read(&args.left)?, is_mlt_extension(&args.left))?;
+    let right = decode_tile(fs::read(&args.right)?, is_mlt_extension(&args.right))?;
+    let diff = diff_layers(&left, &right);
+    match args.format {
+        DiffFormat::Text => print!("{diff}"),
+        DiffFormat::Json => println!("{}", serde_json::to_string_pretty(&diff)?),
+    }
+    Ok(diff.is_empty())
+}
+
+async fn diff_archives(args: &DiffArgs) -> AnyResult<bool> {
+    let mut left = TileArchive::open(&args.left).await?;
+    let mut right = TileArchive::open(&args.right).await?;
+    let left_tiles = left.tiles().await?;
+    let right_tiles = right.tiles().await?;
+
+    let coords: BTreeSet<_> = left_tiles
+        .keys()
+        .chain(right_tiles.keys())
+        .copied()
+        .collect();
+    let mut changes = Vec::new();
+    for &(z, x, y) in &coords {
+        let (status, diff) = match (left_tiles.get(&(z, x, y)), right_tiles.get(&(z, x, y))) {
+            (Some(_), None) => ("removed", None),
+            (None, Some(_)) => ("added", None),
+            (Some(&left_tile), Some(&right_tile)) => {
+                let left_layers = read_tile(&mut left, (z, x, y), left_tile).await?;
+                let right_layers = read_tile(&mut right, (z, x, y), right_tile).await?;
+                let diff = diff_layers(&left_layers, &right_layers);
+                if diff.is_empty() {
+                    continue;
+                }
+                ("changed", Some(diff))
+            }
+            (None, None) => unreachable!("coordinate comes from one of the archives"),
+        };
+        let change = TileChange {
+            z,
+            x,
+            y,
+            status,
+            diff,
+        };
+        if args.format == DiffFormat::Text {
+            print_change(&change);
+        }
+        changes.push(change);
+    }
+
+    match args.format {
+        DiffFormat::Text => eprintln!("{} of {} tiles differ", changes.len(), coords.len()),
+        DiffFormat::Json => println!("{}", serde_json::to_string_pretty(&changes)?),
+    }
+    Ok(changes.is_empty())
+}
+
+fn print_change(change: &TileChange) {
+    let TileChange { z, x, y, .. } = change;
+    match &change.diff {
+        None if change.status == "added" => println!("+ tile {z}/{x}/{y}"),
+        None => println!("- tile {z}/{x}/{y}"),
+        Some(diff) => {
+            println!("~ tile {z}/{x}/{y}");
+            for line in diff.to_string().lines() {
+                println!("  {line}");
+            }
+        }
+    }
+}
+
+async fn read_tile(
+    archive: &mut TileArchive,
+    coord: (u8, u32, u32),
+    tile: TileRef,
+) -> AnyResult<Vec<TileLayer>> {
+    let mlt = archive.format() == Format::Mlt;
+    match archive.get_tile(coord, tile).await? {
+        Some(data) => decode_tile(data, mlt),
+        None => Ok(Vec::new()),
+    }
+}
+
+/// Decode a tile to [`TileLayer`]s; unknown MLT layers have no semantic content to compare.
+fn decode_tile(data: Vec<u8>, mlt: bool) -> AnyResult<Vec<TileLayer>> {
+    if mlt {
+        mlt_buffer_to_tile_layers(&data, UnknownLayers::Skip)
+    } else {
+        Ok(mvt_to_tile_layers(data)?)
+    }
+}
@@ -1,8 +1,11 @@
 pub mod convert;
+pub mod diff;
 pub mod dump;
 pub mod hexdump;
 pub mod ls;
+pub mod overzoom;
 pub mod ui;
+pub mod validate;
 
 use std::process::exit;
 
@@ -14,16 +17,24 @@ use clap::{Parser, Subcommand, ValueEnum};
 #[global_allocator]
 static GLOBAL: mimalloc::MiMalloc = mimalloc::MiMalloc;
 
-use crate::convert::{ConvertArgs, convert};
+use crate::convert::{ConvertArgs, PyramidArgs, convert, pyramid};
+use crate::diff::{DiffArgs, diff};
 use crate::dump::{AfterDump, DumpArgs, dump};
 use crate::hexdump::{HexdumpArgs, hexdump};
 use crate::ls::{LsArgs, ls};
+use crate::overzoom::{OverzoomArgs, overzoom};
 use crate::ui::{UiArgs, ui};
+use crate::validate::{ValidateArgs, validate};
 
 #[hotpath::main]
 fn main() -> AnyResult<()> {
     match Cli::parse().command {
         Commands::Convert(args) => convert(&args)?,
+        Commands::Diff(args) => {
+            if !diff(&args)? {
+                exit(1)
+            }
+        }
         Commands::Dump(args) => dump(&args, AfterDump::KeepRaw)?,
         Commands::Decode(args) => dump(&args, AfterDump::Decode)?,
         Commands::Hexdump(args) => hexdump(&args)?,
@@ -32,7 +43,14 @@ fn main() -> AnyResult<()> {
                 exit(1)
             }
         }
+        Commands::Overzoom(args) => overzoom(&args)?,
+        Commands::Pyramid(args) => pyramid(&args)?,
         Commands::Ui(args) => ui(&args)?,
+        Commands::Validate(args) => {
+            if !validate(&args)? {
+                exit(1)
+            }
+        }
     }
 
     Ok(())
@@ -50,6 +68,8 @@ struct Cli {
 enum Commands {
     /// Convert .mlt, .mvt, and .pbf tiles in a directory tree to re-encoded .mlt files
     Convert(ConvertArgs),
+    /// Compare two tiles or two .mbtiles/.pmtiles archives semantically; exits with 1 if they differ
+    Diff(DiffArgs),
     /// Parse a tile file (.mlt, .mvt, .pbf) and dump raw layer data without decoding
     Dump(DumpArgs),
     /// Parse a tile file (.mlt, .mvt, .pbf), decode all layers, and dump the result
@@ -58,8 +78,14 @@ enum Commands {
     Hexdump(HexdumpArgs),
     /// List tile files with statistics
     Ls(LsArgs),
+    /// Derive a child tile from a parent tile by scaling and clipping its layers
+    Overzoom(OverzoomArgs),
+    /// Build lower zoom levels of an MLT archive by merging and generalizing child tiles
+    Pyramid(PyramidArgs),
     /// Visualize a tile file (.mlt, .mvt, .pbf) in an interactive TUI
     Ui(UiArgs),
+    /// Check MLT tiles or archives against the specification; exits with 1 on errors
+    Validate(ValidateArgs),
 }
 
 #[derive(Clone, Default, ValueEnum)]
@@ -0,0 +1,47 @@
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::Result as AnyResult;
+use clap::Args;
+use mlt_core::encoder::{EncoderConfig, overzoom as overzoom_layers};
+use mlt_core::mvt::mvt_to_tile_layers;
+
+use crate::convert::{UnknownLayers, mlt_buffer_to_tile_layers};
+use crate::ls::is_mlt_extension;
+
+#[derive(Args)]
+pub struct OverzoomArgs {
+    /// Parent tile file (.mlt, .mvt, .pbf)
+    input: PathBuf,
+    /// Output .mlt file for the child tile
+    output: PathBuf,
+    /// Number of zoom levels between the parent and the child tile
+    #[arg(long)]
+    dz: u8,
+    /// Column of the child tile within the parent, `0..2^dz`
+    #[arg(long)]
+    dx: u32,
+    /// Row of the child tile within the parent, `0..2^dz`
+    #[arg(long)]
+    dy: u32,
+    /// Keep geometries up to this many units beyond the child tile extent
+    #[arg(long, default_value_t = 0)]
+    buffer: u32,
+}
+
+pub fn overzoom(args: &OverzoomArgs) -> AnyResult<()> {
+    let data = fs::read(&args.input)?;
+    let layers = if is_mlt_extension(&args.input) {
+        // Unknown layers cannot be rescaled.
+        mlt_buffer_to_tile_layers(&data, UnknownLayers::Reject("overzoom MLT tile"))?
+    } else {
+        mvt_to_tile_layers(data)?
+    };
+
+    let mut out = Vec::new();
+    for layer in overzoom_layers(layers, args.dz, args.dx, args.dy, args.buffer)? {
+        out.extend_from_slice(&layer.encode(EncoderConfig::default())?);
+    }
+    fs::write(&args.output, out)?;
+    Ok(())
+}
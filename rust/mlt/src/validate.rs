@@ -0,0 +1,161 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Result as AnyResult, bail};
+use clap::{Args, ValueEnum};
+use martin_tile_utils::Format;
+use mlt_core::dump::{Finding, Severity, validate_tile};
+use serde::Serialize;
+
+use crate::convert::{ContainerFormat, TileArchive};
+use crate::ls::is_mlt_extension;
+
+#[derive(Args)]
+pub struct ValidateArgs {
+    /// .mlt tile files, directories of them, or .mbtiles/.pmtiles archives of MLT tiles
+    #[arg(required = true)]
+    paths: Vec<PathBuf>,
+    /// Output format (text or JSON)
+    #[arg(short, long, default_value = "text", value_enum)]
+    format: ValidateFormat,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ValidateFormat {
+    /// One line per finding, grouped by tile
+    Text,
+    /// JSON output
+    Json,
+}
+
+/// A finding with the tile it was found in.
+#[derive(Serialize)]
+struct Report {
+    tile: String,
+    severity: String,
+    offset: usize,
+    len: usize,
+    /// Enclosing regions and the offending region, outermost first.
+    path: Vec<String>,
+    message: String,
+}
+
+impl Report {
+    fn new(tile: &str, finding: Finding) -> Self {
+        let mut path = finding.path;
+        path.push(finding.region.label);
+        Self {
+            tile: tile.to_string(),
+            severity: finding.severity.to_string(),
+            offset: finding.region.offset,
+            len: finding.region.len,
+            path,
+            message: finding.message,
+        }
+    }
+}
+
+/// Totals over all validated tiles.
+#[derive(Default)]
+struct Summary {
+    tiles: usize,
+    invalid: usize,
+    errors: usize,
+    warnings: usize,
+    reports: Vec<Report>,
+}
+
+impl Summary {
+    fn add(&mut self, tile: &str, data: &[u8], format: ValidateFormat) {
+        let findings = validate_tile(data);
+        self.tiles += 1;
+        if findings.is_empty() {
+            return;
+        }
+        self.invalid += 1;
+        if format == ValidateFormat::Text {
+            println!("{tile}");
+        }
+        for finding in findings {
+            match finding.severity {
+                Severity::Error => self.errors += 1,
+                Severity::Warning => self.warnings += 1,
+            }
+            let report = Report::new(tile, finding);
+            if format == ValidateFormat::Text {
+                println!(
+                    "  {:<7} 0x{:06x}+{:<5} {}: {}",
+                    report.severity,
+                    report.offset,
+                    report.len,
+                    report.path.join(" > "),
+                    report.message
+                );
+            }
+            self.reports.push(report);
+        }
+    }
+}
+
+/// Check MLT tiles against the specification.
+/// Returns `true` if no tile has errors; warnings alone do not fail validation.
+pub fn validate(args: &ValidateArgs) -> AnyResult<bool> {
+    let mut summary = Summary::default();
+    for path in &args.paths {
+        match ContainerFormat::from_path(path) {
+            ContainerFormat::Files => validate_files(path, &mut summary, args.format)?,
+            _ => tokio::runtime::Builder::new_current_thread()
+                .enable_io()
+                .enable_time()
+                .build()?
+                .block_on(validate_archive(path, &mut summary, args.format))?,
+        }
+    }
+    if summary.tiles == 0 {
+        bail!("No .mlt tiles found");
+    }
+
+    match args.format {
+        ValidateFormat::Text => eprintln!(
+            "{} errors and {} warnings in {} of {} tiles",
+            summary.errors, summary.warnings, summary.invalid, summary.tiles
+        ),
+        ValidateFormat::Json => println!("{}", serde_json::to_string_pretty(&summary.reports)?),
+    }
+    Ok(summary.errors == 0)
+}
+
+/// Validate a tile file, or every `.mlt` file below a directory.
+fn validate_files(path: &Path, summary: &mut Summary, format: ValidateFormat) -> AnyResult<()> {
+    if path.is_dir() {
+        let mut entries = fs::read_dir(path)?
+            .map(|entry| Ok(entry?.path()))
+            .collect::<AnyResult<Vec<_>>>()?;
+        entries.sort();
+        for entry in entries {
+            if entry.is_dir() || is_mlt_extension(&entry) {
+                validate_files(&entry, summary, format)?;
+            }
+        }
+    } else {
+        summary.add(&path.display().to_string(), &fs::read(path)?, format);
+    }
+    Ok(())
+}
+
+async fn validate_archive(
+    path: &Path,
+    summary: &mut Summary,
+    format: ValidateFormat,
+) -> AnyResult<()> {
+    let mut archive = TileArchive::open(path).await?;
+    if archive.format() != Format::Mlt {
+        bail!("{} does not contain MLT tiles", path.display());
+    }
+    for ((z, x, y), tile) in archive.tiles().await? {
+        if let Some(data) = archive.get_tile((z, x, y), tile).await? {
+            summary.add(&format!("{} {z}/{x}/{y}", path.display()), &data, format);
+        }
+    }
+    Ok(())
+}